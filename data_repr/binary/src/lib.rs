@@ -62,6 +62,13 @@ impl BinaryValue {
         }
     }
 
+    pub fn as_i64(&self) -> i64 {
+        match self {
+            Self::Int64(val) => *val,
+            _ => panic!("invalid use of Datum::as_i64"),
+        }
+    }
+
     pub fn as_string(&self) -> String {
         match self {
             Self::String(s) => s.clone(),
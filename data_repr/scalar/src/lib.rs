@@ -22,9 +22,14 @@ use types::SqlTypeFamily;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum ScalarValue {
-    Num { value: BigDecimal, type_family: SqlTypeFamily },
+    Num {
+        value: BigDecimal,
+        type_family: SqlTypeFamily,
+    },
     String(String),
     Bool(bool),
+    /// microseconds since 1970-01-01 00:00:00 UTC
+    TimestampTz(i64),
     Null,
 }
 
@@ -34,6 +39,7 @@ impl ScalarValue {
             ScalarValue::Num { type_family, .. } => Some(*type_family),
             ScalarValue::String(_) => Some(SqlTypeFamily::String),
             ScalarValue::Bool(_) => Some(SqlTypeFamily::Bool),
+            ScalarValue::TimestampTz(_) => Some(SqlTypeFamily::TimestampTz),
             ScalarValue::Null => None,
         }
     }
@@ -45,6 +51,7 @@ impl ScalarValue {
             ScalarValue::Bool(false) => "f".to_owned(),
             ScalarValue::Num { value, .. } => value.to_string(),
             ScalarValue::String(val) => val.clone(),
+            ScalarValue::TimestampTz(micros) => timestamp_tz(*micros),
         }
     }
 
@@ -72,6 +79,7 @@ impl ScalarValue {
             } => BinaryValue::from(value.to_i64().unwrap()),
             ScalarValue::String(str) => BinaryValue::from(str),
             ScalarValue::Bool(boolean) => BinaryValue::from(boolean),
+            ScalarValue::TimestampTz(micros) => BinaryValue::from(timestamp_tz(micros)),
             ScalarValue::Null => BinaryValue::null(),
             _ => unreachable!(),
         }
@@ -123,7 +131,42 @@ impl Display for ScalarValue {
             ScalarValue::Num { value, .. } => write!(f, "{}", value),
             ScalarValue::String(value) => write!(f, "{}", value),
             ScalarValue::Bool(value) => write!(f, "{}", value),
+            ScalarValue::TimestampTz(micros) => write!(f, "{}", timestamp_tz(*micros)),
             ScalarValue::Null => write!(f, "NULL"),
         }
     }
 }
+
+// UTC time formatted as PostgreSQL formats `timestamp with time zone`
+fn timestamp_tz(micros: i64) -> String {
+    let seconds = micros.div_euclid(1_000_000);
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let seconds_of_day = seconds.rem_euclid(86_400);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}+00",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+        micros.rem_euclid(1_000_000)
+    )
+}
+
+// converts number of days since 1970-01-01 into a date of proleptic Gregorian calendar
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests;
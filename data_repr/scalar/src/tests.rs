@@ -0,0 +1,29 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[test]
+fn dates_from_days_since_epoch() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    assert_eq!(civil_from_days(-1), (1969, 12, 31));
+}
+
+#[test]
+fn timestamp_as_text() {
+    assert_eq!(ScalarValue::TimestampTz(0).as_text(), "1970-01-01 00:00:00.000000+00");
+    assert_eq!(ScalarValue::TimestampTz(951_825_600_000_001).as_text(), "2000-02-29 12:00:00.000001+00");
+    assert_eq!(ScalarValue::TimestampTz(-1).as_text(), "1969-12-31 23:59:59.999999+00");
+}
@@ -43,7 +43,7 @@ impl<S: ToString, T: ToString, I: ToString> From<(&S, &T, &I)> for FullIndexName
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct FullTableName {
-    schema: Option<String>,
+    schema: String,
     table: String,
 }
 
@@ -55,7 +55,7 @@ impl<'f> From<&'f FullTableName> for String {
 
 impl FullTableName {
     pub fn schema(&self) -> &str {
-        self.schema.as_deref().unwrap_or("public")
+        &self.schema
     }
 
     pub fn table(&self) -> &str {
@@ -67,27 +67,15 @@ impl<S: ToString, T: ToString> From<(&S, &T)> for FullTableName {
     fn from(tuple: (&S, &T)) -> Self {
         let (schema, table) = tuple;
         FullTableName {
-            schema: Some(schema.to_string()),
+            schema: schema.to_string(),
             table: table.to_string(),
         }
     }
 }
 
-impl From<&str> for FullTableName {
-    fn from(table: &str) -> Self {
-        FullTableName {
-            schema: None,
-            table: table.to_owned(),
-        }
-    }
-}
-
 impl Display for FullTableName {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self.schema.as_ref() {
-            None => write!(f, "{}", self.table),
-            Some(schema_name) => write!(f, "{}.{}", schema_name, self.table),
-        }
+        write!(f, "{}.{}", self.schema, self.table)
     }
 }
 
@@ -106,16 +94,77 @@ impl SchemaName {
     }
 }
 
+/// Schemas where objects that are referenced without a schema are looked up in order,
+/// `pg_catalog` is looked up first unless the path has it explicitly
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchPath {
+    schemas: Vec<String>,
+}
+
+impl SearchPath {
+    /// `search_path` is the setting value where `$user` stands for the session user
+    pub fn new(search_path: &str, user: &str) -> SearchPath {
+        let schemas = search_path
+            .split(',')
+            .map(str::trim)
+            .map(|schema| {
+                if schema.starts_with('"') && schema.ends_with('"') && schema.len() > 1 {
+                    schema[1..schema.len() - 1].to_owned()
+                } else {
+                    schema.to_lowercase()
+                }
+            })
+            .map(|schema| if schema == "$user" { user.to_owned() } else { schema })
+            .filter(|schema| !schema.is_empty())
+            .collect();
+        SearchPath { schemas }
+    }
+
+    /// new objects are created in the first existing schema of the path
+    pub fn schemas(&self) -> &[String] {
+        &self.schemas
+    }
+
+    pub fn lookup_schemas(&self) -> Vec<&str> {
+        let mut schemas = vec![];
+        if !self.schemas.iter().any(|schema| schema == "pg_catalog") {
+            schemas.push("pg_catalog");
+        }
+        schemas.extend(self.schemas.iter().map(String::as_str));
+        schemas
+    }
+}
+
+impl Default for SearchPath {
+    fn default() -> SearchPath {
+        SearchPath::new("\"$user\", public", "")
+    }
+}
+
+/// Column values generated by the column sequence unless they are specified explicitly,
+/// which is not allowed for `Always` identity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnIdentity {
+    Always,
+    ByDefault,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ColumnDef {
     name: String,
     sql_type: SqlType,
     ord_num: usize,
+    identity: Option<ColumnIdentity>,
 }
 
 impl ColumnDef {
-    pub fn new(name: String, sql_type: SqlType, ord_num: usize) -> ColumnDef {
-        ColumnDef { name, sql_type, ord_num }
+    pub fn new(name: String, sql_type: SqlType, ord_num: usize, identity: Option<ColumnIdentity>) -> ColumnDef {
+        ColumnDef {
+            name,
+            sql_type,
+            ord_num,
+            identity,
+        }
     }
 
     pub fn name(&self) -> &str {
@@ -133,6 +182,10 @@ impl ColumnDef {
     pub fn index(&self) -> usize {
         self.ord_num as usize
     }
+
+    pub fn identity(&self) -> Option<ColumnIdentity> {
+        self.identity
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -158,3 +211,80 @@ impl TableDef {
         self.columns.iter().any(|col| col.name == column_name)
     }
 }
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ViewDef {
+    full_view_name: FullTableName,
+    columns: Vec<String>,
+    definition: String,
+    source: FullTableName,
+    materialized: bool,
+}
+
+impl ViewDef {
+    pub fn new(full_view_name: FullTableName, columns: Vec<String>, definition: String, source: FullTableName, materialized: bool) -> ViewDef {
+        ViewDef {
+            full_view_name,
+            columns,
+            definition,
+            source,
+            materialized,
+        }
+    }
+
+    pub fn full_view_name(&self) -> &FullTableName {
+        &self.full_view_name
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn definition(&self) -> &str {
+        &self.definition
+    }
+
+    pub fn source(&self) -> &FullTableName {
+        &self.source
+    }
+
+    pub fn is_materialized(&self) -> bool {
+        self.materialized
+    }
+}
+
+/// SQL function with a body made of a single expression over its parameters
+#[derive(Debug, PartialEq, Clone)]
+pub struct FunctionDef {
+    full_function_name: FullTableName,
+    parameters: Vec<(String, SqlType)>,
+    return_type: SqlType,
+    body: String,
+}
+
+impl FunctionDef {
+    pub fn new(full_function_name: FullTableName, parameters: Vec<(String, SqlType)>, return_type: SqlType, body: String) -> FunctionDef {
+        FunctionDef {
+            full_function_name,
+            parameters,
+            return_type,
+            body,
+        }
+    }
+
+    pub fn full_function_name(&self) -> &FullTableName {
+        &self.full_function_name
+    }
+
+    pub fn parameters(&self) -> &[(String, SqlType)] {
+        &self.parameters
+    }
+
+    pub fn return_type(&self) -> SqlType {
+        self.return_type
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use node_engine::Worker;
+use postgre_sql::wire_protocol::{
+    payload::{InboundMessage, OutboundMessage, SMALLINT},
+    WireConnection, WireError, WireResult,
+};
+use std::io;
+use storage::Database;
+
+const ROUNDS: usize = 100;
+
+struct BenchConnection {
+    inbound: Vec<InboundMessage>,
+}
+
+impl BenchConnection {
+    fn new(inbound: Vec<InboundMessage>) -> BenchConnection {
+        BenchConnection {
+            inbound: inbound.into_iter().rev().collect(),
+        }
+    }
+}
+
+impl WireConnection for BenchConnection {
+    fn receive(&mut self) -> io::Result<WireResult> {
+        match self.inbound.pop() {
+            None => Ok(Err(WireError)),
+            Some(inbound) => Ok(Ok(inbound)),
+        }
+    }
+
+    fn send(&mut self, _outbound: OutboundMessage) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn setup() -> Database {
+    let database = Database::new("IN_MEMORY");
+    let mut connection = BenchConnection::new(vec![
+        InboundMessage::Query {
+            sql: "create schema schema_name;".to_owned(),
+        },
+        InboundMessage::Query {
+            sql: "create table schema_name.table_name (col_1 smallint, col_2 smallint);".to_owned(),
+        },
+        InboundMessage::Query {
+            sql: "insert into schema_name.table_name values (1, 2), (3, 4), (5, 6), (7, 8), (9, 10);".to_owned(),
+        },
+    ]);
+    Worker::default().process(&mut connection, database.clone());
+    database
+}
+
+fn parse_bind_execute(sql: String) -> Vec<InboundMessage> {
+    vec![
+        InboundMessage::Parse {
+            statement_name: "statement_name".to_owned(),
+            sql,
+            param_types: vec![SMALLINT],
+        },
+        InboundMessage::Bind {
+            portal_name: "".to_owned(),
+            statement_name: "statement_name".to_owned(),
+            query_param_formats: vec![0],
+            query_params: vec![Some(b"4".to_vec())],
+            result_value_formats: vec![],
+        },
+        InboundMessage::Execute {
+            portal_name: "".to_owned(),
+            max_rows: 0,
+        },
+        InboundMessage::Sync,
+    ]
+}
+
+fn same_statement() -> Vec<InboundMessage> {
+    (0..ROUNDS)
+        .flat_map(|_| parse_bind_execute("select col_1, col_2 from schema_name.table_name where col_1 > $1".to_owned()))
+        .collect()
+}
+
+fn changing_statement() -> Vec<InboundMessage> {
+    (0..ROUNDS)
+        .flat_map(|round| {
+            parse_bind_execute(format!(
+                "select col_1, col_2 from schema_name.table_name where col_1 > $1 and col_2 > {}",
+                round
+            ))
+        })
+        .collect()
+}
+
+fn parse_bind_execute_benchmark(c: &mut Criterion) {
+    let database = setup();
+    let worker = Worker::default();
+
+    let mut group = c.benchmark_group("parse_bind_execute");
+    group.bench_function("same_statement", |b| {
+        b.iter_batched(
+            || BenchConnection::new(same_statement()),
+            |mut connection| worker.process(&mut connection, database.clone()),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("changing_statement", |b| {
+        b.iter_batched(
+            || BenchConnection::new(changing_statement()),
+            |mut connection| worker.process(&mut connection, database.clone()),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse_bind_execute_benchmark);
+criterion_main!(benches);
@@ -12,67 +12,198 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::worker::Worker;
-use native_tls::Identity;
-use postgre_sql::wire_protocol::PgWireAcceptor;
+use crate::{
+    idle::IdleConnections,
+    pool::WorkerPool,
+    settings::{ServerConfig, SessionSettings},
+    worker::{Served, Session, Worker},
+};
+use native_tls::{Identity, TlsStream};
+use postgre_sql::{
+    query_response::QueryError,
+    wire_protocol::{
+        connection::{Connection, Established},
+        PgWireAcceptor,
+    },
+};
 use std::{
-    env,
-    env::VarError,
-    io::{self, Read},
-    net::TcpListener,
-    path::{Path, PathBuf},
+    fs, io,
+    net::{TcpListener, TcpStream},
+    os::unix::io::{AsRawFd, RawFd},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, TrySendError},
+        Arc,
+    },
     thread,
+    time::Duration,
 };
 use storage::Database;
 
+// clients that are slow to start up hold only startup threads, never the
+// threads that run queries
+const STARTUP_THREADS: usize = 8;
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+const REJECT_TIMEOUT: Duration = Duration::from_secs(1);
+const MAX_PENDING_REJECTIONS: usize = 64;
+
 #[derive(Default, Clone)]
-pub struct NodeEngine;
+pub struct NodeEngine {
+    config: ServerConfig,
+}
 
 impl NodeEngine {
+    pub fn new(config: ServerConfig) -> NodeEngine {
+        NodeEngine { config }
+    }
+
     pub fn start(&self, database: Database) {
-        let listener = TcpListener::bind("0.0.0.0:5432").expect("create listener");
+        let settings = self.config.session_settings().expect("valid session settings");
+        let acceptor = Arc::new(self.acceptor().expect("create acceptor"));
+        let listener = TcpListener::bind(self.config.listen_address.as_str()).expect("create listener");
+        log::info!(
+            "listening on {} for at most {} connections served by {} threads",
+            self.config.listen_address,
+            self.config.max_connections,
+            self.config.worker_threads
+        );
+
+        let pool = WorkerPool::new(self.config.worker_threads);
+        let startup = WorkerPool::new(STARTUP_THREADS);
+        let idle = {
+            let pool = pool.clone();
+            IdleConnections::start(move |idle: &IdleConnections<Client>, client| {
+                let (idle, next) = (idle.clone(), pool.clone());
+                pool.execute(move || serve(client, &idle, &next));
+            })
+            .expect("watch idle connections")
+        };
+        let rejections = {
+            let acceptor = acceptor.clone();
+            let (sender, receiver) = mpsc::sync_channel::<TcpStream>(MAX_PENDING_REJECTIONS);
+            thread::spawn(move || {
+                for socket in receiver {
+                    if let Err(error) = reject(&acceptor, socket) {
+                        log::error!("could not reject connection: {}", error);
+                    }
+                }
+            });
+            sender
+        };
 
+        let connections = Arc::new(AtomicUsize::new(0));
         for stream in listener.incoming() {
             match stream {
                 Err(_) => break,
+                Ok(socket) if connections.load(Ordering::SeqCst) >= self.config.max_connections => {
+                    log::warn!("connection from {:?} is rejected: too many clients", socket.peer_addr());
+                    if let Err(TrySendError::Full(socket)) = rejections.try_send(socket) {
+                        log::warn!("connection from {:?} is closed: too many pending rejections", socket.peer_addr());
+                    }
+                }
                 Ok(socket) => {
+                    let admitted = Admitted::new(&connections);
                     let db = database.clone();
-                    thread::spawn(move || -> io::Result<()> {
-                        let acceptor: PgWireAcceptor<Identity> = match (pfx_certificate_path(), pfx_certificate_password()) {
-                            (Ok(path), Ok(pass)) => {
-                                let mut buff = vec![];
-                                let mut file = std::fs::File::open(path).unwrap();
-                                file.read_to_end(&mut buff)?;
-                                PgWireAcceptor::new(Some(Identity::from_pkcs12(&buff, &pass).unwrap()))
-                            }
-                            _ => PgWireAcceptor::new(None),
-                        };
-
-                        let mut connection = acceptor.accept(socket).unwrap();
-
-                        let worker = Worker;
-
-                        worker.process(&mut connection, db);
-
-                        Ok(())
+                    let acceptor = acceptor.clone();
+                    let settings = settings.clone();
+                    let idle = idle.clone();
+                    let pool = pool.clone();
+                    startup.execute(move || match accept(&acceptor, socket, db, settings) {
+                        Ok((connection, session)) => {
+                            let client = Client {
+                                connection,
+                                session,
+                                _admitted: admitted,
+                            };
+                            wait(client, &idle, &pool);
+                        }
+                        Err(error) => log::error!("connection is closed with an error: {}", error),
                     });
                 }
             }
         }
     }
+
+    fn acceptor(&self) -> io::Result<PgWireAcceptor<Identity>> {
+        match (&self.config.pfx_certificate_file, &self.config.pfx_certificate_password) {
+            (Some(path), Some(pass)) => {
+                let buff = fs::read(path)?;
+                Ok(PgWireAcceptor::new(Some(Identity::from_pkcs12(&buff, pass).unwrap())))
+            }
+            _ => Ok(PgWireAcceptor::new(None)),
+        }
+    }
 }
 
-fn pfx_certificate_path() -> Result<PathBuf, VarError> {
-    let file = env::var("PFX_CERTIFICATE_FILE")?;
-    let path = Path::new(&file);
-    if path.is_absolute() {
-        return Ok(path.to_path_buf());
+type ClientConnection = Connection<Established, TcpStream, TlsStream<TcpStream>>;
+
+/// Counts a connection against `max_connections` until it is closed
+struct Admitted(Arc<AtomicUsize>);
+
+impl Admitted {
+    fn new(connections: &Arc<AtomicUsize>) -> Admitted {
+        connections.fetch_add(1, Ordering::SeqCst);
+        Admitted(connections.clone())
+    }
+}
+
+impl Drop for Admitted {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+struct Client {
+    connection: ClientConnection,
+    session: Session,
+    _admitted: Admitted,
+}
+
+impl AsRawFd for Client {
+    fn as_raw_fd(&self) -> RawFd {
+        self.connection.socket().as_raw_fd()
     }
+}
+
+// a pool thread serves a client only while it runs a transaction,
+// afterwards the client waits for its next message without a thread
+fn serve(mut client: Client, idle: &IdleConnections<Client>, pool: &WorkerPool) {
+    if let Served::Idle = client.session.serve(&mut client.connection, true) {
+        wait(client, idle, pool);
+    }
+}
+
+// messages already buffered by TLS stream do not make the socket readable,
+// such a client is served again instead of being parked
+fn wait(client: Client, idle: &IdleConnections<Client>, pool: &WorkerPool) {
+    if client.connection.has_buffered_input() {
+        let (idle, next) = (idle.clone(), pool.clone());
+        pool.execute(move || serve(client, &idle, &next));
+    } else {
+        idle.park(client);
+    }
+}
+
+fn accept(
+    acceptor: &PgWireAcceptor<Identity>,
+    socket: TcpStream,
+    database: Database,
+    mut settings: SessionSettings,
+) -> io::Result<(ClientConnection, Session)> {
+    socket.set_read_timeout(Some(STARTUP_TIMEOUT))?;
+    socket.set_write_timeout(Some(STARTUP_TIMEOUT))?;
+    let connection = acceptor.accept(socket, |params| {
+        settings.apply_startup_params(params);
+        settings.reported()
+    })?;
+    connection.socket().set_read_timeout(None)?;
+    connection.socket().set_write_timeout(None)?;
 
-    let current_dir = env::current_dir().unwrap();
-    Ok(current_dir.as_path().join(path))
+    Ok((connection, Worker::new(settings).session(database)))
 }
 
-fn pfx_certificate_password() -> Result<String, VarError> {
-    env::var("PFX_CERTIFICATE_PASSWORD")
+fn reject(acceptor: &PgWireAcceptor<Identity>, socket: TcpStream) -> io::Result<()> {
+    socket.set_read_timeout(Some(REJECT_TIMEOUT))?;
+    socket.set_write_timeout(Some(REJECT_TIMEOUT))?;
+    acceptor.reject(socket, QueryError::too_many_connections().into())
 }
@@ -0,0 +1,105 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use mio::{unix::SourceFd, Events, Interest, Poll, Token, Waker};
+use std::{
+    collections::HashMap,
+    io,
+    os::unix::io::AsRawFd,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+};
+
+const WAKER: Token = Token(0);
+
+/// Keeps connections that wait for the next client message without
+/// holding a thread. A connection is handed to `on_ready` once its socket
+/// becomes readable or is closed by the client.
+pub(crate) struct IdleConnections<C> {
+    sender: Sender<C>,
+    waker: Arc<Waker>,
+}
+
+impl<C> Clone for IdleConnections<C> {
+    fn clone(&self) -> IdleConnections<C> {
+        IdleConnections {
+            sender: self.sender.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+impl<C: AsRawFd + Send + 'static> IdleConnections<C> {
+    pub(crate) fn start<F: Fn(&IdleConnections<C>, C) + Send + 'static>(on_ready: F) -> io::Result<IdleConnections<C>> {
+        let poll = Poll::new()?;
+        let waker = Arc::new(Waker::new(poll.registry(), WAKER)?);
+        let (sender, receiver) = mpsc::channel();
+        let idle = IdleConnections { sender, waker };
+        let handle = idle.clone();
+        thread::spawn(move || {
+            if let Err(error) = watch(poll, receiver, &handle, on_ready) {
+                log::error!("idle connections are not watched anymore: {}", error);
+            }
+        });
+        Ok(idle)
+    }
+
+    pub(crate) fn park(&self, connection: C) {
+        if self.sender.send(connection).is_ok() {
+            if let Err(error) = self.waker.wake() {
+                log::error!("could not wake up idle connections watcher: {}", error);
+            }
+        }
+    }
+}
+
+fn watch<C: AsRawFd, F: Fn(&IdleConnections<C>, C)>(
+    mut poll: Poll,
+    receiver: Receiver<C>,
+    handle: &IdleConnections<C>,
+    on_ready: F,
+) -> io::Result<()> {
+    let mut events = Events::with_capacity(1024);
+    let mut parked = HashMap::new();
+    let mut next_token = WAKER.0 + 1;
+    loop {
+        poll.poll(&mut events, None)?;
+        for event in events.iter() {
+            if event.token() == WAKER {
+                for connection in receiver.try_iter() {
+                    let token = Token(next_token);
+                    next_token = next_token.wrapping_add(1).max(WAKER.0 + 1);
+                    match poll
+                        .registry()
+                        .register(&mut SourceFd(&connection.as_raw_fd()), token, Interest::READABLE)
+                    {
+                        Ok(()) => {
+                            parked.insert(token, connection);
+                        }
+                        Err(error) => log::error!("connection is closed, could not wait for its messages: {}", error),
+                    }
+                }
+            } else if let Some(connection) = parked.remove(&event.token()) {
+                poll.registry().deregister(&mut SourceFd(&connection.as_raw_fd()))?;
+                on_ready(handle, connection);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
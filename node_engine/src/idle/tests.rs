@@ -0,0 +1,91 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use std::{
+    io::Write,
+    net::{TcpListener, TcpStream},
+    sync::mpsc::RecvTimeoutError,
+    time::Duration,
+};
+
+fn socket_pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    (client, server)
+}
+
+fn idle_connections() -> (IdleConnections<TcpStream>, Receiver<TcpStream>) {
+    let (sender, receiver) = mpsc::channel();
+    let idle = IdleConnections::start(move |_idle, connection| sender.send(connection).unwrap()).unwrap();
+    (idle, receiver)
+}
+
+#[test]
+fn connection_is_ready_when_client_sends_data() {
+    let (idle, ready) = idle_connections();
+    let (mut client, server) = socket_pair();
+
+    idle.park(server);
+
+    assert_eq!(ready.recv_timeout(Duration::from_millis(100)).err(), Some(RecvTimeoutError::Timeout));
+
+    client.write_all(b"Q").unwrap();
+
+    assert!(ready.recv_timeout(Duration::from_secs(5)).is_ok());
+}
+
+#[test]
+fn connection_is_ready_when_data_was_sent_before_parking() {
+    let (idle, ready) = idle_connections();
+    let (mut client, server) = socket_pair();
+
+    client.write_all(b"Q").unwrap();
+    idle.park(server);
+
+    assert!(ready.recv_timeout(Duration::from_secs(5)).is_ok());
+}
+
+#[test]
+fn connection_is_ready_when_client_disconnects() {
+    let (idle, ready) = idle_connections();
+    let (client, server) = socket_pair();
+
+    idle.park(server);
+    drop(client);
+
+    assert!(ready.recv_timeout(Duration::from_secs(5)).is_ok());
+}
+
+#[test]
+fn connection_could_be_parked_again() {
+    let (sender, ready) = mpsc::channel();
+    let idle = IdleConnections::start(move |idle: &IdleConnections<TcpStream>, mut connection: TcpStream| {
+        let mut buff = [0u8; 1];
+        std::io::Read::read_exact(&mut connection, &mut buff).unwrap();
+        sender.send(buff[0]).unwrap();
+        idle.park(connection);
+    })
+    .unwrap();
+    let (mut client, server) = socket_pair();
+
+    idle.park(server);
+
+    client.write_all(b"P").unwrap();
+    assert_eq!(ready.recv_timeout(Duration::from_secs(5)), Ok(b'P'));
+
+    client.write_all(b"S").unwrap();
+    assert_eq!(ready.recv_timeout(Duration::from_secs(5)), Ok(b'S'));
+}
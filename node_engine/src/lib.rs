@@ -12,28 +12,40 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use data_manipulation::{QueryPlan, UntypedQuery};
+use crate::transaction_manager::TransactionContext;
+use data_manipulation::{QueryPlan, SelectQueryPlan, TypedQuery, UntypedQuery};
 use data_repr::scalar::ScalarValue;
+use definition::SearchPath;
 pub use engine::NodeEngine;
-use postgre_sql::query_ast::Query;
+use postgre_sql::{
+    query_ast::Query,
+    query_response::{QueryError, QueryEvent},
+};
 use std::collections::HashMap;
 use types::SqlTypeFamily;
 
 pub mod engine;
+mod idle;
+mod pool;
 mod query_executor;
+pub mod settings;
 mod transaction_manager;
 mod worker;
 
+pub use worker::Worker;
+
 #[derive(Default)]
 pub struct QueryPlanCache {
-    plans: HashMap<String, (Query, Vec<SqlTypeFamily>)>,
+    plans: HashMap<String, (Query, Vec<SqlTypeFamily>, CachedPlan)>,
     extended_query: HashMap<String, PreparedStatementState>,
+    statement_plans: HashMap<String, (UntypedQuery, Vec<SqlTypeFamily>, CachedPlan)>,
     portal_per_statement: HashMap<String, Vec<String>>,
     all_portals: HashMap<String, Portal>,
 }
 
 impl QueryPlanCache {
     pub fn save_parsed(&mut self, name: String, sql: String, query: Query, param_types: Vec<u32>) {
+        self.statement_plans.remove(&name);
         self.extended_query
             .insert(name, PreparedStatementState::Parsed { sql, query, param_types });
     }
@@ -46,11 +58,22 @@ impl QueryPlanCache {
         }
     }
 
-    pub fn save_described(&mut self, name: String, untyped_query: UntypedQuery, sql: String, param_types: Vec<u32>) {
+    pub fn save_described(
+        &mut self,
+        name: String,
+        query: Query,
+        untyped_query: UntypedQuery,
+        search_path: SearchPath,
+        sql: String,
+        param_types: Vec<u32>,
+    ) {
+        self.statement_plans.remove(&name);
         self.extended_query.insert(
             name,
             PreparedStatementState::Described {
+                query: Box::new(query),
                 untyped_query,
+                search_path,
                 sql,
                 param_types,
             },
@@ -64,12 +87,73 @@ impl QueryPlanCache {
                 untyped_query,
                 sql,
                 param_types,
+                ..
             }) => Some((&untyped_query, &sql, &param_types)),
             Some(_) => None,
         }
     }
 
+    /// takes the plan of described statement out of the cache, it is built again
+    /// from the statement when the catalog or the search path has changed since it was cached
+    /// query of a described statement that a portal is bound to, unqualified names are
+    /// resolved again when the search path has changed since the statement was described
+    pub fn bind_statement(&self, name: &str, txn: &TransactionContext) -> Result<(UntypedQuery, Vec<u32>), QueryError> {
+        match self.extended_query.get(name) {
+            Some(PreparedStatementState::Described {
+                query,
+                untyped_query,
+                search_path,
+                param_types,
+                ..
+            }) => {
+                let untyped_query = if *search_path == txn.search_path() {
+                    untyped_query.clone()
+                } else {
+                    txn.analyze(query.as_ref().clone())?
+                };
+                Ok((untyped_query, param_types.clone()))
+            }
+            _ => Err(QueryError::prepared_statement_does_not_exist(name)),
+        }
+    }
+
+    /// plans the query that was bound to the portal, the plan cached for its statement
+    /// is reused only when it was built from the same query and is not outdated
+    pub fn checkout_portal_plan(&mut self, portal_name: &str, txn: &TransactionContext) -> Result<QueryPlan, QueryError> {
+        let portal = match self.all_portals.get(portal_name) {
+            Some(portal) => portal,
+            None => return Err(QueryError::portal_does_not_exist(portal_name)),
+        };
+        let catalog_version = txn.catalog_version();
+        let search_path = txn.search_path();
+        if let Some((untyped_query, param_types, cached_plan)) = self.statement_plans.get_mut(&portal.statement_name) {
+            if *untyped_query == portal.untyped_query && *param_types == portal.param_types && !cached_plan.is_outdated(catalog_version, &search_path)
+            {
+                return Ok(cached_plan.checkout(txn));
+            }
+        }
+        let typed_query = txn.process_untyped_query(portal.untyped_query.clone(), portal.param_types.clone())?;
+        let mut cached_plan = CachedPlan::new(catalog_version, search_path, typed_query);
+        let query_plan = cached_plan.checkout(txn);
+        self.statement_plans.insert(
+            portal.statement_name.clone(),
+            (portal.untyped_query.clone(), portal.param_types.clone(), cached_plan),
+        );
+        Ok(query_plan)
+    }
+
+    pub fn checkin_portal_plan(&mut self, portal_name: &str, query_plan: QueryPlan) {
+        if let Some(portal) = self.all_portals.get(portal_name) {
+            if let Some((untyped_query, param_types, cached_plan)) = self.statement_plans.get_mut(&portal.statement_name) {
+                if *untyped_query == portal.untyped_query && *param_types == portal.param_types {
+                    cached_plan.checkin(query_plan);
+                }
+            }
+        }
+    }
+
     pub fn bind_portal(&mut self, statement_name: String, portal_name: String, portal: Portal) {
+        self.close_portal(&portal_name);
         self.portal_per_statement
             .entry(statement_name)
             .or_insert_with(Vec::new)
@@ -77,23 +161,85 @@ impl QueryPlanCache {
         self.all_portals.insert(portal_name, portal);
     }
 
-    pub fn find_portal(&self, portal: &str) -> Option<Portal> {
-        self.all_portals.get(portal).map(Portal::clone)
+    pub fn find_portal(&mut self, portal: &str) -> Option<&mut Portal> {
+        self.all_portals.get_mut(portal)
+    }
+
+    pub fn close_portal(&mut self, portal: &str) -> Option<Portal> {
+        for portals in self.portal_per_statement.values_mut() {
+            portals.retain(|name| name != portal);
+        }
+        self.all_portals.remove(portal)
+    }
+
+    pub fn close_statement(&mut self, name: &str) -> Option<PreparedStatementState> {
+        if let Some(portals) = self.portal_per_statement.remove(name) {
+            for portal in portals {
+                self.all_portals.remove(&portal);
+            }
+        }
+        self.statement_plans.remove(name);
+        self.extended_query.remove(name)
+    }
+
+    pub fn close_all_portals(&mut self) {
+        self.portal_per_statement.clear();
+        self.all_portals.clear();
     }
 
-    pub fn allocate(&mut self, name: String, _query_plan: QueryPlan, query_ast: Query, params: Vec<SqlTypeFamily>) {
-        self.plans.insert(name, (query_ast, params));
+    pub fn allocate(&mut self, name: String, cached_plan: CachedPlan, query_ast: Query, params: Vec<SqlTypeFamily>) {
+        self.plans.insert(name, (query_ast, params, cached_plan));
     }
 
-    pub fn lookup(&self, name: &str) -> Option<&(Query, Vec<SqlTypeFamily>)> {
-        self.plans.get(name)
+    pub fn lookup(&mut self, name: &str) -> Option<&mut (Query, Vec<SqlTypeFamily>, CachedPlan)> {
+        self.plans.get_mut(name)
     }
 
-    pub fn deallocate(&mut self, name: &str) -> Option<(Query, Vec<SqlTypeFamily>)> {
+    pub fn deallocate(&mut self, name: &str) -> Option<(Query, Vec<SqlTypeFamily>, CachedPlan)> {
         self.plans.remove(name)
     }
 }
 
+/// Typed query of a prepared statement and its plan that is reused between executions
+pub struct CachedPlan {
+    catalog_version: u64,
+    search_path: SearchPath,
+    typed_query: TypedQuery,
+    query_plan: Option<QueryPlan>,
+}
+
+impl CachedPlan {
+    pub fn new(catalog_version: u64, search_path: SearchPath, typed_query: TypedQuery) -> CachedPlan {
+        CachedPlan {
+            catalog_version,
+            search_path,
+            typed_query,
+            query_plan: None,
+        }
+    }
+
+    /// the plan refers to tables and columns that could have been dropped or altered since,
+    /// or that unqualified names resolve to in other schemas now
+    pub fn is_outdated(&self, catalog_version: u64, search_path: &SearchPath) -> bool {
+        self.catalog_version != catalog_version || self.search_path != *search_path
+    }
+
+    /// a new plan is built when the cached one is still used, e.g. by a suspended portal
+    pub fn checkout(&mut self, txn: &TransactionContext) -> QueryPlan {
+        match self.query_plan.take() {
+            Some(mut query_plan) => {
+                query_plan.reset();
+                query_plan
+            }
+            None => txn.plan(self.typed_query.clone()),
+        }
+    }
+
+    pub fn checkin(&mut self, query_plan: QueryPlan) {
+        self.query_plan = Some(query_plan);
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum PreparedStatementState {
     Parsed {
@@ -103,15 +249,25 @@ pub enum PreparedStatementState {
     },
     Described {
         sql: String,
+        query: Box<Query>,
         untyped_query: UntypedQuery,
+        search_path: SearchPath,
         param_types: Vec<u32>,
     },
 }
 
-#[derive(Clone)]
 pub struct Portal {
+    pub statement_name: String,
     pub untyped_query: UntypedQuery,
     pub result_value_formats: Vec<i16>,
     pub arguments: Vec<ScalarValue>,
     pub param_types: Vec<SqlTypeFamily>,
+    pub state: PortalState,
+}
+
+pub enum PortalState {
+    Ready,
+    Suspended(SelectQueryPlan),
+    /// executing the portal again only sends its command completion
+    Completed(QueryEvent),
 }
@@ -0,0 +1,61 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Fixed number of threads that run jobs in the order they were submitted.
+/// Jobs wait in the queue while all threads are busy.
+#[derive(Clone)]
+pub(crate) struct WorkerPool {
+    sender: Sender<Job>,
+}
+
+impl WorkerPool {
+    pub(crate) fn new(size: usize) -> WorkerPool {
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..size {
+            spawn(receiver.clone());
+        }
+        WorkerPool { sender }
+    }
+
+    pub(crate) fn execute<F: FnOnce() + Send + 'static>(&self, job: F) {
+        self.sender.send(Box::new(job)).expect("pool threads are alive");
+    }
+}
+
+fn spawn(receiver: Arc<Mutex<Receiver<Job>>>) {
+    thread::spawn(move || loop {
+        let job = match receiver.lock().unwrap().recv() {
+            Ok(job) => job,
+            Err(_) => break,
+        };
+        if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+            log::error!("pool job panicked");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests;
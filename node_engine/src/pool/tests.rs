@@ -0,0 +1,63 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use std::{sync::mpsc, thread::ThreadId, time::Duration};
+
+#[test]
+fn threads_are_reused() {
+    let pool = WorkerPool::new(1);
+    let (sender, receiver) = mpsc::channel::<ThreadId>();
+
+    let first = sender.clone();
+    pool.execute(move || first.send(thread::current().id()).unwrap());
+    let first_thread = receiver.recv().unwrap();
+
+    pool.execute(move || sender.send(thread::current().id()).unwrap());
+    let second_thread = receiver.recv().unwrap();
+
+    assert_eq!(first_thread, second_thread);
+}
+
+#[test]
+fn jobs_wait_for_a_free_thread() {
+    let pool = WorkerPool::new(1);
+    let (release, released) = mpsc::channel::<()>();
+    let (sender, receiver) = mpsc::channel::<&str>();
+
+    let first = sender.clone();
+    pool.execute(move || {
+        released.recv().unwrap();
+        first.send("first").unwrap();
+    });
+    pool.execute(move || sender.send("second").unwrap());
+
+    assert_eq!(receiver.recv_timeout(Duration::from_millis(100)), Err(mpsc::RecvTimeoutError::Timeout));
+
+    release.send(()).unwrap();
+
+    assert_eq!(receiver.recv(), Ok("first"));
+    assert_eq!(receiver.recv(), Ok("second"));
+}
+
+#[test]
+fn thread_survives_panicked_job() {
+    let pool = WorkerPool::new(1);
+    let (sender, receiver) = mpsc::channel::<()>();
+
+    pool.execute(|| panic!("connection is broken"));
+    pool.execute(move || sender.send(()).unwrap());
+
+    assert_eq!(receiver.recv(), Ok(()));
+}
@@ -12,20 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{transaction_manager::TransactionContext, QueryPlanCache};
-use data_manipulation::{QueryExecutionError, QueryExecutionResult, UntypedQuery};
+use crate::{transaction_manager::TransactionContext, CachedPlan, Portal, PortalState, QueryPlanCache};
+use data_manipulation::{QueryExecutionError, QueryExecutionResult, QueryPlan, UntypedQuery};
 use data_repr::scalar::ScalarValue;
 use postgre_sql::{
-    query_ast::{Extended, Query, Statement},
+    query_ast::{Explain, ExplainFormat, Extended, Query, Statement},
     query_response::{QueryError, QueryEvent},
-    wire_protocol::payload::OutboundMessage,
+    wire_protocol::payload::{OutboundMessage, VARCHAR},
 };
 use types::{SqlType, SqlTypeFamily};
 
 pub struct QueryExecutor;
 
 impl QueryExecutor {
-    pub fn describe_statement(&self, query: Query, txn: &TransactionContext) -> (UntypedQuery, Vec<u32>, Vec<OutboundMessage>) {
+    pub fn describe_statement(
+        &self,
+        query: Query,
+        param_types: Vec<u32>,
+        txn: &TransactionContext,
+    ) -> (UntypedQuery, Vec<u32>, Vec<OutboundMessage>) {
         let mut responses = vec![];
         let (untyped_query, params) = match txn.analyze(query) {
             Ok(UntypedQuery::Insert(insert)) => {
@@ -40,87 +45,215 @@ impl QueryExecutor {
                 responses.push(OutboundMessage::StatementParameters(param_types.to_vec()));
                 (UntypedQuery::Update(update), param_types)
             }
+            Ok(UntypedQuery::Select(select)) => {
+                responses.push(OutboundMessage::StatementParameters(param_types.to_vec()));
+                let param_families = param_types.iter().map(From::from).collect::<Vec<SqlTypeFamily>>();
+                responses.push(OutboundMessage::StatementDescription(txn.describe_select(&select, &param_families)));
+                (UntypedQuery::Select(select), param_types)
+            }
+            Ok(UntypedQuery::SetOperation(set_operation)) => {
+                responses.push(OutboundMessage::StatementParameters(param_types.to_vec()));
+                let param_families = param_types.iter().map(From::from).collect::<Vec<SqlTypeFamily>>();
+                responses.push(OutboundMessage::StatementDescription(
+                    txn.describe_set_operation(&set_operation, &param_families),
+                ));
+                (UntypedQuery::SetOperation(set_operation), param_types)
+            }
             other => unimplemented!("{:?}", other),
         };
         (untyped_query, params, responses)
     }
 
+    pub fn describe_portal(&self, portal: &Portal, txn: &TransactionContext) -> OutboundMessage {
+        match &portal.untyped_query {
+            UntypedQuery::Select(select) => OutboundMessage::StatementDescription(txn.describe_select(select, &portal.param_types)),
+            UntypedQuery::SetOperation(set_operation) => {
+                OutboundMessage::StatementDescription(txn.describe_set_operation(set_operation, &portal.param_types))
+            }
+            _ => OutboundMessage::StatementDescription(vec![]),
+        }
+    }
+
     pub fn execute_portal(
         &self,
-        untyped_query: UntypedQuery,
-        params: Vec<SqlTypeFamily>,
-        arguments: Vec<ScalarValue>,
+        portal_name: &str,
+        max_rows: i32,
         txn: &TransactionContext,
-        _query_plan_cache: &mut QueryPlanCache,
-    ) -> Vec<OutboundMessage> {
-        let mut responses = vec![];
-        let typed_query = txn.process_untyped_query(untyped_query, params).unwrap();
-        let query_plan = txn.plan(typed_query);
-        match query_plan.execute(arguments) {
-            Ok(success) => {
-                let events: Vec<QueryEvent> = success.into();
-                let messages: Vec<OutboundMessage> = events.into_iter().map(QueryEvent::into).collect();
-                responses.extend(messages);
+        query_plan_cache: &mut QueryPlanCache,
+        sender: &mut dyn FnMut(OutboundMessage),
+    ) {
+        let state = match query_plan_cache.find_portal(portal_name) {
+            None => {
+                sender(QueryError::portal_does_not_exist(portal_name).into());
+                return;
+            }
+            Some(portal) => std::mem::replace(&mut portal.state, PortalState::Completed(QueryEvent::RecordsSelected(0))),
+        };
+        let (mut select_plan, resumed) = match state {
+            PortalState::Completed(completion) => {
+                let portal = query_plan_cache.find_portal(portal_name).unwrap();
+                portal.state = PortalState::Completed(completion.clone());
+                sender(completion.into());
+                return;
+            }
+            PortalState::Suspended(select_plan) => (select_plan, true),
+            PortalState::Ready => match query_plan_cache.checkout_portal_plan(portal_name, txn) {
+                Err(error) => {
+                    sender(error.into());
+                    return;
+                }
+                Ok(QueryPlan::Select(select_plan)) => (select_plan, false),
+                Ok(mut query_plan) => {
+                    let portal = query_plan_cache.find_portal(portal_name).unwrap();
+                    match query_plan.execute(txn.evaluation_context(portal.arguments.clone()), &mut |event| sender(event.into())) {
+                        Ok(success) => {
+                            portal.state = PortalState::Completed(QueryEvent::from(match success {
+                                QueryExecutionResult::Inserted(_) => QueryExecutionResult::Inserted(0),
+                                QueryExecutionResult::Deleted(_) => QueryExecutionResult::Deleted(0),
+                                QueryExecutionResult::Updated(_) => QueryExecutionResult::Updated(0),
+                                QueryExecutionResult::Selected(_) => QueryExecutionResult::Selected(0),
+                            }));
+                            sender(QueryEvent::from(success).into())
+                        }
+                        Err(failure) => sender(QueryError::from(failure).into()),
+                    }
+                    query_plan_cache.checkin_portal_plan(portal_name, query_plan);
+                    return;
+                }
+            },
+        };
+        let portal = query_plan_cache.find_portal(portal_name).unwrap();
+        let context = txn.evaluation_context(portal.arguments.clone());
+        let mut selected = 0;
+        loop {
+            if max_rows > 0 && selected == max_rows as usize {
+                portal.state = PortalState::Suspended(select_plan);
+                sender(OutboundMessage::PortalSuspended);
+                return;
+            }
+            match select_plan.next_row(&context) {
+                Ok(Some(row)) => {
+                    sender(OutboundMessage::DataRow(row.into_iter().map(|scalar| scalar.as_text()).collect()));
+                    selected += 1;
+                }
+                Ok(None) => {
+                    sender(OutboundMessage::RecordsSelected(selected));
+                    break;
+                }
+                Err(failure) => {
+                    sender(QueryError::from(failure).into());
+                    break;
+                }
             }
-            Err(_) => unimplemented!(),
         }
-        responses
+        // catalog could have changed while the portal was suspended
+        if !resumed {
+            query_plan_cache.checkin_portal_plan(portal_name, QueryPlan::Select(select_plan));
+        }
     }
 
-    pub fn execute_statement(&self, statement: Statement, txn: &TransactionContext, query_plan_cache: &mut QueryPlanCache) -> Vec<OutboundMessage> {
-        let mut responses = vec![];
+    pub fn execute_statement(
+        &self,
+        statement: Statement,
+        txn: &TransactionContext,
+        query_plan_cache: &mut QueryPlanCache,
+        sender: &mut dyn FnMut(OutboundMessage),
+    ) {
         match statement {
             Statement::Definition(definition) => match txn.apply_schema_change(definition) {
-                Ok(success) => responses.push(success.into()),
-                Err(failure) => responses.push(failure.into()),
+                Ok(success) => sender(success.into()),
+                Err(failure) => sender(failure.into()),
             },
             Statement::Extended(extended) => match extended {
                 Extended::Prepare { query, name, param_types } => {
                     let params: Vec<SqlTypeFamily> = param_types.into_iter().map(|dt| SqlType::from(dt).family()).collect();
-                    let typed_query = txn.process(query.clone(), params.clone()).unwrap();
-                    let query_plan = txn.plan(typed_query);
-                    query_plan_cache.allocate(name, query_plan, query, params);
-                    responses.push(OutboundMessage::StatementPrepared);
+                    match txn.process(query.clone(), params.clone()) {
+                        Ok(typed_query) => {
+                            query_plan_cache.allocate(
+                                name,
+                                CachedPlan::new(txn.catalog_version(), txn.search_path(), typed_query),
+                                query,
+                                params,
+                            );
+                            sender(OutboundMessage::StatementPrepared);
+                        }
+                        Err(failure) => sender(failure.into()),
+                    }
                 }
-                Extended::Execute { name, param_values } => {
-                    match query_plan_cache.lookup(&name) {
-                        None => responses.push(QueryError::prepared_statement_does_not_exist(&name).into()),
-                        // TODO: workaround situation that QueryPlan is not cloneable ¯\_(ツ)_/¯
-                        Some((query, params)) => {
-                            let typed_query = txn.process(query.clone(), params.clone()).unwrap();
-                            let query_plan = txn.plan(typed_query);
-                            match query_plan.execute(param_values.into_iter().map(ScalarValue::from).collect()) {
-                                Ok(QueryExecutionResult::Inserted(inserted)) => responses.push(OutboundMessage::RecordsInserted(inserted)),
-                                Ok(_) => {}
-                                Err(_) => unimplemented!(),
+                Extended::Execute { name, param_values } => match query_plan_cache.lookup(&name) {
+                    None => sender(QueryError::prepared_statement_does_not_exist(&name).into()),
+                    Some((query, params, cached_plan)) => {
+                        if cached_plan.is_outdated(txn.catalog_version(), &txn.search_path()) {
+                            match txn.process(query.clone(), params.clone()) {
+                                Ok(typed_query) => *cached_plan = CachedPlan::new(txn.catalog_version(), txn.search_path(), typed_query),
+                                Err(failure) => {
+                                    sender(failure.into());
+                                    return;
+                                }
                             }
                         }
+                        let mut query_plan = cached_plan.checkout(txn);
+                        match query_plan.execute(
+                            txn.evaluation_context(param_values.into_iter().map(ScalarValue::from).collect()),
+                            &mut |event| sender(event.into()),
+                        ) {
+                            Ok(success) => sender(QueryEvent::from(success).into()),
+                            Err(failure) => sender(QueryError::from(failure).into()),
+                        }
+                        cached_plan.checkin(query_plan);
                     }
-                }
+                },
                 Extended::Deallocate { name } => match query_plan_cache.deallocate(&name) {
-                    None => responses.push(QueryError::prepared_statement_does_not_exist(&name).into()),
-                    Some(_) => responses.push(OutboundMessage::StatementDeallocated),
+                    None => sender(QueryError::prepared_statement_does_not_exist(&name).into()),
+                    Some(_) => sender(OutboundMessage::StatementDeallocated),
                 },
             },
             Statement::Query(query) => {
-                let query_events: Vec<OutboundMessage> = match txn
-                    .process(query, vec![])
-                    .map(|typed_query| txn.plan(typed_query))
-                    .and_then(|plan| plan.execute(vec![]).map_err(QueryExecutionError::into))
-                {
-                    Ok(success) => {
-                        let events: Vec<QueryEvent> = success.into();
-                        events.into_iter().map(OutboundMessage::from).collect()
-                    }
-                    Err(failure) => {
-                        vec![failure.into()]
+                match txn.process(query, vec![]).map(|typed_query| txn.plan(typed_query)).and_then(|mut plan| {
+                    plan.execute(txn.evaluation_context(vec![]), &mut |event| sender(event.into()))
+                        .map_err(QueryExecutionError::into)
+                }) {
+                    Ok(success) => sender(QueryEvent::from(success).into()),
+                    Err(failure) => sender(failure.into()),
+                }
+            }
+            Statement::Explain(explain) => self.explain(explain, txn, sender),
+        }
+        sender(OutboundMessage::ReadyForQuery);
+    }
+
+    fn explain(&self, explain: Explain, txn: &TransactionContext, sender: &mut dyn FnMut(OutboundMessage)) {
+        let Explain {
+            query,
+            analyze,
+            verbose,
+            format,
+        } = explain;
+        let explained = txn.process(query, vec![]).and_then(|typed_query| {
+            if analyze {
+                txn.plan_instrumented(typed_query)
+                    .explain_analyze(txn.evaluation_context(vec![]))
+                    .map(|(plan, execution_time)| (plan, Some(execution_time)))
+                    .map_err(QueryExecutionError::into)
+            } else {
+                Ok((txn.plan(typed_query).explain(), None))
+            }
+        });
+        match explained {
+            Ok((plan, execution_time)) => {
+                sender(OutboundMessage::RowDescription(vec![("QUERY PLAN".to_owned(), VARCHAR)]));
+                match format {
+                    ExplainFormat::Text => {
+                        for line in plan.to_text(verbose, execution_time) {
+                            sender(OutboundMessage::DataRow(vec![line]));
+                        }
                     }
-                };
-                responses.extend(query_events);
+                    ExplainFormat::Json => sender(OutboundMessage::DataRow(vec![plan.to_json(verbose, execution_time)])),
+                }
+                sender(OutboundMessage::QueryExplained);
             }
+            Err(failure) => sender(failure.into()),
         }
-        responses.push(OutboundMessage::ReadyForQuery);
-        responses
     }
 }
 
@@ -18,26 +18,38 @@ use postgre_sql::{
     query_ast::Request,
     query_parser::QueryParser,
     query_response::QueryError,
-    wire_protocol::payload::{BIGINT, CHAR, INT, SMALLINT, VARCHAR},
+    wire_protocol::payload::{BIGINT, BOOL, CHAR, INT, SMALLINT, TIMESTAMPTZ, VARCHAR},
 };
 use storage::Database;
 
 #[cfg(test)]
 mod delete;
 #[cfg(test)]
+mod explain;
+#[cfg(test)]
+mod function;
+#[cfg(test)]
 mod insert;
 #[cfg(test)]
+mod materialized_view;
+#[cfg(test)]
 mod prepared_statement;
 #[cfg(test)]
 mod schema;
 #[cfg(test)]
 mod select;
 #[cfg(test)]
+mod sequence;
+#[cfg(test)]
+mod system_views;
+#[cfg(test)]
 mod table;
 #[cfg(test)]
 mod type_constraints;
 #[cfg(test)]
 mod update;
+#[cfg(test)]
+mod view;
 
 fn small_int(value: i16) -> String {
     value.to_string()
@@ -59,7 +71,9 @@ fn assert_cached_statement(query_plan_cache: &mut QueryPlanCache, txn: &Transact
     let executor = QueryExecutor;
     match QueryParser.parse(sql) {
         Ok(Request::Statement(statement)) => {
-            assert_eq!(executor.execute_statement(statement, txn, query_plan_cache), expected);
+            let mut responses = vec![];
+            executor.execute_statement(statement, txn, query_plan_cache, &mut |outbound| responses.push(outbound));
+            assert_eq!(responses, expected);
         }
         other => panic!("expected DDL query but was {:?}", other),
     }
@@ -70,7 +84,9 @@ fn assert_statement(txn: &TransactionContext, sql: &str, expected: Vec<OutboundM
     let mut query_plan_cache = QueryPlanCache::default();
     match QueryParser.parse(sql) {
         Ok(Request::Statement(statement)) => {
-            assert_eq!(executor.execute_statement(statement, txn, &mut query_plan_cache), expected);
+            let mut responses = vec![];
+            executor.execute_statement(statement, txn, &mut query_plan_cache, &mut |outbound| responses.push(outbound));
+            assert_eq!(responses, expected);
         }
         other => panic!("expected DDL query but was {:?}", other),
     }
@@ -0,0 +1,271 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn explain(txn: &TransactionContext, sql: &str) -> Vec<String> {
+    let executor = QueryExecutor;
+    let mut query_plan_cache = QueryPlanCache::default();
+    let mut responses = vec![];
+    match QueryParser.parse(sql) {
+        Ok(Request::Statement(statement)) => {
+            executor.execute_statement(statement, txn, &mut query_plan_cache, &mut |outbound| responses.push(outbound))
+        }
+        other => panic!("expected EXPLAIN query but was {:?}", other),
+    }
+    assert_eq!(
+        responses.first(),
+        Some(&OutboundMessage::RowDescription(vec![("QUERY PLAN".to_owned(), VARCHAR)]))
+    );
+    assert_eq!(
+        responses[responses.len() - 2..],
+        [OutboundMessage::QueryExplained, OutboundMessage::ReadyForQuery]
+    );
+    responses
+        .into_iter()
+        .filter_map(|outbound| match outbound {
+            OutboundMessage::DataRow(mut row) => Some(row.remove(0)),
+            _ => None,
+        })
+        .collect()
+}
+
+// timing differs from run to run, so it is replaced with `_`
+fn without_timing(line: String) -> String {
+    let mut result = String::new();
+    let mut rest = line.as_str();
+    while let Some(start) = rest.find("time=").or_else(|| rest.find("Time: ")) {
+        let value_start = start + rest[start..].find(|c: char| c.is_ascii_digit()).unwrap();
+        let value_end = value_start + rest[value_start..].find(' ').unwrap_or(rest.len() - value_start);
+        result.push_str(&rest[..value_start]);
+        result.push('_');
+        rest = &rest[value_end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[rstest::fixture]
+fn with_table(with_schema: TransactionManager) -> TransactionManager {
+    let txn = with_schema.start_transaction();
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 smallint, column_2 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 4), (2, 5), (3, 6);",
+        vec![OutboundMessage::RecordsInserted(3), OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+    with_schema
+}
+
+#[rstest::rstest]
+fn explain_select(with_table: TransactionManager) {
+    let txn = with_table.start_transaction();
+
+    assert_eq!(
+        explain(&txn, "explain select column_1 from schema_name.table_name where column_1 > 1;"),
+        vec![
+            "Select  (rows=1)",
+            "  ->  Filter  (rows=1)",
+            "        Filter: (column_1 > 1)",
+            "        ->  Projection  (rows=3)",
+            "              ->  Full Table Scan on schema_name.table_name  (rows=3)",
+        ]
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn explain_select_with_simplified_filter(with_table: TransactionManager) {
+    let txn = with_table.start_transaction();
+
+    assert_eq!(
+        explain(
+            &txn,
+            "explain select column_1 from schema_name.table_name where true and 1 + 1 < column_1;"
+        ),
+        vec![
+            "Select  (rows=1)",
+            "  ->  Filter  (rows=1)",
+            "        Filter: (column_1 > 2)",
+            "        ->  Projection  (rows=3)",
+            "              ->  Full Table Scan on schema_name.table_name  (rows=3)",
+        ]
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn explain_select_after_analyze(with_table: TransactionManager) {
+    let txn = with_table.start_transaction();
+    assert_statement(
+        &txn,
+        "analyze schema_name.table_name;",
+        vec![OutboundMessage::TablesAnalyzed, OutboundMessage::ReadyForQuery],
+    );
+
+    assert_eq!(
+        explain(&txn, "explain select column_1 from schema_name.table_name where column_1 > 2;"),
+        vec![
+            "Select  (rows=2)",
+            "  ->  Filter  (rows=2)",
+            "        Filter: (column_1 > 2)",
+            "        ->  Projection  (rows=3)",
+            "              ->  Full Table Scan on schema_name.table_name  (rows=3)",
+        ]
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn explain_verbose_select(with_table: TransactionManager) {
+    let txn = with_table.start_transaction();
+
+    assert_eq!(
+        explain(&txn, "explain verbose select column_2, column_1 from schema_name.table_name;"),
+        vec![
+            "Select  (rows=3)",
+            "  Output: column_2, column_1",
+            "  ->  Filter  (rows=3)",
+            "        ->  Projection  (rows=3)",
+            "              ->  Full Table Scan on schema_name.table_name  (rows=3)",
+        ]
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn explain_does_not_execute_query(with_table: TransactionManager) {
+    let txn = with_table.start_transaction();
+
+    assert_eq!(
+        explain(&txn, "explain delete from schema_name.table_name;"),
+        vec![
+            "Delete on schema_name.table_name  (rows=3)",
+            "  ->  Table Record Keys  (rows=3)",
+            "        ->  Filter  (rows=3)",
+            "              ->  Projection  (rows=3)",
+            "                    ->  Full Table Scan on schema_name.table_name  (rows=3)",
+        ]
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT), ("column_2".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(1), small_int(4)]),
+            OutboundMessage::DataRow(vec![small_int(2), small_int(5)]),
+            OutboundMessage::DataRow(vec![small_int(3), small_int(6)]),
+            OutboundMessage::RecordsSelected(3),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn explain_analyze_select(with_table: TransactionManager) {
+    let txn = with_table.start_transaction();
+
+    assert_eq!(
+        explain(&txn, "explain analyze select column_1 from schema_name.table_name where column_1 > 1;")
+            .into_iter()
+            .map(without_timing)
+            .collect::<Vec<String>>(),
+        vec![
+            "Select  (rows=1) (actual time=_ rows=2 loops=1)",
+            "  ->  Filter  (rows=1) (actual time=_ rows=2 loops=1)",
+            "        Filter: (column_1 > 1)",
+            "        ->  Projection  (rows=3) (actual time=_ rows=3 loops=1)",
+            "              ->  Full Table Scan on schema_name.table_name  (rows=3) (actual time=_ rows=3 loops=1)",
+            "Execution Time: _ ms",
+        ]
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn explain_analyze_executes_query(with_table: TransactionManager) {
+    let txn = with_table.start_transaction();
+
+    assert_eq!(
+        explain(&txn, "explain analyze delete from schema_name.table_name where column_1 > 1;")
+            .into_iter()
+            .map(without_timing)
+            .collect::<Vec<String>>(),
+        vec![
+            "Delete on schema_name.table_name  (rows=1) (actual time=_ rows=2 loops=1)",
+            "  ->  Table Record Keys  (rows=1) (actual time=_ rows=2 loops=1)",
+            "        ->  Filter  (rows=1) (actual time=_ rows=2 loops=1)",
+            "              Filter: (column_1 > 1)",
+            "              ->  Projection  (rows=3) (actual time=_ rows=3 loops=1)",
+            "                    ->  Full Table Scan on schema_name.table_name  (rows=3) (actual time=_ rows=3 loops=1)",
+            "Execution Time: _ ms",
+        ]
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT), ("column_2".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(1), small_int(4)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn explain_in_json_format(with_table: TransactionManager) {
+    let txn = with_table.start_transaction();
+
+    assert_eq!(
+        explain(&txn, "explain (format json) insert into schema_name.table_name values (4, 7);"),
+        vec![
+            r#"[
+  {
+    "Plan": {
+      "Node Type": "Insert",
+      "Relation Name": "schema_name.table_name",
+      "Plan Rows": 1,
+      "Plans": [
+        {
+          "Node Type": "Constraint Validator",
+          "Plan Rows": 1,
+          "Plans": [
+            {
+              "Node Type": "Static Expression Eval",
+              "Plan Rows": 1,
+              "Plans": [
+                {
+                  "Node Type": "Static Values",
+                  "Plan Rows": 1
+                }
+              ]
+            }
+          ]
+        }
+      ]
+    }
+  }
+]"#
+        ]
+    );
+    txn.commit();
+}
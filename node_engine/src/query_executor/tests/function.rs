@@ -0,0 +1,143 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[rstest::fixture]
+fn with_function(with_schema: TransactionManager) -> TransactionManager {
+    let txn = with_schema.start_transaction();
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 smallint, column_2 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 4), (2, 5), (3, 6);",
+        vec![OutboundMessage::RecordsInserted(3), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create function schema_name.add_ints(a integer, b integer) returns integer language sql as 'select a + b';",
+        vec![OutboundMessage::FunctionCreated, OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+    with_schema
+}
+
+#[rstest::rstest]
+fn select_with_function(with_function: TransactionManager) {
+    let txn = with_function.start_transaction();
+
+    assert_statement(
+        &txn,
+        "select schema_name.add_ints(column_1, column_2) as total from schema_name.table_name where column_1 > 1;",
+        vec![
+            OutboundMessage::RowDescription(vec![("total".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![integer(7)]),
+            OutboundMessage::DataRow(vec![integer(9)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn create_function_that_already_exists(with_function: TransactionManager) {
+    let txn = with_function.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create function schema_name.add_ints(a integer, b integer) returns integer language sql as 'select a - b';",
+        vec![
+            QueryError::function_already_exists("schema_name.add_ints").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "create or replace function schema_name.add_ints(a integer, b integer) returns integer language sql as 'select a - b';",
+        vec![OutboundMessage::FunctionCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select schema_name.add_ints(column_2, column_1) from schema_name.table_name where column_1 = 1;",
+        vec![
+            OutboundMessage::RowDescription(vec![("add_ints".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![integer(3)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn drop_function(with_function: TransactionManager) {
+    let txn = with_function.start_transaction();
+
+    assert_statement(
+        &txn,
+        "drop function schema_name.add_ints;",
+        vec![OutboundMessage::FunctionDropped, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "drop function schema_name.add_ints;",
+        vec![
+            QueryError::function_does_not_exist("schema_name.add_ints").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "drop function if exists schema_name.add_ints;",
+        vec![OutboundMessage::FunctionDropped, OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn drop_function_with_dependent_view(with_function: TransactionManager) {
+    let txn = with_function.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create view schema_name.view_name as select schema_name.add_ints(column_1, column_2) as total from schema_name.table_name;",
+        vec![OutboundMessage::ViewCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "drop function schema_name.add_ints;",
+        vec![
+            QueryError::dependent_objects_still_exist("function schema_name.add_ints").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "drop function schema_name.add_ints cascade;",
+        vec![OutboundMessage::FunctionDropped, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "drop view schema_name.view_name;",
+        vec![
+            QueryError::view_does_not_exist("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
@@ -371,6 +371,18 @@ mod operators {
             );
         }
 
+        #[rstest::rstest]
+        fn division_by_zero(with_table: TransactionManager) {
+            let txn = with_table.start_transaction();
+
+            assert_statement(
+                &txn,
+                "insert into schema_name.table_name values (8 / (2 - 2));",
+                vec![QueryError::division_by_zero().into(), OutboundMessage::ReadyForQuery],
+            );
+            txn.commit();
+        }
+
         #[rstest::rstest]
         fn modulo(with_table: TransactionManager) {
             let txn = with_table.start_transaction();
@@ -0,0 +1,193 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[rstest::fixture]
+fn with_materialized_view(with_schema: TransactionManager) -> TransactionManager {
+    let txn = with_schema.start_transaction();
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 smallint, column_2 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 4), (2, 5), (3, 6);",
+        vec![OutboundMessage::RecordsInserted(3), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create materialized view schema_name.view_name (view_column) as select column_2, column_1 from schema_name.table_name where column_1 > 1;",
+        vec![OutboundMessage::MaterializedViewCreated(2), OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+    with_schema
+}
+
+#[rstest::rstest]
+fn select_from_materialized_view(with_materialized_view: TransactionManager) {
+    let txn = with_materialized_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "select * from schema_name.view_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("view_column".to_owned(), SMALLINT), ("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(5), small_int(2)]),
+            OutboundMessage::DataRow(vec![small_int(6), small_int(3)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn materialized_view_is_not_updated_until_refresh(with_materialized_view: TransactionManager) {
+    let txn = with_materialized_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (4, 7);",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select column_1 from schema_name.view_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(2)]),
+            OutboundMessage::DataRow(vec![small_int(3)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "refresh materialized view schema_name.view_name;",
+        vec![OutboundMessage::MaterializedViewRefreshed, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select column_1 from schema_name.view_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(2)]),
+            OutboundMessage::DataRow(vec![small_int(3)]),
+            OutboundMessage::DataRow(vec![small_int(4)]),
+            OutboundMessage::RecordsSelected(3),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn refresh_materialized_view_concurrently(with_materialized_view: TransactionManager) {
+    let txn = with_materialized_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "delete from schema_name.table_name where column_1 = 2;",
+        vec![OutboundMessage::RecordsDeleted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "refresh materialized view concurrently schema_name.view_name;",
+        vec![OutboundMessage::MaterializedViewRefreshed, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select column_1 from schema_name.view_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(3)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn refresh_nonexistent_materialized_view(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "refresh materialized view schema_name.view_name;",
+        vec![
+            QueryError::materialized_view_does_not_exist("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn drop_materialized_view(with_materialized_view: TransactionManager) {
+    let txn = with_materialized_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "drop view schema_name.view_name;",
+        vec![
+            QueryError::view_does_not_exist("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "drop table schema_name.view_name;",
+        vec![
+            QueryError::table_does_not_exist("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "drop materialized view schema_name.view_name;",
+        vec![OutboundMessage::MaterializedViewDropped, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.view_name;",
+        vec![
+            QueryError::table_does_not_exist("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn drop_table_cascades_to_materialized_view(with_materialized_view: TransactionManager) {
+    let txn = with_materialized_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "drop table schema_name.table_name cascade;",
+        vec![OutboundMessage::TableDropped, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.view_name;",
+        vec![
+            QueryError::table_does_not_exist("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
@@ -64,6 +64,108 @@ fn prepare_execute_and_deallocate(with_schema: TransactionManager) {
     );
 }
 
+#[rstest::rstest]
+fn execute_prepared_select_multiple_times(with_schema: TransactionManager) {
+    let mut query_plan_cache = QueryPlanCache::default();
+    let txn = with_schema.start_transaction();
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "create table schema_name.table_name (column_1 smallint)",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "prepare foo_plan (smallint) as select * from schema_name.table_name where column_1 > $1",
+        vec![OutboundMessage::StatementPrepared, OutboundMessage::ReadyForQuery],
+    );
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "execute foo_plan (0)",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::RecordsSelected(0),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "insert into schema_name.table_name values (1), (2)",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "execute foo_plan (0)",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(1)]),
+            OutboundMessage::DataRow(vec![small_int(2)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+}
+
+#[rstest::rstest]
+fn execute_prepared_statement_after_table_dropped(with_schema: TransactionManager) {
+    let mut query_plan_cache = QueryPlanCache::default();
+    let txn = with_schema.start_transaction();
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "create table schema_name.table_name (column_1 smallint)",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "prepare foo_plan (smallint) as insert into schema_name.table_name values ($1)",
+        vec![OutboundMessage::StatementPrepared, OutboundMessage::ReadyForQuery],
+    );
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "drop table schema_name.table_name",
+        vec![OutboundMessage::TableDropped, OutboundMessage::ReadyForQuery],
+    );
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "execute foo_plan (123)",
+        vec![
+            QueryError::table_does_not_exist("schema_name.table_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "create table schema_name.table_name (column_1 smallint)",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+
+    assert_cached_statement(
+        &mut query_plan_cache,
+        &txn,
+        "execute foo_plan (123)",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+}
+
 #[rstest::rstest]
 fn deallocate_statement_that_does_not_exists(with_schema: TransactionManager) {
     let mut query_plan_cache = QueryPlanCache::default();
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::*;
+use definition::SearchPath;
 
 #[test]
 fn create_schema() {
@@ -187,3 +188,40 @@ fn delete_from_table_in_nonexistent_schema() {
     );
     txn.commit();
 }
+
+#[rstest::rstest]
+fn unqualified_names_are_resolved_with_search_path(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+    txn.set_search_path(SearchPath::new("schema_name, public", ""));
+    assert_statement(
+        &txn,
+        "create table table_name (column_test smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (123);",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "delete from table_name;",
+        vec![OutboundMessage::RecordsDeleted(1), OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+}
+
+#[test]
+fn create_table_when_no_schema_in_search_path_exists() {
+    let database = Database::new("IN_MEMORY");
+    let query_engine = TransactionManager::new(database);
+
+    let txn = query_engine.start_transaction();
+    txn.set_search_path(SearchPath::new("schema_name", ""));
+    assert_statement(
+        &txn,
+        "create table table_name (column_test smallint);",
+        vec![QueryError::no_schema_selected().into(), OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+}
@@ -289,3 +289,562 @@ fn select_value_by_predicate_on_single_field(with_schema: TransactionManager) {
     );
     txn.commit();
 }
+
+#[rstest::rstest]
+fn select_expressions_with_aliases(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (price integer, qty integer);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (10, 2), (3, 5);",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select price * qty as total, table_name.qty, price + 1 from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("total".to_owned(), INT), ("qty".to_owned(), INT), ("?column?".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![integer(20), integer(2), integer(11)]),
+            OutboundMessage::DataRow(vec![integer(15), integer(5), integer(4)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_without_from_clause(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "select 1, 'abc' as str;",
+        vec![
+            OutboundMessage::RowDescription(vec![("?column?".to_owned(), INT), ("str".to_owned(), VARCHAR)]),
+            OutboundMessage::DataRow(vec![integer(1), string("abc")]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_built_in_functions(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (name varchar(10), qty integer);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values ('  Apple ', 7);",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select upper(trim(name)) as fruit, length(name), mod(qty, 4), concat(name, qty) from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![
+                ("fruit".to_owned(), VARCHAR),
+                ("length".to_owned(), INT),
+                ("mod".to_owned(), INT),
+                ("concat".to_owned(), VARCHAR),
+            ]),
+            OutboundMessage::DataRow(vec![string("APPLE"), integer(8), integer(3), string("  Apple 7")]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select lower(1);",
+        vec![
+            QueryError::function_does_not_exist("lower(integer)").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn now_is_transaction_start_time(with_schema: TransactionManager) {
+    fn select_now(txn: &TransactionContext) -> Vec<OutboundMessage> {
+        let mut responses = vec![];
+        match QueryParser.parse("select now();") {
+            Ok(Request::Statement(statement)) => {
+                QueryExecutor.execute_statement(statement, txn, &mut QueryPlanCache::default(), &mut |outbound| responses.push(outbound))
+            }
+            other => panic!("expected select query but was {:?}", other),
+        }
+        responses
+    }
+
+    let txn = with_schema.start_transaction();
+    let first = select_now(&txn);
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let second = select_now(&txn);
+    txn.commit();
+
+    assert_eq!(first[0], OutboundMessage::RowDescription(vec![("now".to_owned(), TIMESTAMPTZ)]));
+    assert_eq!(first, second);
+
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let txn = with_schema.start_transaction();
+    assert_ne!(select_now(&txn), first);
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_conditional_expressions(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (qty integer, total integer, discount integer);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (0, 10, null), (5, 20, 3);",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select case when qty = 0 then 0 else total / qty end as ratio, coalesce(discount, 0), nullif(qty, 0), greatest(qty, discount) \
+         from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![
+                ("ratio".to_owned(), INT),
+                ("coalesce".to_owned(), INT),
+                ("nullif".to_owned(), INT),
+                ("greatest".to_owned(), INT),
+            ]),
+            OutboundMessage::DataRow(vec![integer(0), integer(0), string("NULL"), integer(0)]),
+            OutboundMessage::DataRow(vec![integer(4), integer(3), integer(5), integer(5)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_with_null_semantics(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (id integer, qty integer);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 10), (2, null);",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select id, qty + 1, qty is null, qty is distinct from 10 from schema_name.table_name where qty > 5 or id = 2;",
+        vec![
+            OutboundMessage::RowDescription(vec![
+                ("id".to_owned(), INT),
+                ("?column?".to_owned(), INT),
+                ("?column?".to_owned(), BOOL),
+                ("?column?".to_owned(), BOOL),
+            ]),
+            OutboundMessage::DataRow(vec![integer(1), big_int(11), string("f"), string("f")]),
+            OutboundMessage::DataRow(vec![integer(2), string("NULL"), string("t"), string("t")]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_name where qty <> 10;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT)]),
+            OutboundMessage::RecordsSelected(0),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_with_in_list_and_between(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (id integer, qty integer);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 10), (2, null), (3, 30);",
+        vec![OutboundMessage::RecordsInserted(3), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_name where id in (1, 3) and qty between 5 and 20;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![integer(1)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_name where qty = any(array[10, 30]);",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![integer(1)]),
+            OutboundMessage::DataRow(vec![integer(3)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_name where id not in (1, null);",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT)]),
+            OutboundMessage::RecordsSelected(0),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_with_pattern_matching(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (id integer, name varchar(20));",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 'abc'), (2, 'a_c'), (3, 'ABBC');",
+        vec![OutboundMessage::RecordsInserted(3), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select id, name like 'a_c', name ilike 'ab%', name like 'a#_c' escape '#', name similar to '(a|A)(b|B)+(c|C)', name ~* '^ab' \
+        from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![
+                ("id".to_owned(), INT),
+                ("?column?".to_owned(), BOOL),
+                ("?column?".to_owned(), BOOL),
+                ("?column?".to_owned(), BOOL),
+                ("?column?".to_owned(), BOOL),
+                ("?column?".to_owned(), BOOL),
+            ]),
+            OutboundMessage::DataRow(vec![integer(1), string("t"), string("t"), string("f"), string("t"), string("t")]),
+            OutboundMessage::DataRow(vec![integer(2), string("t"), string("f"), string("t"), string("f"), string("f")]),
+            OutboundMessage::DataRow(vec![integer(3), string("f"), string("t"), string("f"), string("t"), string("t")]),
+            OutboundMessage::RecordsSelected(3),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_distinct(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (id integer, category varchar(10), qty integer);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 'b', 10), (2, 'a', 20), (3, 'b', 10), (4, null, 30), (5, null, 30), (6, 'a', 5);",
+        vec![OutboundMessage::RecordsInserted(6), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select distinct category, qty from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("category".to_owned(), VARCHAR), ("qty".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![string("b"), integer(10)]),
+            OutboundMessage::DataRow(vec![string("a"), integer(20)]),
+            OutboundMessage::DataRow(vec![string("NULL"), integer(30)]),
+            OutboundMessage::DataRow(vec![string("a"), integer(5)]),
+            OutboundMessage::RecordsSelected(4),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select distinct on (category) category, id from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("category".to_owned(), VARCHAR), ("id".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![string("a"), integer(2)]),
+            OutboundMessage::DataRow(vec![string("b"), integer(1)]),
+            OutboundMessage::DataRow(vec![string("NULL"), integer(4)]),
+            OutboundMessage::RecordsSelected(3),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_with_set_operations(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create schema other_schema;",
+        vec![OutboundMessage::SchemaCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create table schema_name.table_1 (id smallint, name varchar(10));",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create table other_schema.table_2 (id integer, name varchar(10));",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_1 values (1, 'a'), (2, 'b'), (2, 'b'), (3, 'c');",
+        vec![OutboundMessage::RecordsInserted(4), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into other_schema.table_2 values (2, 'b'), (3, 'c'), (4, 'd');",
+        vec![OutboundMessage::RecordsInserted(3), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_1 union select id from other_schema.table_2;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![integer(1)]),
+            OutboundMessage::DataRow(vec![integer(2)]),
+            OutboundMessage::DataRow(vec![integer(3)]),
+            OutboundMessage::DataRow(vec![integer(4)]),
+            OutboundMessage::RecordsSelected(4),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select name from schema_name.table_1 union all select name from other_schema.table_2;",
+        vec![
+            OutboundMessage::RowDescription(vec![("name".to_owned(), VARCHAR)]),
+            OutboundMessage::DataRow(vec![string("a")]),
+            OutboundMessage::DataRow(vec![string("b")]),
+            OutboundMessage::DataRow(vec![string("b")]),
+            OutboundMessage::DataRow(vec![string("c")]),
+            OutboundMessage::DataRow(vec![string("b")]),
+            OutboundMessage::DataRow(vec![string("c")]),
+            OutboundMessage::DataRow(vec![string("d")]),
+            OutboundMessage::RecordsSelected(7),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select id, name from schema_name.table_1 intersect select id, name from other_schema.table_2;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT), ("name".to_owned(), VARCHAR)]),
+            OutboundMessage::DataRow(vec![integer(2), string("b")]),
+            OutboundMessage::DataRow(vec![integer(3), string("c")]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select id, name from schema_name.table_1 except all select id, name from other_schema.table_2;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT), ("name".to_owned(), VARCHAR)]),
+            OutboundMessage::DataRow(vec![integer(1), string("a")]),
+            OutboundMessage::DataRow(vec![integer(2), string("b")]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_1 except select id from other_schema.table_2 union select 5;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![integer(1)]),
+            OutboundMessage::DataRow(vec![integer(5)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_1 union select id, name from other_schema.table_2;",
+        vec![
+            QueryError::syntax_error("each UNION query must have the same number of columns").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_1 union select name from other_schema.table_2;",
+        vec![
+            QueryError::set_operation_type_mismatch("UNION", "smallint", "string").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_1 union select id > 2 from other_schema.table_2;",
+        vec![
+            QueryError::set_operation_type_mismatch("UNION", "smallint", "bool").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_with_window_functions(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_1 (grp varchar(10), amount integer);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_1 values ('a', 10), ('b', 20), ('a', 30), ('b', 20);",
+        vec![OutboundMessage::RecordsInserted(4), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select grp, amount, row_number() over (partition by grp order by amount desc), sum(amount) over (partition by grp) \
+         from schema_name.table_1;",
+        vec![
+            OutboundMessage::RowDescription(vec![
+                ("grp".to_owned(), VARCHAR),
+                ("amount".to_owned(), INT),
+                ("row_number".to_owned(), BIGINT),
+                ("sum".to_owned(), BIGINT),
+            ]),
+            OutboundMessage::DataRow(vec![string("a"), integer(30), big_int(1), big_int(40)]),
+            OutboundMessage::DataRow(vec![string("a"), integer(10), big_int(2), big_int(40)]),
+            OutboundMessage::DataRow(vec![string("b"), integer(20), big_int(1), big_int(40)]),
+            OutboundMessage::DataRow(vec![string("b"), integer(20), big_int(2), big_int(40)]),
+            OutboundMessage::RecordsSelected(4),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select amount, rank() over (order by amount), lag(amount, 1, 0) over (order by amount) from schema_name.table_1;",
+        vec![
+            OutboundMessage::RowDescription(vec![("amount".to_owned(), INT), ("rank".to_owned(), BIGINT), ("lag".to_owned(), INT)]),
+            OutboundMessage::DataRow(vec![integer(10), big_int(1), integer(0)]),
+            OutboundMessage::DataRow(vec![integer(20), big_int(2), integer(10)]),
+            OutboundMessage::DataRow(vec![integer(20), big_int(2), integer(20)]),
+            OutboundMessage::DataRow(vec![integer(30), big_int(4), integer(20)]),
+            OutboundMessage::RecordsSelected(4),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select amount from schema_name.table_1 where row_number() over () > 1;",
+        vec![
+            QueryError::windowing_error("window functions are not allowed in WHERE").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn rows_are_sent_before_scan_finishes(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1), (2), (3), (4);",
+        vec![OutboundMessage::RecordsInserted(4), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select column_1 from schema_name.table_name where 6 / (column_1 - 3) < 0;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(1)]),
+            OutboundMessage::DataRow(vec![small_int(2)]),
+            QueryError::division_by_zero().into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+}
+
+#[rstest::rstest]
+fn select_canceled_by_statement_timeout(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1), (2), (3);",
+        vec![OutboundMessage::RecordsInserted(3), OutboundMessage::ReadyForQuery],
+    );
+
+    txn.start_statement(Some(std::time::Duration::from_millis(1)));
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    assert_statement(
+        &txn,
+        "select * from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            QueryError::query_canceled().into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+
+    txn.start_statement(None);
+    assert_statement(
+        &txn,
+        "select * from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(1)]),
+            OutboundMessage::DataRow(vec![small_int(2)]),
+            OutboundMessage::DataRow(vec![small_int(3)]),
+            OutboundMessage::RecordsSelected(3),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+}
@@ -0,0 +1,264 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[rstest::rstest]
+fn insert_into_table_with_serial_column(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (id serial, column_1 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name (column_1) values (10), (20);",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (100, 30);",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT), ("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![integer(1), small_int(10)]),
+            OutboundMessage::DataRow(vec![integer(2), small_int(20)]),
+            OutboundMessage::DataRow(vec![integer(100), small_int(30)]),
+            OutboundMessage::RecordsSelected(3),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn insert_into_generated_always_identity_column(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (id bigint generated always as identity, column_1 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 10);",
+        vec![
+            QueryError::cannot_insert_into_generated_column("id").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name (column_1) values (10);",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "update schema_name.table_name set id = 5;",
+        vec![QueryError::cannot_update_generated_column("id").into(), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select id from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), BIGINT)]),
+            OutboundMessage::DataRow(vec![big_int(1)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn sequence_functions(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create sequence schema_name.sequence_name start 5 increment 5;",
+        vec![OutboundMessage::SequenceCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 bigint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (currval('schema_name.sequence_name'));",
+        vec![
+            QueryError::sequence_current_value_not_defined("sequence_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (nextval('schema_name.sequence_name')), (nextval('schema_name.sequence_name'));",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (currval('schema_name.sequence_name')), (setval('schema_name.sequence_name', 100));",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (nextval('schema_name.sequence_name'));",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), BIGINT)]),
+            OutboundMessage::DataRow(vec![big_int(5)]),
+            OutboundMessage::DataRow(vec![big_int(10)]),
+            OutboundMessage::DataRow(vec![big_int(10)]),
+            OutboundMessage::DataRow(vec![big_int(100)]),
+            OutboundMessage::DataRow(vec![big_int(105)]),
+            OutboundMessage::RecordsSelected(5),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn sequence_reaches_its_maximum_value(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create sequence schema_name.sequence_name maxvalue 2;",
+        vec![OutboundMessage::SequenceCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 bigint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (nextval('schema_name.sequence_name')), (nextval('schema_name.sequence_name'));",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (nextval('schema_name.sequence_name'));",
+        vec![
+            QueryError::sequence_limit_reached("sequence_name", true, 2).into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn create_sequence_with_the_same_name(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create sequence schema_name.sequence_name;",
+        vec![OutboundMessage::SequenceCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create sequence schema_name.sequence_name;",
+        vec![
+            QueryError::sequence_already_exists("schema_name.sequence_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "create sequence if not exists schema_name.sequence_name;",
+        vec![OutboundMessage::SequenceCreated, OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+}
+
+#[test]
+fn current_value_is_local_to_session() {
+    let database = Database::new("IN_MEMORY");
+    let session_1 = TransactionManager::new(database.clone());
+    let session_2 = TransactionManager::new(database);
+
+    let txn = session_1.start_transaction();
+    assert_statement(
+        &txn,
+        "create schema schema_name",
+        vec![OutboundMessage::SchemaCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create sequence schema_name.sequence_name;",
+        vec![OutboundMessage::SequenceCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 bigint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (nextval('schema_name.sequence_name'));",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+
+    let txn = session_2.start_transaction();
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (currval('schema_name.sequence_name'));",
+        vec![
+            QueryError::sequence_current_value_not_defined("sequence_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (nextval('schema_name.sequence_name'));",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+
+    let txn = session_1.start_transaction();
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (currval('schema_name.sequence_name'));",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), BIGINT)]),
+            OutboundMessage::DataRow(vec![big_int(1)]),
+            OutboundMessage::DataRow(vec![big_int(2)]),
+            OutboundMessage::DataRow(vec![big_int(1)]),
+            OutboundMessage::RecordsSelected(3),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
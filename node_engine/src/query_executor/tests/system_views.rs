@@ -0,0 +1,132 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[rstest::rstest]
+fn select_tables_from_information_schema(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 smallint, column_2 varchar(10));",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select table_schema, table_name, table_type from information_schema.tables where table_schema = 'schema_name';",
+        vec![
+            OutboundMessage::RowDescription(vec![
+                ("table_schema".to_owned(), VARCHAR),
+                ("table_name".to_owned(), VARCHAR),
+                ("table_type".to_owned(), VARCHAR),
+            ]),
+            OutboundMessage::DataRow(vec![string("schema_name"), string("table_name"), string("BASE TABLE")]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select column_name, ordinal_position, data_type from information_schema.columns where table_name = 'table_name';",
+        vec![
+            OutboundMessage::RowDescription(vec![
+                ("column_name".to_owned(), VARCHAR),
+                ("ordinal_position".to_owned(), INT),
+                ("data_type".to_owned(), VARCHAR),
+            ]),
+            OutboundMessage::DataRow(vec![string("column_1"), integer(1), string("smallint")]),
+            OutboundMessage::DataRow(vec![string("column_2"), integer(2), string("character varying")]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_relations_from_pg_class(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (id serial, column_1 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select relname, relkind, relnatts from pg_catalog.pg_class where relnamespace <> 11;",
+        vec![
+            OutboundMessage::RowDescription(vec![
+                ("relname".to_owned(), VARCHAR),
+                ("relkind".to_owned(), CHAR),
+                ("relnatts".to_owned(), SMALLINT),
+            ]),
+            OutboundMessage::DataRow(vec![string("table_name"), string("r"), small_int(2)]),
+            OutboundMessage::DataRow(vec![string("table_name_id_seq"), string("S"), small_int(0)]),
+            OutboundMessage::DataRow(vec![string("tables"), string("v"), small_int(4)]),
+            OutboundMessage::DataRow(vec![string("columns"), string("v"), small_int(11)]),
+            OutboundMessage::RecordsSelected(4),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn system_views_are_read_only(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "insert into pg_catalog.pg_namespace values (1, 'new_schema');",
+        vec![
+            QueryError::insufficient_privilege("permission denied for table pg_namespace").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "delete from information_schema.tables;",
+        vec![
+            QueryError::insufficient_privilege("permission denied for table tables").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "drop table pg_catalog.pg_class;",
+        vec![
+            QueryError::insufficient_privilege("permission denied: \"pg_class\" is a system catalog").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "create table pg_catalog.table_name (column_1 smallint);",
+        vec![
+            QueryError::insufficient_privilege("permission denied to create \"pg_catalog.table_name\"").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "drop schema information_schema cascade;",
+        vec![
+            QueryError::insufficient_privilege("permission denied for schema information_schema").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
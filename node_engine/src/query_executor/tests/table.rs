@@ -149,6 +149,110 @@ fn drop_if_exists_existent_and_non_existent_table(with_schema: TransactionManage
     txn.commit();
 }
 
+#[rstest::rstest]
+fn analyze_non_existent_table(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "analyze schema_name.non_existent;",
+        vec![
+            QueryError::table_does_not_exist("schema_name.non_existent").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn truncate_non_existent_table(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "truncate schema_name.non_existent;",
+        vec![
+            QueryError::table_does_not_exist("schema_name.non_existent").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn truncate_table(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (id serial, column_1 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name (column_1) values (1), (2);",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "truncate table schema_name.table_name;",
+        vec![OutboundMessage::TablesTruncated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name (column_1) values (3);",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT), ("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![integer(3), small_int(3)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn truncate_table_restart_identity(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (id serial, column_1 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name (column_1) values (1), (2);",
+        vec![OutboundMessage::RecordsInserted(2), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "truncate table schema_name.table_name restart identity;",
+        vec![OutboundMessage::TablesTruncated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name (column_1) values (3);",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.table_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("id".to_owned(), INT), ("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![integer(1), small_int(3)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
 #[rstest::rstest]
 fn delete_from_nonexistent_table(with_schema: TransactionManager) {
     let txn = with_schema.start_transaction();
@@ -0,0 +1,312 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[rstest::fixture]
+fn with_view(with_schema: TransactionManager) -> TransactionManager {
+    let txn = with_schema.start_transaction();
+    assert_statement(
+        &txn,
+        "create table schema_name.table_name (column_1 smallint, column_2 smallint);",
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 4), (2, 5), (3, 6);",
+        vec![OutboundMessage::RecordsInserted(3), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create view schema_name.view_name (view_column) as select column_2, column_1 from schema_name.table_name where column_1 > 1;",
+        vec![OutboundMessage::ViewCreated, OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+    with_schema
+}
+
+#[rstest::rstest]
+fn select_all_from_view(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "select * from schema_name.view_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("view_column".to_owned(), SMALLINT), ("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(5), small_int(2)]),
+            OutboundMessage::DataRow(vec![small_int(6), small_int(3)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_from_view_with_filter(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "select column_1 from schema_name.view_name where view_column < 6;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(2)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_from_view_over_view(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create view schema_name.view_over_view as select column_1 from schema_name.view_name where column_1 < 3;",
+        vec![OutboundMessage::ViewCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.view_over_view;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(2)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn select_from_view_with_distinct_on_with_filter(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "insert into schema_name.table_name values (1, 7);",
+        vec![OutboundMessage::RecordsInserted(1), OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create view schema_name.first_rows as select distinct on (column_1) column_1, column_2 from schema_name.table_name;",
+        vec![OutboundMessage::ViewCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.first_rows where column_2 > 4;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT), ("column_2".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(2), small_int(5)]),
+            OutboundMessage::DataRow(vec![small_int(3), small_int(6)]),
+            OutboundMessage::RecordsSelected(2),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "select distinct column_2 from schema_name.first_rows where column_1 = 1;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_2".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(4)]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn create_view_that_already_exists(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create view schema_name.view_name as select column_1 from schema_name.table_name;",
+        vec![
+            QueryError::view_already_exists("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn create_or_replace_view(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create or replace view schema_name.view_name as select column_1 from schema_name.table_name;",
+        vec![OutboundMessage::ViewCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.view_name;",
+        vec![
+            OutboundMessage::RowDescription(vec![("column_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec![small_int(1)]),
+            OutboundMessage::DataRow(vec![small_int(2)]),
+            OutboundMessage::DataRow(vec![small_int(3)]),
+            OutboundMessage::RecordsSelected(3),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn create_view_with_table_name(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create view schema_name.table_name as select column_1 from schema_name.table_name;",
+        vec![
+            QueryError::table_already_exists("schema_name.table_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn drop_view(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "drop view schema_name.view_name;",
+        vec![OutboundMessage::ViewDropped, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.view_name;",
+        vec![
+            QueryError::table_does_not_exist("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn drop_non_existent_view(with_schema: TransactionManager) {
+    let txn = with_schema.start_transaction();
+
+    assert_statement(
+        &txn,
+        "drop view schema_name.non_existent;",
+        vec![
+            QueryError::view_does_not_exist("schema_name.non_existent").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "drop view if exists schema_name.non_existent;",
+        vec![OutboundMessage::ViewDropped, OutboundMessage::ReadyForQuery],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn drop_table_with_dependent_view(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "drop table schema_name.table_name;",
+        vec![
+            QueryError::dependent_objects_still_exist("table schema_name.table_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "drop table schema_name.table_name cascade;",
+        vec![OutboundMessage::TableDropped, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "drop view schema_name.view_name;",
+        vec![
+            QueryError::view_does_not_exist("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn drop_view_with_dependent_view(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "create view schema_name.view_over_view as select column_1 from schema_name.view_name;",
+        vec![OutboundMessage::ViewCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "drop view schema_name.view_name;",
+        vec![
+            QueryError::dependent_objects_still_exist("view schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    assert_statement(
+        &txn,
+        "drop view schema_name.view_name cascade;",
+        vec![OutboundMessage::ViewDropped, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.view_over_view;",
+        vec![
+            QueryError::table_does_not_exist("schema_name.view_over_view").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
+
+#[rstest::rstest]
+fn drop_schema_with_view(with_view: TransactionManager) {
+    let txn = with_view.start_transaction();
+
+    assert_statement(
+        &txn,
+        "drop schema schema_name cascade;",
+        vec![OutboundMessage::SchemaDropped, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "create schema schema_name;",
+        vec![OutboundMessage::SchemaCreated, OutboundMessage::ReadyForQuery],
+    );
+    assert_statement(
+        &txn,
+        "select * from schema_name.view_name;",
+        vec![
+            QueryError::table_does_not_exist("schema_name.view_name").into(),
+            OutboundMessage::ReadyForQuery,
+        ],
+    );
+    txn.commit();
+}
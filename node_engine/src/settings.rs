@@ -0,0 +1,413 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use definition::SearchPath;
+use postgre_sql::query_response::QueryError;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    env,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const DEFAULT_LISTEN_ADDRESS: &str = "0.0.0.0:5432";
+pub const DEFAULT_MAX_CONNECTIONS: usize = 100;
+pub const DEFAULT_WORKER_THREADS: usize = 8;
+pub const DEFAULT_ROOT_PATH: &str = "./root_directory";
+
+/// Server wide settings that are read from a TOML file on start up.
+/// Environment variables take precedence over the values from the file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub listen_address: String,
+    pub max_connections: usize,
+    /// number of threads that run client transactions
+    pub worker_threads: usize,
+    pub root_path: String,
+    pub pfx_certificate_file: Option<PathBuf>,
+    pub pfx_certificate_password: Option<String>,
+    /// default values of session settings
+    pub session: HashMap<String, String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> ServerConfig {
+        ServerConfig {
+            listen_address: DEFAULT_LISTEN_ADDRESS.to_owned(),
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            worker_threads: DEFAULT_WORKER_THREADS,
+            root_path: DEFAULT_ROOT_PATH.to_owned(),
+            pfx_certificate_file: None,
+            pfx_certificate_password: None,
+            session: HashMap::new(),
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<ServerConfig, ConfigError> {
+        let content = fs::read_to_string(path).map_err(ConfigError::Io)?;
+        ServerConfig::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<ServerConfig, ConfigError> {
+        let config: ServerConfig = toml::from_str(content).map_err(ConfigError::Format)?;
+        config.session_settings()?;
+        Ok(config)
+    }
+
+    pub fn with_env(mut self) -> Result<ServerConfig, ConfigError> {
+        if let Ok(root_path) = env::var("ROOT_PATH") {
+            self.root_path = root_path;
+        }
+        if let Ok(listen_address) = env::var("LISTEN_ADDRESS") {
+            self.listen_address = listen_address;
+        }
+        if let Ok(max_connections) = env::var("MAX_CONNECTIONS") {
+            self.max_connections = max_connections
+                .parse()
+                .map_err(|_| ConfigError::Invalid(format!("MAX_CONNECTIONS should be a positive number: \"{}\"", max_connections)))?;
+        }
+        if let Ok(worker_threads) = env::var("WORKER_THREADS") {
+            self.worker_threads = worker_threads
+                .parse()
+                .map_err(|_| ConfigError::Invalid(format!("WORKER_THREADS should be a positive number: \"{}\"", worker_threads)))?;
+        }
+        if let Ok(file) = env::var("PFX_CERTIFICATE_FILE") {
+            self.pfx_certificate_file = Some(PathBuf::from(file));
+        }
+        if let Ok(password) = env::var("PFX_CERTIFICATE_PASSWORD") {
+            self.pfx_certificate_password = Some(password);
+        }
+        Ok(self)
+    }
+
+    pub fn session_settings(&self) -> Result<SessionSettings, ConfigError> {
+        SessionSettings::new(&self.session).map_err(|error| ConfigError::Invalid(error.to_string()))
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Format(toml::de::Error),
+    Invalid(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "could not read configuration file: {}", error),
+            ConfigError::Format(error) => write!(f, "could not parse configuration file: {}", error),
+            ConfigError::Invalid(message) => write!(f, "invalid configuration: {}", message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SettingKind {
+    Text,
+    /// comma separated list of names
+    List,
+    /// only UTF8 is supported
+    Encoding,
+    DateStyle,
+    /// time interval that is measured in milliseconds when no unit is specified
+    Milliseconds,
+    /// could not be changed by clients
+    ReadOnly,
+}
+
+struct SettingDefinition {
+    name: &'static str,
+    kind: SettingKind,
+    default: &'static str,
+    /// clients are notified with `ParameterStatus` when value is changed
+    reported: bool,
+}
+
+const SETTINGS: &[SettingDefinition] = &[
+    SettingDefinition {
+        name: "application_name",
+        kind: SettingKind::Text,
+        default: "",
+        reported: true,
+    },
+    SettingDefinition {
+        name: "client_encoding",
+        kind: SettingKind::Encoding,
+        default: "UTF8",
+        reported: true,
+    },
+    SettingDefinition {
+        name: "DateStyle",
+        kind: SettingKind::DateStyle,
+        default: "ISO, MDY",
+        reported: true,
+    },
+    SettingDefinition {
+        name: "integer_datetimes",
+        kind: SettingKind::ReadOnly,
+        default: "on",
+        reported: true,
+    },
+    SettingDefinition {
+        name: "search_path",
+        kind: SettingKind::List,
+        default: "\"$user\", public",
+        reported: false,
+    },
+    SettingDefinition {
+        name: "server_encoding",
+        kind: SettingKind::ReadOnly,
+        default: "UTF8",
+        reported: true,
+    },
+    SettingDefinition {
+        name: "server_version",
+        kind: SettingKind::ReadOnly,
+        default: "13.0",
+        reported: true,
+    },
+    SettingDefinition {
+        name: "statement_timeout",
+        kind: SettingKind::Milliseconds,
+        default: "0",
+        reported: false,
+    },
+];
+
+fn definition(name: &str) -> Result<&'static SettingDefinition, QueryError> {
+    SETTINGS
+        .iter()
+        .find(|setting| setting.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| QueryError::unrecognized_configuration_parameter(name.to_lowercase()))
+}
+
+impl SettingDefinition {
+    fn parse(&self, value: &str, current: &str) -> Result<String, QueryError> {
+        let parsed = match self.kind {
+            SettingKind::Text => Some(value.to_owned()),
+            SettingKind::List => Some(value.split(',').map(str::trim).collect::<Vec<&str>>().join(", ")),
+            SettingKind::Encoding => match value.to_uppercase().replace(&['-', '_'][..], "").as_str() {
+                "UTF8" | "UNICODE" => Some("UTF8".to_owned()),
+                _ => None,
+            },
+            SettingKind::DateStyle => parse_date_style(value, current),
+            SettingKind::Milliseconds => parse_milliseconds(value),
+            SettingKind::ReadOnly => return Err(QueryError::parameter_cannot_be_changed(self.name)),
+        };
+        parsed.ok_or_else(|| QueryError::invalid_parameter_value(format!("invalid value for parameter \"{}\": \"{}\"", self.name, value)))
+    }
+}
+
+fn parse_date_style(value: &str, current: &str) -> Option<String> {
+    let mut parts = current.split(", ");
+    let mut style = parts.next().unwrap_or("ISO").to_owned();
+    let mut order = parts.next().unwrap_or("MDY").to_owned();
+    for part in value.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty()) {
+        match part.to_lowercase().as_str() {
+            "iso" => style = "ISO".to_owned(),
+            "sql" => style = "SQL".to_owned(),
+            "postgres" => style = "Postgres".to_owned(),
+            "german" => style = "German".to_owned(),
+            "ymd" => order = "YMD".to_owned(),
+            "dmy" | "euro" | "european" => order = "DMY".to_owned(),
+            "mdy" | "us" | "noneuro" | "noneuropean" => order = "MDY".to_owned(),
+            _ => return None,
+        }
+    }
+    Some(format!("{}, {}", style, order))
+}
+
+const TIME_UNITS: &[(&str, u64)] = &[
+    ("d", 24 * 60 * 60 * 1000),
+    ("h", 60 * 60 * 1000),
+    ("min", 60 * 1000),
+    ("s", 1000),
+    ("ms", 1),
+];
+
+fn milliseconds(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let unit_start = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let amount = value[..unit_start].parse::<u64>().ok()?;
+    let multiplier = match value[unit_start..].trim() {
+        "" => 1,
+        unit => TIME_UNITS.iter().find(|(name, _)| *name == unit)?.1,
+    };
+    amount.checked_mul(multiplier)
+}
+
+fn parse_milliseconds(value: &str) -> Option<String> {
+    let millis = milliseconds(value).filter(|millis| *millis <= i32::MAX as u64)?;
+    if millis == 0 {
+        return Some("0".to_owned());
+    }
+    TIME_UNITS
+        .iter()
+        .find(|(_, multiplier)| millis % multiplier == 0)
+        .map(|(unit, multiplier)| format!("{}{}", millis / multiplier, unit))
+}
+
+/// Settings of a client session. `SET LOCAL` values are kept until the end
+/// of the current transaction, `RESET` restores values that the session started with
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSettings {
+    reset_values: HashMap<&'static str, String>,
+    values: HashMap<&'static str, String>,
+    local_values: HashMap<&'static str, String>,
+    /// name of the user that the session was started by, substituted for `$user` in `search_path`
+    user: String,
+}
+
+impl Default for SessionSettings {
+    fn default() -> SessionSettings {
+        let values = SETTINGS
+            .iter()
+            .map(|setting| (setting.name, setting.default.to_owned()))
+            .collect::<HashMap<&'static str, String>>();
+        SessionSettings {
+            reset_values: values.clone(),
+            values,
+            local_values: HashMap::new(),
+            user: String::new(),
+        }
+    }
+}
+
+impl SessionSettings {
+    pub fn new(defaults: &HashMap<String, String>) -> Result<SessionSettings, QueryError> {
+        let mut settings = SessionSettings::default();
+        for (name, value) in defaults {
+            settings.set_reset_value(name, value)?;
+        }
+        Ok(settings)
+    }
+
+    /// applies parameters that client sent in startup message, `user` is remembered
+    /// for `search_path` resolution, other unknown parameters such as `database` are ignored
+    pub fn apply_startup_params(&mut self, params: &HashMap<String, String>) {
+        for (name, value) in params {
+            if name == "user" {
+                self.user = value.clone();
+            }
+            match definition(name) {
+                Ok(setting) if setting.kind != SettingKind::ReadOnly => {
+                    if let Err(error) = self.set_reset_value(name, value) {
+                        log::warn!("startup parameter {} is ignored: {:?}", name, error);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn set_reset_value(&mut self, name: &str, value: &str) -> Result<(), QueryError> {
+        let setting = definition(name)?;
+        let value = setting.parse(value, &self.reset_values[setting.name])?;
+        self.reset_values.insert(setting.name, value.clone());
+        self.values.insert(setting.name, value);
+        Ok(())
+    }
+
+    /// Returns settings which values were changed and have to be reported to the client
+    pub fn set(&mut self, name: &str, value: Option<&str>, local: bool) -> Result<Vec<(String, String)>, QueryError> {
+        let setting = definition(name)?;
+        let value = match value {
+            Some(value) => setting.parse(value, &self.value(setting))?,
+            None if setting.kind == SettingKind::ReadOnly => return Err(QueryError::parameter_cannot_be_changed(setting.name)),
+            None => self.reset_values[setting.name].clone(),
+        };
+        Ok(self.track_changes(|settings| {
+            if local {
+                settings.local_values.insert(setting.name, value);
+            } else {
+                settings.local_values.remove(setting.name);
+                settings.values.insert(setting.name, value);
+            }
+        }))
+    }
+
+    /// Resets all settings if `name` is not specified
+    pub fn reset(&mut self, name: Option<&str>) -> Result<Vec<(String, String)>, QueryError> {
+        match name {
+            None => Ok(self.track_changes(|settings| {
+                settings.local_values.clear();
+                settings.values = settings.reset_values.clone();
+            })),
+            Some(name) => {
+                let setting = definition(name)?;
+                if setting.kind == SettingKind::ReadOnly {
+                    return Err(QueryError::parameter_cannot_be_changed(setting.name));
+                }
+                Ok(self.track_changes(|settings| {
+                    settings.local_values.remove(setting.name);
+                    settings.values.insert(setting.name, settings.reset_values[setting.name].clone());
+                }))
+            }
+        }
+    }
+
+    pub fn show(&self, name: &str) -> Result<(&'static str, String), QueryError> {
+        let setting = definition(name)?;
+        Ok((setting.name, self.value(setting)))
+    }
+
+    pub fn all(&self) -> Vec<(&'static str, String)> {
+        SETTINGS.iter().map(|setting| (setting.name, self.value(setting))).collect()
+    }
+
+    /// Discards `SET LOCAL` values and returns settings that have to be reported to the client
+    pub fn end_transaction(&mut self) -> Vec<(String, String)> {
+        self.track_changes(|settings| settings.local_values.clear())
+    }
+
+    pub fn search_path(&self) -> SearchPath {
+        SearchPath::new(&self.value(definition("search_path").unwrap()), &self.user)
+    }
+
+    /// `None` when statements are not limited in time
+    pub fn statement_timeout(&self) -> Option<Duration> {
+        milliseconds(&self.value(definition("statement_timeout").unwrap()))
+            .filter(|millis| *millis > 0)
+            .map(Duration::from_millis)
+    }
+
+    /// Settings that are reported to the client on start up and whenever they are changed
+    pub fn reported(&self) -> Vec<(String, String)> {
+        SETTINGS
+            .iter()
+            .filter(|setting| setting.reported)
+            .map(|setting| (setting.name.to_owned(), self.value(setting)))
+            .collect()
+    }
+
+    fn value(&self, setting: &SettingDefinition) -> String {
+        self.local_values.get(setting.name).unwrap_or_else(|| &self.values[setting.name]).clone()
+    }
+
+    fn track_changes<F: FnOnce(&mut SessionSettings)>(&mut self, change: F) -> Vec<(String, String)> {
+        let before = self.reported();
+        change(self);
+        self.reported().into_iter().filter(|param| !before.contains(param)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests;
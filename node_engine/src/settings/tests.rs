@@ -0,0 +1,248 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn param(name: &str, value: &str) -> (String, String) {
+    (name.to_owned(), value.to_owned())
+}
+
+#[cfg(test)]
+mod server_config {
+    use super::*;
+
+    #[test]
+    fn defaults_for_empty_file() {
+        assert_eq!(ServerConfig::from_toml("").unwrap(), ServerConfig::default());
+    }
+
+    #[test]
+    fn read_from_toml() {
+        let config = ServerConfig::from_toml(
+            r#"
+            listen_address = "127.0.0.1:5433"
+            max_connections = 2000
+            worker_threads = 4
+            root_path = "/var/lib/data"
+
+            [session]
+            search_path = "schema_name, public"
+            statement_timeout = "30s"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.listen_address, "127.0.0.1:5433");
+        assert_eq!(config.max_connections, 2000);
+        assert_eq!(config.worker_threads, 4);
+        assert_eq!(config.root_path, "/var/lib/data");
+
+        let settings = config.session_settings().unwrap();
+        assert_eq!(settings.show("search_path"), Ok(("search_path", "schema_name, public".to_owned())));
+        assert_eq!(settings.show("statement_timeout"), Ok(("statement_timeout", "30s".to_owned())));
+    }
+
+    #[test]
+    fn unknown_field() {
+        assert!(matches!(ServerConfig::from_toml("port = 5432"), Err(ConfigError::Format(_))));
+    }
+
+    #[test]
+    fn invalid_session_setting() {
+        assert!(matches!(
+            ServerConfig::from_toml("[session]\nstatement_timeout = \"forever\""),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod session {
+    use super::*;
+
+    #[test]
+    fn reported_on_start_up() {
+        let mut settings = SessionSettings::default();
+        settings.apply_startup_params(
+            &vec![
+                param("user", "username"),
+                param("application_name", "psql"),
+                param("server_version", "9.6"),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert_eq!(
+            settings.reported(),
+            vec![
+                param("application_name", "psql"),
+                param("client_encoding", "UTF8"),
+                param("DateStyle", "ISO, MDY"),
+                param("integer_datetimes", "on"),
+                param("server_encoding", "UTF8"),
+                param("server_version", "13.0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_and_show() {
+        let mut settings = SessionSettings::default();
+
+        assert_eq!(settings.set("search_path", Some("schema_name,public"), false), Ok(vec![]));
+        assert_eq!(settings.show("SEARCH_PATH"), Ok(("search_path", "schema_name, public".to_owned())));
+    }
+
+    #[test]
+    fn changes_of_reported_settings() {
+        let mut settings = SessionSettings::default();
+
+        assert_eq!(
+            settings.set("application_name", Some("app"), false),
+            Ok(vec![param("application_name", "app")])
+        );
+        assert_eq!(settings.set("application_name", Some("app"), false), Ok(vec![]));
+    }
+
+    #[test]
+    fn unrecognized_setting() {
+        let mut settings = SessionSettings::default();
+
+        assert_eq!(
+            settings.set("non_existent", Some("value"), false),
+            Err(QueryError::unrecognized_configuration_parameter("non_existent"))
+        );
+        assert_eq!(
+            settings.show("non_existent"),
+            Err(QueryError::unrecognized_configuration_parameter("non_existent"))
+        );
+    }
+
+    #[test]
+    fn read_only_setting() {
+        let mut settings = SessionSettings::default();
+
+        assert_eq!(
+            settings.set("server_version", Some("14.0"), false),
+            Err(QueryError::parameter_cannot_be_changed("server_version"))
+        );
+        assert_eq!(
+            settings.reset(Some("server_version")),
+            Err(QueryError::parameter_cannot_be_changed("server_version"))
+        );
+    }
+
+    #[test]
+    fn client_encoding() {
+        let mut settings = SessionSettings::default();
+
+        assert_eq!(settings.set("client_encoding", Some("unicode"), false), Ok(vec![]));
+        assert_eq!(
+            settings.set("client_encoding", Some("latin1"), false),
+            Err(QueryError::invalid_parameter_value(
+                "invalid value for parameter \"client_encoding\": \"latin1\""
+            ))
+        );
+    }
+
+    #[test]
+    fn date_style() {
+        let mut settings = SessionSettings::default();
+
+        assert_eq!(settings.set("datestyle", Some("SQL"), false), Ok(vec![param("DateStyle", "SQL, MDY")]));
+        assert_eq!(
+            settings.set("datestyle", Some("iso, dmy"), false),
+            Ok(vec![param("DateStyle", "ISO, DMY")])
+        );
+        assert!(settings.set("datestyle", Some("unknown"), false).is_err());
+    }
+
+    #[test]
+    fn statement_timeout() {
+        let mut settings = SessionSettings::default();
+
+        assert_eq!(settings.show("statement_timeout"), Ok(("statement_timeout", "0".to_owned())));
+        assert_eq!(settings.statement_timeout(), None);
+
+        settings.set("statement_timeout", Some("5000"), false).unwrap();
+        assert_eq!(settings.show("statement_timeout"), Ok(("statement_timeout", "5s".to_owned())));
+        assert_eq!(settings.statement_timeout(), Some(Duration::from_secs(5)));
+
+        settings.set("statement_timeout", Some("90 min"), false).unwrap();
+        assert_eq!(settings.show("statement_timeout"), Ok(("statement_timeout", "90min".to_owned())));
+        assert_eq!(settings.statement_timeout(), Some(Duration::from_secs(90 * 60)));
+
+        settings.set("statement_timeout", Some("1500ms"), false).unwrap();
+        assert_eq!(settings.show("statement_timeout"), Ok(("statement_timeout", "1500ms".to_owned())));
+        assert_eq!(settings.statement_timeout(), Some(Duration::from_millis(1500)));
+
+        assert!(settings.set("statement_timeout", Some("-1"), false).is_err());
+        assert!(settings.set("statement_timeout", Some("1 year"), false).is_err());
+    }
+
+    #[test]
+    fn reset_to_start_up_value() {
+        let mut settings = SessionSettings::default();
+        settings.apply_startup_params(&vec![param("application_name", "psql")].into_iter().collect());
+        settings.set("application_name", Some("app"), false).unwrap();
+
+        assert_eq!(settings.reset(Some("application_name")), Ok(vec![param("application_name", "psql")]));
+        assert_eq!(settings.show("application_name"), Ok(("application_name", "psql".to_owned())));
+    }
+
+    #[test]
+    fn search_path_of_start_up_user() {
+        let mut settings = SessionSettings::default();
+        settings.apply_startup_params(&vec![param("user", "username")].into_iter().collect());
+
+        assert_eq!(settings.search_path().schemas(), &["username".to_owned(), "public".to_owned()]);
+
+        settings.set("search_path", Some("schema_name, \"$user\""), false).unwrap();
+        assert_eq!(settings.search_path().schemas(), &["schema_name".to_owned(), "username".to_owned()]);
+    }
+
+    #[test]
+    fn set_to_default() {
+        let mut settings = SessionSettings::default();
+        settings.set("search_path", Some("schema_name"), false).unwrap();
+
+        assert_eq!(settings.set("search_path", None, false), Ok(vec![]));
+        assert_eq!(settings.show("search_path"), Ok(("search_path", "\"$user\", public".to_owned())));
+    }
+
+    #[test]
+    fn reset_all() {
+        let mut settings = SessionSettings::default();
+        settings.set("application_name", Some("app"), false).unwrap();
+        settings.set("search_path", Some("schema_name"), false).unwrap();
+
+        assert_eq!(settings.reset(None), Ok(vec![param("application_name", "")]));
+        assert_eq!(settings, SessionSettings::default());
+    }
+
+    #[test]
+    fn local_settings_are_discarded_at_the_end_of_transaction() {
+        let mut settings = SessionSettings::default();
+        settings.set("application_name", Some("app"), false).unwrap();
+
+        assert_eq!(
+            settings.set("application_name", Some("local"), true),
+            Ok(vec![param("application_name", "local")])
+        );
+        assert_eq!(settings.show("application_name"), Ok(("application_name", "local".to_owned())));
+        assert_eq!(settings.end_transaction(), vec![param("application_name", "app")]);
+        assert_eq!(settings.show("application_name"), Ok(("application_name", "app".to_owned())));
+    }
+}
@@ -14,33 +14,50 @@
 
 use catalog::CatalogHandler;
 use data_manipulation::{
-    QueryPlan, TypedDeleteQuery, TypedInsertQuery, TypedQuery, TypedSelectQuery, TypedTree, TypedUpdateQuery, UntypedInsertQuery, UntypedQuery,
-    UntypedUpdateQuery,
+    Deadline, EvaluationContext, QueryPlan, TypedDeleteQuery, TypedDistinct, TypedInsertQuery, TypedQuery, TypedSelectQuery, TypedSetOperation,
+    TypedTree, TypedUpdateQuery, UntypedDistinct, UntypedInsertQuery, UntypedItem, UntypedQuery, UntypedSelectQuery, UntypedSetOperation,
+    UntypedTree, UntypedUpdateQuery,
 };
-use definition::ColumnDef;
+use data_repr::scalar::ScalarValue;
+use definition::{ColumnDef, SearchPath};
 use definition_planner::DefinitionPlanner;
 use postgre_sql::{
     query_ast::{Definition, Query},
     query_response::{QueryError, QueryEvent},
 };
 use query_analyzer::QueryAnalyzer;
-use query_planner::QueryPlanner;
-use query_processing::{TypeChecker, TypeCoercion, TypeInference};
-use std::fmt::{self, Debug, Formatter};
-use storage::{Database, Transaction};
+use query_planner::{QueryPlanner, StatisticsCache};
+use query_processing::{Simplifier, TypeChecker, TypeCoercion, TypeInference};
+use std::{
+    fmt::{self, Debug, Formatter},
+    time::Duration,
+};
+use storage::{Database, SessionSequences, Transaction};
 use types::SqlTypeFamily;
 
 pub struct TransactionManager {
     database: Database,
+    session_sequences: SessionSequences,
+    deadline: Deadline,
+    statistics: StatisticsCache,
 }
 
 impl TransactionManager {
     pub fn new(database: Database) -> TransactionManager {
-        TransactionManager { database }
+        TransactionManager {
+            database,
+            session_sequences: SessionSequences::default(),
+            deadline: Deadline::default(),
+            statistics: StatisticsCache::default(),
+        }
     }
 
     pub fn start_transaction(&self) -> TransactionContext {
-        TransactionContext::new(self.database.transaction())
+        TransactionContext::new(
+            self.database.session_transaction(self.session_sequences.clone()),
+            self.deadline.clone(),
+            self.statistics.clone(),
+        )
     }
 }
 
@@ -51,7 +68,10 @@ pub struct TransactionContext<'t> {
     type_inference: TypeInference,
     type_checker: TypeChecker,
     type_coercion: TypeCoercion,
+    simplifier: Simplifier,
     query_planner: QueryPlanner<'t>,
+    deadline: Deadline,
+    transaction_timestamp: i64,
 }
 
 impl<'t> Debug for TransactionContext<'t> {
@@ -61,15 +81,18 @@ impl<'t> Debug for TransactionContext<'t> {
 }
 
 impl<'t> TransactionContext<'t> {
-    pub fn new(transaction: Transaction<'t>) -> TransactionContext<'t> {
+    pub fn new(transaction: Transaction<'t>, deadline: Deadline, statistics: StatisticsCache) -> TransactionContext<'t> {
         TransactionContext {
+            transaction_timestamp: transaction.started_at(),
             definition_planner: DefinitionPlanner::from(transaction.clone()),
             catalog: CatalogHandler::from(transaction.clone()),
             query_analyzer: QueryAnalyzer::from(transaction.clone()),
             type_inference: TypeInference::default(),
             type_checker: TypeChecker,
             type_coercion: TypeCoercion,
-            query_planner: QueryPlanner::from(transaction.clone()),
+            simplifier: Simplifier,
+            query_planner: QueryPlanner::new(transaction.clone(), deadline.clone(), statistics),
+            deadline,
         }
     }
 
@@ -93,6 +116,64 @@ impl<'t> TransactionContext<'t> {
             .collect::<Vec<u32>>()
     }
 
+    pub fn set_search_path(&self, search_path: SearchPath) {
+        self.query_analyzer.set_search_path(search_path.clone());
+        self.definition_planner.set_search_path(search_path);
+    }
+
+    /// the statement that is executed next is canceled when it runs longer than `timeout`
+    pub fn start_statement(&self, timeout: Option<Duration>) {
+        self.deadline.start(timeout);
+    }
+
+    /// `now()` returns the same value for every statement of the transaction
+    pub fn evaluation_context(&self, params: Vec<ScalarValue>) -> EvaluationContext {
+        EvaluationContext::new(params, self.transaction_timestamp)
+    }
+
+    pub fn search_path(&self) -> SearchPath {
+        self.query_analyzer.search_path()
+    }
+
+    /// names and type oids of the columns that select query returns
+    pub fn describe_select(&self, select: &UntypedSelectQuery, param_types: &[SqlTypeFamily]) -> Vec<(String, u32)> {
+        select
+            .column_names
+            .iter()
+            .cloned()
+            .zip(select.projection_items.iter())
+            .map(|(name, item)| match item {
+                UntypedTree::Item(UntypedItem::Column { sql_type, .. }) => (name, sql_type.into()),
+                item => {
+                    let typed = self.type_inference.infer_type(item.clone(), param_types);
+                    let typed = self.type_coercion.coerce(self.type_checker.type_check(typed));
+                    (name, (&typed.result_type().unwrap_or(SqlTypeFamily::String)).into())
+                }
+            })
+            .collect()
+    }
+
+    // types of the result columns are known only after columns of both sides are unified
+    pub fn describe_set_operation(&self, set_operation: &UntypedSetOperation, param_types: &[SqlTypeFamily]) -> Vec<(String, u32)> {
+        match self.process_set_operation(set_operation.clone(), param_types) {
+            Ok(typed) => typed
+                .column_names
+                .into_iter()
+                .zip(typed.column_types.iter())
+                .map(|(name, column_type)| (name, column_type.into()))
+                .collect(),
+            Err(_) => set_operation
+                .column_names
+                .iter()
+                .map(|name| (name.clone(), (&SqlTypeFamily::String).into()))
+                .collect(),
+        }
+    }
+
+    pub fn catalog_version(&self) -> u64 {
+        self.catalog.version()
+    }
+
     pub fn analyze(&self, query: Query) -> Result<UntypedQuery, QueryError> {
         Ok(self.query_analyzer.analyze(query)?)
     }
@@ -100,7 +181,7 @@ impl<'t> TransactionContext<'t> {
     pub fn process_untyped_query(&self, untyped_query: UntypedQuery, param_types: Vec<SqlTypeFamily>) -> Result<TypedQuery, QueryError> {
         match untyped_query {
             UntypedQuery::Insert(insert) => {
-                let type_coerced = insert
+                let simplified = insert
                     .values
                     .into_iter()
                     .map(|values| {
@@ -115,34 +196,21 @@ impl<'t> TransactionContext<'t> {
                             .map(|value| value.map(|v| self.type_checker.type_check(v)))
                             .collect::<Vec<Option<TypedTree>>>()
                     })
-                    .map(|values| values.into_iter().map(|value| value.map(|c| self.type_coercion.coerce(c))).collect())
-                    .collect::<Vec<Vec<Option<TypedTree>>>>();
+                    .map(|values| {
+                        values
+                            .into_iter()
+                            .map(|value| value.map(|v| self.type_coercion.coerce(v)))
+                            .map(|value| value.map(|v| self.simplifier.simplify(v)).transpose())
+                            .collect::<Result<Vec<Option<TypedTree>>, _>>()
+                    })
+                    .collect::<Result<Vec<Vec<Option<TypedTree>>>, _>>()?;
                 Ok(TypedQuery::Insert(TypedInsertQuery {
                     full_table_name: insert.full_table_name,
-                    values: type_coerced,
-                }))
-            }
-            UntypedQuery::Select(select) => {
-                let typed_values = select
-                    .projection_items
-                    .into_iter()
-                    .map(|value| self.type_inference.infer_type(value, &param_types));
-                let type_checked_values = typed_values.into_iter().map(|value| self.type_checker.type_check(value));
-                let type_coerced_values = type_checked_values
-                    .into_iter()
-                    .map(|value| self.type_coercion.coerce(value))
-                    .collect::<Vec<TypedTree>>();
-
-                let typed_filter = select.filter.map(|value| self.type_inference.infer_type(value, &param_types));
-                let type_checked_filter = typed_filter.map(|value| self.type_checker.type_check(value));
-                let type_coerced_filter = type_checked_filter.map(|value| self.type_coercion.coerce(value));
-
-                Ok(TypedQuery::Select(TypedSelectQuery {
-                    projection_items: type_coerced_values,
-                    full_table_name: select.full_table_name,
-                    filter: type_coerced_filter,
+                    values: simplified,
                 }))
             }
+            UntypedQuery::Select(select) => Ok(TypedQuery::Select(self.process_select(select, &param_types)?)),
+            UntypedQuery::SetOperation(set_operation) => Ok(TypedQuery::SetOperation(self.process_set_operation(set_operation, &param_types)?)),
             UntypedQuery::Update(update) => {
                 let typed_values = update
                     .assignments
@@ -151,36 +219,144 @@ impl<'t> TransactionContext<'t> {
                 let type_checked = typed_values
                     .into_iter()
                     .map(|value| value.map(|value| self.type_checker.type_check(value)));
-                let type_coerced = type_checked
+                let simplified = type_checked
                     .into_iter()
                     .map(|value| value.map(|value| self.type_coercion.coerce(value)))
-                    .collect::<Vec<Option<TypedTree>>>();
+                    .map(|value| value.map(|value| self.simplifier.simplify(value)).transpose())
+                    .collect::<Result<Vec<Option<TypedTree>>, _>>()?;
 
                 let typed_filter = update.filter.map(|value| self.type_inference.infer_type(value, &param_types));
                 let type_checked_filter = typed_filter.map(|value| self.type_checker.type_check(value));
                 let type_coerced_filter = type_checked_filter.map(|value| self.type_coercion.coerce(value));
+                let simplified_filter = type_coerced_filter.map(|value| self.simplifier.simplify(value)).transpose()?;
 
                 Ok(TypedQuery::Update(TypedUpdateQuery {
                     full_table_name: update.full_table_name,
-                    assignments: type_coerced,
-                    filter: type_coerced_filter,
+                    assignments: simplified,
+                    filter: simplified_filter,
                 }))
             }
             UntypedQuery::Delete(delete) => {
                 let typed_filter = delete.filter.map(|value| self.type_inference.infer_type(value, &param_types));
                 let type_checked_filter = typed_filter.map(|value| self.type_checker.type_check(value));
                 let type_coerced_filter = type_checked_filter.map(|value| self.type_coercion.coerce(value));
+                let simplified_filter = type_coerced_filter.map(|value| self.simplifier.simplify(value)).transpose()?;
 
                 Ok(TypedQuery::Delete(TypedDeleteQuery {
                     full_table_name: delete.full_table_name,
-                    filter: type_coerced_filter,
+                    filter: simplified_filter,
                 }))
             }
         }
     }
 
+    fn process_select(&self, select: UntypedSelectQuery, param_types: &[SqlTypeFamily]) -> Result<TypedSelectQuery, QueryError> {
+        let typed_values = select
+            .projection_items
+            .into_iter()
+            .map(|value| self.type_inference.infer_type(value, param_types));
+        let type_checked_values = typed_values.into_iter().map(|value| self.type_checker.type_check(value));
+        let simplified_values = type_checked_values
+            .into_iter()
+            .map(|value| self.type_coercion.coerce(value))
+            .map(|value| self.simplifier.simplify(value))
+            .collect::<Result<Vec<TypedTree>, _>>()?;
+
+        let typed_filter = select.filter.map(|value| self.type_inference.infer_type(value, param_types));
+        let type_checked_filter = typed_filter.map(|value| self.type_checker.type_check(value));
+        let type_coerced_filter = type_checked_filter.map(|value| self.type_coercion.coerce(value));
+        let simplified_filter = type_coerced_filter.map(|value| self.simplifier.simplify(value)).transpose()?;
+        let distinct = self.process_distinct(select.distinct, param_types)?;
+        let subquery = match select.subquery {
+            None => None,
+            Some(subquery) => Some(Box::new(self.process_select(*subquery, param_types)?)),
+        };
+
+        Ok(TypedSelectQuery {
+            projection_items: simplified_values,
+            column_names: select.column_names,
+            full_table_name: select.full_table_name,
+            subquery,
+            filter: simplified_filter,
+            distinct,
+        })
+    }
+
+    // columns of both sides are cast to the widest of their types
+    fn process_set_operation(&self, set_operation: UntypedSetOperation, param_types: &[SqlTypeFamily]) -> Result<TypedSetOperation, QueryError> {
+        let op = set_operation.op;
+        let left = self.process_set_operand(*set_operation.left, param_types)?;
+        let right = self.process_set_operand(*set_operation.right, param_types)?;
+        let column_types = result_types(&left)
+            .into_iter()
+            .zip(result_types(&right))
+            .map(|types| match types {
+                (Some(left), Some(right)) => left.compare(&right).map_err(|_| QueryError::set_operation_type_mismatch(op, left, right)),
+                (Some(known), None) | (None, Some(known)) => Ok(known),
+                (None, None) => Ok(SqlTypeFamily::String),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(TypedSetOperation {
+            op,
+            all: set_operation.all,
+            left: Box::new(self.cast_columns(left, &column_types)),
+            right: Box::new(self.cast_columns(right, &column_types)),
+            column_names: set_operation.column_names,
+            column_types,
+        })
+    }
+
+    fn process_set_operand(&self, query: UntypedQuery, param_types: &[SqlTypeFamily]) -> Result<TypedQuery, QueryError> {
+        match query {
+            UntypedQuery::Select(select) => Ok(TypedQuery::Select(self.process_select(select, param_types)?)),
+            UntypedQuery::SetOperation(set_operation) => Ok(TypedQuery::SetOperation(self.process_set_operation(set_operation, param_types)?)),
+            other => unreachable!("{:?} could not be used in a set operation", other),
+        }
+    }
+
+    fn cast_columns(&self, query: TypedQuery, column_types: &[SqlTypeFamily]) -> TypedQuery {
+        match query {
+            TypedQuery::Select(mut select) => {
+                select.projection_items = select
+                    .projection_items
+                    .into_iter()
+                    .zip(column_types.iter())
+                    .map(|(item, column_type)| self.type_coercion.coerce_to(item, Some(*column_type)))
+                    .collect();
+                TypedQuery::Select(select)
+            }
+            TypedQuery::SetOperation(set_operation) if set_operation.column_types != column_types => TypedQuery::SetOperation(TypedSetOperation {
+                left: Box::new(self.cast_columns(*set_operation.left, column_types)),
+                right: Box::new(self.cast_columns(*set_operation.right, column_types)),
+                column_types: column_types.to_vec(),
+                ..set_operation
+            }),
+            other => other,
+        }
+    }
+
+    fn process_distinct(&self, distinct: Option<UntypedDistinct>, param_types: &[SqlTypeFamily]) -> Result<Option<TypedDistinct>, QueryError> {
+        let process = |keys: Vec<UntypedTree>| {
+            keys.into_iter()
+                .map(|key| self.type_inference.infer_type(key, param_types))
+                .map(|key| self.type_checker.type_check(key))
+                .map(|key| self.type_coercion.coerce(key))
+                .map(|key| self.simplifier.simplify(key))
+                .collect::<Result<Vec<TypedTree>, _>>()
+        };
+        Ok(match distinct {
+            None => None,
+            Some(UntypedDistinct::Rows(keys)) => Some(TypedDistinct::Rows(process(keys)?)),
+            Some(UntypedDistinct::On(keys)) => Some(TypedDistinct::On(process(keys)?)),
+        })
+    }
+
     pub fn commit(self) {}
 
+    /// storage applies writes as soon as they are executed, so statements
+    /// that succeeded before an error are not undone yet
+    pub fn abort(self) {}
+
     pub fn apply_schema_change(&self, definition: Definition) -> Result<QueryEvent, QueryError> {
         let schema_change = self.definition_planner.plan(definition)?;
         Ok(self.catalog.apply(schema_change)?.into())
@@ -189,7 +365,7 @@ impl<'t> TransactionContext<'t> {
     pub fn process(&self, query: Query, param_types: Vec<SqlTypeFamily>) -> Result<TypedQuery, QueryError> {
         match self.query_analyzer.analyze(query)? {
             UntypedQuery::Insert(insert) => {
-                let type_coerced = insert
+                let simplified = insert
                     .values
                     .into_iter()
                     .map(|values| {
@@ -204,34 +380,21 @@ impl<'t> TransactionContext<'t> {
                             .map(|value| value.map(|v| self.type_checker.type_check(v)))
                             .collect::<Vec<Option<TypedTree>>>()
                     })
-                    .map(|values| values.into_iter().map(|value| value.map(|v| self.type_coercion.coerce(v))).collect())
-                    .collect::<Vec<Vec<Option<TypedTree>>>>();
+                    .map(|values| {
+                        values
+                            .into_iter()
+                            .map(|value| value.map(|v| self.type_coercion.coerce(v)))
+                            .map(|value| value.map(|v| self.simplifier.simplify(v)).transpose())
+                            .collect::<Result<Vec<Option<TypedTree>>, _>>()
+                    })
+                    .collect::<Result<Vec<Vec<Option<TypedTree>>>, _>>()?;
                 Ok(TypedQuery::Insert(TypedInsertQuery {
                     full_table_name: insert.full_table_name,
-                    values: type_coerced,
-                }))
-            }
-            UntypedQuery::Select(select) => {
-                let typed_values = select
-                    .projection_items
-                    .into_iter()
-                    .map(|value| self.type_inference.infer_type(value, &[]));
-                let type_checked_values = typed_values.into_iter().map(|value| self.type_checker.type_check(value));
-                let type_coerced_values = type_checked_values
-                    .into_iter()
-                    .map(|value| self.type_coercion.coerce(value))
-                    .collect::<Vec<TypedTree>>();
-
-                let typed_filter = select.filter.map(|value| self.type_inference.infer_type(value, &[]));
-                let type_checked_filter = typed_filter.map(|value| self.type_checker.type_check(value));
-                let type_coerced_filter = type_checked_filter.map(|value| self.type_coercion.coerce(value));
-
-                Ok(TypedQuery::Select(TypedSelectQuery {
-                    projection_items: type_coerced_values,
-                    full_table_name: select.full_table_name,
-                    filter: type_coerced_filter,
+                    values: simplified,
                 }))
             }
+            UntypedQuery::Select(select) => Ok(TypedQuery::Select(self.process_select(select, &[])?)),
+            UntypedQuery::SetOperation(set_operation) => Ok(TypedQuery::SetOperation(self.process_set_operation(set_operation, &[])?)),
             UntypedQuery::Update(update) => {
                 let typed_values = update
                     .assignments
@@ -240,29 +403,32 @@ impl<'t> TransactionContext<'t> {
                 let type_checked = typed_values
                     .into_iter()
                     .map(|value| value.map(|value| self.type_checker.type_check(value)));
-                let type_coerced = type_checked
+                let simplified = type_checked
                     .into_iter()
                     .map(|value| value.map(|value| self.type_coercion.coerce(value)))
-                    .collect::<Vec<Option<TypedTree>>>();
+                    .map(|value| value.map(|value| self.simplifier.simplify(value)).transpose())
+                    .collect::<Result<Vec<Option<TypedTree>>, _>>()?;
 
                 let typed_filter = update.filter.map(|value| self.type_inference.infer_type(value, &[]));
                 let type_checked_filter = typed_filter.map(|value| self.type_checker.type_check(value));
                 let type_coerced_filter = type_checked_filter.map(|value| self.type_coercion.coerce(value));
+                let simplified_filter = type_coerced_filter.map(|value| self.simplifier.simplify(value)).transpose()?;
 
                 Ok(TypedQuery::Update(TypedUpdateQuery {
                     full_table_name: update.full_table_name,
-                    assignments: type_coerced,
-                    filter: type_coerced_filter,
+                    assignments: simplified,
+                    filter: simplified_filter,
                 }))
             }
             UntypedQuery::Delete(delete) => {
                 let typed_filter = delete.filter.map(|value| self.type_inference.infer_type(value, &[]));
                 let type_checked_filter = typed_filter.map(|value| self.type_checker.type_check(value));
                 let type_coerced_filter = type_checked_filter.map(|value| self.type_coercion.coerce(value));
+                let simplified_filter = type_coerced_filter.map(|value| self.simplifier.simplify(value)).transpose()?;
 
                 Ok(TypedQuery::Delete(TypedDeleteQuery {
                     full_table_name: delete.full_table_name,
-                    filter: type_coerced_filter,
+                    filter: simplified_filter,
                 }))
             }
         }
@@ -271,4 +437,16 @@ impl<'t> TransactionContext<'t> {
     pub fn plan(&self, typed_query: TypedQuery) -> QueryPlan {
         self.query_planner.plan(typed_query)
     }
+
+    pub fn plan_instrumented(&self, typed_query: TypedQuery) -> QueryPlan {
+        self.query_planner.plan_instrumented(typed_query)
+    }
+}
+
+fn result_types(query: &TypedQuery) -> Vec<Option<SqlTypeFamily>> {
+    match query {
+        TypedQuery::Select(select) => select.projection_items.iter().map(TypedTree::result_type).collect(),
+        TypedQuery::SetOperation(set_operation) => set_operation.column_types.iter().copied().map(Some).collect(),
+        other => unreachable!("{:?} could not be used in a set operation", other),
+    }
 }
@@ -14,44 +14,127 @@
 
 use crate::{
     query_executor::QueryExecutor,
+    settings::SessionSettings,
     transaction_manager::{TransactionContext, TransactionManager},
-    QueryPlanCache,
+    PortalState, QueryPlanCache,
 };
 use data_repr::scalar::ScalarValue;
 use postgre_sql::{
-    query_ast::{Request, Statement, Transaction},
+    query_ast::{Config, Request, Statement, Transaction},
     query_parser::QueryParser,
     query_response::{QueryError, QueryEvent},
     wire_protocol::{
         payload::{InboundMessage, OutboundMessage, Value, BIGINT, BOOL, CHAR, INT, SMALLINT, VARCHAR},
-        WireConnection,
+        WireConnection, WireResult,
     },
 };
+use std::io;
 use storage::Database;
 use types::SqlTypeFamily;
 
-pub struct Worker;
+/// Remembers whether an error was sent to the client, so that the rest of
+/// the extended query messages could be skipped until `Sync`
+struct ErrorAwareConnection<'c, C: WireConnection> {
+    connection: &'c mut C,
+    error_occurred: bool,
+}
+
+impl<'c, C: WireConnection> WireConnection for ErrorAwareConnection<'c, C> {
+    fn receive(&mut self) -> io::Result<WireResult> {
+        self.connection.receive()
+    }
+
+    fn send(&mut self, outbound: OutboundMessage) -> io::Result<()> {
+        match outbound {
+            OutboundMessage::Error(_, _, _) => self.error_occurred = true,
+            OutboundMessage::ReadyForQuery => self.error_occurred = false,
+            _ => {}
+        }
+        self.connection.send(outbound)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.connection.flush()
+    }
+}
+
+#[derive(Default)]
+pub struct Worker {
+    settings: SessionSettings,
+}
 
 impl Worker {
+    pub fn new(settings: SessionSettings) -> Worker {
+        Worker { settings }
+    }
+
     pub fn process<C: WireConnection>(&self, connection: &mut C, database: Database) {
-        let mut query_plan_cache = QueryPlanCache::default();
-        let query_parser = QueryParser;
+        self.session(database).serve(connection, false);
+    }
 
-        let transaction_manager = TransactionManager::new(database);
+    pub(crate) fn session(&self, database: Database) -> Session {
+        Session {
+            settings: self.settings.clone(),
+            query_plan_cache: QueryPlanCache::default(),
+            transaction_manager: TransactionManager::new(database),
+        }
+    }
+}
+
+pub(crate) enum Served {
+    Idle,
+    Closed,
+}
+
+/// State of a client connection that outlives its transactions, so that
+/// different threads could serve the connection between transactions
+pub(crate) struct Session {
+    settings: SessionSettings,
+    query_plan_cache: QueryPlanCache,
+    transaction_manager: TransactionManager,
+}
+
+impl Session {
+    /// Handles client messages until the connection is closed. With `yield_when_idle`
+    /// it returns once a message is handled and no transaction is in progress.
+    pub(crate) fn serve<C: WireConnection>(&mut self, connection: &mut C, yield_when_idle: bool) -> Served {
+        let Session {
+            settings,
+            query_plan_cache,
+            transaction_manager,
+        } = self;
+        let mut connection = ErrorAwareConnection {
+            connection,
+            error_occurred: false,
+        };
+        let query_parser = QueryParser;
 
         let executor = QueryExecutor;
         let mut txn_state: Option<TransactionContext> = None;
+        let mut implicit_txn = false;
+        let mut handled = false;
         loop {
+            if yield_when_idle && handled && txn_state.is_none() && !connection.error_occurred {
+                return match connection.flush() {
+                    Ok(()) => Served::Idle,
+                    Err(_) => Served::Closed,
+                };
+            }
+            handled = true;
             let inbound_request = connection.receive();
             match inbound_request {
+                Ok(Ok(inbound)) if connection.error_occurred && !matches!(inbound, InboundMessage::Sync | InboundMessage::Terminate) => {
+                    log::debug!("{:?} is skipped until Sync", inbound);
+                }
                 Ok(Ok(inbound)) => match inbound {
                     InboundMessage::Query { sql } => match query_parser.parse(&sql) {
                         Ok(request) => match request {
                             Request::Transaction(transaction) => {
                                 match transaction {
                                     Transaction::Begin => {
-                                        debug_assert!(txn_state.is_none(), "transaction state should be implicit");
-                                        txn_state = Some(transaction_manager.start_transaction());
+                                        debug_assert!(txn_state.is_none() || implicit_txn, "transaction state should be implicit");
+                                        txn_state.get_or_insert_with(|| transaction_manager.start_transaction());
+                                        implicit_txn = false;
                                         connection.send(OutboundMessage::TransactionBegin).unwrap();
                                     }
                                     Transaction::Commit => {
@@ -60,7 +143,11 @@ impl Worker {
                                             None => unimplemented!(),
                                             Some(txn) => {
                                                 txn.commit();
+                                                query_plan_cache.close_all_portals();
                                                 connection.send(OutboundMessage::TransactionCommit).unwrap();
+                                                for (name, value) in settings.end_transaction() {
+                                                    connection.send(OutboundMessage::ParameterStatus(name, value)).unwrap();
+                                                }
                                                 txn_state = None;
                                             }
                                         }
@@ -68,8 +155,9 @@ impl Worker {
                                 }
                                 connection.send(OutboundMessage::ReadyForQuery).unwrap();
                             }
-                            Request::Config(_) => {
-                                connection.send(OutboundMessage::VariableSet).unwrap();
+                            Request::Config(config) => {
+                                let transaction_block = txn_state.is_some() && !implicit_txn;
+                                configure(config, settings, transaction_block, &mut |outbound| connection.send(outbound).unwrap());
                                 connection.send(OutboundMessage::ReadyForQuery).unwrap();
                             }
                             Request::Statement(statement) => {
@@ -77,9 +165,9 @@ impl Worker {
                                     None => (transaction_manager.start_transaction(), true),
                                     Some(txn) => (txn, false),
                                 };
-                                for outbound in executor.execute_statement(statement, &txn, &mut query_plan_cache) {
-                                    connection.send(outbound).unwrap();
-                                }
+                                txn.set_search_path(settings.search_path());
+                                txn.start_statement(settings.statement_timeout());
+                                executor.execute_statement(statement, &txn, query_plan_cache, &mut |outbound| connection.send(outbound).unwrap());
                                 if finish_txn {
                                     txn.commit();
                                 } else {
@@ -98,17 +186,19 @@ impl Worker {
                         sql,
                         param_types,
                     } => {
-                        let (txn, finish_txn) = match txn_state.take() {
-                            None => (transaction_manager.start_transaction(), true),
-                            Some(txn) => (txn, false),
-                        };
+                        txn_state.get_or_insert_with(|| {
+                            implicit_txn = true;
+                            transaction_manager.start_transaction()
+                        });
                         match query_plan_cache.find_described(&statement_name) {
                             Some((_, saved_sql, _)) if saved_sql == sql => {
                                 connection.send(QueryEvent::ParseComplete.into()).unwrap();
                             }
                             _ => match query_parser.parse(&sql) {
                                 Ok(request) => {
-                                    connection.send(QueryEvent::ParseComplete.into()).unwrap();
+                                    if !matches!(request, Request::Config(_)) {
+                                        connection.send(QueryEvent::ParseComplete.into()).unwrap();
+                                    }
                                     match request {
                                         Request::Statement(statement) => match statement {
                                             Statement::Query(query) => {
@@ -120,55 +210,52 @@ impl Worker {
                                         },
                                         Request::Transaction(transaction) => match transaction {
                                             Transaction::Begin => {
-                                                debug_assert!(txn_state.is_none(), "transaction state should be implicit");
-                                                unimplemented!()
+                                                debug_assert!(implicit_txn, "transaction state should be implicit");
+                                                implicit_txn = false;
+                                                connection.send(OutboundMessage::TransactionBegin).unwrap();
                                             }
                                             Transaction::Commit => {
-                                                debug_assert!(txn_state.is_some(), "transaction state should be in progress");
-                                                match txn_state {
-                                                    None => unimplemented!(),
-                                                    Some(txn) => {
-                                                        txn.commit();
-                                                        connection.send(OutboundMessage::TransactionCommit).unwrap();
-                                                        txn_state = None;
-                                                    }
+                                                debug_assert!(!implicit_txn, "transaction state should be in progress");
+                                                if let Some(txn) = txn_state.take() {
+                                                    txn.commit();
+                                                }
+                                                query_plan_cache.close_all_portals();
+                                                connection.send(OutboundMessage::TransactionCommit).unwrap();
+                                                for (name, value) in settings.end_transaction() {
+                                                    connection.send(OutboundMessage::ParameterStatus(name, value)).unwrap();
                                                 }
                                             }
                                         },
-                                        other => unimplemented!("{:?} unimplemented", other),
+                                        Request::Config(_) => {
+                                            connection
+                                                .send(QueryError::feature_not_supported("SET, SHOW and RESET in extended query protocol").into())
+                                                .unwrap();
+                                        }
                                     }
                                 }
                                 Err(parser_error) => {
-                                    connection.send(QueryError::syntax_error(parser_error).into()).unwrap();
+                                    let query_error: QueryError = parser_error.into();
+                                    connection.send(query_error.into()).unwrap();
                                 }
                             },
                         }
-                        if finish_txn {
-                            txn.commit();
-                        } else {
-                            txn_state = Some(txn);
-                        }
                     }
                     InboundMessage::DescribeStatement { name } => {
-                        let (txn, finish_txn) = match txn_state.take() {
-                            None => (transaction_manager.start_transaction(), true),
-                            Some(txn) => (txn, false),
-                        };
+                        let txn = txn_state.get_or_insert_with(|| {
+                            implicit_txn = true;
+                            transaction_manager.start_transaction()
+                        });
                         match query_plan_cache.find_parsed(&name) {
                             None => connection.send(QueryError::prepared_statement_does_not_exist(name).into()).unwrap(),
-                            Some((query, sql, _)) => {
-                                let (untyped_query, param_types, responses) = executor.describe_statement(query.clone(), &txn);
+                            Some((query, sql, param_types)) => {
+                                txn.set_search_path(settings.search_path());
+                                let (untyped_query, param_types, responses) = executor.describe_statement(query.clone(), param_types, txn);
                                 for response in responses {
                                     connection.send(response).unwrap();
                                 }
-                                query_plan_cache.save_described(name, untyped_query, sql, param_types);
+                                query_plan_cache.save_described(name, query, untyped_query, txn.search_path(), sql, param_types);
                             }
                         }
-                        if finish_txn {
-                            txn.commit();
-                        } else {
-                            txn_state = Some(txn);
-                        }
                     }
                     InboundMessage::Bind {
                         portal_name,
@@ -177,14 +264,13 @@ impl Worker {
                         query_params,
                         result_value_formats,
                     } => {
-                        let (txn, finish_txn) = match txn_state.take() {
-                            None => (transaction_manager.start_transaction(), true),
-                            Some(txn) => (txn, false),
-                        };
-                        match query_plan_cache.find_described(&statement_name) {
-                            Some((untyped_query, _, param_types)) => {
-                                let (untyped_query, param_types) = (untyped_query.clone(), param_types.to_vec());
-
+                        let txn = txn_state.get_or_insert_with(|| {
+                            implicit_txn = true;
+                            transaction_manager.start_transaction()
+                        });
+                        txn.set_search_path(settings.search_path());
+                        match query_plan_cache.bind_statement(&statement_name, txn) {
+                            Ok((untyped_query, param_types)) => {
                                 let mut arguments: Vec<ScalarValue> = vec![];
                                 debug_assert!(
                                     query_params.len() == param_types.len() && query_params.len() == query_param_formats.len(),
@@ -206,79 +292,120 @@ impl Worker {
                                     }
                                 }
                                 let portal = crate::Portal {
+                                    statement_name: statement_name.clone(),
                                     untyped_query,
                                     result_value_formats: result_value_formats.clone(),
                                     arguments,
                                     param_types: param_types.iter().map(From::from).collect::<Vec<SqlTypeFamily>>(),
+                                    state: PortalState::Ready,
                                 };
                                 query_plan_cache.bind_portal(statement_name, portal_name, portal);
                                 connection.send(OutboundMessage::BindComplete).unwrap();
                             }
-                            None => connection
-                                .send(QueryError::prepared_statement_does_not_exist(&statement_name).into())
-                                .unwrap(),
-                        }
-                        if finish_txn {
-                            txn.commit();
-                        } else {
-                            txn_state = Some(txn);
+                            Err(error) => connection.send(error.into()).unwrap(),
                         }
                     }
                     InboundMessage::DescribePortal { name } => {
-                        let (txn, finish_txn) = match txn_state.take() {
-                            None => (transaction_manager.start_transaction(), true),
-                            Some(txn) => (txn, false),
-                        };
-                        match query_plan_cache.find_described(&name) {
-                            None => connection.send(QueryError::prepared_statement_does_not_exist(&name).into()).unwrap(),
-                            Some(_) => {
-                                connection.send(OutboundMessage::StatementDescription(vec![])).unwrap();
-                            }
-                        }
-                        if finish_txn {
-                            txn.commit();
-                        } else {
-                            txn_state = Some(txn);
+                        let txn = txn_state.get_or_insert_with(|| {
+                            implicit_txn = true;
+                            transaction_manager.start_transaction()
+                        });
+                        match query_plan_cache.find_portal(&name) {
+                            None => connection.send(QueryError::portal_does_not_exist(&name).into()).unwrap(),
+                            Some(portal) => connection.send(executor.describe_portal(portal, txn)).unwrap(),
                         }
                     }
-                    InboundMessage::Execute {
-                        portal_name,
-                        max_rows: _max_rows,
-                    } => {
-                        let (txn, finish_txn) = match txn_state.take() {
-                            None => (transaction_manager.start_transaction(), true),
-                            Some(txn) => (txn, false),
-                        };
-                        let responses = match query_plan_cache.find_portal(&portal_name) {
-                            None => vec![QueryError::prepared_statement_does_not_exist(portal_name).into()],
-                            Some(crate::Portal {
-                                untyped_query,
-                                result_value_formats: _result_value_formats,
-                                arguments,
-                                param_types,
-                            }) => executor.execute_portal(untyped_query, param_types, arguments, &txn, &mut query_plan_cache),
-                        };
-                        for response in responses {
-                            connection.send(response).unwrap();
-                        }
-                        if finish_txn {
-                            txn.commit();
-                        } else {
-                            txn_state = Some(txn);
-                        }
+                    InboundMessage::Execute { portal_name, max_rows } => {
+                        let txn = txn_state.get_or_insert_with(|| {
+                            implicit_txn = true;
+                            transaction_manager.start_transaction()
+                        });
+                        txn.set_search_path(settings.search_path());
+                        txn.start_statement(settings.statement_timeout());
+                        executor.execute_portal(&portal_name, max_rows, txn, query_plan_cache, &mut |outbound| {
+                            connection.send(outbound).unwrap()
+                        });
                     }
+                    InboundMessage::ClosePortal { name } => {
+                        query_plan_cache.close_portal(&name);
+                        connection.send(OutboundMessage::CloseComplete).unwrap();
+                    }
+                    InboundMessage::CloseStatement { name } => {
+                        query_plan_cache.close_statement(&name);
+                        connection.send(OutboundMessage::CloseComplete).unwrap();
+                    }
+                    InboundMessage::Flush => connection.flush().unwrap(),
                     InboundMessage::Sync => {
+                        if implicit_txn {
+                            if let Some(txn) = txn_state.take() {
+                                if connection.error_occurred {
+                                    txn.abort();
+                                } else {
+                                    txn.commit();
+                                }
+                            }
+                            query_plan_cache.close_all_portals();
+                            implicit_txn = false;
+                        }
                         connection.send(OutboundMessage::ReadyForQuery).unwrap();
                     }
-                    InboundMessage::Terminate => break,
-                    other => unimplemented!("other inbound request {:?} is not handled", other),
+                    InboundMessage::Terminate => return Served::Closed,
                 },
-                _ => break,
+                _ => return Served::Closed,
             }
         }
     }
 }
 
+fn configure(config: Config, settings: &mut SessionSettings, transaction_block: bool, sender: &mut dyn FnMut(OutboundMessage)) {
+    match config {
+        Config::Set { variable, value, local } => {
+            let changes = if local && !transaction_block {
+                sender(QueryError::set_local_outside_transaction_block().into());
+                Ok(vec![])
+            } else {
+                settings.set(&variable, value.as_deref(), local)
+            };
+            match changes {
+                Ok(changes) => {
+                    sender(OutboundMessage::VariableSet);
+                    for (name, value) in changes {
+                        sender(OutboundMessage::ParameterStatus(name, value));
+                    }
+                }
+                Err(error) => sender(error.into()),
+            }
+        }
+        Config::Show { variable } if variable == "all" => {
+            sender(OutboundMessage::RowDescription(vec![
+                ("name".to_owned(), VARCHAR),
+                ("setting".to_owned(), VARCHAR),
+            ]));
+            for (name, value) in settings.all() {
+                sender(OutboundMessage::DataRow(vec![name.to_owned(), value]));
+            }
+            sender(OutboundMessage::VariableShown);
+        }
+        Config::Show { variable } => match settings.show(&variable) {
+            Ok((name, value)) => {
+                sender(OutboundMessage::RowDescription(vec![(name.to_owned(), VARCHAR)]));
+                sender(OutboundMessage::DataRow(vec![value]));
+                sender(OutboundMessage::VariableShown);
+            }
+            Err(error) => sender(error.into()),
+        },
+        Config::Reset { variable } => match settings.reset(variable.as_deref()) {
+            Ok(changes) => {
+                sender(OutboundMessage::VariableReset);
+                for (name, value) in changes {
+                    sender(OutboundMessage::ParameterStatus(name, value));
+                }
+            }
+            Err(error) => sender(error.into()),
+        },
+    }
+}
+
 pub fn decode(ty: u32, format: i16, raw: &[u8]) -> Result<Value, ()> {
     match format {
         1 => decode_binary(ty, raw),
@@ -17,11 +17,17 @@ mod jdbc_flow;
 #[cfg(test)]
 mod parse_bind_execute;
 #[cfg(test)]
+mod pipeline;
+#[cfg(test)]
+mod portal;
+#[cfg(test)]
+mod settings;
+#[cfg(test)]
 mod statement_description;
 
 use super::*;
 use postgre_sql::wire_protocol::{
-    payload::{OutboundMessage, SMALLINT},
+    payload::{OutboundMessage, SMALLINT, VARCHAR},
     WireError, WireResult,
 };
 use std::io;
@@ -52,6 +58,10 @@ impl WireConnection for MockConnection {
         self.outbound.push(outbound);
         Ok(())
     }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 #[test]
@@ -60,7 +70,7 @@ fn single_create_schema_request() {
         sql: "create schema schema_name;".to_owned(),
     }]);
 
-    let worker = Worker;
+    let worker = Worker::default();
 
     worker.process(&mut connection, Database::new("IN_MEMORY"));
 
@@ -78,7 +88,7 @@ fn transaction_per_query() {
         },
     ]);
 
-    let worker = Worker;
+    let worker = Worker::default();
 
     worker.process(&mut connection, Database::new("IN_MEMORY"));
 
@@ -106,7 +116,7 @@ fn multiple_ddl_in_single_transaction() {
         InboundMessage::Query { sql: "commit".to_owned() },
     ]);
 
-    let worker = Worker;
+    let worker = Worker::default();
 
     worker.process(&mut connection, Database::new("IN_MEMORY"));
 
@@ -154,7 +164,7 @@ fn prepare_and_execute_multiple_times_in_single_transaction() {
         InboundMessage::Query { sql: "commit".to_owned() },
     ]);
 
-    let node_engine = Worker;
+    let node_engine = Worker::default();
 
     node_engine.process(&mut connection, Database::new("IN_MEMORY"));
 
@@ -191,3 +201,40 @@ fn prepare_and_execute_multiple_times_in_single_transaction() {
         ]
     );
 }
+
+#[test]
+fn session_yields_between_transactions() {
+    let mut connection = MockConnection::new(vec![
+        InboundMessage::Query { sql: "begin".to_owned() },
+        InboundMessage::Query {
+            sql: "create schema schema_name;".to_owned(),
+        },
+        InboundMessage::Query { sql: "commit".to_owned() },
+        InboundMessage::Query {
+            sql: "create table schema_name.table_name (col1 smallint);".to_owned(),
+        },
+    ]);
+
+    let mut session = Worker::default().session(Database::new("IN_MEMORY"));
+
+    assert!(matches!(session.serve(&mut connection, true), Served::Idle));
+    assert_eq!(
+        connection.outbound.drain(..).collect::<Vec<OutboundMessage>>(),
+        vec![
+            OutboundMessage::TransactionBegin,
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::SchemaCreated,
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::TransactionCommit,
+            OutboundMessage::ReadyForQuery
+        ]
+    );
+
+    assert!(matches!(session.serve(&mut connection, true), Served::Idle));
+    assert_eq!(
+        connection.outbound.drain(..).collect::<Vec<OutboundMessage>>(),
+        vec![OutboundMessage::TableCreated, OutboundMessage::ReadyForQuery]
+    );
+
+    assert!(matches!(session.serve(&mut connection, true), Served::Closed));
+}
@@ -44,7 +44,7 @@ fn insert() {
         InboundMessage::Sync,
     ]);
 
-    let worker = Worker;
+    let worker = Worker::default();
 
     worker.process(&mut connection, Database::new("IN_MEMORY"));
 
@@ -96,7 +96,7 @@ fn update() {
         InboundMessage::Sync,
     ]);
 
-    let worker = Worker;
+    let worker = Worker::default();
 
     worker.process(&mut connection, Database::new("IN_MEMORY"));
 
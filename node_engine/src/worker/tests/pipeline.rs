@@ -0,0 +1,135 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn setup() -> Vec<InboundMessage> {
+    vec![
+        InboundMessage::Query {
+            sql: "create schema schema_name;".to_owned(),
+        },
+        InboundMessage::Query {
+            sql: "create table schema_name.table_name(col_1 smallint);".to_owned(),
+        },
+    ]
+}
+
+fn setup_responses() -> Vec<OutboundMessage> {
+    vec![
+        OutboundMessage::SchemaCreated,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::TableCreated,
+        OutboundMessage::ReadyForQuery,
+    ]
+}
+
+fn insert(value: &str) -> Vec<InboundMessage> {
+    vec![
+        InboundMessage::Parse {
+            statement_name: "".to_owned(),
+            sql: format!("insert into schema_name.table_name values ({})", value),
+            param_types: vec![],
+        },
+        InboundMessage::Bind {
+            portal_name: "".to_owned(),
+            statement_name: "".to_owned(),
+            query_param_formats: vec![],
+            query_params: vec![],
+            result_value_formats: vec![],
+        },
+        InboundMessage::Execute {
+            portal_name: "".to_owned(),
+            max_rows: 0,
+        },
+    ]
+}
+
+#[test]
+fn many_statements_before_sync() {
+    let mut inbound = setup();
+    inbound.extend(insert("1"));
+    inbound.extend(insert("2"));
+    inbound.extend(insert("3"));
+    inbound.push(InboundMessage::Sync);
+    inbound.push(InboundMessage::Query {
+        sql: "select * from schema_name.table_name".to_owned(),
+    });
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    for _ in 0..3 {
+        expected.extend(vec![
+            OutboundMessage::ParseComplete,
+            OutboundMessage::BindComplete,
+            OutboundMessage::RecordsInserted(1),
+        ]);
+    }
+    expected.extend(vec![
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::RowDescription(vec![("col_1".to_owned(), SMALLINT)]),
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::RecordsSelected(3),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn messages_after_error_are_skipped_until_sync() {
+    let mut inbound = setup();
+    inbound.extend(insert("1"));
+    inbound.push(InboundMessage::Execute {
+        portal_name: "non_existent".to_owned(),
+        max_rows: 0,
+    });
+    inbound.extend(insert("2"));
+    inbound.push(InboundMessage::Flush);
+    inbound.push(InboundMessage::Sync);
+    inbound.extend(insert("3"));
+    inbound.push(InboundMessage::Sync);
+    // rows written before the error are not rolled back yet, so only rows
+    // after the failed statement are checked
+    inbound.push(InboundMessage::Query {
+        sql: "select * from schema_name.table_name where col_1 > 1".to_owned(),
+    });
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::ParseComplete,
+        OutboundMessage::BindComplete,
+        OutboundMessage::RecordsInserted(1),
+        QueryError::portal_does_not_exist("non_existent").into(),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::ParseComplete,
+        OutboundMessage::BindComplete,
+        OutboundMessage::RecordsInserted(1),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::RowDescription(vec![("col_1".to_owned(), SMALLINT)]),
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::RecordsSelected(1),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
@@ -0,0 +1,508 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn setup() -> Vec<InboundMessage> {
+    vec![
+        InboundMessage::Query {
+            sql: "create schema schema_name;".to_owned(),
+        },
+        InboundMessage::Query {
+            sql: "create table schema_name.table_name(col_1 smallint);".to_owned(),
+        },
+        InboundMessage::Query {
+            sql: "insert into schema_name.table_name values (1), (2), (3);".to_owned(),
+        },
+        InboundMessage::Parse {
+            statement_name: "statement_name".to_owned(),
+            sql: "select col_1 from schema_name.table_name".to_owned(),
+            param_types: vec![],
+        },
+        InboundMessage::DescribeStatement {
+            name: "statement_name".to_owned(),
+        },
+        InboundMessage::Bind {
+            portal_name: "portal_name".to_owned(),
+            statement_name: "statement_name".to_owned(),
+            query_param_formats: vec![],
+            query_params: vec![],
+            result_value_formats: vec![],
+        },
+    ]
+}
+
+fn setup_responses() -> Vec<OutboundMessage> {
+    vec![
+        OutboundMessage::SchemaCreated,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::TableCreated,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::RecordsInserted(3),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::ParseComplete,
+        OutboundMessage::StatementParameters(vec![]),
+        OutboundMessage::StatementDescription(vec![("col_1".to_owned(), SMALLINT)]),
+        OutboundMessage::BindComplete,
+    ]
+}
+
+#[test]
+fn execute_portal_without_limit() {
+    let mut inbound = setup();
+    inbound.extend(vec![
+        InboundMessage::DescribePortal {
+            name: "portal_name".to_owned(),
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 0,
+        },
+        InboundMessage::Sync,
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::StatementDescription(vec![("col_1".to_owned(), SMALLINT)]),
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::RecordsSelected(3),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn suspend_portal_when_max_rows_reached() {
+    let mut inbound = setup();
+    inbound.extend(vec![
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 2,
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 2,
+        },
+        InboundMessage::Sync,
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::PortalSuspended,
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::RecordsSelected(1),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn execute_completed_portal() {
+    let mut inbound = setup();
+    inbound.extend(vec![
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 3,
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 3,
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 3,
+        },
+        InboundMessage::Sync,
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::PortalSuspended,
+        OutboundMessage::RecordsSelected(0),
+        OutboundMessage::RecordsSelected(0),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn close_portal() {
+    let mut inbound = setup();
+    inbound.extend(vec![
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 1,
+        },
+        InboundMessage::ClosePortal {
+            name: "portal_name".to_owned(),
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 1,
+        },
+        InboundMessage::Sync,
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::PortalSuspended,
+        OutboundMessage::CloseComplete,
+        QueryError::portal_does_not_exist("portal_name").into(),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn close_statement_closes_its_portals() {
+    let mut inbound = setup();
+    inbound.extend(vec![
+        InboundMessage::CloseStatement {
+            name: "statement_name".to_owned(),
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 0,
+        },
+        InboundMessage::Sync,
+        InboundMessage::Bind {
+            portal_name: "portal_name".to_owned(),
+            statement_name: "statement_name".to_owned(),
+            query_param_formats: vec![],
+            query_params: vec![],
+            result_value_formats: vec![],
+        },
+        InboundMessage::Sync,
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::CloseComplete,
+        QueryError::portal_does_not_exist("portal_name").into(),
+        OutboundMessage::ReadyForQuery,
+        QueryError::prepared_statement_does_not_exist("statement_name").into(),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn sync_closes_portals_of_implicit_transaction() {
+    let mut inbound = setup();
+    inbound.extend(vec![
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 1,
+        },
+        InboundMessage::Sync,
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 1,
+        },
+        InboundMessage::Sync,
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::PortalSuspended,
+        OutboundMessage::ReadyForQuery,
+        QueryError::portal_does_not_exist("portal_name").into(),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn portals_survive_sync_in_explicit_transaction() {
+    let mut inbound = vec![InboundMessage::Query { sql: "begin".to_owned() }];
+    inbound.extend(setup());
+    inbound.extend(vec![
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 2,
+        },
+        InboundMessage::Sync,
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 2,
+        },
+        InboundMessage::Sync,
+        InboundMessage::Query { sql: "commit".to_owned() },
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = vec![OutboundMessage::TransactionBegin, OutboundMessage::ReadyForQuery];
+    expected.extend(setup_responses());
+    expected.extend(vec![
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::PortalSuspended,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::RecordsSelected(1),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::TransactionCommit,
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+fn bind_and_execute() -> Vec<InboundMessage> {
+    vec![
+        InboundMessage::Bind {
+            portal_name: "portal_name".to_owned(),
+            statement_name: "statement_name".to_owned(),
+            query_param_formats: vec![],
+            query_params: vec![],
+            result_value_formats: vec![],
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 0,
+        },
+        InboundMessage::Sync,
+    ]
+}
+
+#[test]
+fn reexecute_statement_with_new_rows() {
+    let mut inbound = setup();
+    inbound.extend(vec![
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 0,
+        },
+        InboundMessage::Sync,
+        InboundMessage::Query {
+            sql: "insert into schema_name.table_name values (4);".to_owned(),
+        },
+    ]);
+    inbound.extend(bind_and_execute());
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::RecordsSelected(3),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::RecordsInserted(1),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::BindComplete,
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::DataRow(vec!["4".to_owned()]),
+        OutboundMessage::RecordsSelected(4),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn reexecute_statement_after_table_recreated() {
+    let mut inbound = setup();
+    inbound.extend(vec![
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 0,
+        },
+        InboundMessage::Sync,
+        InboundMessage::Query {
+            sql: "drop table schema_name.table_name;".to_owned(),
+        },
+        InboundMessage::Query {
+            sql: "create table schema_name.table_name(col_1 smallint);".to_owned(),
+        },
+        InboundMessage::Query {
+            sql: "insert into schema_name.table_name values (5);".to_owned(),
+        },
+    ]);
+    inbound.extend(bind_and_execute());
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::RecordsSelected(3),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::TableDropped,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::TableCreated,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::RecordsInserted(1),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::BindComplete,
+        OutboundMessage::DataRow(vec!["5".to_owned()]),
+        OutboundMessage::RecordsSelected(1),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn execute_insert_portal_twice() {
+    let mut connection = MockConnection::new(vec![
+        InboundMessage::Query {
+            sql: "create schema schema_name;".to_owned(),
+        },
+        InboundMessage::Query {
+            sql: "create table schema_name.table_name(col_1 smallint);".to_owned(),
+        },
+        InboundMessage::Parse {
+            statement_name: "statement_name".to_owned(),
+            sql: "insert into schema_name.table_name values (1)".to_owned(),
+            param_types: vec![],
+        },
+        InboundMessage::DescribeStatement {
+            name: "statement_name".to_owned(),
+        },
+        InboundMessage::Bind {
+            portal_name: "portal_name".to_owned(),
+            statement_name: "statement_name".to_owned(),
+            query_param_formats: vec![],
+            query_params: vec![],
+            result_value_formats: vec![],
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 0,
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 0,
+        },
+        InboundMessage::Sync,
+        InboundMessage::Query {
+            sql: "select col_1 from schema_name.table_name".to_owned(),
+        },
+    ]);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    assert_eq!(
+        connection.outbound,
+        vec![
+            OutboundMessage::SchemaCreated,
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::TableCreated,
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::ParseComplete,
+            OutboundMessage::StatementDescription(vec![]),
+            OutboundMessage::StatementParameters(vec![]),
+            OutboundMessage::BindComplete,
+            OutboundMessage::RecordsInserted(1),
+            OutboundMessage::RecordsInserted(0),
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::RowDescription(vec![("col_1".to_owned(), SMALLINT)]),
+            OutboundMessage::DataRow(vec!["1".to_owned()]),
+            OutboundMessage::RecordsSelected(1),
+            OutboundMessage::ReadyForQuery,
+        ]
+    );
+}
+
+#[test]
+fn portal_executes_query_it_was_bound_to() {
+    let mut inbound = setup();
+    inbound.extend(vec![
+        InboundMessage::Parse {
+            statement_name: "statement_name".to_owned(),
+            sql: "select col_1 from schema_name.table_name where col_1 > 2".to_owned(),
+            param_types: vec![],
+        },
+        InboundMessage::DescribeStatement {
+            name: "statement_name".to_owned(),
+        },
+        InboundMessage::Execute {
+            portal_name: "portal_name".to_owned(),
+            max_rows: 0,
+        },
+        InboundMessage::Sync,
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = setup_responses();
+    expected.extend(vec![
+        OutboundMessage::ParseComplete,
+        OutboundMessage::StatementParameters(vec![]),
+        OutboundMessage::StatementDescription(vec![("col_1".to_owned(), SMALLINT)]),
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::DataRow(vec!["3".to_owned()]),
+        OutboundMessage::RecordsSelected(3),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
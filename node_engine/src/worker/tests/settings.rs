@@ -0,0 +1,288 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn query(sql: &str) -> InboundMessage {
+    InboundMessage::Query { sql: sql.to_owned() }
+}
+
+#[test]
+fn set_and_show() {
+    let mut connection = MockConnection::new(vec![query("set search_path = schema_name, public"), query("show search_path")]);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    assert_eq!(
+        connection.outbound,
+        vec![
+            OutboundMessage::VariableSet,
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::RowDescription(vec![("search_path".to_owned(), VARCHAR)]),
+            OutboundMessage::DataRow(vec!["schema_name, public".to_owned()]),
+            OutboundMessage::VariableShown,
+            OutboundMessage::ReadyForQuery,
+        ]
+    );
+}
+
+#[test]
+fn parameter_status_on_change() {
+    let mut connection = MockConnection::new(vec![query("set application_name = 'app'"), query("reset application_name")]);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    assert_eq!(
+        connection.outbound,
+        vec![
+            OutboundMessage::VariableSet,
+            OutboundMessage::ParameterStatus("application_name".to_owned(), "app".to_owned()),
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::VariableReset,
+            OutboundMessage::ParameterStatus("application_name".to_owned(), "".to_owned()),
+            OutboundMessage::ReadyForQuery,
+        ]
+    );
+}
+
+#[test]
+fn unrecognized_configuration_parameter() {
+    let mut connection = MockConnection::new(vec![query("set non_existent = 'value'"), query("show non_existent")]);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    assert_eq!(
+        connection.outbound,
+        vec![
+            QueryError::unrecognized_configuration_parameter("non_existent").into(),
+            OutboundMessage::ReadyForQuery,
+            QueryError::unrecognized_configuration_parameter("non_existent").into(),
+            OutboundMessage::ReadyForQuery,
+        ]
+    );
+}
+
+#[test]
+fn set_local_outside_of_transaction_block() {
+    let mut connection = MockConnection::new(vec![query("set local statement_timeout = 1000"), query("show statement_timeout")]);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    assert_eq!(
+        connection.outbound,
+        vec![
+            QueryError::set_local_outside_transaction_block().into(),
+            OutboundMessage::VariableSet,
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::RowDescription(vec![("statement_timeout".to_owned(), VARCHAR)]),
+            OutboundMessage::DataRow(vec!["0".to_owned()]),
+            OutboundMessage::VariableShown,
+            OutboundMessage::ReadyForQuery,
+        ]
+    );
+}
+
+#[test]
+fn set_local_until_end_of_transaction() {
+    let mut connection = MockConnection::new(vec![
+        query("begin"),
+        query("set local application_name = 'app'"),
+        query("show application_name"),
+        query("commit"),
+        query("show application_name"),
+    ]);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    assert_eq!(
+        connection.outbound,
+        vec![
+            OutboundMessage::TransactionBegin,
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::VariableSet,
+            OutboundMessage::ParameterStatus("application_name".to_owned(), "app".to_owned()),
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::RowDescription(vec![("application_name".to_owned(), VARCHAR)]),
+            OutboundMessage::DataRow(vec!["app".to_owned()]),
+            OutboundMessage::VariableShown,
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::TransactionCommit,
+            OutboundMessage::ParameterStatus("application_name".to_owned(), "".to_owned()),
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::RowDescription(vec![("application_name".to_owned(), VARCHAR)]),
+            OutboundMessage::DataRow(vec!["".to_owned()]),
+            OutboundMessage::VariableShown,
+            OutboundMessage::ReadyForQuery,
+        ]
+    );
+}
+
+fn tenant_schemas() -> Vec<InboundMessage> {
+    vec![
+        query("create schema tenant_a"),
+        query("create table tenant_a.t (col smallint)"),
+        query("insert into tenant_a.t values (1)"),
+        query("create schema tenant_b"),
+        query("create table tenant_b.t (col smallint)"),
+        query("insert into tenant_b.t values (2)"),
+        query("set search_path = tenant_a"),
+    ]
+}
+
+fn tenant_schemas_responses() -> Vec<OutboundMessage> {
+    vec![
+        OutboundMessage::SchemaCreated,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::TableCreated,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::RecordsInserted(1),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::SchemaCreated,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::TableCreated,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::RecordsInserted(1),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::VariableSet,
+        OutboundMessage::ReadyForQuery,
+    ]
+}
+
+#[test]
+fn described_statement_is_replanned_when_search_path_changes() {
+    let bind = || InboundMessage::Bind {
+        portal_name: "".to_owned(),
+        statement_name: "statement_name".to_owned(),
+        query_param_formats: vec![],
+        query_params: vec![],
+        result_value_formats: vec![],
+    };
+    let execute = || InboundMessage::Execute {
+        portal_name: "".to_owned(),
+        max_rows: 0,
+    };
+    let mut inbound = tenant_schemas();
+    inbound.extend(vec![
+        InboundMessage::Parse {
+            statement_name: "statement_name".to_owned(),
+            sql: "select col from t".to_owned(),
+            param_types: vec![],
+        },
+        InboundMessage::DescribeStatement {
+            name: "statement_name".to_owned(),
+        },
+        bind(),
+        execute(),
+        InboundMessage::Sync,
+        query("set search_path = tenant_b"),
+        bind(),
+        execute(),
+        InboundMessage::Sync,
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = tenant_schemas_responses();
+    expected.extend(vec![
+        OutboundMessage::ParseComplete,
+        OutboundMessage::StatementParameters(vec![]),
+        OutboundMessage::StatementDescription(vec![("col".to_owned(), SMALLINT)]),
+        OutboundMessage::BindComplete,
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::RecordsSelected(1),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::VariableSet,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::BindComplete,
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::RecordsSelected(1),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn prepared_statement_is_replanned_when_search_path_changes() {
+    let mut inbound = tenant_schemas();
+    inbound.extend(vec![
+        query("prepare select_col as select col from t"),
+        query("execute select_col"),
+        query("set search_path = tenant_b"),
+        query("execute select_col"),
+    ]);
+    let mut connection = MockConnection::new(inbound);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    let mut expected = tenant_schemas_responses();
+    expected.extend(vec![
+        OutboundMessage::StatementPrepared,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::RowDescription(vec![("col".to_owned(), SMALLINT)]),
+        OutboundMessage::DataRow(vec!["1".to_owned()]),
+        OutboundMessage::RecordsSelected(1),
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::VariableSet,
+        OutboundMessage::ReadyForQuery,
+        OutboundMessage::RowDescription(vec![("col".to_owned(), SMALLINT)]),
+        OutboundMessage::DataRow(vec!["2".to_owned()]),
+        OutboundMessage::RecordsSelected(1),
+        OutboundMessage::ReadyForQuery,
+    ]);
+    assert_eq!(connection.outbound, expected);
+}
+
+#[test]
+fn set_in_extended_query_protocol() {
+    let mut connection = MockConnection::new(vec![
+        InboundMessage::Parse {
+            statement_name: "".to_owned(),
+            sql: "set application_name = 'app'".to_owned(),
+            param_types: vec![],
+        },
+        InboundMessage::Sync,
+        query("show application_name"),
+    ]);
+
+    let worker = Worker::default();
+
+    worker.process(&mut connection, Database::new("IN_MEMORY"));
+
+    assert_eq!(
+        connection.outbound,
+        vec![
+            QueryError::feature_not_supported("SET, SHOW and RESET in extended query protocol").into(),
+            OutboundMessage::ReadyForQuery,
+            OutboundMessage::RowDescription(vec![("application_name".to_owned(), VARCHAR)]),
+            OutboundMessage::DataRow(vec!["".to_owned()]),
+            OutboundMessage::VariableShown,
+            OutboundMessage::ReadyForQuery,
+        ]
+    );
+}
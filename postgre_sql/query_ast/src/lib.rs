@@ -12,12 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 
 #[derive(Debug, PartialEq)]
 pub enum Request {
     Transaction(Transaction),
-    Config(Set),
+    Config(Config),
     Statement(Statement),
 }
 
@@ -26,6 +27,7 @@ pub enum Statement {
     Definition(Definition),
     Query(Query),
     Extended(Extended),
+    Explain(Explain),
 }
 
 impl Display for Statement {
@@ -41,26 +43,81 @@ pub enum Definition {
         if_not_exists: bool,
     },
     CreateTable {
-        schema_name: String,
+        schema_name: Option<String>,
         table_name: String,
         columns: Vec<ColumnDef>,
         if_not_exists: bool,
     },
     CreateIndex {
         name: String,
-        schema_name: String,
+        schema_name: Option<String>,
         table_name: String,
         column_names: Vec<String>,
     },
+    CreateSequence {
+        schema_name: Option<String>,
+        sequence_name: String,
+        start: Option<i64>,
+        increment: Option<i64>,
+        min_value: Option<i64>,
+        max_value: Option<i64>,
+        if_not_exists: bool,
+    },
+    CreateView {
+        schema_name: Option<String>,
+        view_name: String,
+        columns: Vec<String>,
+        query: SelectQuery,
+        or_replace: bool,
+    },
+    CreateMaterializedView {
+        schema_name: Option<String>,
+        view_name: String,
+        columns: Vec<String>,
+        query: SelectQuery,
+        if_not_exists: bool,
+    },
+    RefreshMaterializedView {
+        schema_name: Option<String>,
+        view_name: String,
+        concurrently: bool,
+    },
+    CreateFunction {
+        schema_name: Option<String>,
+        function_name: String,
+        parameters: Vec<(Option<String>, DataType)>,
+        return_type: DataType,
+        body: SelectQuery,
+        or_replace: bool,
+    },
     DropSchemas {
         names: Vec<String>,
         if_exists: bool,
         cascade: bool,
     },
     DropTables {
-        names: Vec<(String, String)>,
+        names: Vec<(Option<String>, String)>,
+        if_exists: bool,
+        cascade: bool,
+    },
+    DropViews {
+        names: Vec<(Option<String>, String)>,
         if_exists: bool,
         cascade: bool,
+        materialized: bool,
+    },
+    DropFunctions {
+        names: Vec<(Option<String>, String)>,
+        if_exists: bool,
+        cascade: bool,
+    },
+    Analyze {
+        names: Vec<(Option<String>, String)>,
+    },
+    Truncate {
+        names: Vec<(Option<String>, String)>,
+        restart_identity: bool,
+        cascade: bool,
     },
 }
 
@@ -68,9 +125,17 @@ pub enum Definition {
 pub struct ColumnDef {
     pub name: String,
     pub data_type: DataType,
+    pub identity: Option<Identity>,
 }
 
+/// `GENERATED {ALWAYS|BY DEFAULT} AS IDENTITY` columns, `serial` columns are generated by default
 #[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Identity {
+    Always,
+    ByDefault,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum DataType {
     SmallInt,
     Int,
@@ -94,11 +159,12 @@ pub enum Query {
     Update(UpdateQuery),
     Delete(DeleteQuery),
     Select(SelectQuery),
+    SetOperation(SetOperation),
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct InsertQuery {
-    pub schema_name: String,
+    pub schema_name: Option<String>,
     pub table_name: String,
     pub columns: Vec<String>,
     pub source: InsertSource,
@@ -106,7 +172,7 @@ pub struct InsertQuery {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct UpdateQuery {
-    pub schema_name: String,
+    pub schema_name: Option<String>,
     pub table_name: String,
     pub assignments: Vec<Assignment>,
     pub where_clause: Option<Expr>,
@@ -120,23 +186,50 @@ pub struct Assignment {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct DeleteQuery {
-    pub schema_name: String,
+    pub schema_name: Option<String>,
     pub table_name: String,
     pub where_clause: Option<Expr>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct SelectQuery {
+    pub distinct: Option<Distinct>,
     pub select_items: Vec<SelectItem>,
-    pub schema_name: String,
-    pub table_name: String,
+    pub schema_name: Option<String>,
+    /// `None` when query has no `FROM` clause
+    pub table_name: Option<String>,
     pub where_clause: Option<Expr>,
 }
 
+/// `UNION`, `INTERSECT` or `EXCEPT` of two queries, each side is either a `SELECT` or another set operation
 #[derive(Debug, PartialEq, Clone)]
+pub struct SetOperation {
+    pub op: SetOperator,
+    pub all: bool,
+    pub left: Box<Query>,
+    pub right: Box<Query>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SetOperator {
+    Union,
+    Intersect,
+    Except,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum Distinct {
+    /// `SELECT DISTINCT` removes duplicated rows
+    Rows,
+    /// `SELECT DISTINCT ON (...)` keeps the first row of every group with equal expressions
+    On(Vec<Expr>),
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum SelectItem {
     Wildcard,
     UnnamedExpr(Expr),
+    ExprWithAlias { expr: Expr, alias: String },
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -147,17 +240,111 @@ pub enum InsertSource {
 #[derive(Debug, PartialEq, Clone)]
 pub struct Values(pub Vec<Vec<Expr>>);
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Expr {
     Value(Value),
     Param(u32),
-    BinaryOp { left: Box<Expr>, op: BinaryOperator, right: Box<Expr> },
-    UnaryOp { op: UnaryOperator, expr: Box<Expr> },
+    BinaryOp {
+        left: Box<Expr>,
+        op: BinaryOperator,
+        right: Box<Expr>,
+    },
+    UnaryOp {
+        op: UnaryOperator,
+        expr: Box<Expr>,
+    },
     Column(String),
-    Cast { expr: Box<Expr>, data_type: DataType },
+    Cast {
+        expr: Box<Expr>,
+        data_type: DataType,
+    },
+    Function {
+        name: String,
+        args: Vec<Expr>,
+    },
+    /// with `operand` conditions are values that are compared to it
+    Case {
+        operand: Option<Box<Expr>>,
+        conditions: Vec<Expr>,
+        results: Vec<Expr>,
+        else_result: Option<Box<Expr>>,
+    },
+    Coalesce(Vec<Expr>),
+    NullIf {
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Greatest(Vec<Expr>),
+    Least(Vec<Expr>),
+    InList {
+        expr: Box<Expr>,
+        list: Vec<Expr>,
+        negated: bool,
+    },
+    Between {
+        expr: Box<Expr>,
+        low: Box<Expr>,
+        high: Box<Expr>,
+        negated: bool,
+    },
+    /// `left op ANY (ARRAY[...])`
+    Any {
+        left: Box<Expr>,
+        op: BinaryOperator,
+        right: Vec<Expr>,
+    },
+    /// `left op ALL (ARRAY[...])`
+    All {
+        left: Box<Expr>,
+        op: BinaryOperator,
+        right: Vec<Expr>,
+    },
+    /// `name(args) OVER (PARTITION BY ... ORDER BY ... frame)`, `count(*)` has no arguments
+    Window {
+        name: String,
+        args: Vec<Expr>,
+        over: Box<WindowSpec>,
+    },
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct WindowSpec {
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>,
+    /// `None` when window has no frame clause
+    pub frame: Option<WindowFrame>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OrderByExpr {
+    pub expr: Expr,
+    pub ascending: bool,
+    pub nulls_first: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start: WindowFrameBound,
+    pub end: WindowFrameBound,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum WindowFrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum BinaryOperator {
     Plus,
     Minus,
@@ -176,14 +363,24 @@ pub enum BinaryOperator {
     Or,
     Like,
     NotLike,
+    ILike,
+    NotILike,
+    SimilarTo,
+    NotSimilarTo,
+    RegexMatch,
+    RegexIMatch,
+    NotRegexMatch,
+    NotRegexIMatch,
     BitwiseOr,
     BitwiseAnd,
     BitwiseXor,
     BitwiseShiftLeft,
     BitwiseShiftRight,
+    IsDistinctFrom,
+    IsNotDistinctFrom,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum UnaryOperator {
     Minus,
     Plus,
@@ -194,9 +391,17 @@ pub enum UnaryOperator {
     PostfixFactorial,
     PrefixFactorial,
     Abs,
+    IsNull,
+    IsNotNull,
+    IsTrue,
+    IsNotTrue,
+    IsFalse,
+    IsNotFalse,
+    IsUnknown,
+    IsNotUnknown,
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Value {
     Int(i32),
     Number(String),
@@ -205,9 +410,24 @@ pub enum Value {
 }
 
 #[derive(Debug, PartialEq)]
-pub struct Set {
-    pub variable: String,
-    pub value: String,
+pub struct Explain {
+    pub query: Query,
+    pub analyze: bool,
+    pub verbose: bool,
+    pub format: ExplainFormat,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ExplainFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Config {
+    Set { variable: String, value: Option<String>, local: bool },
+    Show { variable: String },
+    Reset { variable: Option<String> },
 }
 
 #[derive(Debug, PartialEq)]
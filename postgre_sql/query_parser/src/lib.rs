@@ -14,12 +14,27 @@
 
 use postgres_parser::{nodes, sys, Node, PgParserError, SqlStatementScanner};
 use query_ast::{
-    Assignment, BinaryOperator, ColumnDef, DataType, Definition, DeleteQuery, Expr, Extended, InsertQuery, InsertSource, Query, Request, SelectItem,
-    SelectQuery, Set, Statement, Transaction, UnaryOperator, UpdateQuery, Value, Values,
+    Assignment, BinaryOperator, ColumnDef, Config, DataType, Definition, DeleteQuery, Distinct, Explain, ExplainFormat, Expr, Extended, Identity,
+    InsertQuery, InsertSource, OrderByExpr, Query, Request, SelectItem, SelectQuery, SetOperation, SetOperator, Statement, Transaction,
+    UnaryOperator, UpdateQuery, Value, Values, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec,
 };
 use query_response::QueryError;
 use std::fmt::{self, Display, Formatter};
 
+// frame options of window definitions as they are defined in PostgreSQL `parsenodes.h`
+const FRAMEOPTION_NONDEFAULT: i32 = 0x00001;
+const FRAMEOPTION_RANGE: i32 = 0x00002;
+const FRAMEOPTION_ROWS: i32 = 0x00004;
+const FRAMEOPTION_START_UNBOUNDED_PRECEDING: i32 = 0x00020;
+const FRAMEOPTION_END_UNBOUNDED_FOLLOWING: i32 = 0x00100;
+const FRAMEOPTION_START_CURRENT_ROW: i32 = 0x00200;
+const FRAMEOPTION_END_CURRENT_ROW: i32 = 0x00400;
+const FRAMEOPTION_START_OFFSET_PRECEDING: i32 = 0x00800;
+const FRAMEOPTION_END_OFFSET_PRECEDING: i32 = 0x01000;
+const FRAMEOPTION_START_OFFSET_FOLLOWING: i32 = 0x02000;
+const FRAMEOPTION_END_OFFSET_FOLLOWING: i32 = 0x04000;
+const FRAMEOPTION_EXCLUSION: i32 = 0x08000 | 0x10000 | 0x20000;
+
 pub struct QueryParser;
 
 impl QueryParser {
@@ -64,7 +79,7 @@ impl QueryParser {
                     let table_name = table_name.unwrap();
                     return Ok(Request::Statement(Statement::Definition(Definition::CreateTable {
                         if_not_exists,
-                        schema_name: table_name.schemaname.unwrap_or_else(|| "public".to_owned()),
+                        schema_name: table_name.schemaname,
                         table_name: table_name.relname.unwrap(),
                         columns,
                     })));
@@ -93,40 +108,210 @@ impl QueryParser {
                             })));
                         }
                         sys::ObjectType::OBJECT_TABLE => {
-                            let mut names = vec![];
-                            for object in objects.unwrap() {
-                                log::trace!("OBJECT - {:?}", object);
-                                match object {
-                                    Node::List(mut values) => {
-                                        if values.len() == 1 {
-                                            match values.pop() {
-                                                Some(Node::Value(nodes::Value { string: Some(name), .. })) => names.push(("public".to_owned(), name)),
-                                                _ => unimplemented!(),
-                                            }
-                                        } else if values.len() == 2 {
-                                            match (values.pop(), values.pop()) {
-                                                (
-                                                    Some(Node::Value(nodes::Value { string: Some(name), .. })),
-                                                    Some(Node::Value(nodes::Value { string: Some(schema), .. })),
-                                                ) => names.push((schema, name)),
-                                                _ => unimplemented!(),
-                                            }
-                                        } else {
-                                            unimplemented!()
-                                        }
-                                    }
-                                    _ => unimplemented!(),
-                                }
-                            }
+                            let names = self.process_qualified_names(objects.unwrap());
                             return Ok(Request::Statement(Statement::Definition(Definition::DropTables {
                                 names,
                                 if_exists: missing_ok,
                                 cascade: behavior == sys::DropBehavior::DROP_CASCADE,
                             })));
                         }
+                        sys::ObjectType::OBJECT_VIEW | sys::ObjectType::OBJECT_MATVIEW => {
+                            let names = self.process_qualified_names(objects.unwrap());
+                            return Ok(Request::Statement(Statement::Definition(Definition::DropViews {
+                                names,
+                                if_exists: missing_ok,
+                                cascade: behavior == sys::DropBehavior::DROP_CASCADE,
+                                materialized: remove_type == sys::ObjectType::OBJECT_MATVIEW,
+                            })));
+                        }
+                        sys::ObjectType::OBJECT_FUNCTION => {
+                            // functions are not overloaded, so they are dropped by name regardless of argument types
+                            let names = objects
+                                .unwrap()
+                                .into_iter()
+                                .map(|object| match object {
+                                    Node::ObjectWithArgs(nodes::ObjectWithArgs { objname: Some(names), .. }) => self.process_qualified_name(names),
+                                    _ => unimplemented!(),
+                                })
+                                .collect();
+                            return Ok(Request::Statement(Statement::Definition(Definition::DropFunctions {
+                                names,
+                                if_exists: missing_ok,
+                                cascade: behavior == sys::DropBehavior::DROP_CASCADE,
+                            })));
+                        }
                         _ => unimplemented!(),
                     };
                 }
+                Ok(Some(Node::CreateSeqStmt(nodes::CreateSeqStmt {
+                    sequence,
+                    options,
+                    for_identity: false,
+                    if_not_exists,
+                    ..
+                }))) => {
+                    let sequence = sequence.unwrap();
+                    let (mut start, mut increment, mut min_value, mut max_value) = (None, None, None, None);
+                    for option in options.unwrap_or_else(Vec::new) {
+                        match option {
+                            Node::DefElem(nodes::DefElem {
+                                defname: Some(name), arg, ..
+                            }) => {
+                                let value = arg.map(|arg| self.parse_sequence_option(*arg));
+                                match name.as_str() {
+                                    "start" => start = value,
+                                    "increment" => increment = value,
+                                    "minvalue" => min_value = value,
+                                    "maxvalue" => max_value = value,
+                                    _ => unimplemented!(),
+                                }
+                            }
+                            _ => unimplemented!(),
+                        }
+                    }
+                    return Ok(Request::Statement(Statement::Definition(Definition::CreateSequence {
+                        schema_name: sequence.schemaname,
+                        sequence_name: sequence.relname.unwrap(),
+                        start,
+                        increment,
+                        min_value,
+                        max_value,
+                        if_not_exists,
+                    })));
+                }
+                Ok(Some(Node::ViewStmt(nodes::ViewStmt {
+                    view: Some(view_name),
+                    aliases,
+                    query: Some(query),
+                    replace,
+                    options: _options,
+                    withCheckOption: _with_check_option,
+                }))) => {
+                    let mut columns = vec![];
+                    for alias in aliases.unwrap_or_default() {
+                        match alias {
+                            Node::Value(nodes::Value { string: Some(name), .. }) => columns.push(name),
+                            _ => unimplemented!(),
+                        }
+                    }
+                    let query = match self.process_query(*query)? {
+                        Query::Select(query) => query,
+                        other => unimplemented!("{:?} could not be used as a view definition", other),
+                    };
+                    return Ok(Request::Statement(Statement::Definition(Definition::CreateView {
+                        schema_name: view_name.schemaname,
+                        view_name: view_name.relname.unwrap(),
+                        columns,
+                        query,
+                        or_replace: replace,
+                    })));
+                }
+                Ok(Some(Node::CreateTableAsStmt(nodes::CreateTableAsStmt {
+                    query: Some(query),
+                    into: Some(into),
+                    relkind: sys::ObjectType::OBJECT_MATVIEW,
+                    is_select_into: false,
+                    if_not_exists,
+                }))) if !into.skipData => {
+                    let nodes::IntoClause {
+                        rel, colNames: column_names, ..
+                    } = *into;
+                    let view_name = rel.unwrap();
+                    let mut columns = vec![];
+                    for column_name in column_names.unwrap_or_default() {
+                        match column_name {
+                            Node::Value(nodes::Value { string: Some(name), .. }) => columns.push(name),
+                            _ => return Err(ParserError::not_supported("materialized view column name that is not a string")),
+                        }
+                    }
+                    let query = match self.process_query(*query)? {
+                        Query::Select(query) => query,
+                        _ => return Err(ParserError::not_supported("materialized view that is not defined by a single SELECT")),
+                    };
+                    return Ok(Request::Statement(Statement::Definition(Definition::CreateMaterializedView {
+                        schema_name: view_name.schemaname,
+                        view_name: view_name.relname.unwrap(),
+                        columns,
+                        query,
+                        if_not_exists,
+                    })));
+                }
+                Ok(Some(Node::RefreshMatViewStmt(nodes::RefreshMatViewStmt {
+                    concurrent,
+                    skipData: false,
+                    relation: Some(view_name),
+                }))) => {
+                    return Ok(Request::Statement(Statement::Definition(Definition::RefreshMaterializedView {
+                        schema_name: view_name.schemaname,
+                        view_name: view_name.relname.unwrap(),
+                        concurrently: concurrent,
+                    })));
+                }
+                Ok(Some(Node::CreateTableAsStmt(nodes::CreateTableAsStmt {
+                    relkind: sys::ObjectType::OBJECT_MATVIEW,
+                    ..
+                })))
+                | Ok(Some(Node::RefreshMatViewStmt(nodes::RefreshMatViewStmt { skipData: true, .. }))) => {
+                    return Err(ParserError::not_supported("materialized view WITH NO DATA"));
+                }
+                Ok(Some(Node::CreateFunctionStmt(nodes::CreateFunctionStmt {
+                    is_procedure: false,
+                    replace,
+                    funcname: Some(function_name),
+                    parameters,
+                    returnType: Some(return_type),
+                    options,
+                }))) => {
+                    let (schema_name, function_name) = self.process_qualified_name(function_name);
+                    let parameters = parameters
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|parameter| match parameter {
+                            Node::FunctionParameter(nodes::FunctionParameter {
+                                name,
+                                argType: Some(arg_type),
+                                mode: sys::FunctionParameterMode::FUNC_PARAM_IN,
+                                defexpr: None,
+                            }) => (name, self.process_type(*arg_type)),
+                            other => unimplemented!("{:?} could not be used as a function parameter", other),
+                        })
+                        .collect();
+                    let (mut language, mut body) = (None, None);
+                    for option in options.unwrap_or_default() {
+                        match option {
+                            Node::DefElem(nodes::DefElem {
+                                defname: Some(name),
+                                arg: Some(arg),
+                                ..
+                            }) => match (name.as_str(), *arg) {
+                                ("language", Node::Value(nodes::Value { string: Some(value), .. })) => language = Some(value),
+                                ("as", Node::List(mut values)) => match values.pop() {
+                                    Some(Node::Value(nodes::Value { string: Some(value), .. })) => body = Some(value),
+                                    _ => unimplemented!(),
+                                },
+                                // volatility and strictness do not change how SQL functions are inlined
+                                _ => {}
+                            },
+                            _ => unimplemented!(),
+                        }
+                    }
+                    match language.as_deref() {
+                        Some("sql") => {}
+                        other => unimplemented!("functions in language {:?}", other),
+                    }
+                    let body = match self.parse(&body.unwrap())? {
+                        Request::Statement(Statement::Query(Query::Select(query))) => query,
+                        other => unimplemented!("{:?} could not be used as a function body", other),
+                    };
+                    return Ok(Request::Statement(Statement::Definition(Definition::CreateFunction {
+                        schema_name,
+                        function_name,
+                        parameters,
+                        return_type: self.process_type(*return_type),
+                        body,
+                        or_replace: replace,
+                    })));
+                }
                 Ok(Some(Node::IndexStmt(nodes::IndexStmt {
                     idxname: index_name,
                     relation: table_name,
@@ -163,28 +348,110 @@ impl QueryParser {
                     let table_name = table_name.unwrap();
                     return Ok(Request::Statement(Statement::Definition(Definition::CreateIndex {
                         name: index_name.unwrap(),
-                        schema_name: table_name.schemaname.unwrap_or_else(|| "public".to_owned()),
+                        schema_name: table_name.schemaname,
                         table_name: table_name.relname.unwrap(),
                         column_names,
                     })));
                 }
+                Ok(Some(Node::VacuumStmt(nodes::VacuumStmt {
+                    rels, is_vacuumcmd: false, ..
+                }))) => {
+                    let mut names = vec![];
+                    for rel in rels.unwrap_or_default() {
+                        match rel {
+                            Node::VacuumRelation(nodes::VacuumRelation {
+                                relation: Some(relation),
+                                va_cols: None,
+                                ..
+                            }) => names.push((relation.schemaname, relation.relname.unwrap())),
+                            other => unimplemented!("{:?} could not be analyzed", other),
+                        }
+                    }
+                    return Ok(Request::Statement(Statement::Definition(Definition::Analyze { names })));
+                }
+                Ok(Some(Node::TruncateStmt(nodes::TruncateStmt {
+                    relations,
+                    restart_seqs,
+                    behavior,
+                }))) => {
+                    let mut names = vec![];
+                    for relation in relations.unwrap_or_default() {
+                        match relation {
+                            Node::RangeVar(nodes::RangeVar { schemaname, relname, .. }) => names.push((schemaname, relname.unwrap())),
+                            other => unimplemented!("{:?} could not be truncated", other),
+                        }
+                    }
+                    return Ok(Request::Statement(Statement::Definition(Definition::Truncate {
+                        names,
+                        restart_identity: restart_seqs,
+                        cascade: behavior == sys::DropBehavior::DROP_CASCADE,
+                    })));
+                }
                 Ok(Some(insert @ Node::InsertStmt(_))) => {
-                    return Ok(Request::Statement(Statement::Query(self.process_query(insert))));
+                    return Ok(Request::Statement(Statement::Query(self.process_query(insert)?)));
                 }
                 Ok(Some(select @ Node::SelectStmt(_))) => {
-                    return Ok(Request::Statement(Statement::Query(self.process_query(select))));
+                    return Ok(Request::Statement(Statement::Query(self.process_query(select)?)));
                 }
                 Ok(Some(update @ Node::UpdateStmt(_))) => {
-                    return Ok(Request::Statement(Statement::Query(self.process_query(update))));
+                    return Ok(Request::Statement(Statement::Query(self.process_query(update)?)));
                 }
                 Ok(Some(delete @ Node::DeleteStmt(_))) => {
-                    return Ok(Request::Statement(Statement::Query(self.process_query(delete))));
+                    return Ok(Request::Statement(Statement::Query(self.process_query(delete)?)));
+                }
+                Ok(Some(Node::ExplainStmt(nodes::ExplainStmt { query: Some(query), options }))) => {
+                    let mut explain = Explain {
+                        query: self.process_query(*query)?,
+                        analyze: false,
+                        verbose: false,
+                        format: ExplainFormat::Text,
+                    };
+                    for option in options.unwrap_or_default() {
+                        match option {
+                            Node::DefElem(nodes::DefElem {
+                                defname: Some(name), arg, ..
+                            }) => match name.as_str() {
+                                "analyze" => explain.analyze = self.process_explain_flag(&name, arg)?,
+                                "verbose" => explain.verbose = self.process_explain_flag(&name, arg)?,
+                                "format" => match arg.map(|arg| *arg) {
+                                    Some(Node::Value(nodes::Value { string: Some(format), .. })) if format == "text" => {
+                                        explain.format = ExplainFormat::Text
+                                    }
+                                    Some(Node::Value(nodes::Value { string: Some(format), .. })) if format == "json" => {
+                                        explain.format = ExplainFormat::Json
+                                    }
+                                    Some(Node::Value(nodes::Value { string: Some(format), .. })) => {
+                                        return Err(ParserError::not_supported(format!("EXPLAIN format {}", format)))
+                                    }
+                                    _ => return Err(ParserError::not_supported("EXPLAIN format without a name")),
+                                },
+                                other => return Err(ParserError::not_supported(format!("EXPLAIN option {}", other))),
+                            },
+                            _ => return Err(ParserError::not_supported("EXPLAIN option without a name")),
+                        }
+                    }
+                    return Ok(Request::Statement(Statement::Explain(explain)));
                 }
-                Ok(Some(Node::VariableSetStmt(nodes::VariableSetStmt { name, .. }))) => {
-                    return Ok(Request::Config(Set {
-                        variable: name.unwrap(),
-                        value: "value".to_owned(),
-                    }))
+                Ok(Some(Node::VariableSetStmt(nodes::VariableSetStmt { kind, name, args, is_local }))) => {
+                    let config = match kind {
+                        sys::VariableSetKind::VAR_SET_VALUE => Config::Set {
+                            variable: name.unwrap(),
+                            value: Some(self.process_config_value(args.unwrap())),
+                            local: is_local,
+                        },
+                        sys::VariableSetKind::VAR_SET_DEFAULT => Config::Set {
+                            variable: name.unwrap(),
+                            value: None,
+                            local: is_local,
+                        },
+                        sys::VariableSetKind::VAR_RESET => Config::Reset { variable: name },
+                        sys::VariableSetKind::VAR_RESET_ALL => Config::Reset { variable: None },
+                        _ => unimplemented!(),
+                    };
+                    return Ok(Request::Config(config));
+                }
+                Ok(Some(Node::VariableShowStmt(nodes::VariableShowStmt { name }))) => {
+                    return Ok(Request::Config(Config::Show { variable: name.unwrap() }))
                 }
                 Ok(Some(Node::PrepareStmt(nodes::PrepareStmt {
                     name: Some(name),
@@ -201,7 +468,7 @@ impl QueryParser {
                     return Ok(Request::Statement(Statement::Extended(Extended::Prepare {
                         name,
                         param_types,
-                        query: self.process_query(*query),
+                        query: self.process_query(*query)?,
                     })));
                 }
                 Ok(Some(Node::ExecuteStmt(nodes::ExecuteStmt {
@@ -210,7 +477,7 @@ impl QueryParser {
                 }))) => {
                     let mut param_values = vec![];
                     for param in params {
-                        match self.parse_expr(param) {
+                        match self.parse_expr(param)? {
                             Expr::Value(value) => param_values.push(value),
                             other => unreachable!("{:?} could not be used as parameter", other),
                         }
@@ -234,8 +501,8 @@ impl QueryParser {
         unreachable!("should not reach here!")
     }
 
-    fn process_query(&self, node: Node) -> Query {
-        match node {
+    fn process_query(&self, node: Node) -> Result<Query, ParserError> {
+        let query = match node {
             Node::InsertStmt(nodes::InsertStmt {
                 relation,
                 cols,
@@ -246,7 +513,7 @@ impl QueryParser {
                 override_: _override,
             }) => {
                 let relation = relation.unwrap();
-                let schema_name = relation.schemaname.unwrap_or_else(|| "public".to_owned());
+                let schema_name = relation.schemaname;
                 let table_name = relation.relname.unwrap();
                 let mut columns = vec![];
                 for col in cols.unwrap_or_else(Vec::new) {
@@ -269,7 +536,7 @@ impl QueryParser {
                             if let Node::List(list) = list {
                                 let mut row = vec![];
                                 for raw_value in list {
-                                    row.push(self.parse_expr(raw_value));
+                                    row.push(self.parse_expr(raw_value)?);
                                 }
                                 values.push(row);
                             }
@@ -286,14 +553,14 @@ impl QueryParser {
                 })
             }
             Node::SelectStmt(nodes::SelectStmt {
-                distinctClause: None,
+                distinctClause: distinct_clause,
                 intoClause: None,
                 targetList: target_list,
                 fromClause: from_clause,
                 whereClause: where_clause,
                 groupClause: None,
                 havingClause: None,
-                windowClause: None,
+                windowClause: window_clause,
                 valuesLists: None,
                 sortClause: None,
                 limitOffset: None,
@@ -306,40 +573,85 @@ impl QueryParser {
                 larg: None,
                 rarg: None,
             }) => {
+                if window_clause.is_some() {
+                    return Err(ParserError::not_supported("WINDOW clause"));
+                }
                 log::trace!("TARGET LIST {:?}", target_list);
                 let mut select_items = vec![];
                 for target in target_list.unwrap() {
                     match target {
-                        Node::ResTarget(nodes::ResTarget { val: Some(ident), .. }) => match *ident {
-                            Node::ColumnRef(nodes::ColumnRef { fields: Some(fields), .. }) => {
-                                for field in fields {
-                                    match field {
-                                        Node::A_Star(_) => select_items.push(SelectItem::Wildcard),
-                                        Node::Value(nodes::Value { string: Some(col_name), .. }) => {
-                                            select_items.push(SelectItem::UnnamedExpr(Expr::Column(col_name)))
-                                        }
-                                        _ => unimplemented!(),
-                                    }
+                        Node::ResTarget(nodes::ResTarget { val: Some(value), name, .. }) => match *value {
+                            Node::ColumnRef(nodes::ColumnRef { fields: Some(fields) }) if matches!(fields.last(), Some(Node::A_Star(_))) => {
+                                select_items.push(SelectItem::Wildcard)
+                            }
+                            value => {
+                                let expr = self.parse_expr(value)?;
+                                match name {
+                                    None => select_items.push(SelectItem::UnnamedExpr(expr)),
+                                    Some(alias) => select_items.push(SelectItem::ExprWithAlias { expr, alias }),
                                 }
                             }
-                            _ => unimplemented!(),
                         },
                         _ => unimplemented!(),
                     }
                 }
-                let (schema_name, table_name) = match from_clause.unwrap().pop() {
+                let (schema_name, table_name) = match from_clause.and_then(|mut from_clause| from_clause.pop()) {
+                    None => (None, None),
                     Some(Node::RangeVar(nodes::RangeVar {
                         schemaname: schema_name,
                         relname: table_name,
                         ..
-                    })) => (schema_name.unwrap_or_else(|| "public".to_owned()), table_name.unwrap()),
+                    })) => (schema_name, table_name),
                     _ => unimplemented!(),
                 };
+                // plain `DISTINCT` is a list with a single NULL that is skipped by the parser
+                let distinct = match distinct_clause {
+                    None => None,
+                    Some(exprs) if exprs.is_empty() => Some(Distinct::Rows),
+                    Some(exprs) => Some(Distinct::On(
+                        exprs.into_iter().map(|expr| self.parse_expr(expr)).collect::<Result<_, _>>()?,
+                    )),
+                };
                 Query::Select(SelectQuery {
+                    distinct,
                     select_items,
                     schema_name,
                     table_name,
-                    where_clause: where_clause.map(|expr| self.parse_expr(*expr)),
+                    where_clause: where_clause.map(|expr| self.parse_expr(*expr)).transpose()?,
+                })
+            }
+            Node::SelectStmt(nodes::SelectStmt {
+                distinctClause: None,
+                intoClause: None,
+                targetList: None,
+                fromClause: None,
+                whereClause: None,
+                groupClause: None,
+                havingClause: None,
+                windowClause: None,
+                valuesLists: None,
+                sortClause: None,
+                limitOffset: None,
+                limitCount: None,
+                limitOption: sys::LimitOption::LIMIT_OPTION_COUNT,
+                lockingClause: None,
+                withClause: None,
+                op,
+                all,
+                larg: Some(left),
+                rarg: Some(right),
+            }) => {
+                let op = match op {
+                    sys::SetOperation::SETOP_UNION => SetOperator::Union,
+                    sys::SetOperation::SETOP_INTERSECT => SetOperator::Intersect,
+                    sys::SetOperation::SETOP_EXCEPT => SetOperator::Except,
+                    sys::SetOperation::SETOP_NONE => unreachable!("set operation without operator"),
+                };
+                Query::SetOperation(SetOperation {
+                    op,
+                    all,
+                    left: Box::new(self.process_query(Node::SelectStmt(*left))?),
+                    right: Box::new(self.process_query(Node::SelectStmt(*right))?),
                 })
             }
             Node::UpdateStmt(nodes::UpdateStmt {
@@ -351,7 +663,7 @@ impl QueryParser {
                 withClause: None,
             }) => {
                 let relation = relation.unwrap();
-                let schema_name = relation.schemaname.unwrap_or_else(|| "public".to_owned());
+                let schema_name = relation.schemaname;
                 let table_name = relation.relname.unwrap();
                 let mut assignments = vec![];
                 for target in target_list.unwrap() {
@@ -359,7 +671,7 @@ impl QueryParser {
                     match target {
                         Node::ResTarget(nodes::ResTarget { name, val, .. }) => assignments.push(Assignment {
                             column: name.unwrap().to_lowercase(),
-                            value: self.parse_expr(*val.unwrap()),
+                            value: self.parse_expr(*val.unwrap())?,
                         }),
                         _ => unimplemented!(),
                     }
@@ -368,7 +680,7 @@ impl QueryParser {
                     schema_name,
                     table_name,
                     assignments,
-                    where_clause: where_clause.map(|expr| self.parse_expr(*expr)),
+                    where_clause: where_clause.map(|expr| self.parse_expr(*expr)).transpose()?,
                 })
             }
             Node::DeleteStmt(nodes::DeleteStmt {
@@ -379,35 +691,134 @@ impl QueryParser {
                 withClause: None,
             }) => {
                 let relation = relation.unwrap();
-                let schema_name = relation.schemaname.unwrap_or_else(|| "public".to_owned());
+                let schema_name = relation.schemaname;
                 let table_name = relation.relname.unwrap();
                 Query::Delete(DeleteQuery {
                     schema_name,
                     table_name,
-                    where_clause: where_clause.map(|expr| self.parse_expr(*expr)),
+                    where_clause: where_clause.map(|expr| self.parse_expr(*expr)).transpose()?,
                 })
             }
             other => unimplemented!("NOT IMPL: {:?}", other),
+        };
+        Ok(query)
+    }
+
+    fn process_qualified_names(&self, objects: Vec<Node>) -> Vec<(Option<String>, String)> {
+        let mut names = vec![];
+        for object in objects {
+            log::trace!("OBJECT - {:?}", object);
+            match object {
+                Node::List(values) => names.push(self.process_qualified_name(values)),
+                _ => unimplemented!(),
+            }
+        }
+        names
+    }
+
+    fn process_qualified_name(&self, mut values: Vec<Node>) -> (Option<String>, String) {
+        if values.len() == 1 {
+            match values.pop() {
+                Some(Node::Value(nodes::Value { string: Some(name), .. })) => (None, name),
+                _ => unimplemented!(),
+            }
+        } else if values.len() == 2 {
+            match (values.pop(), values.pop()) {
+                (Some(Node::Value(nodes::Value { string: Some(name), .. })), Some(Node::Value(nodes::Value { string: Some(schema), .. }))) => {
+                    (Some(schema), name)
+                }
+                _ => unimplemented!(),
+            }
+        } else {
+            unimplemented!()
+        }
+    }
+
+    fn process_config_value(&self, args: Vec<Node>) -> String {
+        let mut values = vec![];
+        for arg in args {
+            match arg {
+                Node::A_Const(nodes::A_Const {
+                    val: nodes::Value { int: Some(int), .. },
+                }) => values.push(int.to_string()),
+                Node::A_Const(nodes::A_Const {
+                    val: nodes::Value { float: Some(num), .. },
+                }) => values.push(num),
+                Node::A_Const(nodes::A_Const {
+                    val: nodes::Value { string: Some(value), .. },
+                }) => values.push(value),
+                other => unimplemented!("{:?} could not be used as configuration value", other),
+            }
+        }
+        values.join(", ")
+    }
+
+    fn process_explain_flag(&self, option: &str, arg: Option<Box<Node>>) -> Result<bool, ParserError> {
+        match arg.map(|arg| *arg) {
+            None => Ok(true),
+            Some(Node::Value(nodes::Value { int: Some(int), .. })) => Ok(int != 0),
+            Some(Node::Value(nodes::Value { string: Some(value), .. })) => match value.as_str() {
+                "true" | "on" => Ok(true),
+                "false" | "off" => Ok(false),
+                other => Err(ParserError::not_supported(format!("{} as value of EXPLAIN option {}", other, option))),
+            },
+            _ => Err(ParserError::not_supported(format!("non boolean value of EXPLAIN option {}", option))),
         }
     }
 
     fn process_column(&self, node: Node) -> ColumnDef {
         if let Node::ColumnDef(column_def) = node {
-            let data_type = self.process_type(*column_def.typeName.unwrap());
+            let type_name = *column_def.typeName.unwrap();
+            let serial_type = match type_name.names.as_deref() {
+                Some([Node::Value(nodes::Value { string: Some(name), .. })]) => match name.as_str() {
+                    "smallserial" | "serial2" => Some(DataType::SmallInt),
+                    "serial" | "serial4" => Some(DataType::Int),
+                    "bigserial" | "serial8" => Some(DataType::BigInt),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let (data_type, mut identity) = match serial_type {
+                Some(data_type) => (data_type, Some(Identity::ByDefault)),
+                None => (self.process_type(type_name), None),
+            };
+            for constraint in column_def.constraints.unwrap_or_else(Vec::new) {
+                if let Node::Constraint(nodes::Constraint {
+                    contype: sys::ConstrType::CONSTR_IDENTITY,
+                    generated_when,
+                    ..
+                }) = constraint
+                {
+                    identity = match generated_when {
+                        'a' => Some(Identity::Always),
+                        _ => Some(Identity::ByDefault),
+                    }
+                }
+            }
             ColumnDef {
                 name: column_def.colname.unwrap().to_lowercase(),
                 data_type,
+                identity,
             }
         } else {
             unimplemented!()
         }
     }
 
+    fn parse_sequence_option(&self, node: Node) -> i64 {
+        match node {
+            Node::Value(nodes::Value { int: Some(value), .. }) => value as i64,
+            Node::Value(nodes::Value { float: Some(value), .. }) => value.parse().unwrap(),
+            _ => unimplemented!(),
+        }
+    }
+
     fn process_type(&self, type_name: nodes::TypeName) -> DataType {
         log::trace!("TYPE NAME {:#?}", type_name);
         let name = type_name.names.unwrap();
         let mode = type_name.typmods;
-        match &name[1] {
+        // built-in types are qualified with `pg_catalog` except for `text`
+        match name.last().unwrap() {
             Node::Value(nodes::Value { string, .. }) if string.as_deref() == Some("int2") => DataType::SmallInt,
             Node::Value(nodes::Value { string, .. }) if string.as_deref() == Some("int4") => DataType::Int,
             Node::Value(nodes::Value { string, .. }) if string.as_deref() == Some("int8") => DataType::BigInt,
@@ -423,6 +834,7 @@ impl QueryParser {
                 })) => DataType::Char(len as u32),
                 _ => unimplemented!(),
             },
+            Node::Value(nodes::Value { string, .. }) if string.as_deref() == Some("text") => DataType::VarChar(None),
             Node::Value(nodes::Value { string, .. }) if string.as_deref() == Some("varchar") => match mode.as_ref().map(|inner| &inner[0]) {
                 None => DataType::VarChar(None),
                 Some(&Node::A_Const(nodes::A_Const {
@@ -434,56 +846,193 @@ impl QueryParser {
         }
     }
 
-    fn parse_expr(&self, node: Node) -> Expr {
+    fn parse_expr(&self, node: Node) -> Result<Expr, ParserError> {
         log::trace!("NODE {:?}", node);
-        match node {
+        let expr = match node {
             Node::BoolExpr(nodes::BoolExpr { boolop: bool_op, args }) => {
+                let mut args = self.parse_args(args)?.into_iter();
                 let op = match bool_op {
                     sys::BoolExprType::AND_EXPR => BinaryOperator::And,
                     sys::BoolExprType::OR_EXPR => BinaryOperator::Or,
-                    sys::BoolExprType::NOT_EXPR => unimplemented!(),
+                    sys::BoolExprType::NOT_EXPR => match args.next() {
+                        Some(expr) => {
+                            return Ok(Expr::UnaryOp {
+                                op: UnaryOperator::Not,
+                                expr: Box::new(expr),
+                            })
+                        }
+                        None => unimplemented!(),
+                    },
                 };
-                if let Some(mut values) = args {
-                    let right = match values.pop() {
-                        Some(Node::A_Const(constant)) => self.parse_const(constant),
-                        _ => unimplemented!(),
-                    };
-                    let left = match values.pop() {
-                        Some(Node::A_Const(constant)) => self.parse_const(constant),
-                        _ => unimplemented!(),
-                    };
-                    Expr::BinaryOp {
+                // `a AND b AND c` comes as a single node with all its operands
+                match args.next() {
+                    Some(first) => args.fold(first, |left, right| Expr::BinaryOp {
                         left: Box::new(left),
                         op,
                         right: Box::new(right),
+                    }),
+                    None => unimplemented!(),
+                }
+            }
+            Node::NullTest(nodes::NullTest {
+                arg: Some(arg),
+                nulltesttype,
+                ..
+            }) => Expr::UnaryOp {
+                op: match nulltesttype {
+                    sys::NullTestType::IS_NULL => UnaryOperator::IsNull,
+                    sys::NullTestType::IS_NOT_NULL => UnaryOperator::IsNotNull,
+                },
+                expr: Box::new(self.parse_expr(*arg)?),
+            },
+            Node::BooleanTest(nodes::BooleanTest {
+                arg: Some(arg),
+                booltesttype,
+            }) => Expr::UnaryOp {
+                op: match booltesttype {
+                    sys::BoolTestType::IS_TRUE => UnaryOperator::IsTrue,
+                    sys::BoolTestType::IS_NOT_TRUE => UnaryOperator::IsNotTrue,
+                    sys::BoolTestType::IS_FALSE => UnaryOperator::IsFalse,
+                    sys::BoolTestType::IS_NOT_FALSE => UnaryOperator::IsNotFalse,
+                    sys::BoolTestType::IS_UNKNOWN => UnaryOperator::IsUnknown,
+                    sys::BoolTestType::IS_NOT_UNKNOWN => UnaryOperator::IsNotUnknown,
+                },
+                expr: Box::new(self.parse_expr(*arg)?),
+            },
+            Node::A_Expr(nodes::A_Expr {
+                kind: sys::A_Expr_Kind::AEXPR_IN,
+                name: Some(values),
+                lexpr: Some(left_expr),
+                rexpr: Some(right_expr),
+            }) => Expr::InList {
+                expr: Box::new(self.parse_expr(*left_expr)?),
+                list: self.parse_list(*right_expr)?,
+                negated: self.parse_binary_operator(&values) == BinaryOperator::NotEq,
+            },
+            Node::A_Expr(nodes::A_Expr {
+                kind,
+                lexpr: Some(left_expr),
+                rexpr: Some(right_expr),
+                ..
+            }) if kind == sys::A_Expr_Kind::AEXPR_BETWEEN || kind == sys::A_Expr_Kind::AEXPR_NOT_BETWEEN => {
+                let mut bounds = self.parse_list(*right_expr)?.into_iter();
+                match (bounds.next(), bounds.next()) {
+                    (Some(low), Some(high)) => Expr::Between {
+                        expr: Box::new(self.parse_expr(*left_expr)?),
+                        low: Box::new(low),
+                        high: Box::new(high),
+                        negated: kind == sys::A_Expr_Kind::AEXPR_NOT_BETWEEN,
+                    },
+                    _ => unimplemented!(),
+                }
+            }
+            Node::A_Expr(nodes::A_Expr {
+                kind,
+                name: Some(values),
+                lexpr: Some(left_expr),
+                rexpr: Some(right_expr),
+            }) if kind == sys::A_Expr_Kind::AEXPR_OP_ANY || kind == sys::A_Expr_Kind::AEXPR_OP_ALL => {
+                let left = Box::new(self.parse_expr(*left_expr)?);
+                let op = self.parse_binary_operator(&values);
+                let right = match *right_expr {
+                    Node::A_ArrayExpr(nodes::A_ArrayExpr { elements }) => self.parse_args(elements)?,
+                    _ => unimplemented!(),
+                };
+                if kind == sys::A_Expr_Kind::AEXPR_OP_ANY {
+                    Expr::Any { left, op, right }
+                } else {
+                    Expr::All { left, op, right }
+                }
+            }
+            Node::A_Expr(nodes::A_Expr {
+                kind: sys::A_Expr_Kind::AEXPR_DISTINCT,
+                lexpr: Some(left_expr),
+                rexpr: Some(right_expr),
+                ..
+            }) => Expr::BinaryOp {
+                left: Box::new(self.parse_expr(*left_expr)?),
+                op: BinaryOperator::IsDistinctFrom,
+                right: Box::new(self.parse_expr(*right_expr)?),
+            },
+            Node::A_Expr(nodes::A_Expr {
+                kind: sys::A_Expr_Kind::AEXPR_NOT_DISTINCT,
+                lexpr: Some(left_expr),
+                rexpr: Some(right_expr),
+                ..
+            }) => Expr::BinaryOp {
+                left: Box::new(self.parse_expr(*left_expr)?),
+                op: BinaryOperator::IsNotDistinctFrom,
+                right: Box::new(self.parse_expr(*right_expr)?),
+            },
+            // `ESCAPE` clause is parsed into `like_escape(pattern, escape)` function call
+            Node::A_Expr(nodes::A_Expr {
+                kind: kind @ sys::A_Expr_Kind::AEXPR_LIKE,
+                name: Some(values),
+                lexpr: Some(left_expr),
+                rexpr: Some(right_expr),
+            })
+            | Node::A_Expr(nodes::A_Expr {
+                kind: kind @ sys::A_Expr_Kind::AEXPR_ILIKE,
+                name: Some(values),
+                lexpr: Some(left_expr),
+                rexpr: Some(right_expr),
+            }) => {
+                let op = if let Some(Node::Value(nodes::Value { string: Some(op), .. })) = values.first() {
+                    match (kind, op.as_str()) {
+                        (sys::A_Expr_Kind::AEXPR_LIKE, "~~") => BinaryOperator::Like,
+                        (sys::A_Expr_Kind::AEXPR_LIKE, "!~~") => BinaryOperator::NotLike,
+                        (sys::A_Expr_Kind::AEXPR_ILIKE, "~~*") => BinaryOperator::ILike,
+                        (sys::A_Expr_Kind::AEXPR_ILIKE, "!~~*") => BinaryOperator::NotILike,
+                        _ => unimplemented!(),
                     }
                 } else {
                     unimplemented!()
+                };
+                Expr::BinaryOp {
+                    left: Box::new(self.parse_expr(*left_expr)?),
+                    op,
+                    right: Box::new(self.parse_expr(*right_expr)?),
                 }
             }
+            // pattern is wrapped into `similar_to_escape(pattern [, escape])` function call
             Node::A_Expr(nodes::A_Expr {
-                kind: sys::A_Expr_Kind::AEXPR_LIKE,
-                name,
-                lexpr: left_expr,
-                rexpr: right_expr,
+                kind: sys::A_Expr_Kind::AEXPR_SIMILAR,
+                name: Some(values),
+                lexpr: Some(left_expr),
+                rexpr: Some(right_expr),
             }) => {
-                if let Some(values) = name {
-                    let op = if let Node::Value(nodes::Value { string: Some(op), .. }) = &values[0] {
-                        match op.as_str() {
-                            "~~" => BinaryOperator::Like,
-                            "!~~" => BinaryOperator::NotLike,
-                            _ => unimplemented!(),
-                        }
-                    } else {
-                        unimplemented!()
-                    };
-                    Expr::BinaryOp {
-                        left: Box::new(self.parse_expr(*left_expr.unwrap())),
-                        op,
-                        right: Box::new(self.parse_expr(*right_expr.unwrap())),
+                let op = if let Some(Node::Value(nodes::Value { string: Some(op), .. })) = values.first() {
+                    match op.as_str() {
+                        "~" => BinaryOperator::SimilarTo,
+                        "!~" => BinaryOperator::NotSimilarTo,
+                        _ => unimplemented!(),
                     }
                 } else {
                     unimplemented!()
+                };
+                let pattern = match *right_expr {
+                    Node::FuncCall(nodes::FuncCall { args: Some(args), .. }) => {
+                        let mut args = args
+                            .into_iter()
+                            .map(|arg| self.parse_expr(arg))
+                            .collect::<Result<Vec<_>, _>>()?
+                            .into_iter();
+                        match (args.next(), args.next()) {
+                            (Some(pattern), None) | (Some(pattern), Some(Expr::Value(Value::Null))) => pattern,
+                            // both LIKE and SIMILAR TO use `\` as the default escape character
+                            (Some(pattern), Some(escape)) => Expr::Function {
+                                name: "like_escape".to_owned(),
+                                args: vec![pattern, escape],
+                            },
+                            _ => unimplemented!(),
+                        }
+                    }
+                    _ => unimplemented!(),
+                };
+                Expr::BinaryOp {
+                    left: Box::new(self.parse_expr(*left_expr)?),
+                    op,
+                    right: Box::new(pattern),
                 }
             }
             Node::A_Expr(nodes::A_Expr {
@@ -509,7 +1058,7 @@ impl QueryParser {
                 };
                 Expr::UnaryOp {
                     op,
-                    expr: Box::new(self.parse_expr(*right_expr)),
+                    expr: Box::new(self.parse_expr(*right_expr)?),
                 }
             }
             Node::A_Expr(nodes::A_Expr {
@@ -528,7 +1077,7 @@ impl QueryParser {
                 };
                 Expr::UnaryOp {
                     op,
-                    expr: Box::new(self.parse_expr(*left_expr)),
+                    expr: Box::new(self.parse_expr(*left_expr)?),
                 }
             }
             Node::A_Expr(nodes::A_Expr {
@@ -537,35 +1086,11 @@ impl QueryParser {
                 lexpr: Some(left_expr),
                 rexpr: Some(right_expr),
             }) => {
-                let op = if let Node::Value(nodes::Value { string: Some(op), .. }) = &values[0] {
-                    match op.as_str() {
-                        "+" => BinaryOperator::Plus,
-                        "-" => BinaryOperator::Minus,
-                        "*" => BinaryOperator::Multiply,
-                        "/" => BinaryOperator::Divide,
-                        "%" => BinaryOperator::Modulus,
-                        "^" => BinaryOperator::Exp,
-                        "||" => BinaryOperator::StringConcat,
-                        ">" => BinaryOperator::Gt,
-                        "<" => BinaryOperator::Lt,
-                        ">=" => BinaryOperator::GtEq,
-                        "<=" => BinaryOperator::LtEq,
-                        "=" => BinaryOperator::Eq,
-                        "<>" => BinaryOperator::NotEq,
-                        "|" => BinaryOperator::BitwiseOr,
-                        "&" => BinaryOperator::BitwiseAnd,
-                        "#" => BinaryOperator::BitwiseXor,
-                        "<<" => BinaryOperator::BitwiseShiftLeft,
-                        ">>" => BinaryOperator::BitwiseShiftRight,
-                        _ => unimplemented!(),
-                    }
-                } else {
-                    unimplemented!()
-                };
+                let op = self.parse_binary_operator(&values);
                 Expr::BinaryOp {
-                    left: Box::new(self.parse_expr(*left_expr)),
+                    left: Box::new(self.parse_expr(*left_expr)?),
                     op,
-                    right: Box::new(self.parse_expr(*right_expr)),
+                    right: Box::new(self.parse_expr(*right_expr)?),
                 }
             }
             Node::A_Const(nodes::A_Const {
@@ -574,6 +1099,9 @@ impl QueryParser {
             Node::A_Const(nodes::A_Const {
                 val: nodes::Value { float: Some(num), .. },
             }) => Expr::Value(Value::Number(num)),
+            Node::A_Const(nodes::A_Const {
+                val: nodes::Value { null: Some(()), .. },
+            }) => Expr::Value(Value::Null),
             Node::A_Const(nodes::A_Const {
                 val: nodes::Value { string: Some(value), .. },
             }) => Expr::Value(Value::String(value)),
@@ -586,46 +1114,278 @@ impl QueryParser {
                 arg: Some(expr),
                 typeName: Some(type_name),
             }) => Expr::Cast {
-                expr: Box::new(self.parse_expr(*expr)),
+                expr: Box::new(self.parse_expr(*expr)?),
                 data_type: self.process_type(*type_name),
             },
+            Node::FuncCall(nodes::FuncCall {
+                funcname: Some(mut names),
+                args,
+                agg_order: None,
+                agg_filter: None,
+                agg_within_group: false,
+                agg_distinct: false,
+                over: Some(window),
+                ..
+            }) => {
+                let name = match names.pop() {
+                    Some(Node::Value(nodes::Value { string: Some(name), .. })) => name.to_lowercase(),
+                    _ => unimplemented!(),
+                };
+                match *window {
+                    nodes::WindowDef {
+                        name: None,
+                        refname: None,
+                        partitionClause: partition_clause,
+                        orderClause: order_clause,
+                        frameOptions: frame_options,
+                        startOffset: start_offset,
+                        endOffset: end_offset,
+                    } => Expr::Window {
+                        name,
+                        args: self.parse_args(args)?,
+                        over: Box::new(WindowSpec {
+                            partition_by: self.parse_args(partition_clause)?,
+                            order_by: order_clause
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|sort_key| self.parse_sort_key(sort_key))
+                                .collect::<Result<_, _>>()?,
+                            frame: self.parse_window_frame(frame_options, start_offset, end_offset)?,
+                        }),
+                    },
+                    _ => return Err(ParserError::not_supported("reference to a named window")),
+                }
+            }
+            Node::FuncCall(nodes::FuncCall {
+                funcname: Some(mut names),
+                args,
+                agg_order: None,
+                agg_filter: None,
+                over: None,
+                ..
+            }) => match names.pop() {
+                Some(Node::Value(nodes::Value { string: Some(name), .. })) => Expr::Function {
+                    name: name.to_lowercase(),
+                    args: self.parse_args(args)?,
+                },
+                _ => unimplemented!(),
+            },
+            Node::CaseExpr(nodes::CaseExpr { arg, args, defresult, .. }) => {
+                let (mut conditions, mut results) = (vec![], vec![]);
+                for when in args.unwrap_or_default() {
+                    match when {
+                        Node::CaseWhen(nodes::CaseWhen {
+                            expr: Some(condition),
+                            result: Some(result),
+                        }) => {
+                            conditions.push(self.parse_expr(*condition)?);
+                            results.push(self.parse_expr(*result)?);
+                        }
+                        _ => unimplemented!(),
+                    }
+                }
+                Expr::Case {
+                    operand: arg.map(|operand| self.parse_expr(*operand).map(Box::new)).transpose()?,
+                    conditions,
+                    results,
+                    else_result: defresult.map(|else_result| self.parse_expr(*else_result).map(Box::new)).transpose()?,
+                }
+            }
+            Node::CoalesceExpr(nodes::CoalesceExpr { args, .. }) => Expr::Coalesce(self.parse_args(args)?),
+            Node::MinMaxExpr(nodes::MinMaxExpr {
+                op: sys::MinMaxOp::IS_GREATEST,
+                args,
+                ..
+            }) => Expr::Greatest(self.parse_args(args)?),
+            Node::MinMaxExpr(nodes::MinMaxExpr {
+                op: sys::MinMaxOp::IS_LEAST,
+                args,
+                ..
+            }) => Expr::Least(self.parse_args(args)?),
+            Node::A_Expr(nodes::A_Expr {
+                kind: sys::A_Expr_Kind::AEXPR_NULLIF,
+                lexpr: Some(left_expr),
+                rexpr: Some(right_expr),
+                ..
+            }) => Expr::NullIf {
+                left: Box::new(self.parse_expr(*left_expr)?),
+                right: Box::new(self.parse_expr(*right_expr)?),
+            },
             _ => unimplemented!(),
+        };
+        Ok(expr)
+    }
+
+    fn parse_binary_operator(&self, values: &[Node]) -> BinaryOperator {
+        if let Some(Node::Value(nodes::Value { string: Some(op), .. })) = values.first() {
+            match op.as_str() {
+                "+" => BinaryOperator::Plus,
+                "-" => BinaryOperator::Minus,
+                "*" => BinaryOperator::Multiply,
+                "/" => BinaryOperator::Divide,
+                "%" => BinaryOperator::Modulus,
+                "^" => BinaryOperator::Exp,
+                "||" => BinaryOperator::StringConcat,
+                ">" => BinaryOperator::Gt,
+                "<" => BinaryOperator::Lt,
+                ">=" => BinaryOperator::GtEq,
+                "<=" => BinaryOperator::LtEq,
+                "=" => BinaryOperator::Eq,
+                "<>" => BinaryOperator::NotEq,
+                "|" => BinaryOperator::BitwiseOr,
+                "&" => BinaryOperator::BitwiseAnd,
+                "#" => BinaryOperator::BitwiseXor,
+                "<<" => BinaryOperator::BitwiseShiftLeft,
+                ">>" => BinaryOperator::BitwiseShiftRight,
+                "~" => BinaryOperator::RegexMatch,
+                "~*" => BinaryOperator::RegexIMatch,
+                "!~" => BinaryOperator::NotRegexMatch,
+                "!~*" => BinaryOperator::NotRegexIMatch,
+                _ => unimplemented!(),
+            }
+        } else {
+            unimplemented!()
         }
     }
 
-    fn parse_const(&self, node: nodes::A_Const) -> Expr {
+    fn parse_list(&self, node: Node) -> Result<Vec<Expr>, ParserError> {
         match node {
-            nodes::A_Const {
-                val: nodes::Value { string: Some(value), .. },
-            } => Expr::Value(Value::String(value)),
-            nodes::A_Const {
-                val: nodes::Value { int: Some(value), .. },
-            } => Expr::Value(Value::Int(value)),
+            Node::List(items) => items.into_iter().map(|item| self.parse_expr(item)).collect(),
             _ => unimplemented!(),
         }
     }
+
+    fn parse_args(&self, args: Option<Vec<Node>>) -> Result<Vec<Expr>, ParserError> {
+        args.unwrap_or_default().into_iter().map(|arg| self.parse_expr(arg)).collect()
+    }
+
+    // NULLs are larger than any other value, so they go last in ascending order by default
+    fn parse_sort_key(&self, node: Node) -> Result<OrderByExpr, ParserError> {
+        match node {
+            Node::SortBy(nodes::SortBy {
+                node: Some(expr),
+                sortby_dir,
+                sortby_nulls,
+                useOp: None,
+            }) => {
+                let ascending = match sortby_dir {
+                    sys::SortByDir::SORTBY_DEFAULT | sys::SortByDir::SORTBY_ASC => true,
+                    sys::SortByDir::SORTBY_DESC => false,
+                    sys::SortByDir::SORTBY_USING => return Err(ParserError::not_supported("ORDER BY USING")),
+                };
+                let nulls_first = match sortby_nulls {
+                    sys::SortByNulls::SORTBY_NULLS_DEFAULT => !ascending,
+                    sys::SortByNulls::SORTBY_NULLS_FIRST => true,
+                    sys::SortByNulls::SORTBY_NULLS_LAST => false,
+                };
+                Ok(OrderByExpr {
+                    expr: self.parse_expr(*expr)?,
+                    ascending,
+                    nulls_first,
+                })
+            }
+            Node::SortBy(nodes::SortBy { useOp: Some(_), .. }) => Err(ParserError::not_supported("ORDER BY USING")),
+            other => unimplemented!("{:?} could not be used as sort key", other),
+        }
+    }
+
+    fn parse_window_frame(
+        &self,
+        options: i32,
+        start_offset: Option<Box<Node>>,
+        end_offset: Option<Box<Node>>,
+    ) -> Result<Option<WindowFrame>, ParserError> {
+        if options & FRAMEOPTION_NONDEFAULT == 0 {
+            return Ok(None);
+        }
+        if options & FRAMEOPTION_EXCLUSION != 0 {
+            return Err(ParserError::not_supported("frame exclusion"));
+        }
+        let units = if options & FRAMEOPTION_ROWS != 0 {
+            WindowFrameUnits::Rows
+        } else if options & FRAMEOPTION_RANGE != 0 {
+            WindowFrameUnits::Range
+        } else {
+            return Err(ParserError::not_supported("GROUPS frame"));
+        };
+        let start = if options & FRAMEOPTION_START_UNBOUNDED_PRECEDING != 0 {
+            WindowFrameBound::UnboundedPreceding
+        } else if options & FRAMEOPTION_START_CURRENT_ROW != 0 {
+            WindowFrameBound::CurrentRow
+        } else if options & FRAMEOPTION_START_OFFSET_PRECEDING != 0 {
+            WindowFrameBound::Preceding(self.parse_frame_offset(start_offset)?)
+        } else if options & FRAMEOPTION_START_OFFSET_FOLLOWING != 0 {
+            WindowFrameBound::Following(self.parse_frame_offset(start_offset)?)
+        } else {
+            unreachable!("frame start could not be UNBOUNDED FOLLOWING")
+        };
+        // frame without `BETWEEN` ends with the current row
+        let end = if options & FRAMEOPTION_END_UNBOUNDED_FOLLOWING != 0 {
+            WindowFrameBound::UnboundedFollowing
+        } else if options & FRAMEOPTION_END_CURRENT_ROW != 0 {
+            WindowFrameBound::CurrentRow
+        } else if options & FRAMEOPTION_END_OFFSET_PRECEDING != 0 {
+            WindowFrameBound::Preceding(self.parse_frame_offset(end_offset)?)
+        } else if options & FRAMEOPTION_END_OFFSET_FOLLOWING != 0 {
+            WindowFrameBound::Following(self.parse_frame_offset(end_offset)?)
+        } else {
+            unreachable!("frame end could not be UNBOUNDED PRECEDING")
+        };
+        Ok(Some(WindowFrame { units, start, end }))
+    }
+
+    fn parse_frame_offset(&self, offset: Option<Box<Node>>) -> Result<u64, ParserError> {
+        match offset.map(|offset| *offset) {
+            Some(Node::A_Const(nodes::A_Const {
+                val: nodes::Value { int: Some(offset), .. },
+            })) if offset >= 0 => Ok(offset as u64),
+            _ => Err(ParserError::not_supported("frame offset that is not a non-negative integer constant")),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct ParserError {
-    error: PgParserError,
+    kind: ParserErrorKind,
+}
+
+#[derive(Debug, PartialEq)]
+enum ParserErrorKind {
+    Syntax(PgParserError),
+    NotSupported(String),
+}
+
+impl ParserError {
+    fn not_supported<S: ToString>(feature: S) -> ParserError {
+        ParserError {
+            kind: ParserErrorKind::NotSupported(feature.to_string()),
+        }
+    }
 }
 
 impl From<PgParserError> for ParserError {
     fn from(error: PgParserError) -> ParserError {
-        ParserError { error }
+        ParserError {
+            kind: ParserErrorKind::Syntax(error),
+        }
     }
 }
 
 impl From<ParserError> for QueryError {
     fn from(error: ParserError) -> QueryError {
-        QueryError::syntax_error(error)
+        match error.kind {
+            ParserErrorKind::Syntax(_) => QueryError::syntax_error(error),
+            ParserErrorKind::NotSupported(feature) => QueryError::feature_not_supported(feature),
+        }
     }
 }
 
 impl Display for ParserError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.error)
+        match &self.kind {
+            ParserErrorKind::Syntax(error) => write!(f, "{:?}", error),
+            ParserErrorKind::NotSupported(feature) => write!(f, "{} is not supported", feature),
+        }
     }
 }
 
@@ -0,0 +1,116 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[test]
+fn set_variable() {
+    let statements = QUERY_PARSER.parse("set variable=value;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Config(Config::Set {
+            variable: "variable".to_owned(),
+            value: Some("value".to_owned()),
+            local: false
+        }))
+    );
+}
+
+#[test]
+fn set_variable_to_number() {
+    let statements = QUERY_PARSER.parse("set statement_timeout to 5000;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Config(Config::Set {
+            variable: "statement_timeout".to_owned(),
+            value: Some("5000".to_owned()),
+            local: false
+        }))
+    );
+}
+
+#[test]
+fn set_variable_to_list() {
+    let statements = QUERY_PARSER.parse("set search_path = schema_name, public;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Config(Config::Set {
+            variable: "search_path".to_owned(),
+            value: Some("schema_name, public".to_owned()),
+            local: false
+        }))
+    );
+}
+
+#[test]
+fn set_local_variable() {
+    let statements = QUERY_PARSER.parse("set local application_name = 'app';");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Config(Config::Set {
+            variable: "application_name".to_owned(),
+            value: Some("app".to_owned()),
+            local: true
+        }))
+    );
+}
+
+#[test]
+fn set_variable_to_default() {
+    let statements = QUERY_PARSER.parse("set application_name to default;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Config(Config::Set {
+            variable: "application_name".to_owned(),
+            value: None,
+            local: false
+        }))
+    );
+}
+
+#[test]
+fn show_variable() {
+    let statements = QUERY_PARSER.parse("show DateStyle;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Config(Config::Show {
+            variable: "datestyle".to_owned()
+        }))
+    );
+}
+
+#[test]
+fn reset_variable() {
+    let statements = QUERY_PARSER.parse("reset application_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Config(Config::Reset {
+            variable: Some("application_name".to_owned())
+        }))
+    );
+}
+
+#[test]
+fn reset_all() {
+    let statements = QUERY_PARSER.parse("reset all;");
+
+    assert_eq!(statements, Ok(Request::Config(Config::Reset { variable: None })));
+}
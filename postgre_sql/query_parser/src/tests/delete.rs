@@ -21,7 +21,7 @@ fn delete_from_table() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Delete(DeleteQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             where_clause: None,
         }))))
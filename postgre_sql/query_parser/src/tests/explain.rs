@@ -0,0 +1,117 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn select_all() -> Query {
+    Query::Select(SelectQuery {
+        distinct: None,
+        select_items: vec![SelectItem::Wildcard],
+        schema_name: Some("schema_name".to_owned()),
+        table_name: Some("table_name".to_owned()),
+        where_clause: None,
+    })
+}
+
+#[test]
+fn explain_query() {
+    let statements = QUERY_PARSER.parse("explain select * from schema_name.table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Explain(Explain {
+            query: select_all(),
+            analyze: false,
+            verbose: false,
+            format: ExplainFormat::Text
+        })))
+    );
+}
+
+#[test]
+fn explain_analyze_verbose_query() {
+    let statements = QUERY_PARSER.parse("explain analyze verbose select * from schema_name.table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Explain(Explain {
+            query: select_all(),
+            analyze: true,
+            verbose: true,
+            format: ExplainFormat::Text
+        })))
+    );
+}
+
+#[test]
+fn explain_with_options() {
+    let statements = QUERY_PARSER.parse("explain (analyze, verbose false, format json) select * from schema_name.table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Explain(Explain {
+            query: select_all(),
+            analyze: true,
+            verbose: false,
+            format: ExplainFormat::Json
+        })))
+    );
+}
+
+#[test]
+fn explain_delete() {
+    let statements = QUERY_PARSER.parse("explain delete from schema_name.table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Explain(Explain {
+            query: Query::Delete(DeleteQuery {
+                schema_name: Some("schema_name".to_owned()),
+                table_name: "table_name".to_owned(),
+                where_clause: None,
+            }),
+            analyze: false,
+            verbose: false,
+            format: ExplainFormat::Text
+        })))
+    );
+}
+
+#[test]
+fn explain_unsupported_format() {
+    let statements = QUERY_PARSER.parse("explain (format yaml) select * from schema_name.table_name;");
+
+    assert_eq!(statements, Err(ParserError::not_supported("EXPLAIN format yaml")));
+}
+
+#[test]
+fn explain_unsupported_option() {
+    let statements = QUERY_PARSER.parse("explain (costs) select * from schema_name.table_name;");
+
+    assert_eq!(statements, Err(ParserError::not_supported("EXPLAIN option costs")));
+}
+
+#[test]
+fn explain_unsupported_option_value() {
+    let statements = QUERY_PARSER.parse("explain (analyze maybe) select * from schema_name.table_name;");
+
+    assert_eq!(statements, Err(ParserError::not_supported("maybe as value of EXPLAIN option analyze")));
+}
+
+#[test]
+fn unsupported_explain_option_is_reported_as_not_supported_feature() {
+    let error = QUERY_PARSER.parse("explain (costs) select * from schema_name.table_name;").unwrap_err();
+
+    assert_eq!(QueryError::from(error), QueryError::feature_not_supported("EXPLAIN option costs"));
+}
@@ -23,7 +23,7 @@ fn prepare() {
         statement,
         Ok(Request::Statement(Statement::Extended(Extended::Prepare {
             query: Query::Insert(InsertQuery {
-                schema_name: "schema_name".to_owned(),
+                schema_name: Some("schema_name".to_owned()),
                 table_name: "table_name".to_owned(),
                 columns: vec![],
                 source: InsertSource::Values(Values(vec![vec![Expr::Param(1)]]))
@@ -0,0 +1,106 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[test]
+fn create_function() {
+    let statements =
+        QUERY_PARSER.parse("create function add_tax(price int, rate int) returns int language sql as $$ select price + price * rate / 100 $$;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::CreateFunction {
+            schema_name: None,
+            function_name: "add_tax".to_owned(),
+            parameters: vec![(Some("price".to_owned()), DataType::Int), (Some("rate".to_owned()), DataType::Int)],
+            return_type: DataType::Int,
+            body: SelectQuery {
+                distinct: None,
+                select_items: vec![SelectItem::UnnamedExpr(Expr::BinaryOp {
+                    left: Box::new(Expr::Column("price".to_owned())),
+                    op: BinaryOperator::Plus,
+                    right: Box::new(Expr::BinaryOp {
+                        left: Box::new(Expr::BinaryOp {
+                            left: Box::new(Expr::Column("price".to_owned())),
+                            op: BinaryOperator::Multiply,
+                            right: Box::new(Expr::Column("rate".to_owned())),
+                        }),
+                        op: BinaryOperator::Divide,
+                        right: Box::new(Expr::Value(Value::Int(100))),
+                    }),
+                })],
+                schema_name: None,
+                table_name: None,
+                where_clause: None,
+            },
+            or_replace: false,
+        })))
+    );
+}
+
+#[test]
+fn create_or_replace_function_with_unnamed_parameters() {
+    let statements =
+        QUERY_PARSER.parse("create or replace function schema_name.greet(text) returns text as 'select concat(''hello, '', $1)' language sql;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::CreateFunction {
+            schema_name: Some("schema_name".to_owned()),
+            function_name: "greet".to_owned(),
+            parameters: vec![(None, DataType::VarChar(None))],
+            return_type: DataType::VarChar(None),
+            body: SelectQuery {
+                distinct: None,
+                select_items: vec![SelectItem::UnnamedExpr(Expr::Function {
+                    name: "concat".to_owned(),
+                    args: vec![Expr::Value(Value::String("hello, ".to_owned())), Expr::Param(1)],
+                })],
+                schema_name: None,
+                table_name: None,
+                where_clause: None,
+            },
+            or_replace: true,
+        })))
+    );
+}
+
+#[test]
+fn drop_functions() {
+    let statements = QUERY_PARSER.parse("drop function add_tax(int, int), schema_name.greet;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::DropFunctions {
+            names: vec![(None, "add_tax".to_owned()), (Some("schema_name".to_owned()), "greet".to_owned())],
+            if_exists: false,
+            cascade: false,
+        })))
+    );
+}
+
+#[test]
+fn drop_function_if_exists_cascade() {
+    let statements = QUERY_PARSER.parse("drop function if exists add_tax cascade;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::DropFunctions {
+            names: vec![(None, "add_tax".to_owned())],
+            if_exists: true,
+            cascade: true,
+        })))
+    );
+}
@@ -22,7 +22,7 @@ fn create_index() {
         statements,
         Ok(Request::Statement(Statement::Definition(Definition::CreateIndex {
             name: "index_name".to_owned(),
-            schema_name: "public".to_owned(),
+            schema_name: None,
             table_name: "table_name".to_owned(),
             column_names: vec!["col_1".to_owned(), "col_2".to_owned()]
         })))
@@ -21,7 +21,7 @@ fn insert_int() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             columns: vec![],
             source: InsertSource::Values(Values(vec![vec![Expr::Value(Value::Int(123))]]))
@@ -36,7 +36,7 @@ fn insert_string() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             columns: vec![],
             source: InsertSource::Values(Values(vec![vec![Expr::Value(Value::String("abc".to_owned()))]]))
@@ -68,6 +68,14 @@ mod operators {
         case::or(BinaryOperator::Or, "OR"),
         case::like(BinaryOperator::Like, "LIKE"),
         case::not_like(BinaryOperator::NotLike, "NOT LIKE"),
+        case::ilike(BinaryOperator::ILike, "ILIKE"),
+        case::not_ilike(BinaryOperator::NotILike, "NOT ILIKE"),
+        case::similar_to(BinaryOperator::SimilarTo, "SIMILAR TO"),
+        case::not_similar_to(BinaryOperator::NotSimilarTo, "NOT SIMILAR TO"),
+        case::regex_match(BinaryOperator::RegexMatch, "~"),
+        case::regex_i_match(BinaryOperator::RegexIMatch, "~*"),
+        case::not_regex_match(BinaryOperator::NotRegexMatch, "!~"),
+        case::not_regex_i_match(BinaryOperator::NotRegexIMatch, "!~*"),
         case::bitwise_or(BinaryOperator::BitwiseOr, "|"),
         case::bitwise_and(BinaryOperator::BitwiseAnd, "&"),
         case::bitwise_xor(BinaryOperator::BitwiseXor, "#"),
@@ -80,7 +88,7 @@ mod operators {
         assert_eq!(
             statements,
             Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-                schema_name: "schema_name".to_owned(),
+                schema_name: Some("schema_name".to_owned()),
                 table_name: "table_name".to_owned(),
                 columns: vec![],
                 source: InsertSource::Values(Values(vec![vec![Expr::BinaryOp {
@@ -110,7 +118,7 @@ mod operators {
         assert_eq!(
             statements,
             Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-                schema_name: "schema_name".to_owned(),
+                schema_name: Some("schema_name".to_owned()),
                 table_name: "table_name".to_owned(),
                 columns: vec![],
                 source: InsertSource::Values(Values(vec![vec![Expr::UnaryOp {
@@ -132,7 +140,7 @@ mod operators {
         assert_eq!(
             statements,
             Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-                schema_name: "schema_name".to_owned(),
+                schema_name: Some("schema_name".to_owned()),
                 table_name: "table_name".to_owned(),
                 columns: vec![],
                 source: InsertSource::Values(Values(vec![vec![Expr::UnaryOp {
@@ -151,7 +159,7 @@ fn insert_with_columns() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             columns: vec!["col1".to_owned()],
             source: InsertSource::Values(Values(vec![vec![Expr::Value(Value::Int(123))]]))
@@ -166,7 +174,7 @@ fn insert_params() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             columns: vec!["col1".to_owned()],
             source: InsertSource::Values(Values(vec![vec![Expr::Param(1)]]))
@@ -181,7 +189,7 @@ fn insert_column() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             columns: vec!["col1".to_owned()],
             source: InsertSource::Values(Values(vec![vec![Expr::Column("col2".to_owned())]]))
@@ -199,7 +207,7 @@ fn insert_int_max() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             columns: vec![],
             source: InsertSource::Values(Values(vec![vec![
@@ -221,7 +229,7 @@ fn cast() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             columns: vec![],
             source: InsertSource::Values(Values(vec![vec![Expr::Cast {
@@ -14,11 +14,19 @@
 
 use super::*;
 
+#[cfg(test)]
+mod analyze;
+#[cfg(test)]
+mod config;
 #[cfg(test)]
 mod delete;
 #[cfg(test)]
+mod explain;
+#[cfg(test)]
 mod extended;
 #[cfg(test)]
+mod function;
+#[cfg(test)]
 mod index;
 #[cfg(test)]
 mod insert;
@@ -29,21 +37,14 @@ mod schema;
 #[cfg(test)]
 mod select;
 #[cfg(test)]
+mod sequence;
+#[cfg(test)]
 mod table;
 #[cfg(test)]
+mod truncate;
+#[cfg(test)]
 mod update;
+#[cfg(test)]
+mod view;
 
 const QUERY_PARSER: QueryParser = QueryParser::new();
-
-#[test]
-fn set_variable() {
-    let statements = QUERY_PARSER.parse("set variable=value;");
-
-    assert_eq!(
-        statements,
-        Ok(Request::Config(Set {
-            variable: "variable".to_owned(),
-            value: "value".to_owned()
-        }))
-    );
-}
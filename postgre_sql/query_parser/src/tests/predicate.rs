@@ -21,9 +21,10 @@ fn select() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
             select_items: vec![SelectItem::Wildcard],
-            schema_name: "schema_name".to_owned(),
-            table_name: "table_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
+            table_name: Some("table_name".to_owned()),
             where_clause: Some(Expr::BinaryOp {
                 left: Box::new(Expr::Column("col1".to_owned())),
                 op: BinaryOperator::Eq,
@@ -40,7 +41,7 @@ fn update() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Update(UpdateQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             assignments: vec![Assignment {
                 column: "col1".to_owned(),
@@ -62,7 +63,7 @@ fn delete() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Delete(DeleteQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             where_clause: Some(Expr::BinaryOp {
                 left: Box::new(Expr::Column("col1".to_owned())),
@@ -72,3 +73,178 @@ fn delete() {
         }))))
     );
 }
+
+#[test]
+fn logical_operators_with_many_operands() {
+    let statements = QUERY_PARSER.parse("select * from schema_name.table_name where col1 = 1 and col2 and not col3;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::Wildcard],
+            schema_name: Some("schema_name".to_owned()),
+            table_name: Some("table_name".to_owned()),
+            where_clause: Some(Expr::BinaryOp {
+                left: Box::new(Expr::BinaryOp {
+                    left: Box::new(Expr::BinaryOp {
+                        left: Box::new(Expr::Column("col1".to_owned())),
+                        op: BinaryOperator::Eq,
+                        right: Box::new(Expr::Value(Value::Int(1)))
+                    }),
+                    op: BinaryOperator::And,
+                    right: Box::new(Expr::Column("col2".to_owned()))
+                }),
+                op: BinaryOperator::And,
+                right: Box::new(Expr::UnaryOp {
+                    op: UnaryOperator::Not,
+                    expr: Box::new(Expr::Column("col3".to_owned()))
+                })
+            }),
+        }))))
+    );
+}
+
+#[test]
+fn null_and_truth_tests() {
+    let statements = QUERY_PARSER.parse("select col1 is null, col1 is not null, col2 is not true, col2 is unknown from schema_name.table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![
+                SelectItem::UnnamedExpr(Expr::UnaryOp {
+                    op: UnaryOperator::IsNull,
+                    expr: Box::new(Expr::Column("col1".to_owned()))
+                }),
+                SelectItem::UnnamedExpr(Expr::UnaryOp {
+                    op: UnaryOperator::IsNotNull,
+                    expr: Box::new(Expr::Column("col1".to_owned()))
+                }),
+                SelectItem::UnnamedExpr(Expr::UnaryOp {
+                    op: UnaryOperator::IsNotTrue,
+                    expr: Box::new(Expr::Column("col2".to_owned()))
+                }),
+                SelectItem::UnnamedExpr(Expr::UnaryOp {
+                    op: UnaryOperator::IsUnknown,
+                    expr: Box::new(Expr::Column("col2".to_owned()))
+                }),
+            ],
+            schema_name: Some("schema_name".to_owned()),
+            table_name: Some("table_name".to_owned()),
+            where_clause: None,
+        }))))
+    );
+}
+
+#[test]
+fn distinct_from() {
+    let statements = QUERY_PARSER.parse("select * from schema_name.table_name where col1 is not distinct from null;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::Wildcard],
+            schema_name: Some("schema_name".to_owned()),
+            table_name: Some("table_name".to_owned()),
+            where_clause: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Column("col1".to_owned())),
+                op: BinaryOperator::IsNotDistinctFrom,
+                right: Box::new(Expr::Value(Value::Null))
+            }),
+        }))))
+    );
+}
+
+#[test]
+fn in_list_and_between() {
+    let statements = QUERY_PARSER.parse("select * from schema_name.table_name where col1 not in (1, 2) and col2 between 1 and 10;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::Wildcard],
+            schema_name: Some("schema_name".to_owned()),
+            table_name: Some("table_name".to_owned()),
+            where_clause: Some(Expr::BinaryOp {
+                left: Box::new(Expr::InList {
+                    expr: Box::new(Expr::Column("col1".to_owned())),
+                    list: vec![Expr::Value(Value::Int(1)), Expr::Value(Value::Int(2))],
+                    negated: true
+                }),
+                op: BinaryOperator::And,
+                right: Box::new(Expr::Between {
+                    expr: Box::new(Expr::Column("col2".to_owned())),
+                    low: Box::new(Expr::Value(Value::Int(1))),
+                    high: Box::new(Expr::Value(Value::Int(10))),
+                    negated: false
+                })
+            }),
+        }))))
+    );
+}
+
+#[test]
+fn any_and_all_of_array() {
+    let statements = QUERY_PARSER.parse("select * from schema_name.table_name where col1 = any(array[1, 2]) or col2 > all(array[3]);");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::Wildcard],
+            schema_name: Some("schema_name".to_owned()),
+            table_name: Some("table_name".to_owned()),
+            where_clause: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Any {
+                    left: Box::new(Expr::Column("col1".to_owned())),
+                    op: BinaryOperator::Eq,
+                    right: vec![Expr::Value(Value::Int(1)), Expr::Value(Value::Int(2))]
+                }),
+                op: BinaryOperator::Or,
+                right: Box::new(Expr::All {
+                    left: Box::new(Expr::Column("col2".to_owned())),
+                    op: BinaryOperator::Gt,
+                    right: vec![Expr::Value(Value::Int(3))]
+                })
+            }),
+        }))))
+    );
+}
+
+#[test]
+fn pattern_matching_with_escape_character() {
+    let statements = QUERY_PARSER.parse("select * from schema_name.table_name where col1 like 'a#%' escape '#' or col2 similar to 'b#_' escape '#';");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::Wildcard],
+            schema_name: Some("schema_name".to_owned()),
+            table_name: Some("table_name".to_owned()),
+            where_clause: Some(Expr::BinaryOp {
+                left: Box::new(Expr::BinaryOp {
+                    left: Box::new(Expr::Column("col1".to_owned())),
+                    op: BinaryOperator::Like,
+                    right: Box::new(Expr::Function {
+                        name: "like_escape".to_owned(),
+                        args: vec![Expr::Value(Value::String("a#%".to_owned())), Expr::Value(Value::String("#".to_owned()))]
+                    })
+                }),
+                op: BinaryOperator::Or,
+                right: Box::new(Expr::BinaryOp {
+                    left: Box::new(Expr::Column("col2".to_owned())),
+                    op: BinaryOperator::SimilarTo,
+                    right: Box::new(Expr::Function {
+                        name: "like_escape".to_owned(),
+                        args: vec![Expr::Value(Value::String("b#_".to_owned())), Expr::Value(Value::String("#".to_owned()))]
+                    })
+                })
+            }),
+        }))))
+    );
+}
@@ -21,10 +21,373 @@ fn select_all_from_table() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
             select_items: vec![SelectItem::Wildcard],
-            schema_name: "schema_name".to_owned(),
-            table_name: "table_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
+            table_name: Some("table_name".to_owned()),
             where_clause: None,
         }))))
     );
 }
+
+#[test]
+fn select_expression_with_alias() {
+    let statements = QUERY_PARSER.parse("select col1 + 1 as col2 from table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::ExprWithAlias {
+                expr: Expr::BinaryOp {
+                    left: Box::new(Expr::Column("col1".to_owned())),
+                    op: BinaryOperator::Plus,
+                    right: Box::new(Expr::Value(Value::Int(1)))
+                },
+                alias: "col2".to_owned()
+            }],
+            schema_name: None,
+            table_name: Some("table_name".to_owned()),
+            where_clause: None,
+        }))))
+    );
+}
+
+#[test]
+fn select_qualified_column() {
+    let statements = QUERY_PARSER.parse("select table_name.col1 from table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::UnnamedExpr(Expr::Column("col1".to_owned()))],
+            schema_name: None,
+            table_name: Some("table_name".to_owned()),
+            where_clause: None,
+        }))))
+    );
+}
+
+#[test]
+fn select_without_from() {
+    let statements = QUERY_PARSER.parse("select 1;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::UnnamedExpr(Expr::Value(Value::Int(1)))],
+            schema_name: None,
+            table_name: None,
+            where_clause: None,
+        }))))
+    );
+}
+
+#[test]
+fn select_functions_with_special_syntax() {
+    let statements = QUERY_PARSER.parse("select substring(col from 2 for 3), position('b' in col), trim(col) from table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![
+                SelectItem::UnnamedExpr(Expr::Function {
+                    name: "substring".to_owned(),
+                    args: vec![Expr::Column("col".to_owned()), Expr::Value(Value::Int(2)), Expr::Value(Value::Int(3))]
+                }),
+                SelectItem::UnnamedExpr(Expr::Function {
+                    name: "position".to_owned(),
+                    args: vec![Expr::Column("col".to_owned()), Expr::Value(Value::String("b".to_owned()))]
+                }),
+                SelectItem::UnnamedExpr(Expr::Function {
+                    name: "btrim".to_owned(),
+                    args: vec![Expr::Column("col".to_owned())]
+                }),
+            ],
+            schema_name: None,
+            table_name: Some("table_name".to_owned()),
+            where_clause: None,
+        }))))
+    );
+}
+
+#[test]
+fn select_conditional_expressions() {
+    let statements = QUERY_PARSER.parse(
+        "select case when col > 0 then 'positive' else 'other' end, case col when 1 then 'one' end, \
+         coalesce(col, null), nullif(col, 0), greatest(col, 1), least(col, 1) from table_name;",
+    );
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![
+                SelectItem::UnnamedExpr(Expr::Case {
+                    operand: None,
+                    conditions: vec![Expr::BinaryOp {
+                        left: Box::new(Expr::Column("col".to_owned())),
+                        op: BinaryOperator::Gt,
+                        right: Box::new(Expr::Value(Value::Int(0)))
+                    }],
+                    results: vec![Expr::Value(Value::String("positive".to_owned()))],
+                    else_result: Some(Box::new(Expr::Value(Value::String("other".to_owned()))))
+                }),
+                SelectItem::UnnamedExpr(Expr::Case {
+                    operand: Some(Box::new(Expr::Column("col".to_owned()))),
+                    conditions: vec![Expr::Value(Value::Int(1))],
+                    results: vec![Expr::Value(Value::String("one".to_owned()))],
+                    else_result: None
+                }),
+                SelectItem::UnnamedExpr(Expr::Coalesce(vec![Expr::Column("col".to_owned()), Expr::Value(Value::Null)])),
+                SelectItem::UnnamedExpr(Expr::NullIf {
+                    left: Box::new(Expr::Column("col".to_owned())),
+                    right: Box::new(Expr::Value(Value::Int(0)))
+                }),
+                SelectItem::UnnamedExpr(Expr::Greatest(vec![Expr::Column("col".to_owned()), Expr::Value(Value::Int(1))])),
+                SelectItem::UnnamedExpr(Expr::Least(vec![Expr::Column("col".to_owned()), Expr::Value(Value::Int(1))])),
+            ],
+            schema_name: None,
+            table_name: Some("table_name".to_owned()),
+            where_clause: None,
+        }))))
+    );
+}
+
+#[test]
+fn select_distinct() {
+    let statements = QUERY_PARSER.parse("select distinct col1 from table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: Some(Distinct::Rows),
+            select_items: vec![SelectItem::UnnamedExpr(Expr::Column("col1".to_owned()))],
+            schema_name: None,
+            table_name: Some("table_name".to_owned()),
+            where_clause: None,
+        }))))
+    );
+}
+
+#[test]
+fn select_distinct_on() {
+    let statements = QUERY_PARSER.parse("select distinct on (col1, col2 + 1) col3 from table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: Some(Distinct::On(vec![
+                Expr::Column("col1".to_owned()),
+                Expr::BinaryOp {
+                    left: Box::new(Expr::Column("col2".to_owned())),
+                    op: BinaryOperator::Plus,
+                    right: Box::new(Expr::Value(Value::Int(1)))
+                }
+            ])),
+            select_items: vec![SelectItem::UnnamedExpr(Expr::Column("col3".to_owned()))],
+            schema_name: None,
+            table_name: Some("table_name".to_owned()),
+            where_clause: None,
+        }))))
+    );
+}
+
+fn select_column_from(column: &str, schema_name: &str, table_name: &str) -> Query {
+    Query::Select(SelectQuery {
+        distinct: None,
+        select_items: vec![SelectItem::UnnamedExpr(Expr::Column(column.to_owned()))],
+        schema_name: Some(schema_name.to_owned()),
+        table_name: Some(table_name.to_owned()),
+        where_clause: None,
+    })
+}
+
+#[rstest::rstest(
+    sql,
+    op,
+    all,
+    case::union("union", SetOperator::Union, false),
+    case::union_all("union all", SetOperator::Union, true),
+    case::intersect("intersect", SetOperator::Intersect, false),
+    case::intersect_all("intersect all", SetOperator::Intersect, true),
+    case::except("except", SetOperator::Except, false),
+    case::except_all("except all", SetOperator::Except, true)
+)]
+fn set_operations(sql: &str, op: SetOperator, all: bool) {
+    let statements = QUERY_PARSER.parse(&format!("select col1 from schema_1.table_1 {} select col2 from schema_2.table_2;", sql));
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::SetOperation(SetOperation {
+            op,
+            all,
+            left: Box::new(select_column_from("col1", "schema_1", "table_1")),
+            right: Box::new(select_column_from("col2", "schema_2", "table_2")),
+        }))))
+    );
+}
+
+#[test]
+fn intersect_takes_precedence_over_union() {
+    let statements = QUERY_PARSER
+        .parse("select col1 from schema_name.table_1 union select col1 from schema_name.table_2 intersect select col1 from schema_name.table_3;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::SetOperation(SetOperation {
+            op: SetOperator::Union,
+            all: false,
+            left: Box::new(select_column_from("col1", "schema_name", "table_1")),
+            right: Box::new(Query::SetOperation(SetOperation {
+                op: SetOperator::Intersect,
+                all: false,
+                left: Box::new(select_column_from("col1", "schema_name", "table_2")),
+                right: Box::new(select_column_from("col1", "schema_name", "table_3")),
+            })),
+        }))))
+    );
+}
+
+#[test]
+fn select_window_functions() {
+    let statements = QUERY_PARSER.parse(
+        "select row_number() over (partition by col1 order by col2 desc), count(*) over (), \
+         lag(col2, 2, 0) over (order by col2 nulls first) from table_name;",
+    );
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![
+                SelectItem::UnnamedExpr(Expr::Window {
+                    name: "row_number".to_owned(),
+                    args: vec![],
+                    over: Box::new(WindowSpec {
+                        partition_by: vec![Expr::Column("col1".to_owned())],
+                        order_by: vec![OrderByExpr {
+                            expr: Expr::Column("col2".to_owned()),
+                            ascending: false,
+                            nulls_first: true
+                        }],
+                        frame: None
+                    })
+                }),
+                SelectItem::UnnamedExpr(Expr::Window {
+                    name: "count".to_owned(),
+                    args: vec![],
+                    over: Box::new(WindowSpec {
+                        partition_by: vec![],
+                        order_by: vec![],
+                        frame: None
+                    })
+                }),
+                SelectItem::UnnamedExpr(Expr::Window {
+                    name: "lag".to_owned(),
+                    args: vec![Expr::Column("col2".to_owned()), Expr::Value(Value::Int(2)), Expr::Value(Value::Int(0))],
+                    over: Box::new(WindowSpec {
+                        partition_by: vec![],
+                        order_by: vec![OrderByExpr {
+                            expr: Expr::Column("col2".to_owned()),
+                            ascending: true,
+                            nulls_first: true
+                        }],
+                        frame: None
+                    })
+                }),
+            ],
+            schema_name: None,
+            table_name: Some("table_name".to_owned()),
+            where_clause: None,
+        }))))
+    );
+}
+
+#[rstest::rstest(
+    frame,
+    expected,
+    case::rows_from_preceding(
+        "rows 2 preceding",
+        WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start: WindowFrameBound::Preceding(2),
+            end: WindowFrameBound::CurrentRow
+        }
+    ),
+    case::rows_between(
+        "rows between 1 preceding and 1 following",
+        WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start: WindowFrameBound::Preceding(1),
+            end: WindowFrameBound::Following(1)
+        }
+    ),
+    case::range_unbounded(
+        "range between unbounded preceding and unbounded following",
+        WindowFrame {
+            units: WindowFrameUnits::Range,
+            start: WindowFrameBound::UnboundedPreceding,
+            end: WindowFrameBound::UnboundedFollowing
+        }
+    ),
+    case::range_from_current_row(
+        "range between current row and 5 following",
+        WindowFrame {
+            units: WindowFrameUnits::Range,
+            start: WindowFrameBound::CurrentRow,
+            end: WindowFrameBound::Following(5)
+        }
+    )
+)]
+fn select_window_frames(frame: &str, expected: WindowFrame) {
+    let statements = QUERY_PARSER.parse(&format!("select sum(col1) over (order by col1 {}) from table_name;", frame));
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::UnnamedExpr(Expr::Window {
+                name: "sum".to_owned(),
+                args: vec![Expr::Column("col1".to_owned())],
+                over: Box::new(WindowSpec {
+                    partition_by: vec![],
+                    order_by: vec![OrderByExpr {
+                        expr: Expr::Column("col1".to_owned()),
+                        ascending: true,
+                        nulls_first: false
+                    }],
+                    frame: Some(expected)
+                })
+            })],
+            schema_name: None,
+            table_name: Some("table_name".to_owned()),
+            where_clause: None,
+        }))))
+    );
+}
+
+#[rstest::rstest(
+    over,
+    feature,
+    case::frame_exclusion("(order by col1 rows between 1 preceding and 1 following exclude current row)", "frame exclusion"),
+    case::groups_frame("(order by col1 groups between 1 preceding and 1 following)", "GROUPS frame"),
+    case::parameter_offset("(order by col1 rows $1 preceding)", "frame offset that is not a non-negative integer constant"),
+    case::order_by_using("(order by col1 using <)", "ORDER BY USING"),
+    case::named_window("w", "reference to a named window")
+)]
+fn select_unsupported_windows(over: &str, feature: &str) {
+    let statements = QUERY_PARSER.parse(&format!("select sum(col1) over {} from table_name;", over));
+
+    assert_eq!(statements, Err(ParserError::not_supported(feature)));
+}
+
+#[test]
+fn select_with_window_clause() {
+    let statements = QUERY_PARSER.parse("select sum(col1) over w from table_name window w as (order by col1);");
+
+    assert_eq!(statements, Err(ParserError::not_supported("WINDOW clause")));
+}
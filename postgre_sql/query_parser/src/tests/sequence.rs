@@ -0,0 +1,91 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[test]
+fn create_sequence() {
+    let statements = QUERY_PARSER.parse("create sequence schema_name.sequence_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::CreateSequence {
+            schema_name: Some("schema_name".to_owned()),
+            sequence_name: "sequence_name".to_owned(),
+            start: None,
+            increment: None,
+            min_value: None,
+            max_value: None,
+            if_not_exists: false,
+        })))
+    );
+}
+
+#[test]
+fn create_sequence_with_options() {
+    let statements =
+        QUERY_PARSER.parse("create sequence if not exists sequence_name increment by -2 minvalue 10 maxvalue 10000000000 start with 100;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::CreateSequence {
+            schema_name: None,
+            sequence_name: "sequence_name".to_owned(),
+            start: Some(100),
+            increment: Some(-2),
+            min_value: Some(10),
+            max_value: Some(10_000_000_000),
+            if_not_exists: true,
+        })))
+    );
+}
+
+#[test]
+fn insert_next_value_of_sequence() {
+    let statements = QUERY_PARSER.parse("insert into table_name values (nextval('sequence_name'));");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Insert(InsertQuery {
+            schema_name: None,
+            table_name: "table_name".to_owned(),
+            columns: vec![],
+            source: InsertSource::Values(Values(vec![vec![Expr::Function {
+                name: "nextval".to_owned(),
+                args: vec![Expr::Value(Value::String("sequence_name".to_owned()))]
+            }]]))
+        }))))
+    );
+}
+
+#[test]
+fn update_with_set_value_of_sequence() {
+    let statements = QUERY_PARSER.parse("update table_name set col = setval('sequence_name', 10);");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Query(Query::Update(UpdateQuery {
+            schema_name: None,
+            table_name: "table_name".to_owned(),
+            assignments: vec![Assignment {
+                column: "col".to_owned(),
+                value: Expr::Function {
+                    name: "setval".to_owned(),
+                    args: vec![Expr::Value(Value::String("sequence_name".to_owned())), Expr::Value(Value::Int(10))]
+                }
+            }],
+            where_clause: None
+        }))))
+    );
+}
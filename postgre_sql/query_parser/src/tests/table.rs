@@ -22,20 +22,23 @@ fn create_ints_table() {
         statements,
         Ok(Request::Statement(Statement::Definition(Definition::CreateTable {
             if_not_exists: false,
-            schema_name: "public".to_owned(),
+            schema_name: None,
             table_name: "table_name".to_owned(),
             columns: vec![
                 ColumnDef {
                     name: "col_si".to_owned(),
                     data_type: DataType::SmallInt,
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_i".to_owned(),
                     data_type: DataType::Int,
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_bi".to_owned(),
                     data_type: DataType::BigInt,
+                    identity: None,
                 }
             ],
         })))
@@ -63,40 +66,48 @@ fn create_strings_table() {
         statements,
         Ok(Request::Statement(Statement::Definition(Definition::CreateTable {
             if_not_exists: false,
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             columns: vec![
                 ColumnDef {
                     name: "col_c".to_owned(),
                     data_type: DataType::Char(1),
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_cs".to_owned(),
                     data_type: DataType::Char(255),
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_cl".to_owned(),
                     data_type: DataType::Char(1),
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_cls".to_owned(),
                     data_type: DataType::Char(255),
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_v".to_owned(),
                     data_type: DataType::VarChar(None),
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_vs".to_owned(),
                     data_type: DataType::VarChar(Some(255)),
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_vl".to_owned(),
                     data_type: DataType::VarChar(None),
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_vls".to_owned(),
                     data_type: DataType::VarChar(Some(255)),
+                    identity: None,
                 }
             ],
         })))
@@ -111,16 +122,18 @@ fn create_float_table() {
         statements,
         Ok(Request::Statement(Statement::Definition(Definition::CreateTable {
             if_not_exists: false,
-            schema_name: "public".to_owned(),
+            schema_name: None,
             table_name: "table_name".to_owned(),
             columns: vec![
                 ColumnDef {
                     name: "col_r".to_owned(),
                     data_type: DataType::Real,
+                    identity: None,
                 },
                 ColumnDef {
                     name: "col_d".to_owned(),
                     data_type: DataType::Double,
+                    identity: None,
                 }
             ],
         })))
@@ -135,11 +148,12 @@ fn create_boolean_table() {
         statements,
         Ok(Request::Statement(Statement::Definition(Definition::CreateTable {
             if_not_exists: false,
-            schema_name: "public".to_owned(),
+            schema_name: None,
             table_name: "table_name".to_owned(),
             columns: vec![ColumnDef {
                 name: "col_b".to_owned(),
                 data_type: DataType::Bool,
+                identity: None,
             }],
         })))
     );
@@ -152,7 +166,7 @@ fn drop_table() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Definition(Definition::DropTables {
-            names: vec![("public".to_owned(), "table_name".to_owned())],
+            names: vec![(None, "table_name".to_owned())],
             if_exists: false,
             cascade: false
         })))
@@ -166,10 +180,7 @@ fn drop_tables() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Definition(Definition::DropTables {
-            names: vec![
-                ("public".to_owned(), "table_name_1".to_owned()),
-                ("public".to_owned(), "table_name_2".to_owned())
-            ],
+            names: vec![(None, "table_name_1".to_owned()), (None, "table_name_2".to_owned())],
             if_exists: false,
             cascade: false
         })))
@@ -183,10 +194,7 @@ fn drop_table_cascade() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Definition(Definition::DropTables {
-            names: vec![
-                ("public".to_owned(), "table_name_1".to_owned()),
-                ("public".to_owned(), "table_name_2".to_owned())
-            ],
+            names: vec![(None, "table_name_1".to_owned()), (None, "table_name_2".to_owned())],
             if_exists: false,
             cascade: true
         })))
@@ -200,9 +208,71 @@ fn drop_table_if_exists() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Definition(Definition::DropTables {
-            names: vec![("public".to_owned(), "table_name".to_owned())],
+            names: vec![(None, "table_name".to_owned())],
             if_exists: true,
             cascade: false
         })))
     );
 }
+
+#[test]
+fn create_table_with_serial_columns() {
+    let statements = QUERY_PARSER.parse("create table table_name (col_ss smallserial, col_s serial, col_bs bigserial);");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::CreateTable {
+            if_not_exists: false,
+            schema_name: None,
+            table_name: "table_name".to_owned(),
+            columns: vec![
+                ColumnDef {
+                    name: "col_ss".to_owned(),
+                    data_type: DataType::SmallInt,
+                    identity: Some(Identity::ByDefault),
+                },
+                ColumnDef {
+                    name: "col_s".to_owned(),
+                    data_type: DataType::Int,
+                    identity: Some(Identity::ByDefault),
+                },
+                ColumnDef {
+                    name: "col_bs".to_owned(),
+                    data_type: DataType::BigInt,
+                    identity: Some(Identity::ByDefault),
+                }
+            ],
+        })))
+    );
+}
+
+#[test]
+fn create_table_with_identity_columns() {
+    let statements = QUERY_PARSER.parse(
+        "create table table_name (\
+            col_a int generated always as identity,\
+            col_d bigint generated by default as identity\
+        );",
+    );
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::CreateTable {
+            if_not_exists: false,
+            schema_name: None,
+            table_name: "table_name".to_owned(),
+            columns: vec![
+                ColumnDef {
+                    name: "col_a".to_owned(),
+                    data_type: DataType::Int,
+                    identity: Some(Identity::Always),
+                },
+                ColumnDef {
+                    name: "col_d".to_owned(),
+                    data_type: DataType::BigInt,
+                    identity: Some(Identity::ByDefault),
+                }
+            ],
+        })))
+    );
+}
@@ -0,0 +1,43 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[test]
+fn truncate_table() {
+    let statements = QUERY_PARSER.parse("truncate schema_name.table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::Truncate {
+            names: vec![(Some("schema_name".to_owned()), "table_name".to_owned())],
+            restart_identity: false,
+            cascade: false,
+        })))
+    );
+}
+
+#[test]
+fn truncate_tables_restart_identity_cascade() {
+    let statements = QUERY_PARSER.parse("truncate table schema_name.table_1, table_2 restart identity cascade;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::Truncate {
+            names: vec![(Some("schema_name".to_owned()), "table_1".to_owned()), (None, "table_2".to_owned())],
+            restart_identity: true,
+            cascade: true,
+        })))
+    );
+}
@@ -21,7 +21,7 @@ fn update_int() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Update(UpdateQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             assignments: vec![Assignment {
                 column: "col1".to_owned(),
@@ -39,7 +39,7 @@ fn update_string() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Update(UpdateQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             assignments: vec![Assignment {
                 column: "col1".to_owned(),
@@ -74,6 +74,14 @@ mod bi_ops {
         case::or(BinaryOperator::Or, "OR"),
         case::like(BinaryOperator::Like, "LIKE"),
         case::not_like(BinaryOperator::NotLike, "NOT LIKE"),
+        case::ilike(BinaryOperator::ILike, "ILIKE"),
+        case::not_ilike(BinaryOperator::NotILike, "NOT ILIKE"),
+        case::similar_to(BinaryOperator::SimilarTo, "SIMILAR TO"),
+        case::not_similar_to(BinaryOperator::NotSimilarTo, "NOT SIMILAR TO"),
+        case::regex_match(BinaryOperator::RegexMatch, "~"),
+        case::regex_i_match(BinaryOperator::RegexIMatch, "~*"),
+        case::not_regex_match(BinaryOperator::NotRegexMatch, "!~"),
+        case::not_regex_i_match(BinaryOperator::NotRegexIMatch, "!~*"),
         case::bitwise_or(BinaryOperator::BitwiseOr, "|"),
         case::bitwise_and(BinaryOperator::BitwiseAnd, "&"),
         case::bitwise_xor(BinaryOperator::BitwiseXor, "#"),
@@ -86,7 +94,7 @@ mod bi_ops {
         assert_eq!(
             statements,
             Ok(Request::Statement(Statement::Query(Query::Update(UpdateQuery {
-                schema_name: "schema_name".to_owned(),
+                schema_name: Some("schema_name".to_owned()),
                 table_name: "table_name".to_owned(),
                 assignments: vec![Assignment {
                     column: "col1".to_owned(),
@@ -109,7 +117,7 @@ fn update_params() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Update(UpdateQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             assignments: vec![Assignment {
                 column: "col1".to_owned(),
@@ -127,7 +135,7 @@ fn update_columns() {
     assert_eq!(
         statements,
         Ok(Request::Statement(Statement::Query(Query::Update(UpdateQuery {
-            schema_name: "schema_name".to_owned(),
+            schema_name: Some("schema_name".to_owned()),
             table_name: "table_name".to_owned(),
             assignments: vec![Assignment {
                 column: "col1".to_owned(),
@@ -0,0 +1,186 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[test]
+fn create_view() {
+    let statements = QUERY_PARSER.parse("create view schema_name.view_name as select col_1 from schema_name.table_name where col_2 > 1;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::CreateView {
+            schema_name: Some("schema_name".to_owned()),
+            view_name: "view_name".to_owned(),
+            columns: vec![],
+            query: SelectQuery {
+                distinct: None,
+                select_items: vec![SelectItem::UnnamedExpr(Expr::Column("col_1".to_owned()))],
+                schema_name: Some("schema_name".to_owned()),
+                table_name: Some("table_name".to_owned()),
+                where_clause: Some(Expr::BinaryOp {
+                    left: Box::new(Expr::Column("col_2".to_owned())),
+                    op: BinaryOperator::Gt,
+                    right: Box::new(Expr::Value(Value::Int(1))),
+                }),
+            },
+            or_replace: false,
+        })))
+    );
+}
+
+#[test]
+fn create_or_replace_view_with_columns() {
+    let statements = QUERY_PARSER.parse("create or replace view view_name (col_a, col_b) as select * from table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::CreateView {
+            schema_name: None,
+            view_name: "view_name".to_owned(),
+            columns: vec!["col_a".to_owned(), "col_b".to_owned()],
+            query: SelectQuery {
+                distinct: None,
+                select_items: vec![SelectItem::Wildcard],
+                schema_name: None,
+                table_name: Some("table_name".to_owned()),
+                where_clause: None,
+            },
+            or_replace: true,
+        })))
+    );
+}
+
+#[test]
+fn drop_views() {
+    let statements = QUERY_PARSER.parse("drop view view_name_1, schema_name.view_name_2;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::DropViews {
+            names: vec![
+                (None, "view_name_1".to_owned()),
+                (Some("schema_name".to_owned()), "view_name_2".to_owned())
+            ],
+            if_exists: false,
+            cascade: false,
+            materialized: false
+        })))
+    );
+}
+
+#[test]
+fn drop_view_if_exists_cascade() {
+    let statements = QUERY_PARSER.parse("drop view if exists view_name cascade;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::DropViews {
+            names: vec![(None, "view_name".to_owned())],
+            if_exists: true,
+            cascade: true,
+            materialized: false
+        })))
+    );
+}
+
+#[test]
+fn create_materialized_view() {
+    let statements = QUERY_PARSER.parse("create materialized view if not exists view_name (col_a) as select col_1 from schema_name.table_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::CreateMaterializedView {
+            schema_name: None,
+            view_name: "view_name".to_owned(),
+            columns: vec!["col_a".to_owned()],
+            query: SelectQuery {
+                distinct: None,
+                select_items: vec![SelectItem::UnnamedExpr(Expr::Column("col_1".to_owned()))],
+                schema_name: Some("schema_name".to_owned()),
+                table_name: Some("table_name".to_owned()),
+                where_clause: None,
+            },
+            if_not_exists: true,
+        })))
+    );
+}
+
+#[test]
+fn refresh_materialized_view() {
+    let statements = QUERY_PARSER.parse("refresh materialized view schema_name.view_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::RefreshMaterializedView {
+            schema_name: Some("schema_name".to_owned()),
+            view_name: "view_name".to_owned(),
+            concurrently: false,
+        })))
+    );
+}
+
+#[test]
+fn refresh_materialized_view_concurrently() {
+    let statements = QUERY_PARSER.parse("refresh materialized view concurrently view_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::RefreshMaterializedView {
+            schema_name: None,
+            view_name: "view_name".to_owned(),
+            concurrently: true,
+        })))
+    );
+}
+
+#[test]
+fn drop_materialized_view() {
+    let statements = QUERY_PARSER.parse("drop materialized view view_name;");
+
+    assert_eq!(
+        statements,
+        Ok(Request::Statement(Statement::Definition(Definition::DropViews {
+            names: vec![(None, "view_name".to_owned())],
+            if_exists: false,
+            cascade: false,
+            materialized: true
+        })))
+    );
+}
+
+#[test]
+fn create_materialized_view_over_set_operation() {
+    let statements = QUERY_PARSER
+        .parse("create materialized view view_name as select col_1 from schema_name.table_1 union select col_1 from schema_name.table_2;");
+
+    assert_eq!(
+        statements,
+        Err(ParserError::not_supported("materialized view that is not defined by a single SELECT"))
+    );
+}
+
+#[test]
+fn create_materialized_view_with_no_data() {
+    let statements = QUERY_PARSER.parse("create materialized view view_name as select col_1 from schema_name.table_name with no data;");
+
+    assert_eq!(statements, Err(ParserError::not_supported("materialized view WITH NO DATA")));
+}
+
+#[test]
+fn refresh_materialized_view_with_no_data() {
+    let statements = QUERY_PARSER.parse("refresh materialized view view_name with no data;");
+
+    assert_eq!(statements, Err(ParserError::not_supported("materialized view WITH NO DATA")));
+}
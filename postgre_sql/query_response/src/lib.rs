@@ -31,6 +31,26 @@ pub enum QueryEvent {
     TableDropped,
     /// Index successfully created
     IndexCreated,
+    /// Sequence successfully created
+    SequenceCreated,
+    /// View successfully created
+    ViewCreated,
+    /// View successfully dropped
+    ViewDropped,
+    /// Materialized view successfully created with number of selected records
+    MaterializedViewCreated(usize),
+    /// Materialized view successfully refreshed
+    MaterializedViewRefreshed,
+    /// Materialized view successfully dropped
+    MaterializedViewDropped,
+    /// Function successfully created
+    FunctionCreated,
+    /// Function successfully dropped
+    FunctionDropped,
+    /// Table statistics successfully collected
+    TablesAnalyzed,
+    /// Tables successfully truncated
+    TablesTruncated,
     /// Variable successfully set
     VariableSet,
     /// Transaction is started
@@ -71,6 +91,16 @@ impl From<QueryEvent> for OutboundMessage {
             QueryEvent::TableCreated => OutboundMessage::TableCreated,
             QueryEvent::TableDropped => OutboundMessage::TableDropped,
             QueryEvent::IndexCreated => OutboundMessage::IndexCreated,
+            QueryEvent::SequenceCreated => OutboundMessage::SequenceCreated,
+            QueryEvent::ViewCreated => OutboundMessage::ViewCreated,
+            QueryEvent::ViewDropped => OutboundMessage::ViewDropped,
+            QueryEvent::MaterializedViewCreated(records) => OutboundMessage::MaterializedViewCreated(records),
+            QueryEvent::MaterializedViewRefreshed => OutboundMessage::MaterializedViewRefreshed,
+            QueryEvent::MaterializedViewDropped => OutboundMessage::MaterializedViewDropped,
+            QueryEvent::FunctionCreated => OutboundMessage::FunctionCreated,
+            QueryEvent::FunctionDropped => OutboundMessage::FunctionDropped,
+            QueryEvent::TablesAnalyzed => OutboundMessage::TablesAnalyzed,
+            QueryEvent::TablesTruncated => OutboundMessage::TablesTruncated,
             QueryEvent::VariableSet => OutboundMessage::VariableSet,
             QueryEvent::TransactionStarted => OutboundMessage::TransactionBegin,
             QueryEvent::RecordsInserted(records) => OutboundMessage::RecordsInserted(records),
@@ -110,6 +140,8 @@ impl From<QueryEvent> for Vec<u8> {
                 SMALLINT => 2,
                 INT => 4,
                 VARCHAR => -1,
+                REAL => 4,
+                DOUBLE_PRECISION => 8,
                 _ => unimplemented!(),
             }
         }
@@ -120,6 +152,16 @@ impl From<QueryEvent> for Vec<u8> {
             QueryEvent::TableCreated => command_complete("CREATE TABLE"),
             QueryEvent::TableDropped => command_complete("DROP TABLE"),
             QueryEvent::IndexCreated => command_complete("CREATE INDEX"),
+            QueryEvent::SequenceCreated => command_complete("CREATE SEQUENCE"),
+            QueryEvent::ViewCreated => command_complete("CREATE VIEW"),
+            QueryEvent::ViewDropped => command_complete("DROP VIEW"),
+            QueryEvent::MaterializedViewCreated(records) => command_complete(format!("SELECT {}", records).as_str()),
+            QueryEvent::MaterializedViewRefreshed => command_complete("REFRESH MATERIALIZED VIEW"),
+            QueryEvent::MaterializedViewDropped => command_complete("DROP MATERIALIZED VIEW"),
+            QueryEvent::FunctionCreated => command_complete("CREATE FUNCTION"),
+            QueryEvent::FunctionDropped => command_complete("DROP FUNCTION"),
+            QueryEvent::TablesAnalyzed => command_complete("ANALYZE"),
+            QueryEvent::TablesTruncated => command_complete("TRUNCATE TABLE"),
             QueryEvent::VariableSet => command_complete("SET"),
             QueryEvent::TransactionStarted => command_complete("BEGIN"),
             QueryEvent::RecordsInserted(records) => command_complete(format!("INSERT 0 {}", records).as_str()),
@@ -242,6 +284,10 @@ pub(crate) enum QueryErrorKind {
     SchemaDoesNotExist(String),
     SchemaHasDependentObjects(String),
     TableDoesNotExist(String),
+    ViewAlreadyExists(String),
+    ViewDoesNotExist(String),
+    MaterializedViewDoesNotExist(String),
+    DependentObjectsStillExist(String),
     ColumnDoesNotExist(String),
     IndeterminateParameterDataType {
         param_index: usize,
@@ -305,8 +351,35 @@ pub(crate) enum QueryErrorKind {
         actual_type: String,
     },
     InvalidArgumentForPowerFunction,
+    DivisionByZero,
+    NegativeSubstringLength,
+    InvalidEscapeSequence(String),
+    InvalidEscapeCharacter,
+    InvalidRegularExpression(String),
     InvalidTextRepresentation2(String, String),
     CannotCoerce(String, String),
+    TooManyConnections,
+    QueryCanceled,
+    UnrecognizedConfigurationParameter(String),
+    ParameterCannotBeChanged(String),
+    SequenceAlreadyExists(String),
+    SequenceDoesNotExist(String),
+    SequenceGeneratorLimitExceeded(String),
+    ObjectNotInPrerequisiteState(String),
+    NumericValueOutOfRange(String),
+    GeneratedAlways(String),
+    FunctionDoesNotExist(String),
+    FunctionAlreadyExists(String),
+    InvalidFunctionDefinition(String),
+    InsufficientPrivilege(String),
+    NoSchemaSelected,
+    WindowingError(String),
+    SetOperationTypeMismatch {
+        op: String,
+        left: String,
+        right: String,
+    },
+    NoActiveSqlTransaction(String),
 }
 
 impl QueryErrorKind {
@@ -317,6 +390,10 @@ impl QueryErrorKind {
             Self::SchemaDoesNotExist(_) => "3F000",
             Self::SchemaHasDependentObjects(_) => "2BP01",
             Self::TableDoesNotExist(_) => "42P01",
+            Self::ViewAlreadyExists(_) => "42P07",
+            Self::ViewDoesNotExist(_) => "42P01",
+            Self::MaterializedViewDoesNotExist(_) => "42P01",
+            Self::DependentObjectsStillExist(_) => "2BP01",
             Self::ColumnDoesNotExist(_) => "42703",
             Self::IndeterminateParameterDataType { .. } => "42P18",
             Self::InvalidParameterValue(_) => "22023",
@@ -340,7 +417,30 @@ impl QueryErrorKind {
             Self::DuplicateColumn(_) => "42701",
             Self::DatatypeMismatch { .. } => "42804",
             Self::InvalidArgumentForPowerFunction => "2201F",
+            Self::DivisionByZero => "22012",
+            Self::NegativeSubstringLength => "22011",
+            Self::InvalidEscapeSequence(_) => "22025",
+            Self::InvalidEscapeCharacter => "22019",
+            Self::InvalidRegularExpression(_) => "2201B",
             Self::CannotCoerce(_, _) => "42846",
+            Self::TooManyConnections => "53300",
+            Self::QueryCanceled => "57014",
+            Self::UnrecognizedConfigurationParameter(_) => "42704",
+            Self::ParameterCannotBeChanged(_) => "55P02",
+            Self::SequenceAlreadyExists(_) => "42P07",
+            Self::SequenceDoesNotExist(_) => "42P01",
+            Self::SequenceGeneratorLimitExceeded(_) => "2200H",
+            Self::ObjectNotInPrerequisiteState(_) => "55000",
+            Self::NumericValueOutOfRange(_) => "22003",
+            Self::GeneratedAlways(_) => "428C9",
+            Self::FunctionDoesNotExist(_) => "42883",
+            Self::FunctionAlreadyExists(_) => "42723",
+            Self::InvalidFunctionDefinition(_) => "42P13",
+            Self::InsufficientPrivilege(_) => "42501",
+            Self::NoSchemaSelected => "3F000",
+            Self::WindowingError(_) => "42P20",
+            Self::SetOperationTypeMismatch { .. } => "42804",
+            Self::NoActiveSqlTransaction(_) => "25P01",
         }
     }
 }
@@ -355,6 +455,12 @@ impl Display for QueryErrorKind {
                 write!(f, "schema \"{}\" has dependent objects", schema_name)
             }
             Self::TableDoesNotExist(table_name) => write!(f, "table \"{}\" does not exist", table_name),
+            Self::ViewAlreadyExists(view_name) => write!(f, "view \"{}\" already exists", view_name),
+            Self::ViewDoesNotExist(view_name) => write!(f, "view \"{}\" does not exist", view_name),
+            Self::MaterializedViewDoesNotExist(view_name) => write!(f, "materialized view \"{}\" does not exist", view_name),
+            Self::DependentObjectsStillExist(object_name) => {
+                write!(f, "cannot drop {} because other objects depend on it", object_name)
+            }
             Self::ColumnDoesNotExist(column) => write!(f, "column {} does not exist", column),
             Self::IndeterminateParameterDataType { param_index } => {
                 write!(f, "could not determine data type of parameter ${}", param_index + 1)
@@ -431,7 +537,30 @@ impl Display for QueryErrorKind {
                 actual_type,
             } => write!(f, "argument of {} must be type {}, not type {}", op, target_type, actual_type),
             Self::InvalidArgumentForPowerFunction => write!(f, "cannot take square root of a negative number"),
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::NegativeSubstringLength => write!(f, "negative substring length not allowed"),
+            Self::InvalidEscapeSequence(message) => write!(f, "{}", message),
+            Self::InvalidEscapeCharacter => write!(f, "invalid escape string"),
+            Self::InvalidRegularExpression(message) => write!(f, "invalid regular expression: {}", message),
             Self::CannotCoerce(from_type, to_type) => write!(f, "cannot cast type {} to {}", from_type, to_type),
+            Self::TooManyConnections => write!(f, "sorry, too many clients already"),
+            Self::QueryCanceled => write!(f, "canceling statement due to statement timeout"),
+            Self::UnrecognizedConfigurationParameter(name) => write!(f, "unrecognized configuration parameter \"{}\"", name),
+            Self::ParameterCannotBeChanged(name) => write!(f, "parameter \"{}\" cannot be changed", name),
+            Self::SequenceAlreadyExists(sequence_name) => write!(f, "relation \"{}\" already exists", sequence_name),
+            Self::SequenceDoesNotExist(sequence_name) => write!(f, "relation \"{}\" does not exist", sequence_name),
+            Self::SequenceGeneratorLimitExceeded(message) => write!(f, "{}", message),
+            Self::ObjectNotInPrerequisiteState(message) => write!(f, "{}", message),
+            Self::NumericValueOutOfRange(message) => write!(f, "{}", message),
+            Self::GeneratedAlways(message) => write!(f, "{}", message),
+            Self::FunctionDoesNotExist(function_name) => write!(f, "function {} does not exist", function_name),
+            Self::FunctionAlreadyExists(function_name) => write!(f, "function \"{}\" already exists", function_name),
+            Self::InvalidFunctionDefinition(message) => write!(f, "{}", message),
+            Self::InsufficientPrivilege(message) => write!(f, "{}", message),
+            Self::NoSchemaSelected => write!(f, "no schema has been selected to create in"),
+            Self::WindowingError(message) => write!(f, "{}", message),
+            Self::SetOperationTypeMismatch { op, left, right } => write!(f, "{} types {} and {} cannot be matched", op, left, right),
+            Self::NoActiveSqlTransaction(message) => write!(f, "{}", message),
         }
     }
 }
@@ -456,32 +585,32 @@ impl QueryError {
     fn message(&self) -> String {
         format!("{}", self.kind)
     }
+
+    fn is_error(&self) -> bool {
+        matches!(self.severity, Severity::Error | Severity::Fatal | Severity::Panic)
+    }
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
 }
 
 impl From<QueryError> for OutboundMessage {
     fn from(error: QueryError) -> Self {
-        OutboundMessage::Error(error.severity().to_owned(), error.code().to_owned(), error.message())
+        if error.is_error() {
+            OutboundMessage::Error(error.severity().to_owned(), error.code().to_owned(), error.message())
+        } else {
+            OutboundMessage::Notice(error.severity().to_owned(), error.code().to_owned(), error.message())
+        }
     }
 }
 
 impl From<QueryError> for Vec<u8> {
     fn from(error: QueryError) -> Vec<u8> {
-        let mut error_response_buff = Vec::new();
-        error_response_buff.extend_from_slice(&[ERROR_RESPONSE]);
-        let mut message_buff = Vec::new();
-        message_buff.extend_from_slice(&[SEVERITY]);
-        message_buff.extend_from_slice(error.severity().as_bytes());
-        message_buff.extend_from_slice(&[0]);
-        message_buff.extend_from_slice(&[CODE]);
-        message_buff.extend_from_slice(error.code().as_bytes());
-        message_buff.extend_from_slice(&[0]);
-        message_buff.extend_from_slice(&[MESSAGE]);
-        message_buff.extend_from_slice(error.message().as_bytes());
-        message_buff.extend_from_slice(&[0]);
-        error_response_buff.extend_from_slice(&(message_buff.len() as i32 + 4 + 1).to_be_bytes());
-        error_response_buff.extend_from_slice(message_buff.as_ref());
-        error_response_buff.extend_from_slice(&[0]);
-        error_response_buff.to_vec()
+        let message: OutboundMessage = error.into();
+        message.into()
     }
 }
 
@@ -526,6 +655,38 @@ impl QueryError {
         }
     }
 
+    /// view already exists error constructor
+    pub fn view_already_exists<S: ToString>(view_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::ViewAlreadyExists(view_name.to_string()),
+        }
+    }
+
+    /// view does not exist error constructor
+    pub fn view_does_not_exist<S: ToString>(view_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::ViewDoesNotExist(view_name.to_string()),
+        }
+    }
+
+    /// materialized view does not exist error constructor
+    pub fn materialized_view_does_not_exist<S: ToString>(view_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::MaterializedViewDoesNotExist(view_name.to_string()),
+        }
+    }
+
+    /// dependent objects still exist error constructor
+    pub fn dependent_objects_still_exist<S: ToString>(object_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::DependentObjectsStillExist(object_name.to_string()),
+        }
+    }
+
     /// column does not exists error constructor
     pub fn column_does_not_exist<S: ToString>(non_existing_column: S) -> QueryError {
         QueryError {
@@ -742,12 +903,217 @@ impl QueryError {
         }
     }
 
+    pub fn division_by_zero() -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::DivisionByZero,
+        }
+    }
+
+    pub fn negative_substring_length() -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::NegativeSubstringLength,
+        }
+    }
+
+    pub fn invalid_escape_sequence<M: ToString>(message: M) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::InvalidEscapeSequence(message.to_string()),
+        }
+    }
+
+    pub fn invalid_escape_character() -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::InvalidEscapeCharacter,
+        }
+    }
+
+    pub fn invalid_regular_expression<M: ToString>(message: M) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::InvalidRegularExpression(message.to_string()),
+        }
+    }
+
     pub fn cannot_coerce<FT: ToString, TT: ToString>(from_type: FT, to_type: TT) -> QueryError {
         QueryError {
             severity: Severity::Error,
             kind: QueryErrorKind::CannotCoerce(from_type.to_string(), to_type.to_string()),
         }
     }
+
+    /// too many connections error constructor
+    pub fn too_many_connections() -> QueryError {
+        QueryError {
+            severity: Severity::Fatal,
+            kind: QueryErrorKind::TooManyConnections,
+        }
+    }
+
+    /// statement timeout error constructor
+    pub fn query_canceled() -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::QueryCanceled,
+        }
+    }
+
+    /// unrecognized configuration parameter error constructor
+    pub fn unrecognized_configuration_parameter<S: ToString>(name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::UnrecognizedConfigurationParameter(name.to_string()),
+        }
+    }
+
+    /// read only configuration parameter error constructor
+    pub fn parameter_cannot_be_changed<S: ToString>(name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::ParameterCannotBeChanged(name.to_string()),
+        }
+    }
+
+    /// sequence already exists error constructor
+    pub fn sequence_already_exists<S: ToString>(sequence_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SequenceAlreadyExists(sequence_name.to_string()),
+        }
+    }
+
+    /// sequence does not exist error constructor
+    pub fn sequence_does_not_exist<S: ToString>(sequence_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SequenceDoesNotExist(sequence_name.to_string()),
+        }
+    }
+
+    /// sequence reached its maximum or minimum value error constructor
+    pub fn sequence_limit_reached<S: ToString>(sequence_name: S, ascending: bool, limit: i64) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SequenceGeneratorLimitExceeded(format!(
+                "nextval: reached {} value of sequence \"{}\" ({})",
+                if ascending { "maximum" } else { "minimum" },
+                sequence_name.to_string(),
+                limit
+            )),
+        }
+    }
+
+    /// current value of never advanced sequence error constructor
+    pub fn sequence_current_value_not_defined<S: ToString>(sequence_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::ObjectNotInPrerequisiteState(format!(
+                "currval of sequence \"{}\" is not yet defined in this session",
+                sequence_name.to_string()
+            )),
+        }
+    }
+
+    /// sequence value out of sequence bounds error constructor
+    pub fn sequence_value_out_of_bounds<S: ToString>(sequence_name: S, value: i64, min_value: i64, max_value: i64) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::NumericValueOutOfRange(format!(
+                "setval: value {} is out of bounds for sequence \"{}\" ({}..{})",
+                value,
+                sequence_name.to_string(),
+                min_value,
+                max_value
+            )),
+        }
+    }
+
+    /// explicit value for `GENERATED ALWAYS` column in insert error constructor
+    pub fn cannot_insert_into_generated_column<S: ToString>(column_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::GeneratedAlways(format!("cannot insert a non-DEFAULT value into column \"{}\"", column_name.to_string())),
+        }
+    }
+
+    /// explicit value for `GENERATED ALWAYS` column in update error constructor
+    pub fn cannot_update_generated_column<S: ToString>(column_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::GeneratedAlways(format!("column \"{}\" can only be updated to DEFAULT", column_name.to_string())),
+        }
+    }
+
+    /// function does not exist error constructor
+    pub fn function_does_not_exist<S: ToString>(function_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::FunctionDoesNotExist(function_name.to_string()),
+        }
+    }
+
+    /// function already exists error constructor
+    pub fn function_already_exists<S: ToString>(function_name: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::FunctionAlreadyExists(function_name.to_string()),
+        }
+    }
+
+    /// invalid function definition error constructor
+    pub fn invalid_function_definition<S: ToString>(message: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::InvalidFunctionDefinition(message.to_string()),
+        }
+    }
+
+    /// insufficient privilege error constructor
+    pub fn insufficient_privilege<S: ToString>(message: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::InsufficientPrivilege(message.to_string()),
+        }
+    }
+
+    /// none of the schemas in search path exists error constructor
+    pub fn no_schema_selected() -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::NoSchemaSelected,
+        }
+    }
+
+    /// misplaced or invalid window function call error constructor
+    pub fn windowing_error<S: ToString>(message: S) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::WindowingError(message.to_string()),
+        }
+    }
+
+    /// columns of set operation sides have types that could not be cast to a common type
+    pub fn set_operation_type_mismatch<O: ToString, L: ToString, R: ToString>(op: O, left: L, right: R) -> QueryError {
+        QueryError {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SetOperationTypeMismatch {
+                op: op.to_string(),
+                left: left.to_string(),
+                right: right.to_string(),
+            },
+        }
+    }
+
+    /// `SET LOCAL` was issued outside of a transaction block and has no effect
+    pub fn set_local_outside_transaction_block() -> QueryError {
+        QueryError {
+            severity: Severity::Warning,
+            kind: QueryErrorKind::NoActiveSqlTransaction("SET LOCAL can only be used in transaction blocks".to_owned()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -806,4 +1172,23 @@ mod tests {
             assert_eq!(severity, "LOG")
         }
     }
+
+    #[test]
+    fn error_is_sent_as_error_response() {
+        let message: OutboundMessage = QueryError::feature_not_supported("feature").into();
+        assert!(matches!(message, OutboundMessage::Error(severity, code, _) if severity == "ERROR" && code == "0A000"));
+    }
+
+    #[test]
+    fn warning_is_sent_as_notice_response() {
+        let message: OutboundMessage = QueryError::set_local_outside_transaction_block().into();
+        assert_eq!(
+            message,
+            OutboundMessage::Notice(
+                "WARNING".to_owned(),
+                "25P01".to_owned(),
+                "SET LOCAL can only be used in transaction blocks".to_owned()
+            )
+        );
+    }
 }
@@ -19,6 +19,9 @@ pub const VARCHAR: u32 = 1043;
 pub const INT: u32 = 23;
 pub const BIGINT: u32 = 20;
 pub const SMALLINT: u32 = 21;
+pub const REAL: u32 = 700;
+pub const DOUBLE_PRECISION: u32 = 701;
+pub const TIMESTAMPTZ: u32 = 1184;
 
 pub const COMMAND_COMPLETE: u8 = b'C';
 pub const DATA_ROW: u8 = b'D';
@@ -38,6 +41,7 @@ pub const NO_DATA: u8 = b'n';
 pub const PARSE_COMPLETE: u8 = b'1';
 pub const BIND_COMPLETE: u8 = b'2';
 pub const CLOSE_COMPLETE: u8 = b'3';
+pub const PORTAL_SUSPENDED: u8 = b's';
 
 pub const QUERY: u8 = b'Q';
 pub const BIND: u8 = b'B';
@@ -105,7 +109,20 @@ pub enum OutboundMessage {
     TableCreated,
     TableDropped,
     IndexCreated,
+    SequenceCreated,
+    ViewCreated,
+    ViewDropped,
+    MaterializedViewCreated(usize),
+    MaterializedViewRefreshed,
+    MaterializedViewDropped,
+    FunctionCreated,
+    FunctionDropped,
+    TablesAnalyzed,
+    TablesTruncated,
     VariableSet,
+    VariableShown,
+    VariableReset,
+    ParameterStatus(String, String),
     TransactionBegin,
     RecordsInserted(usize),
     RowDescription(Vec<(String, u32)>),
@@ -117,10 +134,14 @@ pub enum OutboundMessage {
     StatementDeallocated,
     StatementParameters(Vec<u32>),
     StatementDescription(Vec<(String, u32)>),
+    QueryExplained,
     ReadyForQuery,
     ParseComplete,
     BindComplete,
+    CloseComplete,
+    PortalSuspended,
     Error(String, String, String),
+    Notice(String, String, String),
     TransactionCommit,
 }
 
@@ -154,7 +175,29 @@ impl From<OutboundMessage> for Vec<u8> {
             OutboundMessage::TableCreated => command_complete("CREATE TABLE"),
             OutboundMessage::TableDropped => command_complete("DROP TABLE"),
             OutboundMessage::IndexCreated => command_complete("CREATE INDEX"),
+            OutboundMessage::SequenceCreated => command_complete("CREATE SEQUENCE"),
+            OutboundMessage::ViewCreated => command_complete("CREATE VIEW"),
+            OutboundMessage::ViewDropped => command_complete("DROP VIEW"),
+            OutboundMessage::MaterializedViewCreated(records) => command_complete(format!("SELECT {}", records).as_str()),
+            OutboundMessage::MaterializedViewRefreshed => command_complete("REFRESH MATERIALIZED VIEW"),
+            OutboundMessage::MaterializedViewDropped => command_complete("DROP MATERIALIZED VIEW"),
+            OutboundMessage::FunctionCreated => command_complete("CREATE FUNCTION"),
+            OutboundMessage::FunctionDropped => command_complete("DROP FUNCTION"),
+            OutboundMessage::TablesAnalyzed => command_complete("ANALYZE"),
+            OutboundMessage::TablesTruncated => command_complete("TRUNCATE TABLE"),
             OutboundMessage::VariableSet => command_complete("SET"),
+            OutboundMessage::VariableShown => command_complete("SHOW"),
+            OutboundMessage::VariableReset => command_complete("RESET"),
+            OutboundMessage::ParameterStatus(name, value) => {
+                let mut buff = Vec::new();
+                buff.extend_from_slice(&[PARAMETER_STATUS]);
+                buff.extend_from_slice(&(4 + name.len() as i32 + 1 + value.len() as i32 + 1).to_be_bytes());
+                buff.extend_from_slice(name.as_bytes());
+                buff.extend_from_slice(&[0]);
+                buff.extend_from_slice(value.as_bytes());
+                buff.extend_from_slice(&[0]);
+                buff
+            }
             OutboundMessage::TransactionBegin => command_complete("BEGIN"),
             OutboundMessage::TransactionCommit => command_complete("COMMIT"),
             OutboundMessage::RecordsInserted(records) => command_complete(format!("INSERT 0 {}", records).as_str()),
@@ -196,6 +239,7 @@ impl From<OutboundMessage> for Vec<u8> {
             OutboundMessage::RecordsDeleted(records) => command_complete(format!("DELETE {}", records).as_str()),
             OutboundMessage::StatementPrepared => command_complete("PREPARE"),
             OutboundMessage::StatementDeallocated => command_complete("DEALLOCATE"),
+            OutboundMessage::QueryExplained => command_complete("EXPLAIN"),
             OutboundMessage::StatementParameters(param_types) => {
                 let mut type_id_buff = Vec::new();
                 for oid in param_types.iter() {
@@ -235,24 +279,29 @@ impl From<OutboundMessage> for Vec<u8> {
             OutboundMessage::ReadyForQuery => vec![READY_FOR_QUERY, 0, 0, 0, 5, EMPTY_QUERY_RESPONSE],
             OutboundMessage::ParseComplete => vec![PARSE_COMPLETE, 0, 0, 0, 4],
             OutboundMessage::BindComplete => vec![BIND_COMPLETE, 0, 0, 0, 4],
-            OutboundMessage::Error(severity, code, message) => {
-                let mut error_response_buff = Vec::new();
-                error_response_buff.extend_from_slice(&[ERROR_RESPONSE]);
-                let mut message_buff = Vec::new();
-                message_buff.extend_from_slice(&[SEVERITY]);
-                message_buff.extend_from_slice(severity.as_bytes());
-                message_buff.extend_from_slice(&[0]);
-                message_buff.extend_from_slice(&[CODE]);
-                message_buff.extend_from_slice(code.as_bytes());
-                message_buff.extend_from_slice(&[0]);
-                message_buff.extend_from_slice(&[MESSAGE]);
-                message_buff.extend_from_slice(message.as_bytes());
-                message_buff.extend_from_slice(&[0]);
-                error_response_buff.extend_from_slice(&(message_buff.len() as i32 + 4 + 1).to_be_bytes());
-                error_response_buff.extend_from_slice(message_buff.as_ref());
-                error_response_buff.extend_from_slice(&[0]);
-                error_response_buff.to_vec()
-            }
+            OutboundMessage::CloseComplete => vec![CLOSE_COMPLETE, 0, 0, 0, 4],
+            OutboundMessage::PortalSuspended => vec![PORTAL_SUSPENDED, 0, 0, 0, 4],
+            OutboundMessage::Error(severity, code, message) => response(ERROR_RESPONSE, severity, code, message),
+            OutboundMessage::Notice(severity, code, message) => response(NOTICE_RESPONSE, severity, code, message),
         }
     }
 }
+
+fn response(kind: u8, severity: String, code: String, message: String) -> Vec<u8> {
+    let mut response_buff = Vec::new();
+    response_buff.extend_from_slice(&[kind]);
+    let mut message_buff = Vec::new();
+    message_buff.extend_from_slice(&[SEVERITY]);
+    message_buff.extend_from_slice(severity.as_bytes());
+    message_buff.extend_from_slice(&[0]);
+    message_buff.extend_from_slice(&[CODE]);
+    message_buff.extend_from_slice(code.as_bytes());
+    message_buff.extend_from_slice(&[0]);
+    message_buff.extend_from_slice(&[MESSAGE]);
+    message_buff.extend_from_slice(message.as_bytes());
+    message_buff.extend_from_slice(&[0]);
+    response_buff.extend_from_slice(&(message_buff.len() as i32 + 4 + 1).to_be_bytes());
+    response_buff.extend_from_slice(message_buff.as_ref());
+    response_buff.extend_from_slice(&[0]);
+    response_buff
+}
@@ -30,6 +30,8 @@ const AUTHENTICATION: u8 = b'R';
 const PARAMETER_STATUS: u8 = b'S';
 const BACKEND_KEY_DATA: u8 = b'K';
 
+const WRITE_BUFFER_THRESHOLD: usize = 8 * 1024;
+
 pub trait Securing<P: Plain, S: Secure>: Clone {
     #[allow(clippy::result_unit_err)]
     fn secure(self, socket: P) -> Result<S, ()>;
@@ -202,6 +204,16 @@ impl<P: Plain, S: Secure> Connection<New, P, S> {
 }
 
 impl<P: Plain, S: Secure> Connection<HandShake, P, S> {
+    pub fn props(&self) -> &HashMap<String, String> {
+        &self.state.props
+    }
+
+    pub fn reject(mut self, error: OutboundMessage) -> io::Result<()> {
+        let buff: Vec<u8> = error.into();
+        self.channel.write_all(&buff)?;
+        self.channel.flush()
+    }
+
     pub fn authenticate(mut self, _password: &str) -> io::Result<Connection<Authenticated, P, S>> {
         self.channel.write_all(&[AUTHENTICATION, 0, 0, 0, 8, 0, 0, 0, 3])?;
         self.channel.flush()?;
@@ -225,18 +237,18 @@ impl<P: Plain, S: Secure> Connection<HandShake, P, S> {
 
 impl<P: Plain, S: Secure> Connection<Authenticated, P, S> {
     pub fn send_params(mut self, params: &[(&str, &str)]) -> io::Result<Connection<AllocateBackendKey, P, S>> {
+        let mut buff = vec![];
         for (key, value) in params {
             let len: i32 = 4 + (key.len() as i32) + 1 + (value.len() as i32) + 1;
-            let mut buff = vec![];
             buff.extend_from_slice(&[PARAMETER_STATUS]);
             buff.extend_from_slice(&len.to_be_bytes());
             buff.extend_from_slice(key.as_bytes());
             buff.extend_from_slice(&[0]);
             buff.extend_from_slice(value.as_bytes());
             buff.extend_from_slice(&[0]);
-            self.channel.write_all(&buff)?;
-            self.channel.flush()?;
         }
+        // parameters are flushed together with backend key data
+        self.channel.write_all(&buff)?;
         Ok(Connection {
             channel: self.channel,
             state: AllocateBackendKey,
@@ -246,15 +258,19 @@ impl<P: Plain, S: Secure> Connection<Authenticated, P, S> {
 
 impl<P: Plain, S: Secure> Connection<AllocateBackendKey, P, S> {
     pub fn send_backend_keys(mut self, conn_id: u32, conn_secret_key: u32) -> io::Result<Connection<Established, P, S>> {
-        self.channel.write_all(&[BACKEND_KEY_DATA])?;
-        self.channel.write_all(&12i32.to_be_bytes())?;
-        self.channel.write_all(&conn_id.to_be_bytes())?;
-        self.channel.write_all(&conn_secret_key.to_be_bytes())?;
+        let mut buff = vec![];
+        buff.extend_from_slice(&[BACKEND_KEY_DATA]);
+        buff.extend_from_slice(&12i32.to_be_bytes());
+        buff.extend_from_slice(&conn_id.to_be_bytes());
+        buff.extend_from_slice(&conn_secret_key.to_be_bytes());
+        self.channel.write_all(&buff)?;
         self.channel.flush()?;
 
         Ok(Connection {
             channel: self.channel,
-            state: Established,
+            state: Established {
+                write_buffer: Vec::with_capacity(WRITE_BUFFER_THRESHOLD),
+            },
         })
     }
 }
@@ -433,8 +449,21 @@ impl<P: Plain, S: Secure> WireConnection for Connection<Established, P, S> {
     }
 
     fn send(&mut self, outbound: OutboundMessage) -> io::Result<()> {
+        let ready_for_query = outbound == OutboundMessage::ReadyForQuery;
         let buff: Vec<u8> = outbound.into();
-        self.channel.write_all(&buff)?;
+        self.state.write_buffer.extend_from_slice(&buff);
+        if ready_for_query || self.state.write_buffer.len() >= WRITE_BUFFER_THRESHOLD {
+            self.flush()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.state.write_buffer.is_empty() {
+            self.channel.write_all(&self.state.write_buffer)?;
+            self.state.write_buffer.clear();
+        }
         self.channel.flush()
     }
 }
@@ -454,7 +483,27 @@ pub struct Authenticated;
 pub struct AllocateBackendKey;
 
 #[derive(Debug)]
-pub struct Established;
+pub struct Established {
+    write_buffer: Vec<u8>,
+}
+
+impl Connection<Established, TcpStream, TlsStream<TcpStream>> {
+    pub fn socket(&self) -> &TcpStream {
+        match &self.channel {
+            Channel::Plain(socket) => socket,
+            Channel::Secure(socket) => socket.get_ref(),
+        }
+    }
+
+    /// TLS stream could have already read and decrypted client messages,
+    /// the socket readiness does not reflect them
+    pub fn has_buffered_input(&self) -> bool {
+        match &self.channel {
+            Channel::Plain(_) => false,
+            Channel::Secure(socket) => socket.buffered_read_size().map(|size| size > 0).unwrap_or(false),
+        }
+    }
+}
 
 impl Plain for TcpStream {}
 
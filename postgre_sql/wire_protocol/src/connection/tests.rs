@@ -213,6 +213,40 @@ fn authenticate() {
     assert_eq!(actual_content, expected_content);
 }
 
+#[test]
+fn reject_connection() {
+    let test_data = TestData::new(vec![
+        &8i32.to_be_bytes(),
+        &1234i16.to_be_bytes(),
+        &5679i16.to_be_bytes(),
+        &89i32.to_be_bytes(),
+        &3i16.to_be_bytes(),
+        &0i16.to_be_bytes(),
+        b"user\0",
+        b"username\0",
+        b"database\0",
+        b"database_name\0",
+        b"application_name\0",
+        b"psql\0",
+        b"client_encoding\0",
+        b"UTF8\0",
+        &[0],
+    ]);
+
+    let socket = test_data.clone();
+    let connection: Connection<New, TestData, TestData> = Connection::new(socket);
+    let connection = connection.hand_shake::<TestData>(None).unwrap();
+    let error = || OutboundMessage::Error("FATAL".to_owned(), "53300".to_owned(), "sorry, too many clients already".to_owned());
+
+    assert!(matches!(connection.reject(error()), Ok(())));
+
+    let actual_content = test_data.read_result();
+    let mut expected_content = Vec::new();
+    expected_content.extend_from_slice(&[REJECT_SSL]);
+    expected_content.extend_from_slice(&Vec::<u8>::from(error()));
+    assert_eq!(actual_content, expected_content);
+}
+
 #[test]
 fn send_server_params() {
     let test_data = TestData::new(vec![
@@ -314,3 +348,82 @@ fn send_backend_keys() {
     expected_content.extend_from_slice(&CONNECTION_SECRET_KEY.to_be_bytes());
     assert_eq!(actual_content, expected_content);
 }
+
+fn established() -> (TestData, Connection<Established, TestData, TestData>) {
+    let test_data = TestData::new(vec![
+        &8i32.to_be_bytes(),
+        &1234i16.to_be_bytes(),
+        &5679i16.to_be_bytes(),
+        &89i32.to_be_bytes(),
+        &3i16.to_be_bytes(),
+        &0i16.to_be_bytes(),
+        b"user\0",
+        b"username\0",
+        b"database\0",
+        b"database_name\0",
+        b"application_name\0",
+        b"psql\0",
+        b"client_encoding\0",
+        b"UTF8\0",
+        &[0],
+        b"p",
+        &8i32.to_be_bytes(),
+        b"123\0",
+    ]);
+
+    let socket = test_data.clone();
+    let connection: Connection<New, TestData, TestData> = Connection::new(socket);
+    let connection = connection.hand_shake::<TestData>(None).unwrap();
+    let connection = connection.authenticate("123").unwrap();
+    let connection = connection.send_params(&[]).unwrap();
+    let connection = connection.send_backend_keys(1, 1).unwrap();
+    (test_data, connection)
+}
+
+#[test]
+fn outbound_messages_are_buffered_until_ready_for_query() {
+    let (test_data, mut connection) = established();
+    let handshake_len = test_data.read_result().len();
+
+    connection.send(OutboundMessage::ParseComplete).unwrap();
+    connection.send(OutboundMessage::BindComplete).unwrap();
+
+    assert_eq!(test_data.read_result().len(), handshake_len);
+
+    connection.send(OutboundMessage::ReadyForQuery).unwrap();
+
+    let actual_content = test_data.read_result();
+    let mut expected_content = Vec::new();
+    expected_content.extend_from_slice(&[b'1', 0, 0, 0, 4]);
+    expected_content.extend_from_slice(&[b'2', 0, 0, 0, 4]);
+    expected_content.extend_from_slice(&[b'Z', 0, 0, 0, 5, b'I']);
+    assert_eq!(&actual_content[handshake_len..], expected_content.as_slice());
+}
+
+#[test]
+fn outbound_messages_are_written_on_flush() {
+    let (test_data, mut connection) = established();
+    let handshake_len = test_data.read_result().len();
+
+    connection.send(OutboundMessage::ParseComplete).unwrap();
+    connection.flush().unwrap();
+
+    assert_eq!(&test_data.read_result()[handshake_len..], &[b'1', 0, 0, 0, 4]);
+}
+
+#[test]
+fn outbound_messages_are_written_when_buffer_is_full() {
+    let (test_data, mut connection) = established();
+    let handshake_len = test_data.read_result().len();
+
+    let row = vec!["a".repeat(1024)];
+    let data_row_len = 1 + 4 + 2 + 4 + 1024;
+    let mut sent = 0;
+    while test_data.read_result().len() == handshake_len {
+        connection.send(OutboundMessage::DataRow(row.clone())).unwrap();
+        sent += 1;
+    }
+
+    assert!((sent - 1) * data_row_len < WRITE_BUFFER_THRESHOLD);
+    assert!(sent * data_row_len >= WRITE_BUFFER_THRESHOLD);
+}
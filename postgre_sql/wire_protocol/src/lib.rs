@@ -16,7 +16,7 @@ pub mod connection;
 
 use crate::connection::{Connection, Established, New, Securing};
 use native_tls::{Identity, TlsStream};
-use std::{io, net::TcpStream};
+use std::{collections::HashMap, io, net::TcpStream};
 use wire_protocol_payload::{InboundMessage, OutboundMessage};
 
 pub type WireResult = std::result::Result<InboundMessage, WireError>;
@@ -28,6 +28,8 @@ pub trait WireConnection {
     fn receive(&mut self) -> io::Result<WireResult>;
 
     fn send(&mut self, outbound: OutboundMessage) -> io::Result<()>;
+
+    fn flush(&mut self) -> io::Result<()>;
 }
 
 pub struct PgWireAcceptor<S: Securing<TcpStream, TlsStream<TcpStream>>> {
@@ -41,18 +43,31 @@ impl<S: Securing<TcpStream, TlsStream<TcpStream>>> PgWireAcceptor<S> {
 }
 
 impl PgWireAcceptor<Identity> {
-    pub fn accept(&self, socket: TcpStream) -> io::Result<Connection<Established, TcpStream, TlsStream<TcpStream>>> {
+    /// `server_params` receives parameters from client startup message and
+    /// returns parameters that should be reported back to the client
+    pub fn accept<F: FnOnce(&HashMap<String, String>) -> Vec<(String, String)>>(
+        &self,
+        socket: TcpStream,
+        server_params: F,
+    ) -> io::Result<Connection<Established, TcpStream, TlsStream<TcpStream>>> {
         let connection: Connection<New, TcpStream, TlsStream<TcpStream>> = Connection::new(socket);
         let connection = connection.hand_shake::<Identity>(self.secured.clone())?;
+        let params = server_params(connection.props());
         let connection = connection.authenticate("whatever")?;
-        let connection = connection.send_params(&[
-            ("client_encoding", "UTF8"),
-            ("DateStyle", "ISO"),
-            ("integer_datetimes", "off"),
-            ("server_version", "13.0"),
-        ])?;
+        let connection = connection.send_params(
+            &params
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect::<Vec<(&str, &str)>>(),
+        )?;
         let mut connection = connection.send_backend_keys(1, 1)?;
         connection.send(OutboundMessage::ReadyForQuery)?;
         Ok(connection)
     }
+
+    pub fn reject(&self, socket: TcpStream, error: OutboundMessage) -> io::Result<()> {
+        let connection: Connection<New, TcpStream, TlsStream<TcpStream>> = Connection::new(socket);
+        let connection = connection.hand_shake::<Identity>(self.secured.clone())?;
+        connection.reject(error)
+    }
 }
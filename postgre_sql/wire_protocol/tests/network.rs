@@ -41,7 +41,7 @@ fn non_secure() {
         let (socket, _) = listener.accept().unwrap();
 
         let acceptor: PgWireAcceptor<Identity> = PgWireAcceptor::new(None);
-        acceptor.accept(socket)
+        acceptor.accept(socket, |_| vec![])
     });
 
     std::thread::yield_now();
@@ -80,7 +80,7 @@ fn secure() {
         let cert = Identity::from_pkcs12(&cert, "password").unwrap();
 
         let acceptor: PgWireAcceptor<Identity> = PgWireAcceptor::new(Some(cert));
-        acceptor.accept(socket)
+        acceptor.accept(socket, |_| vec![])
     });
 
     let cert = fs::read("../../tests/fixtures/certificate.crt").unwrap();
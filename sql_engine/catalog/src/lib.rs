@@ -12,12 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod statistics;
+mod system_views;
+
+pub use statistics::{ColumnStatistics, TableStatistics};
+pub use system_views::{is_system_schema, INFORMATION_SCHEMA, PG_CATALOG};
+
 use binary::BinaryValue;
 use data_definition_execution_plan::{
-    CreateIndexQuery, CreateSchemaQuery, CreateTableQuery, DropSchemasQuery, DropTablesQuery, ExecutionError, ExecutionOutcome, SchemaChange,
+    AnalyzeTablesQuery, ColumnInfo, CreateFunctionQuery, CreateIndexQuery, CreateMaterializedViewQuery, CreateSchemaQuery, CreateSequenceQuery,
+    CreateTableQuery, CreateViewQuery, DropFunctionsQuery, DropSchemasQuery, DropTablesQuery, DropViewsQuery, ExecutionError, ExecutionOutcome,
+    RefreshMaterializedViewQuery, SchemaChange, TruncateTablesQuery,
 };
-use definition::{ColumnDef, FullTableName, SchemaName, TableDef};
-use storage::Transaction;
+use definition::{ColumnDef, ColumnIdentity, FullTableName, FunctionDef, SchemaName, SearchPath, TableDef, ViewDef};
+use storage::{Sequence, Transaction, SEQUENCE_NAME, SEQUENCE_OWNER, SEQUENCE_SCHEMA};
 use types::{SqlType, SqlTypeFamily};
 
 const DEFINITION_SCHEMA: &str = "DEFINITION_SCHEMA";
@@ -25,6 +33,18 @@ const SCHEMATA_TABLE: &str = "SCHEMATA";
 const TABLES_TABLE: &str = "TABLES";
 const INDEXES_TABLE: &str = "TABLES";
 const COLUMNS_TABLE: &str = "COLUMNS";
+const VIEWS_TABLE: &str = "VIEWS";
+const SEQUENCES_TABLE: &str = "SEQUENCES";
+const FUNCTIONS_TABLE: &str = "FUNCTIONS";
+const PARAMETERS_TABLE: &str = "PARAMETERS";
+const FUNCTION_USAGE_TABLE: &str = "FUNCTION_USAGE";
+const VIEW_KIND: &str = "VIEW";
+const FUNCTION_KIND: &str = "FUNCTION";
+const STATISTICS_TABLE: &str = "STATISTICS";
+const COLUMN_STATISTICS_TABLE: &str = "COLUMN_STATISTICS";
+const COLUMN_STATISTIC_VALUES_TABLE: &str = "COLUMN_STATISTIC_VALUES";
+const MOST_COMMON_VALUE: &str = "MOST_COMMON_VALUE";
+const HISTOGRAM_BOUND: &str = "HISTOGRAM_BOUND";
 
 pub struct CatalogHandler<'c> {
     transaction: Transaction<'c>,
@@ -47,9 +67,38 @@ impl<'c> CatalogHandler<'c> {
             })
     }
 
+    /// the first existing schema of the search path, where new objects are created
+    pub fn creation_schema(&self, search_path: &SearchPath) -> Option<String> {
+        search_path
+            .schemas()
+            .iter()
+            .find(|schema_name| self.schema_exists(&SchemaName::from(schema_name)))
+            .cloned()
+    }
+
+    /// qualifies the name with the first schema of the search path that has a table, a view or a sequence
+    /// with the name, otherwise with the schema where it would be created
+    pub fn qualify(&self, search_path: &SearchPath, name: &str) -> Option<FullTableName> {
+        search_path
+            .lookup_schemas()
+            .into_iter()
+            .map(|schema_name| FullTableName::from((&schema_name, &name)))
+            .find(|full_name| {
+                matches!(self.table_definition(full_name.clone()), Some(Some(_)))
+                    || self.view_definition(full_name).is_some()
+                    || self.sequence(full_name).is_some()
+            })
+            .or_else(|| {
+                self.creation_schema(search_path)
+                    .map(|schema_name| FullTableName::from((&schema_name, &name)))
+            })
+    }
+
     pub fn table_definition(&self, full_table_name: FullTableName) -> Option<Option<TableDef>> {
         if !self.schema_exists(&SchemaName::from(&full_table_name.schema())) {
             None
+        } else if is_system_schema(full_table_name.schema()) {
+            Some(system_views::system_view_definition(&full_table_name))
         } else {
             match self
                 .transaction
@@ -67,13 +116,7 @@ impl<'c> CatalogHandler<'c> {
                         .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMNS_TABLE))
                         .scan()
                         .filter(|(key, _value)| key.starts_with(&full_table_id))
-                        .map(|(_key, value)| {
-                            let row = value;
-                            let name = row[3].as_string();
-                            let sql_type = SqlType::from_type_id(row[4].as_u64(), row[5].as_u64());
-                            let ord_num = row[6].as_u64() as usize;
-                            ColumnDef::new(name, sql_type, ord_num)
-                        })
+                        .map(|(_key, value)| column_def(value))
                         .collect();
 
                     Some(Some(TableDef::new(full_table_name, columns)))
@@ -83,6 +126,278 @@ impl<'c> CatalogHandler<'c> {
         }
     }
 
+    pub fn view_definition(&self, full_view_name: &FullTableName) -> Option<ViewDef> {
+        self.transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, VIEWS_TABLE))
+            .scan()
+            .find(|(_key, value)| value[1] == full_view_name.schema() && value[2] == full_view_name.table())
+            .map(|(_key, value)| view_def(value))
+    }
+
+    fn views(&self) -> Vec<ViewDef> {
+        self.transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, VIEWS_TABLE))
+            .scan()
+            .map(|(_key, value)| view_def(value))
+            .collect()
+    }
+
+    fn dependent_views(&self, full_table_name: &FullTableName) -> Vec<FullTableName> {
+        self.views()
+            .into_iter()
+            .filter(|view| view.source().schema() == full_table_name.schema() && view.source().table() == full_table_name.table())
+            .map(|view| view.full_view_name().clone())
+            .collect()
+    }
+
+    fn remove_view(&self, full_view_name: &FullTableName) {
+        for dependent_view in self.dependent_views(full_view_name) {
+            self.remove_view(&dependent_view);
+        }
+        self.write_function_usage(full_view_name, VIEW_KIND, &[]);
+        let views_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, VIEWS_TABLE));
+        for (key, value) in views_table
+            .scan()
+            .filter(|(_key, value)| value[1] == full_view_name.schema() && value[2] == full_view_name.table())
+        {
+            if view_def(value).is_materialized() {
+                self.remove_table(full_view_name);
+            }
+            views_table.write_key(key, None);
+        }
+    }
+
+    pub fn function_definition(&self, full_function_name: &FullTableName) -> Option<FunctionDef> {
+        self.transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, FUNCTIONS_TABLE))
+            .scan()
+            .find(|(_key, value)| value[1] == full_function_name.schema() && value[2] == full_function_name.table())
+            .map(|(function_id, value)| {
+                let parameters = self
+                    .transaction
+                    .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, PARAMETERS_TABLE))
+                    .scan()
+                    .filter(|(key, _value)| key.starts_with(&function_id))
+                    .map(|(_key, value)| (value[3].as_string(), SqlType::from_type_id(value[4].as_u64(), value[5].as_u64())))
+                    .collect();
+                FunctionDef::new(
+                    full_function_name.clone(),
+                    parameters,
+                    SqlType::from_type_id(value[3].as_u64(), value[4].as_u64()),
+                    value[5].as_string(),
+                )
+            })
+    }
+
+    /// functions have their own namespace, so the name is qualified with the first schema
+    /// of the search path that has a function with the name
+    pub fn qualify_function(&self, search_path: &SearchPath, name: &str) -> Option<FullTableName> {
+        search_path
+            .lookup_schemas()
+            .into_iter()
+            .map(|schema_name| FullTableName::from((&schema_name, &name)))
+            .find(|full_name| self.function_definition(full_name).is_some())
+    }
+
+    fn functions(&self) -> Vec<FullTableName> {
+        self.transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, FUNCTIONS_TABLE))
+            .scan()
+            .map(|(_key, value)| FullTableName::from((&value[1].as_string(), &value[2].as_string())))
+            .collect()
+    }
+
+    // usage record: schema, name and kind of the view or function that inlines the function,
+    // schema and name of the function
+    fn write_function_usage(&self, dependent: &FullTableName, kind: &str, used_functions: &[FullTableName]) {
+        let usage_table = self
+            .transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, FUNCTION_USAGE_TABLE));
+        for key in usage_table
+            .scan()
+            .filter(|(_key, value)| value[1] == dependent.schema() && value[2] == dependent.table() && value[3] == kind)
+            .map(|(key, _value)| key)
+        {
+            usage_table.write_key(key, None);
+        }
+        for used_function in used_functions {
+            usage_table.write(vec![
+                BinaryValue::from("IN_MEMORY"),
+                BinaryValue::from(dependent.schema()),
+                BinaryValue::from(dependent.table()),
+                BinaryValue::from(kind),
+                BinaryValue::from(used_function.schema()),
+                BinaryValue::from(used_function.table()),
+            ]);
+        }
+    }
+
+    fn function_dependents(&self, full_function_name: &FullTableName) -> Vec<(String, FullTableName)> {
+        self.transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, FUNCTION_USAGE_TABLE))
+            .scan()
+            .filter(|(_key, value)| value[4] == full_function_name.schema() && value[5] == full_function_name.table())
+            .map(|(_key, value)| (value[3].as_string(), FullTableName::from((&value[1].as_string(), &value[2].as_string()))))
+            .collect()
+    }
+
+    fn remove_function(&self, full_function_name: &FullTableName) {
+        for (kind, dependent) in self.function_dependents(full_function_name) {
+            if kind == VIEW_KIND {
+                self.remove_view(&dependent);
+            } else {
+                self.remove_function(&dependent);
+            }
+        }
+        self.write_function_usage(full_function_name, FUNCTION_KIND, &[]);
+        let functions_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, FUNCTIONS_TABLE));
+        let parameters_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, PARAMETERS_TABLE));
+        for function_id in functions_table
+            .scan()
+            .filter(|(_key, value)| value[1] == full_function_name.schema() && value[2] == full_function_name.table())
+            .map(|(key, _value)| key)
+        {
+            for parameter_key in parameters_table
+                .scan()
+                .filter(|(key, _value)| key.starts_with(&function_id))
+                .map(|(key, _value)| key)
+            {
+                parameters_table.write_key(parameter_key, None);
+            }
+            functions_table.write_key(function_id, None);
+        }
+    }
+
+    fn write_table(&self, full_table_name: &FullTableName, column_defs: &[ColumnInfo]) -> u64 {
+        let object_id = self.transaction.next_object_id();
+        let full_table_name_record = vec![
+            BinaryValue::from("IN_MEMORY"),
+            BinaryValue::from(full_table_name.schema()),
+            BinaryValue::from(full_table_name.table()),
+            BinaryValue::from_u64(object_id),
+        ];
+        let full_table_id = self
+            .transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, TABLES_TABLE))
+            .write(full_table_name_record);
+
+        let columns_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMNS_TABLE));
+
+        for (index, def) in column_defs.iter().enumerate() {
+            let record = vec![
+                BinaryValue::from("IN_MEMORY"),
+                BinaryValue::from(full_table_name.schema()),
+                BinaryValue::from(full_table_name.table()),
+                BinaryValue::from(def.name.clone()),
+                BinaryValue::from_u64(def.sql_type.type_id()),
+                BinaryValue::from_u64(def.sql_type.chars_len().unwrap_or_default()),
+                BinaryValue::from_u64(index as u64),
+                match def.identity {
+                    None => BinaryValue::null(),
+                    Some(ColumnIdentity::Always) => BinaryValue::from("ALWAYS"),
+                    Some(ColumnIdentity::ByDefault) => BinaryValue::from("BY DEFAULT"),
+                },
+            ];
+            let mut key = full_table_id.clone();
+            key.push(BinaryValue::from_u64(index as u64));
+            columns_table.write_key(key, Some(record));
+            if def.identity.is_some() {
+                let max_value = match def.sql_type.family() {
+                    SqlTypeFamily::SmallInt => i16::MAX as i64,
+                    SqlTypeFamily::Integer => i32::MAX as i64,
+                    _ => i64::MAX,
+                };
+                self.write_sequence(
+                    &identity_sequence_name(full_table_name, &def.name),
+                    (1, 1, 1, max_value),
+                    full_table_name.table(),
+                );
+            }
+        }
+
+        self.transaction.create_tree(full_table_name);
+        object_id
+    }
+
+    // sequence record: start, increment, min and max values, last value, whether the last value
+    // was returned by `nextval`, the table which identity column owns the sequence and object id
+    fn write_sequence(&self, full_sequence_name: &FullTableName, (start, increment, min_value, max_value): (i64, i64, i64, i64), owner: &str) {
+        self.transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, SEQUENCES_TABLE))
+            .write(vec![
+                BinaryValue::from("IN_MEMORY"),
+                BinaryValue::from(full_sequence_name.schema()),
+                BinaryValue::from(full_sequence_name.table()),
+                BinaryValue::from(start),
+                BinaryValue::from(increment),
+                BinaryValue::from(min_value),
+                BinaryValue::from(max_value),
+                BinaryValue::from(start),
+                BinaryValue::from_bool(false),
+                BinaryValue::from(owner),
+                BinaryValue::from_u64(self.transaction.next_object_id()),
+            ]);
+    }
+
+    pub fn sequence(&self, full_sequence_name: &FullTableName) -> Option<Sequence> {
+        let sequences_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, SEQUENCES_TABLE));
+        sequences_table
+            .scan()
+            .find(|(_key, value)| value[1] == full_sequence_name.schema() && value[2] == full_sequence_name.table())
+            .map(|(key, _value)| {
+                Sequence::new(
+                    full_sequence_name.table().to_owned(),
+                    sequences_table.clone(),
+                    key,
+                    self.transaction.session_sequences(),
+                )
+            })
+    }
+
+    /// sequences generating values of identity columns, `None` for other columns
+    pub fn column_defaults(&self, full_table_name: &FullTableName) -> Vec<Option<Sequence>> {
+        match self.table_definition(full_table_name.clone()) {
+            Some(Some(table_def)) => table_def
+                .columns()
+                .iter()
+                .map(|column| match column.identity() {
+                    None => None,
+                    Some(_) => self.sequence(&identity_sequence_name(full_table_name, column.name())),
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn remove_table(&self, full_table_name: &FullTableName) {
+        let tables_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, TABLES_TABLE));
+        let columns_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMNS_TABLE));
+        for full_table_id in tables_table
+            .scan()
+            .filter(|(_key, value)| value[1] == full_table_name.schema() && value[2] == full_table_name.table())
+            .map(|(key, _value)| key)
+        {
+            for column_key in columns_table
+                .scan()
+                .filter(|(key, _value)| key.starts_with(&full_table_id))
+                .map(|(key, _value)| key)
+            {
+                columns_table.write_key(column_key, None);
+            }
+            tables_table.write_key(full_table_id, None);
+        }
+        let sequences_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, SEQUENCES_TABLE));
+        for key in sequences_table
+            .scan()
+            .filter(|(_key, value)| value[SEQUENCE_SCHEMA] == full_table_name.schema() && value[SEQUENCE_OWNER] == full_table_name.table())
+            .map(|(key, _value)| key)
+        {
+            sequences_table.write_key(key, None);
+        }
+        self.remove_statistics(full_table_name.schema(), full_table_name.table());
+        self.transaction.drop_tree(full_table_name);
+    }
+
     pub fn columns(&self, full_table_name: &FullTableName) -> Vec<(String, SqlTypeFamily)> {
         self.columns_short(full_table_name)
             .into_iter()
@@ -91,6 +406,13 @@ impl<'c> CatalogHandler<'c> {
     }
 
     pub fn columns_short(&self, full_table_name: &FullTableName) -> Vec<(String, SqlType)> {
+        if let Some(table_def) = system_views::system_view_definition(full_table_name) {
+            return table_def
+                .columns()
+                .iter()
+                .map(|column| (column.name().to_owned(), column.sql_type()))
+                .collect();
+        }
         let full_table_id = self
             .transaction
             .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, TABLES_TABLE))
@@ -115,7 +437,131 @@ impl<'c> CatalogHandler<'c> {
             .collect()
     }
 
+    pub fn table_statistics(&self, full_table_name: &FullTableName) -> Option<TableStatistics> {
+        let of_table = |value: &[BinaryValue]| value[1] == full_table_name.schema() && value[2] == full_table_name.table();
+        let rows = self
+            .transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, STATISTICS_TABLE))
+            .scan()
+            .find(|(_key, value)| of_table(value))
+            .map(|(_key, value)| value[3].as_u64() as usize)?;
+
+        let mut columns = self
+            .transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMN_STATISTICS_TABLE))
+            .scan()
+            .filter(|(_key, value)| of_table(value))
+            .map(|(_key, value)| {
+                let null_fraction = match value[4] {
+                    BinaryValue::Float64(null_fraction) => *null_fraction,
+                    _ => unreachable!(),
+                };
+                ColumnStatistics {
+                    name: value[3].as_string(),
+                    null_fraction,
+                    distinct: value[5].as_u64() as usize,
+                    most_common_values: vec![],
+                    histogram_bounds: vec![],
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut values = self
+            .transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMN_STATISTIC_VALUES_TABLE))
+            .scan()
+            .filter(|(_key, value)| of_table(value))
+            .map(|(_key, value)| value)
+            .collect::<Vec<_>>();
+        values.sort_by_key(|value| value[5].as_u64());
+        for mut value in values {
+            let frequency = value.pop();
+            let statistic_value = value.pop().unwrap();
+            if let Some(column) = columns.iter_mut().find(|column| value[3] == column.name.as_str()) {
+                match frequency {
+                    Some(BinaryValue::Float64(frequency)) if value[4] == MOST_COMMON_VALUE => {
+                        column.most_common_values.push((statistic_value, *frequency))
+                    }
+                    _ => column.histogram_bounds.push(statistic_value),
+                }
+            }
+        }
+
+        Some(TableStatistics { rows, columns })
+    }
+
+    fn write_statistics(&self, full_table_name: &FullTableName, statistics: TableStatistics) {
+        self.remove_statistics(full_table_name.schema(), full_table_name.table());
+        let table_record = vec![
+            BinaryValue::from("IN_MEMORY"),
+            BinaryValue::from(full_table_name.schema()),
+            BinaryValue::from(full_table_name.table()),
+        ];
+        let mut record = table_record.clone();
+        record.push(BinaryValue::from_u64(statistics.rows as u64));
+        self.transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, STATISTICS_TABLE))
+            .write(record);
+
+        let column_statistics_table = self
+            .transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMN_STATISTICS_TABLE));
+        let values_table = self
+            .transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMN_STATISTIC_VALUES_TABLE));
+        for column in statistics.columns {
+            let mut column_record = table_record.clone();
+            column_record.push(BinaryValue::from(column.name));
+            let mut record = column_record.clone();
+            record.push(BinaryValue::from(column.null_fraction));
+            record.push(BinaryValue::from_u64(column.distinct as u64));
+            column_statistics_table.write(record);
+
+            let most_common_values = column
+                .most_common_values
+                .into_iter()
+                .map(|(value, frequency)| (MOST_COMMON_VALUE, value, BinaryValue::from(frequency)));
+            let histogram_bounds = column
+                .histogram_bounds
+                .into_iter()
+                .map(|value| (HISTOGRAM_BOUND, value, BinaryValue::null()));
+            for (position, (kind, value, frequency)) in most_common_values.chain(histogram_bounds).enumerate() {
+                let mut record = column_record.clone();
+                record.push(BinaryValue::from(kind));
+                record.push(BinaryValue::from_u64(position as u64));
+                record.push(value);
+                record.push(frequency);
+                values_table.write(record);
+            }
+        }
+    }
+
+    fn remove_statistics(&self, schema_name: &str, table_name: &str) {
+        for statistics_table in &[STATISTICS_TABLE, COLUMN_STATISTICS_TABLE, COLUMN_STATISTIC_VALUES_TABLE] {
+            let statistics_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, statistics_table));
+            for key in statistics_table
+                .scan()
+                .filter(|(_key, value)| value[1] == schema_name && value[2] == table_name)
+                .map(|(key, _value)| key)
+            {
+                statistics_table.write_key(key, None);
+            }
+        }
+    }
+
+    pub fn version(&self) -> u64 {
+        self.transaction.catalog_version()
+    }
+
+    /// catalog version advances only when the change is applied,
+    /// so that failed DDL does not invalidate cached plans
     pub fn apply(&self, schema_change: SchemaChange) -> Result<ExecutionOutcome, ExecutionError> {
+        let outcome = self.apply_change(schema_change)?;
+        self.transaction.advance_catalog_version();
+        Ok(outcome)
+    }
+
+    fn apply_change(&self, schema_change: SchemaChange) -> Result<ExecutionOutcome, ExecutionError> {
         match schema_change {
             SchemaChange::CreateSchema(CreateSchemaQuery { schema_name, if_not_exists }) => {
                 if self.schema_exists(&SchemaName::from(&schema_name.as_ref())) {
@@ -127,7 +573,11 @@ impl<'c> CatalogHandler<'c> {
                 } else {
                     self.transaction
                         .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, SCHEMATA_TABLE))
-                        .write(vec![BinaryValue::from("IN_MEMORY"), BinaryValue::from(schema_name.as_ref())]);
+                        .write(vec![
+                            BinaryValue::from("IN_MEMORY"),
+                            BinaryValue::from(schema_name.as_ref()),
+                            BinaryValue::from_u64(self.transaction.next_object_id()),
+                        ]);
                     Ok(ExecutionOutcome::SchemaCreated)
                 }
             }
@@ -159,10 +609,27 @@ impl<'c> CatalogHandler<'c> {
                                     let value = value;
                                     value[0] == "IN_MEMORY" && value[1] == schema_name.as_ref()
                                 })
-                                .is_none();
+                                .is_none()
+                                && self.views().iter().all(|view| view.full_view_name().schema() != schema_name.as_ref())
+                                && self
+                                    .transaction
+                                    .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, SEQUENCES_TABLE))
+                                    .scan()
+                                    .all(|(_key, value)| value[1] != schema_name.as_ref())
+                                && self.functions().iter().all(|function| function.schema() != schema_name.as_ref());
                             if !is_empty && !cascade {
                                 return Err(ExecutionError::SchemaHasDependentObjects(schema_name.as_ref().to_owned()));
                             } else {
+                                for view in self.views() {
+                                    if view.full_view_name().schema() == schema_name.as_ref() || view.source().schema() == schema_name.as_ref() {
+                                        self.remove_view(view.full_view_name());
+                                    }
+                                }
+                                for function in self.functions() {
+                                    if function.schema() == schema_name.as_ref() {
+                                        self.remove_function(&function);
+                                    }
+                                }
                                 let columns_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMNS_TABLE));
                                 for column_key in columns_table
                                     .scan()
@@ -189,6 +656,16 @@ impl<'c> CatalogHandler<'c> {
                                     tables_table.write_key(table_key, None);
                                     self.transaction.drop_tree(table_name);
                                 }
+                                for statistics_table in &[STATISTICS_TABLE, COLUMN_STATISTICS_TABLE, COLUMN_STATISTIC_VALUES_TABLE, SEQUENCES_TABLE] {
+                                    let statistics_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, statistics_table));
+                                    for key in statistics_table
+                                        .scan()
+                                        .filter(|(_key, value)| value[1] == schema_name.as_ref())
+                                        .map(|(key, _value)| key)
+                                    {
+                                        statistics_table.write_key(key, None);
+                                    }
+                                }
 
                                 schemas_table.write_key(schema_id, None);
                             }
@@ -219,59 +696,57 @@ impl<'c> CatalogHandler<'c> {
                             value[0] == "IN_MEMORY" && value[1] == full_table_name.schema() && value[2] == full_table_name.table()
                         });
                         log::trace!("DEBUG {:?}", table_id);
-                        match table_id {
-                            Some(_table_id) => {
-                                if if_not_exists {
-                                    Ok(ExecutionOutcome::TableCreated)
-                                } else {
-                                    Err(ExecutionError::TableAlreadyExists(
-                                        full_table_name.schema().to_owned(),
-                                        full_table_name.table().to_owned(),
-                                    ))
-                                }
-                            }
-                            None => {
-                                let full_table_name_record = vec![
-                                    BinaryValue::from("IN_MEMORY"),
-                                    BinaryValue::from(full_table_name.schema()),
-                                    BinaryValue::from(full_table_name.table()),
-                                ];
-                                let full_table_id = tables_table.write(full_table_name_record);
-
-                                let columns_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMNS_TABLE));
-
-                                for (index, def) in column_defs.iter().enumerate() {
-                                    let record = vec![
-                                        BinaryValue::from("IN_MEMORY"),
-                                        BinaryValue::from(full_table_name.schema()),
-                                        BinaryValue::from(full_table_name.table()),
-                                        BinaryValue::from(def.name.clone()),
-                                        BinaryValue::from_u64(def.sql_type.type_id()),
-                                        BinaryValue::from_u64(def.sql_type.chars_len().unwrap_or_default()),
-                                        BinaryValue::from_u64(index as u64),
-                                    ];
-                                    let mut key = full_table_id.clone();
-                                    key.push(BinaryValue::from_u64(index as u64));
-                                    columns_table.write_key(key, Some(record));
-                                }
-
-                                self.transaction.create_tree(&full_table_name);
-
+                        if table_id.is_some() || self.view_definition(&full_table_name).is_some() {
+                            if if_not_exists {
                                 Ok(ExecutionOutcome::TableCreated)
+                            } else {
+                                Err(ExecutionError::TableAlreadyExists(
+                                    full_table_name.schema().to_owned(),
+                                    full_table_name.table().to_owned(),
+                                ))
                             }
+                        } else {
+                            self.write_table(&full_table_name, &column_defs);
+                            Ok(ExecutionOutcome::TableCreated)
                         }
                     }
                 }
             }
-            // cascade does not make sense for now, but when `FOREIGN KEY`s will be introduce it will become relevant
+            SchemaChange::CreateSequence(CreateSequenceQuery {
+                full_sequence_name,
+                start,
+                increment,
+                min_value,
+                max_value,
+                if_not_exists,
+            }) => {
+                if !self.schema_exists(&SchemaName::from(&full_sequence_name.schema())) {
+                    return Err(ExecutionError::SchemaDoesNotExist(full_sequence_name.schema().to_owned()));
+                }
+                if self.sequence(&full_sequence_name).is_some()
+                    || matches!(self.table_definition(full_sequence_name.clone()), Some(Some(_)))
+                    || self.view_definition(&full_sequence_name).is_some()
+                {
+                    if if_not_exists {
+                        Ok(ExecutionOutcome::SequenceCreated)
+                    } else {
+                        Err(ExecutionError::SequenceAlreadyExists(
+                            full_sequence_name.schema().to_owned(),
+                            full_sequence_name.table().to_owned(),
+                        ))
+                    }
+                } else {
+                    self.write_sequence(&full_sequence_name, (start, increment, min_value, max_value), "");
+                    Ok(ExecutionOutcome::SequenceCreated)
+                }
+            }
             SchemaChange::DropTables(DropTablesQuery {
                 full_table_names,
-                cascade: _cascade,
+                cascade,
                 if_exists,
             }) => {
                 let schemas_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, SCHEMATA_TABLE));
                 let tables_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, TABLES_TABLE));
-                let columns_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMNS_TABLE));
 
                 for full_table_name in full_table_names {
                     let full_schema_name = vec![BinaryValue::from("IN_MEMORY"), BinaryValue::from(full_table_name.schema())];
@@ -290,7 +765,8 @@ impl<'c> CatalogHandler<'c> {
                                     let value = value;
                                     value[1] == full_table_name.schema() && value[2] == full_table_name.table()
                                 })
-                                .map(|(key, _value)| key);
+                                .map(|(key, _value)| key)
+                                .filter(|_key| self.view_definition(&full_table_name).is_none());
                             match table_id {
                                 None => {
                                     if !if_exists {
@@ -300,16 +776,15 @@ impl<'c> CatalogHandler<'c> {
                                         ));
                                     }
                                 }
-                                Some(full_table_id) => {
-                                    for column_key in columns_table
-                                        .scan()
-                                        .filter(|(key, _value)| key.starts_with(&full_table_id))
-                                        .map(|(key, _value)| key)
-                                    {
-                                        columns_table.write_key(column_key, None);
+                                Some(_full_table_id) => {
+                                    let dependent_views = self.dependent_views(&full_table_name);
+                                    if !dependent_views.is_empty() && !cascade {
+                                        return Err(ExecutionError::DependentObjectsStillExist(format!("table {}", full_table_name)));
                                     }
-                                    tables_table.write_key(full_table_id, None);
-                                    self.transaction.drop_tree(&full_table_name);
+                                    for dependent_view in dependent_views {
+                                        self.remove_view(&dependent_view);
+                                    }
+                                    self.remove_table(&full_table_name);
                                 }
                             }
                         }
@@ -353,13 +828,7 @@ impl<'c> CatalogHandler<'c> {
                                 let table_columns = columns_table
                                     .scan()
                                     .filter(|(key, _value)| key.starts_with(&full_table_id))
-                                    .map(|(_key, value)| {
-                                        let row = value;
-                                        let name = row[3].as_string();
-                                        let sql_type = SqlType::from_type_id(row[4].as_u64(), row[5].as_u64());
-                                        let ord_num = row[6].as_u64() as usize;
-                                        ColumnDef::new(name, sql_type, ord_num)
-                                    })
+                                    .map(|(_key, value)| column_def(value))
                                     .collect::<Vec<_>>();
                                 let mut column_indexes = vec![];
                                 for column_name in column_names.iter() {
@@ -375,6 +844,7 @@ impl<'c> CatalogHandler<'c> {
                                     BinaryValue::from(full_table_name.table()),
                                     BinaryValue::from(name.clone()),
                                     BinaryValue::from(column_names.join(", ")),
+                                    BinaryValue::from_u64(self.transaction.next_object_id()),
                                 ]);
 
                                 self.transaction
@@ -385,6 +855,390 @@ impl<'c> CatalogHandler<'c> {
                     }
                 }
             }
+            SchemaChange::CreateView(CreateViewQuery {
+                full_view_name,
+                columns,
+                definition,
+                source,
+                used_functions,
+                or_replace,
+            }) => {
+                if !self.schema_exists(&SchemaName::from(&full_view_name.schema())) {
+                    return Err(ExecutionError::SchemaDoesNotExist(full_view_name.schema().to_owned()));
+                }
+                if let Some(Some(_table_def)) = self.table_definition(full_view_name.clone()) {
+                    return Err(ExecutionError::TableAlreadyExists(
+                        full_view_name.schema().to_owned(),
+                        full_view_name.table().to_owned(),
+                    ));
+                }
+                let views_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, VIEWS_TABLE));
+                let view = views_table
+                    .scan()
+                    .find(|(_key, value)| value[1] == full_view_name.schema() && value[2] == full_view_name.table());
+                let object_id = match &view {
+                    Some((_key, value)) => value[8].as_u64(),
+                    None => self.transaction.next_object_id(),
+                };
+                let record = vec![
+                    BinaryValue::from("IN_MEMORY"),
+                    BinaryValue::from(full_view_name.schema()),
+                    BinaryValue::from(full_view_name.table()),
+                    BinaryValue::from(columns.join(", ")),
+                    BinaryValue::from(definition),
+                    BinaryValue::from(source.schema()),
+                    BinaryValue::from(source.table()),
+                    BinaryValue::from_bool(false),
+                    BinaryValue::from_u64(object_id),
+                ];
+                match view {
+                    Some((view_id, _value)) if or_replace => views_table.write_key(view_id, Some(record)),
+                    Some(_view) => {
+                        return Err(ExecutionError::ViewAlreadyExists(
+                            full_view_name.schema().to_owned(),
+                            full_view_name.table().to_owned(),
+                        ))
+                    }
+                    None => {
+                        views_table.write(record);
+                    }
+                }
+                self.write_function_usage(&full_view_name, VIEW_KIND, &used_functions);
+                Ok(ExecutionOutcome::ViewCreated)
+            }
+            SchemaChange::DropViews(DropViewsQuery {
+                full_view_names,
+                cascade,
+                if_exists,
+                materialized,
+            }) => {
+                let kind = if materialized { "materialized view" } else { "view" };
+                for full_view_name in full_view_names {
+                    if !self.schema_exists(&SchemaName::from(&full_view_name.schema())) {
+                        return Err(ExecutionError::SchemaDoesNotExist(full_view_name.schema().to_owned()));
+                    }
+                    match self
+                        .view_definition(&full_view_name)
+                        .filter(|view| view.is_materialized() == materialized)
+                    {
+                        None if if_exists => {}
+                        None if materialized => {
+                            return Err(ExecutionError::MaterializedViewDoesNotExist(
+                                full_view_name.schema().to_owned(),
+                                full_view_name.table().to_owned(),
+                            ))
+                        }
+                        None => {
+                            return Err(ExecutionError::ViewDoesNotExist(
+                                full_view_name.schema().to_owned(),
+                                full_view_name.table().to_owned(),
+                            ))
+                        }
+                        Some(_view_def) => {
+                            if !cascade && !self.dependent_views(&full_view_name).is_empty() {
+                                return Err(ExecutionError::DependentObjectsStillExist(format!("{} {}", kind, full_view_name)));
+                            }
+                            self.remove_view(&full_view_name);
+                        }
+                    }
+                }
+                if materialized {
+                    Ok(ExecutionOutcome::MaterializedViewDropped)
+                } else {
+                    Ok(ExecutionOutcome::ViewDropped)
+                }
+            }
+            SchemaChange::CreateMaterializedView(CreateMaterializedViewQuery {
+                full_view_name,
+                column_defs,
+                definition,
+                source,
+                used_functions,
+                rows,
+                if_not_exists,
+            }) => {
+                if !self.schema_exists(&SchemaName::from(&full_view_name.schema())) {
+                    return Err(ExecutionError::SchemaDoesNotExist(full_view_name.schema().to_owned()));
+                }
+                if let Some(Some(_table_def)) = self.table_definition(full_view_name.clone()) {
+                    return if if_not_exists {
+                        Ok(ExecutionOutcome::MaterializedViewCreated(0))
+                    } else {
+                        Err(ExecutionError::TableAlreadyExists(
+                            full_view_name.schema().to_owned(),
+                            full_view_name.table().to_owned(),
+                        ))
+                    };
+                }
+                if self.view_definition(&full_view_name).is_some() {
+                    return if if_not_exists {
+                        Ok(ExecutionOutcome::MaterializedViewCreated(0))
+                    } else {
+                        Err(ExecutionError::ViewAlreadyExists(
+                            full_view_name.schema().to_owned(),
+                            full_view_name.table().to_owned(),
+                        ))
+                    };
+                }
+                let object_id = self.write_table(&full_view_name, &column_defs);
+                self.transaction
+                    .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, VIEWS_TABLE))
+                    .write(vec![
+                        BinaryValue::from("IN_MEMORY"),
+                        BinaryValue::from(full_view_name.schema()),
+                        BinaryValue::from(full_view_name.table()),
+                        BinaryValue::from(
+                            column_defs
+                                .iter()
+                                .map(|column_def| column_def.name.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        ),
+                        BinaryValue::from(definition),
+                        BinaryValue::from(source.schema()),
+                        BinaryValue::from(source.table()),
+                        BinaryValue::from_bool(true),
+                        BinaryValue::from_u64(object_id),
+                    ]);
+                self.write_function_usage(&full_view_name, VIEW_KIND, &used_functions);
+                let records = rows.len();
+                let view_table = self.transaction.lookup_table_ref(&full_view_name);
+                for row in rows {
+                    view_table.write(row);
+                }
+                Ok(ExecutionOutcome::MaterializedViewCreated(records))
+            }
+            SchemaChange::RefreshMaterializedView(RefreshMaterializedViewQuery {
+                full_view_name,
+                mut rows,
+                concurrently,
+            }) => {
+                if !self
+                    .view_definition(&full_view_name)
+                    .map(|view| view.is_materialized())
+                    .unwrap_or_default()
+                {
+                    return Err(ExecutionError::MaterializedViewDoesNotExist(
+                        full_view_name.schema().to_owned(),
+                        full_view_name.table().to_owned(),
+                    ));
+                }
+                let view_table = self.transaction.lookup_table_ref(&full_view_name);
+                for (key, value) in view_table.scan() {
+                    // concurrent refresh keeps records that did not change and applies only the difference
+                    match rows.iter().position(|row| concurrently && row == &value) {
+                        Some(position) => {
+                            rows.swap_remove(position);
+                        }
+                        None => view_table.write_key(key, None),
+                    }
+                }
+                for row in rows {
+                    view_table.write(row);
+                }
+                self.remove_statistics(full_view_name.schema(), full_view_name.table());
+                Ok(ExecutionOutcome::MaterializedViewRefreshed)
+            }
+            SchemaChange::CreateFunction(CreateFunctionQuery {
+                full_function_name,
+                parameters,
+                return_type,
+                body,
+                used_functions,
+                or_replace,
+            }) => {
+                if !self.schema_exists(&SchemaName::from(&full_function_name.schema())) {
+                    return Err(ExecutionError::SchemaDoesNotExist(full_function_name.schema().to_owned()));
+                }
+                let functions_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, FUNCTIONS_TABLE));
+                let parameters_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, PARAMETERS_TABLE));
+                let function = functions_table
+                    .scan()
+                    .find(|(_key, value)| value[1] == full_function_name.schema() && value[2] == full_function_name.table());
+                let object_id = match &function {
+                    Some(_function) if !or_replace => {
+                        return Err(ExecutionError::FunctionAlreadyExists(
+                            full_function_name.schema().to_owned(),
+                            full_function_name.table().to_owned(),
+                        ))
+                    }
+                    Some((_key, value)) => {
+                        // dependent views and functions call the function as it was declared
+                        let existing = self.function_definition(&full_function_name).unwrap();
+                        if existing.return_type() != return_type {
+                            return Err(ExecutionError::InvalidFunctionDefinition(
+                                "cannot change return type of existing function".to_owned(),
+                            ));
+                        }
+                        if !existing
+                            .parameters()
+                            .iter()
+                            .map(|(_name, sql_type)| sql_type)
+                            .eq(parameters.iter().map(|(_name, sql_type)| sql_type))
+                        {
+                            return Err(ExecutionError::InvalidFunctionDefinition(
+                                "cannot change argument types of existing function".to_owned(),
+                            ));
+                        }
+                        value[6].as_u64()
+                    }
+                    None => self.transaction.next_object_id(),
+                };
+                let record = vec![
+                    BinaryValue::from("IN_MEMORY"),
+                    BinaryValue::from(full_function_name.schema()),
+                    BinaryValue::from(full_function_name.table()),
+                    BinaryValue::from_u64(return_type.type_id()),
+                    BinaryValue::from_u64(return_type.chars_len().unwrap_or_default()),
+                    BinaryValue::from(body),
+                    BinaryValue::from_u64(object_id),
+                ];
+                let function_id = match function {
+                    Some((function_id, _value)) => {
+                        functions_table.write_key(function_id.clone(), Some(record));
+                        function_id
+                    }
+                    None => functions_table.write(record),
+                };
+                for (index, (name, sql_type)) in parameters.iter().enumerate() {
+                    let mut key = function_id.clone();
+                    key.push(BinaryValue::from_u64(index as u64));
+                    parameters_table.write_key(
+                        key,
+                        Some(vec![
+                            BinaryValue::from("IN_MEMORY"),
+                            BinaryValue::from(full_function_name.schema()),
+                            BinaryValue::from(full_function_name.table()),
+                            BinaryValue::from(name.clone()),
+                            BinaryValue::from_u64(sql_type.type_id()),
+                            BinaryValue::from_u64(sql_type.chars_len().unwrap_or_default()),
+                            BinaryValue::from_u64(index as u64),
+                        ]),
+                    );
+                }
+                self.write_function_usage(&full_function_name, FUNCTION_KIND, &used_functions);
+                Ok(ExecutionOutcome::FunctionCreated)
+            }
+            SchemaChange::DropFunctions(DropFunctionsQuery {
+                full_function_names,
+                cascade,
+                if_exists,
+            }) => {
+                for full_function_name in full_function_names {
+                    if !self.schema_exists(&SchemaName::from(&full_function_name.schema())) {
+                        return Err(ExecutionError::SchemaDoesNotExist(full_function_name.schema().to_owned()));
+                    }
+                    match self.function_definition(&full_function_name) {
+                        None if if_exists => {}
+                        None => {
+                            return Err(ExecutionError::FunctionDoesNotExist(
+                                full_function_name.schema().to_owned(),
+                                full_function_name.table().to_owned(),
+                            ))
+                        }
+                        Some(_function_def) => {
+                            if !cascade && !self.function_dependents(&full_function_name).is_empty() {
+                                return Err(ExecutionError::DependentObjectsStillExist(format!("function {}", full_function_name)));
+                            }
+                            self.remove_function(&full_function_name);
+                        }
+                    }
+                }
+                Ok(ExecutionOutcome::FunctionDropped)
+            }
+            SchemaChange::AnalyzeTables(AnalyzeTablesQuery { full_table_names }) => {
+                let full_table_names = if full_table_names.is_empty() {
+                    let mut all_tables = vec![];
+                    for (_key, value) in self
+                        .transaction
+                        .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, TABLES_TABLE))
+                        .scan()
+                    {
+                        let full_table_name = FullTableName::from((&value[1].as_string(), &value[2].as_string()));
+                        if !all_tables.contains(&full_table_name) {
+                            all_tables.push(full_table_name);
+                        }
+                    }
+                    all_tables
+                } else {
+                    full_table_names
+                };
+                for full_table_name in full_table_names {
+                    let column_names = self
+                        .columns_short(&full_table_name)
+                        .into_iter()
+                        .map(|(name, _sql_type)| name)
+                        .collect::<Vec<String>>();
+                    let records = self.transaction.lookup_table_ref(&full_table_name).scan().map(|(_key, value)| value);
+                    let statistics = TableStatistics::collect(&column_names, records);
+                    log::debug!("{} STATISTICS {:?}", full_table_name, statistics);
+                    self.write_statistics(&full_table_name, statistics);
+                }
+                Ok(ExecutionOutcome::TablesAnalyzed)
+            }
+            SchemaChange::TruncateTables(TruncateTablesQuery {
+                full_table_names,
+                restart_identity,
+                ..
+            }) => {
+                let sequences_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, SEQUENCES_TABLE));
+                for full_table_name in full_table_names {
+                    self.transaction.truncate_tree(&full_table_name);
+                    if restart_identity {
+                        for (key, value) in sequences_table.scan().filter(|(_key, value)| {
+                            value[SEQUENCE_SCHEMA] == full_table_name.schema() && value[SEQUENCE_OWNER] == full_table_name.table()
+                        }) {
+                            Sequence::new(
+                                value[SEQUENCE_NAME].as_string(),
+                                sequences_table.clone(),
+                                key,
+                                self.transaction.session_sequences(),
+                            )
+                            .restart();
+                        }
+                    }
+                }
+                Ok(ExecutionOutcome::TablesTruncated)
+            }
         }
     }
 }
+
+fn column_def(row: Vec<BinaryValue>) -> ColumnDef {
+    let identity = if row[7] == "ALWAYS" {
+        Some(ColumnIdentity::Always)
+    } else if row[7] == "BY DEFAULT" {
+        Some(ColumnIdentity::ByDefault)
+    } else {
+        None
+    };
+    ColumnDef::new(
+        row[3].as_string(),
+        SqlType::from_type_id(row[4].as_u64(), row[5].as_u64()),
+        row[6].as_u64() as usize,
+        identity,
+    )
+}
+
+fn identity_sequence_name(full_table_name: &FullTableName, column_name: &str) -> FullTableName {
+    FullTableName::from((&full_table_name.schema(), &format!("{}_{}_seq", full_table_name.table(), column_name)))
+}
+
+fn view_def(row: Vec<BinaryValue>) -> ViewDef {
+    let columns = row[3].as_string();
+    let columns = if columns.is_empty() {
+        vec![]
+    } else {
+        columns.split(", ").map(ToOwned::to_owned).collect()
+    };
+    ViewDef::new(
+        FullTableName::from((&row[1].as_string(), &row[2].as_string())),
+        columns,
+        row[4].as_string(),
+        FullTableName::from((&row[5].as_string(), &row[6].as_string())),
+        row[7] == BinaryValue::from_bool(true),
+    )
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,136 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binary::BinaryValue;
+use std::collections::BTreeMap;
+
+/// Maximum number of most common values kept per column
+pub const MOST_COMMON_VALUES: usize = 10;
+/// Number of equal-depth buckets of a column histogram
+pub const HISTOGRAM_BUCKETS: usize = 10;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TableStatistics {
+    pub rows: usize,
+    pub columns: Vec<ColumnStatistics>,
+}
+
+impl TableStatistics {
+    /// `records` are full table rows where values are placed in column order
+    pub fn collect<I: Iterator<Item = Vec<BinaryValue>>>(column_names: &[String], records: I) -> TableStatistics {
+        let mut rows = 0;
+        let mut nulls = vec![0; column_names.len()];
+        let mut counts = vec![BTreeMap::<BinaryValue, usize>::new(); column_names.len()];
+        for record in records {
+            rows += 1;
+            for (index, value) in record.into_iter().enumerate().take(column_names.len()) {
+                match value {
+                    BinaryValue::Null => nulls[index] += 1,
+                    value => *counts[index].entry(value).or_default() += 1,
+                }
+            }
+        }
+
+        let columns = column_names
+            .iter()
+            .zip(nulls.into_iter().zip(counts))
+            .map(|(name, (nulls, counts))| ColumnStatistics::from_counts(name.clone(), rows, nulls, counts))
+            .collect();
+
+        TableStatistics { rows, columns }
+    }
+
+    pub fn column(&self, name: &str) -> Option<&ColumnStatistics> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColumnStatistics {
+    pub name: String,
+    pub null_fraction: f64,
+    pub distinct: usize,
+    /// values with their fraction of all table rows, most frequent go first
+    pub most_common_values: Vec<(BinaryValue, f64)>,
+    /// bounds of equal-depth buckets over values that are not in `most_common_values`
+    pub histogram_bounds: Vec<BinaryValue>,
+}
+
+impl ColumnStatistics {
+    fn from_counts(name: String, rows: usize, nulls: usize, counts: BTreeMap<BinaryValue, usize>) -> ColumnStatistics {
+        let not_null = rows - nulls;
+        let distinct = counts.len();
+
+        let mut by_frequency = counts.iter().filter(|(_value, count)| **count > 1).collect::<Vec<_>>();
+        by_frequency.sort_by(|(_, left), (_, right)| right.cmp(left));
+        let most_common = by_frequency
+            .into_iter()
+            // when there are more distinct values than could be kept only those above the average are
+            .filter(|(_value, count)| distinct <= MOST_COMMON_VALUES || **count * distinct > not_null)
+            .take(MOST_COMMON_VALUES)
+            .map(|(value, count)| (value.clone(), *count))
+            .collect::<Vec<_>>();
+
+        // bounds are taken at equal steps over ordered rows without the most common values,
+        // the row at a step is found by counts of values so that rows are never materialized
+        let rest = counts
+            .into_iter()
+            .filter(|(value, _count)| !most_common.iter().any(|(common, _)| common == value))
+            .collect::<Vec<_>>();
+        let rest_rows = rest.iter().map(|(_value, count)| count).sum::<usize>();
+        let mut histogram_bounds: Vec<BinaryValue> = vec![];
+        if rest_rows > 1 {
+            let mut values = rest.iter();
+            let mut current = values.next();
+            let mut preceding_rows = 0;
+            for bucket in 0..=HISTOGRAM_BUCKETS {
+                let position = bucket * (rest_rows - 1) / HISTOGRAM_BUCKETS;
+                while let Some((_value, count)) = current {
+                    if position < preceding_rows + count {
+                        break;
+                    }
+                    preceding_rows += count;
+                    current = values.next();
+                }
+                if let Some((bound, _count)) = current {
+                    if histogram_bounds.last() != Some(bound) {
+                        histogram_bounds.push(bound.clone());
+                    }
+                }
+            }
+        }
+        if histogram_bounds.len() < 2 {
+            histogram_bounds.clear();
+        }
+
+        ColumnStatistics {
+            name,
+            null_fraction: fraction(nulls, rows),
+            distinct,
+            most_common_values: most_common.into_iter().map(|(value, count)| (value, fraction(count, rows))).collect(),
+            histogram_bounds,
+        }
+    }
+}
+
+fn fraction(count: usize, rows: usize) -> f64 {
+    if rows == 0 {
+        0.0
+    } else {
+        count as f64 / rows as f64
+    }
+}
+
+#[cfg(test)]
+mod tests;
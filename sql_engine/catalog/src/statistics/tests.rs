@@ -0,0 +1,126 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn columns() -> Vec<String> {
+    vec!["col_1".to_owned(), "col_2".to_owned()]
+}
+
+#[test]
+fn empty_table() {
+    assert_eq!(
+        TableStatistics::collect(&columns(), vec![].into_iter()),
+        TableStatistics {
+            rows: 0,
+            columns: vec![
+                ColumnStatistics {
+                    name: "col_1".to_owned(),
+                    null_fraction: 0.0,
+                    distinct: 0,
+                    most_common_values: vec![],
+                    histogram_bounds: vec![],
+                },
+                ColumnStatistics {
+                    name: "col_2".to_owned(),
+                    null_fraction: 0.0,
+                    distinct: 0,
+                    most_common_values: vec![],
+                    histogram_bounds: vec![],
+                }
+            ]
+        }
+    );
+}
+
+#[test]
+fn null_fraction_and_distinct_values() {
+    let statistics = TableStatistics::collect(
+        &columns(),
+        vec![
+            vec![BinaryValue::from(1i16), BinaryValue::Null],
+            vec![BinaryValue::from(2i16), BinaryValue::Null],
+            vec![BinaryValue::from(3i16), BinaryValue::from("a")],
+            vec![BinaryValue::from(4i16), BinaryValue::Null],
+        ]
+        .into_iter(),
+    );
+
+    assert_eq!(statistics.rows, 4);
+    let col_1 = statistics.column("col_1").unwrap();
+    assert_eq!(col_1.null_fraction, 0.0);
+    assert_eq!(col_1.distinct, 4);
+    let col_2 = statistics.column("col_2").unwrap();
+    assert_eq!(col_2.null_fraction, 0.75);
+    assert_eq!(col_2.distinct, 1);
+}
+
+#[test]
+fn unique_values_go_to_histogram() {
+    let statistics = TableStatistics::collect(&columns()[..1], (0..21i32).map(|value| vec![BinaryValue::from(value)]));
+
+    let column = statistics.column("col_1").unwrap();
+    assert_eq!(column.most_common_values, vec![]);
+    assert_eq!(
+        column.histogram_bounds,
+        (0..=20i32).step_by(2).map(BinaryValue::from).collect::<Vec<BinaryValue>>()
+    );
+}
+
+#[test]
+fn repeated_values_are_most_common() {
+    let records = vec![1i32, 1, 1, 2, 2, 3].into_iter().map(|value| vec![BinaryValue::from(value)]);
+    let statistics = TableStatistics::collect(&columns()[..1], records);
+
+    let column = statistics.column("col_1").unwrap();
+    assert_eq!(
+        column.most_common_values,
+        vec![(BinaryValue::from(1i32), 0.5), (BinaryValue::from(2i32), 2.0 / 6.0)]
+    );
+    assert!(column.histogram_bounds.is_empty());
+}
+
+#[test]
+fn values_seen_once_are_not_most_common() {
+    let records = (0..20i32)
+        .chain(vec![5; 10])
+        .chain(vec![7; 2])
+        .map(|value| vec![BinaryValue::from(value)]);
+    let statistics = TableStatistics::collect(&columns()[..1], records);
+
+    let column = statistics.column("col_1").unwrap();
+    assert_eq!(column.distinct, 20);
+    assert_eq!(
+        column.most_common_values,
+        vec![(BinaryValue::from(5i32), 11.0 / 32.0), (BinaryValue::from(7i32), 3.0 / 32.0)]
+    );
+    assert_eq!(column.histogram_bounds.first(), Some(&BinaryValue::from(0i32)));
+    assert_eq!(column.histogram_bounds.last(), Some(&BinaryValue::from(19i32)));
+}
+
+#[test]
+fn histogram_counts_repeated_values() {
+    let records = (0..30i32).chain(0..30i32).map(|value| vec![BinaryValue::from(value)]);
+    let statistics = TableStatistics::collect(&columns()[..1], records);
+
+    let column = statistics.column("col_1").unwrap();
+    assert_eq!(column.most_common_values, vec![]);
+    assert_eq!(
+        column.histogram_bounds,
+        vec![0i32, 2, 5, 8, 11, 14, 17, 20, 23, 26, 29]
+            .into_iter()
+            .map(BinaryValue::from)
+            .collect::<Vec<BinaryValue>>()
+    );
+}
@@ -0,0 +1,454 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{column_def, CatalogHandler, COLUMNS_TABLE, DEFINITION_SCHEMA, SCHEMATA_TABLE, SEQUENCES_TABLE, TABLES_TABLE, VIEWS_TABLE};
+use binary::BinaryValue;
+use definition::{ColumnDef, ColumnIdentity, FullTableName, TableDef};
+use types::{Num, SqlType, Str};
+
+pub const PG_CATALOG: &str = "pg_catalog";
+pub const INFORMATION_SCHEMA: &str = "information_schema";
+
+const NAME: SqlType = SqlType::Str { len: 63, kind: Str::Var };
+const CHAR: SqlType = SqlType::Str { len: 1, kind: Str::Const };
+const OID: SqlType = SqlType::Num(Num::Integer);
+const INTEGER: SqlType = SqlType::Num(Num::Integer);
+const SMALLINT: SqlType = SqlType::Num(Num::SmallInt);
+const BOOL: SqlType = SqlType::Bool;
+
+/// Read-only views over the catalog that tools and drivers query to discover database objects
+struct SystemView {
+    schema: &'static str,
+    name: &'static str,
+    oid: u64,
+    columns: &'static [(&'static str, SqlType)],
+}
+
+const SYSTEM_VIEWS: &[SystemView] = &[
+    SystemView {
+        schema: PG_CATALOG,
+        name: "pg_namespace",
+        oid: 2615,
+        columns: &[("oid", OID), ("nspname", NAME)],
+    },
+    SystemView {
+        schema: PG_CATALOG,
+        name: "pg_class",
+        oid: 1259,
+        columns: &[
+            ("oid", OID),
+            ("relname", NAME),
+            ("relnamespace", OID),
+            ("relkind", CHAR),
+            ("relnatts", SMALLINT),
+        ],
+    },
+    SystemView {
+        schema: PG_CATALOG,
+        name: "pg_attribute",
+        oid: 1249,
+        columns: &[
+            ("attrelid", OID),
+            ("attname", NAME),
+            ("atttypid", OID),
+            ("attlen", SMALLINT),
+            ("attnum", SMALLINT),
+            ("atttypmod", INTEGER),
+            ("attnotnull", BOOL),
+            ("attidentity", CHAR),
+        ],
+    },
+    SystemView {
+        schema: PG_CATALOG,
+        name: "pg_type",
+        oid: 1247,
+        columns: &[
+            ("oid", OID),
+            ("typname", NAME),
+            ("typnamespace", OID),
+            ("typlen", SMALLINT),
+            ("typtype", CHAR),
+            ("typcategory", CHAR),
+        ],
+    },
+    SystemView {
+        schema: INFORMATION_SCHEMA,
+        name: "tables",
+        oid: 13001,
+        columns: &[
+            ("table_catalog", NAME),
+            ("table_schema", NAME),
+            ("table_name", NAME),
+            ("table_type", NAME),
+        ],
+    },
+    SystemView {
+        schema: INFORMATION_SCHEMA,
+        name: "columns",
+        oid: 13002,
+        columns: &[
+            ("table_catalog", NAME),
+            ("table_schema", NAME),
+            ("table_name", NAME),
+            ("column_name", NAME),
+            ("ordinal_position", INTEGER),
+            ("column_default", NAME),
+            ("is_nullable", NAME),
+            ("data_type", NAME),
+            ("character_maximum_length", INTEGER),
+            ("is_identity", NAME),
+            ("identity_generation", NAME),
+        ],
+    },
+];
+
+// oid, name, length and category of the types as PostgreSQL defines them
+const TYPES: &[(i32, &str, i16, &str)] = &[
+    (16, "bool", 1, "B"),
+    (20, "int8", 8, "N"),
+    (21, "int2", 2, "N"),
+    (23, "int4", 4, "N"),
+    (700, "float4", 4, "N"),
+    (701, "float8", 8, "N"),
+    (1042, "bpchar", -1, "S"),
+    (1043, "varchar", -1, "S"),
+];
+
+pub fn is_system_schema(schema_name: &str) -> bool {
+    schema_name == PG_CATALOG || schema_name == INFORMATION_SCHEMA
+}
+
+pub(crate) fn system_view_definition(full_table_name: &FullTableName) -> Option<TableDef> {
+    SYSTEM_VIEWS
+        .iter()
+        .find(|view| view.schema == full_table_name.schema() && view.name == full_table_name.table())
+        .map(|view| TableDef::new(full_table_name.clone(), view.column_defs()))
+}
+
+impl SystemView {
+    fn column_defs(&self) -> Vec<ColumnDef> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(index, (name, sql_type))| ColumnDef::new((*name).to_owned(), *sql_type, index, None))
+            .collect()
+    }
+}
+
+struct Relation {
+    oid: u64,
+    schema: String,
+    name: String,
+    kind: &'static str,
+    number_of_columns: usize,
+    // empty for views and sequences which column types are not kept in the catalog
+    columns: Vec<ColumnDef>,
+}
+
+impl<'c> CatalogHandler<'c> {
+    /// rewrites records of the system view with the current state of the catalog,
+    /// does nothing for any other table
+    pub fn refresh_system_view(&self, full_table_name: &FullTableName) {
+        let rows = match (full_table_name.schema(), full_table_name.table()) {
+            (PG_CATALOG, "pg_namespace") => self.pg_namespace(),
+            (PG_CATALOG, "pg_class") => self.pg_class(),
+            (PG_CATALOG, "pg_attribute") => self.pg_attribute(),
+            (PG_CATALOG, "pg_type") => self.pg_type(),
+            (INFORMATION_SCHEMA, "tables") => self.information_schema_tables(),
+            (INFORMATION_SCHEMA, "columns") => self.information_schema_columns(),
+            _ => return,
+        };
+        self.transaction.create_tree(full_table_name);
+        let table = self.transaction.lookup_table_ref(full_table_name);
+        for row in rows {
+            table.write(row);
+        }
+    }
+
+    fn schemas(&self) -> Vec<(String, u64)> {
+        self.transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, SCHEMATA_TABLE))
+            .scan()
+            .map(|(_key, value)| (value[1].as_string(), value[2].as_u64()))
+            .collect()
+    }
+
+    fn relations(&self) -> Vec<Relation> {
+        let columns_table = self.transaction.lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, COLUMNS_TABLE));
+        let views = self.views();
+        let mut tables = vec![];
+        let mut indexes = vec![];
+        for (key, value) in self
+            .transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, TABLES_TABLE))
+            .scan()
+        {
+            // index records are kept together with table records and have index name and its columns
+            if value.len() > 4 {
+                indexes.push(value);
+                continue;
+            }
+            let schema = value[1].as_string();
+            let name = value[2].as_string();
+            let materialized = views
+                .iter()
+                .any(|view| view.is_materialized() && view.full_view_name().schema() == schema && view.full_view_name().table() == name);
+            let columns = columns_table
+                .scan()
+                .filter(|(column_key, _value)| column_key.starts_with(&key))
+                .map(|(_key, value)| column_def(value))
+                .collect::<Vec<_>>();
+            tables.push(Relation {
+                oid: value[3].as_u64(),
+                schema,
+                name,
+                kind: if materialized { "m" } else { "r" },
+                number_of_columns: columns.len(),
+                columns,
+            });
+        }
+
+        let indexes = indexes
+            .into_iter()
+            .map(|index| {
+                let table_columns = tables
+                    .iter()
+                    .find(|table| index[1] == table.schema.as_str() && index[2] == table.name.as_str())
+                    .map(|table| table.columns.as_slice())
+                    .unwrap_or_default();
+                let columns = index[4]
+                    .as_string()
+                    .split(", ")
+                    .enumerate()
+                    .filter_map(|(ord, name)| {
+                        table_columns
+                            .iter()
+                            .find(|column| column.has_name(name))
+                            .map(|column| ColumnDef::new(name.to_owned(), column.sql_type(), ord, None))
+                    })
+                    .collect::<Vec<_>>();
+                Relation {
+                    oid: index[5].as_u64(),
+                    schema: index[1].as_string(),
+                    name: index[3].as_string(),
+                    kind: "i",
+                    number_of_columns: columns.len(),
+                    columns,
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut relations = tables;
+        relations.extend(indexes);
+        for view in views.into_iter().filter(|view| !view.is_materialized()) {
+            relations.push(Relation {
+                oid: self.view_object_id(view.full_view_name()),
+                schema: view.full_view_name().schema().to_owned(),
+                name: view.full_view_name().table().to_owned(),
+                kind: "v",
+                number_of_columns: view.columns().len(),
+                columns: vec![],
+            });
+        }
+        for (_key, value) in self
+            .transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, SEQUENCES_TABLE))
+            .scan()
+        {
+            relations.push(Relation {
+                oid: value[10].as_u64(),
+                schema: value[1].as_string(),
+                name: value[2].as_string(),
+                kind: "S",
+                number_of_columns: 0,
+                columns: vec![],
+            });
+        }
+        for view in SYSTEM_VIEWS {
+            relations.push(Relation {
+                oid: view.oid,
+                schema: view.schema.to_owned(),
+                name: view.name.to_owned(),
+                kind: "v",
+                number_of_columns: view.columns.len(),
+                columns: view.column_defs(),
+            });
+        }
+        relations
+    }
+
+    fn view_object_id(&self, full_view_name: &FullTableName) -> u64 {
+        self.transaction
+            .lookup_table_ref(format!("{}.{}", DEFINITION_SCHEMA, VIEWS_TABLE))
+            .scan()
+            .find(|(_key, value)| value[1] == full_view_name.schema() && value[2] == full_view_name.table())
+            .map(|(_key, value)| value[8].as_u64())
+            .unwrap_or_default()
+    }
+
+    fn pg_namespace(&self) -> Vec<Vec<BinaryValue>> {
+        self.schemas()
+            .into_iter()
+            .map(|(name, oid)| vec![BinaryValue::from(oid as i32), BinaryValue::from(name)])
+            .collect()
+    }
+
+    fn pg_class(&self) -> Vec<Vec<BinaryValue>> {
+        let schemas = self.schemas();
+        self.relations()
+            .into_iter()
+            .map(|relation| {
+                vec![
+                    BinaryValue::from(relation.oid as i32),
+                    BinaryValue::from(relation.name),
+                    BinaryValue::from(schema_object_id(&schemas, &relation.schema) as i32),
+                    BinaryValue::from(relation.kind),
+                    BinaryValue::from(relation.number_of_columns as i16),
+                ]
+            })
+            .collect()
+    }
+
+    fn pg_attribute(&self) -> Vec<Vec<BinaryValue>> {
+        let mut rows = vec![];
+        for relation in self.relations() {
+            for column in relation.columns {
+                let sql_type = column.sql_type();
+                let (type_oid, _name, type_len, _category) = pg_type_of(&sql_type);
+                rows.push(vec![
+                    BinaryValue::from(relation.oid as i32),
+                    BinaryValue::from(column.name()),
+                    BinaryValue::from(*type_oid),
+                    BinaryValue::from(*type_len),
+                    BinaryValue::from(column.index() as i16 + 1),
+                    BinaryValue::from(sql_type.chars_len().map(|len| len as i32 + 4).unwrap_or(-1)),
+                    BinaryValue::from(column.identity().is_some()),
+                    BinaryValue::from(match column.identity() {
+                        None => "",
+                        Some(ColumnIdentity::Always) => "a",
+                        Some(ColumnIdentity::ByDefault) => "d",
+                    }),
+                ]);
+            }
+        }
+        rows
+    }
+
+    fn pg_type(&self) -> Vec<Vec<BinaryValue>> {
+        let pg_catalog_oid = schema_object_id(&self.schemas(), PG_CATALOG);
+        TYPES
+            .iter()
+            .map(|(oid, name, len, category)| {
+                vec![
+                    BinaryValue::from(*oid),
+                    BinaryValue::from(*name),
+                    BinaryValue::from(pg_catalog_oid as i32),
+                    BinaryValue::from(*len),
+                    BinaryValue::from("b"),
+                    BinaryValue::from(*category),
+                ]
+            })
+            .collect()
+    }
+
+    fn information_schema_tables(&self) -> Vec<Vec<BinaryValue>> {
+        self.relations()
+            .into_iter()
+            .filter_map(|relation| {
+                let table_type = match relation.kind {
+                    "r" => "BASE TABLE",
+                    "v" => "VIEW",
+                    _ => return None,
+                };
+                Some(vec![
+                    BinaryValue::from("IN_MEMORY"),
+                    BinaryValue::from(relation.schema),
+                    BinaryValue::from(relation.name),
+                    BinaryValue::from(table_type),
+                ])
+            })
+            .collect()
+    }
+
+    fn information_schema_columns(&self) -> Vec<Vec<BinaryValue>> {
+        let mut rows = vec![];
+        for relation in self
+            .relations()
+            .into_iter()
+            .filter(|relation| relation.kind == "r" || relation.kind == "v")
+        {
+            for column in relation.columns {
+                let sql_type = column.sql_type();
+                rows.push(vec![
+                    BinaryValue::from("IN_MEMORY"),
+                    BinaryValue::from(relation.schema.as_str()),
+                    BinaryValue::from(relation.name.as_str()),
+                    BinaryValue::from(column.name()),
+                    BinaryValue::from(column.index() as i32 + 1),
+                    BinaryValue::null(),
+                    BinaryValue::from(if column.identity().is_some() { "NO" } else { "YES" }),
+                    BinaryValue::from(data_type(&sql_type)),
+                    sql_type
+                        .chars_len()
+                        .map(|len| BinaryValue::from(len as i32))
+                        .unwrap_or_else(BinaryValue::null),
+                    BinaryValue::from(if column.identity().is_some() { "YES" } else { "NO" }),
+                    match column.identity() {
+                        None => BinaryValue::null(),
+                        Some(ColumnIdentity::Always) => BinaryValue::from("ALWAYS"),
+                        Some(ColumnIdentity::ByDefault) => BinaryValue::from("BY DEFAULT"),
+                    },
+                ]);
+            }
+        }
+        rows
+    }
+}
+
+fn schema_object_id(schemas: &[(String, u64)], schema_name: &str) -> u64 {
+    schemas
+        .iter()
+        .find(|(name, _oid)| name == schema_name)
+        .map(|(_name, oid)| *oid)
+        .unwrap_or_default()
+}
+
+fn pg_type_of(sql_type: &SqlType) -> &'static (i32, &'static str, i16, &'static str) {
+    let name = match sql_type {
+        SqlType::Bool => "bool",
+        SqlType::Str { kind: Str::Const, .. } => "bpchar",
+        SqlType::Str { kind: Str::Var, .. } => "varchar",
+        SqlType::Num(Num::SmallInt) => "int2",
+        SqlType::Num(Num::Integer) => "int4",
+        SqlType::Num(Num::BigInt) => "int8",
+        SqlType::Num(Num::Real) => "float4",
+        SqlType::Num(Num::Double) => "float8",
+    };
+    TYPES.iter().find(|(_oid, type_name, _len, _category)| *type_name == name).unwrap()
+}
+
+fn data_type(sql_type: &SqlType) -> &'static str {
+    match sql_type {
+        SqlType::Bool => "boolean",
+        SqlType::Str { kind: Str::Const, .. } => "character",
+        SqlType::Str { kind: Str::Var, .. } => "character varying",
+        SqlType::Num(Num::SmallInt) => "smallint",
+        SqlType::Num(Num::Integer) => "integer",
+        SqlType::Num(Num::BigInt) => "bigint",
+        SqlType::Num(Num::Real) => "real",
+        SqlType::Num(Num::Double) => "double precision",
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,213 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::{ColumnInfo, CreateIndexQuery, CreateTableQuery, SchemaChange};
+use storage::Database;
+
+const SCHEMA: &str = "public";
+const TABLE: &str = "table_name";
+
+fn system_view(catalog: &CatalogHandler, schema_name: &str, view_name: &str) -> Vec<Vec<BinaryValue>> {
+    let full_view_name = FullTableName::from((&schema_name, &view_name));
+    catalog.refresh_system_view(&full_view_name);
+    catalog
+        .transaction
+        .lookup_table_ref(&full_view_name)
+        .scan()
+        .map(|(_key, value)| value)
+        .collect()
+}
+
+fn create_table(catalog: &CatalogHandler) {
+    catalog
+        .apply(SchemaChange::CreateTable(CreateTableQuery {
+            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            column_defs: vec![
+                ColumnInfo {
+                    name: "id".to_owned(),
+                    sql_type: SqlType::integer(),
+                    identity: Some(ColumnIdentity::Always),
+                },
+                ColumnInfo {
+                    name: "name".to_owned(),
+                    sql_type: SqlType::var_char(20),
+                    identity: None,
+                },
+            ],
+            if_not_exists: false,
+        }))
+        .unwrap();
+}
+
+fn relation<'r>(pg_class: &'r [Vec<BinaryValue>], name: &str) -> &'r [BinaryValue] {
+    pg_class.iter().find(|row| row[1] == name).unwrap()
+}
+
+#[test]
+fn predefined_schemas() {
+    let database = Database::new("");
+    let catalog = CatalogHandler::from(database.transaction());
+
+    assert_eq!(
+        system_view(&catalog, PG_CATALOG, "pg_namespace"),
+        vec![
+            vec![BinaryValue::from(2200), BinaryValue::from("public")],
+            vec![BinaryValue::from(11), BinaryValue::from("pg_catalog")],
+            vec![BinaryValue::from(13000), BinaryValue::from("information_schema")],
+        ]
+    );
+}
+
+#[test]
+fn system_views_are_described_by_themselves() {
+    let database = Database::new("");
+    let catalog = CatalogHandler::from(database.transaction());
+
+    let pg_class = system_view(&catalog, PG_CATALOG, "pg_class");
+
+    assert_eq!(
+        relation(&pg_class, "pg_class"),
+        vec![
+            BinaryValue::from(1259),
+            BinaryValue::from("pg_class"),
+            BinaryValue::from(11),
+            BinaryValue::from("v"),
+            BinaryValue::from(5i16),
+        ]
+    );
+    assert_eq!(
+        system_view(&catalog, INFORMATION_SCHEMA, "tables").len(),
+        SYSTEM_VIEWS.len(),
+        "only system views exist in a new database"
+    );
+}
+
+#[test]
+fn table_with_its_columns_and_index() {
+    let database = Database::new("");
+    let catalog = CatalogHandler::from(database.transaction());
+    create_table(&catalog);
+    catalog
+        .apply(SchemaChange::CreateIndex(CreateIndexQuery {
+            name: "index_name".to_owned(),
+            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            column_names: vec!["name".to_owned()],
+        }))
+        .unwrap();
+
+    let pg_class = system_view(&catalog, PG_CATALOG, "pg_class");
+    let table = relation(&pg_class, TABLE);
+    assert_eq!(table[2], BinaryValue::from(2200));
+    assert_eq!(table[3], BinaryValue::from("r"));
+    assert_eq!(table[4], BinaryValue::from(2i16));
+    let index = relation(&pg_class, "index_name");
+    assert_eq!(index[3], BinaryValue::from("i"));
+    assert_eq!(index[4], BinaryValue::from(1i16));
+    assert_eq!(relation(&pg_class, "table_name_id_seq")[3], BinaryValue::from("S"));
+
+    let table_attributes = system_view(&catalog, PG_CATALOG, "pg_attribute")
+        .into_iter()
+        .filter(|row| row[0] == table[0])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        table_attributes,
+        vec![
+            vec![
+                table[0].clone(),
+                BinaryValue::from("id"),
+                BinaryValue::from(23),
+                BinaryValue::from(4i16),
+                BinaryValue::from(1i16),
+                BinaryValue::from(-1),
+                BinaryValue::from(true),
+                BinaryValue::from("a"),
+            ],
+            vec![
+                table[0].clone(),
+                BinaryValue::from("name"),
+                BinaryValue::from(1043),
+                BinaryValue::from(-1i16),
+                BinaryValue::from(2i16),
+                BinaryValue::from(24),
+                BinaryValue::from(false),
+                BinaryValue::from(""),
+            ],
+        ]
+    );
+}
+
+#[test]
+fn object_ids_are_stable() {
+    let database = Database::new("");
+    let catalog = CatalogHandler::from(database.transaction());
+    create_table(&catalog);
+
+    let table_oid = relation(&system_view(&catalog, PG_CATALOG, "pg_class"), TABLE)[0].clone();
+    catalog
+        .apply(SchemaChange::CreateTable(CreateTableQuery {
+            full_table_name: FullTableName::from((&SCHEMA, &"other_table")),
+            column_defs: vec![],
+            if_not_exists: false,
+        }))
+        .unwrap();
+    let pg_class = system_view(&catalog, PG_CATALOG, "pg_class");
+
+    assert_eq!(relation(&pg_class, TABLE)[0], table_oid);
+    assert_ne!(relation(&pg_class, "other_table")[0], table_oid);
+}
+
+#[test]
+fn information_schema_columns() {
+    let database = Database::new("");
+    let catalog = CatalogHandler::from(database.transaction());
+    create_table(&catalog);
+
+    let columns = system_view(&catalog, INFORMATION_SCHEMA, "columns")
+        .into_iter()
+        .filter(|row| row[2] == TABLE)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        columns,
+        vec![
+            vec![
+                BinaryValue::from("IN_MEMORY"),
+                BinaryValue::from(SCHEMA),
+                BinaryValue::from(TABLE),
+                BinaryValue::from("id"),
+                BinaryValue::from(1),
+                BinaryValue::null(),
+                BinaryValue::from("NO"),
+                BinaryValue::from("integer"),
+                BinaryValue::null(),
+                BinaryValue::from("YES"),
+                BinaryValue::from("ALWAYS"),
+            ],
+            vec![
+                BinaryValue::from("IN_MEMORY"),
+                BinaryValue::from(SCHEMA),
+                BinaryValue::from(TABLE),
+                BinaryValue::from("name"),
+                BinaryValue::from(2),
+                BinaryValue::null(),
+                BinaryValue::from("YES"),
+                BinaryValue::from("character varying"),
+                BinaryValue::from(20),
+                BinaryValue::from("NO"),
+                BinaryValue::null(),
+            ],
+        ]
+    );
+}
@@ -0,0 +1,50 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::{CreateSchemaQuery, SchemaChange};
+use storage::Database;
+
+fn create_schema(catalog: &CatalogHandler, if_not_exists: bool) -> Result<ExecutionOutcome, ExecutionError> {
+    catalog.apply(SchemaChange::CreateSchema(CreateSchemaQuery {
+        schema_name: SchemaName::from(&"schema_name"),
+        if_not_exists,
+    }))
+}
+
+#[test]
+fn applied_change_advances_catalog_version() {
+    let database = Database::new("");
+    let catalog = CatalogHandler::from(database.transaction());
+    let version = catalog.version();
+
+    assert_eq!(create_schema(&catalog, false), Ok(ExecutionOutcome::SchemaCreated));
+
+    assert!(catalog.version() > version);
+}
+
+#[test]
+fn failed_change_keeps_catalog_version() {
+    let database = Database::new("");
+    let catalog = CatalogHandler::from(database.transaction());
+    assert_eq!(create_schema(&catalog, false), Ok(ExecutionOutcome::SchemaCreated));
+    let version = catalog.version();
+
+    assert_eq!(
+        create_schema(&catalog, false),
+        Err(ExecutionError::SchemaAlreadyExists("schema_name".to_owned()))
+    );
+
+    assert_eq!(catalog.version(), version);
+}
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use definition::{FullTableName, SchemaName};
+use binary::BinaryValue;
+use definition::{ColumnIdentity, FullTableName, SchemaName};
 use query_response::{QueryError, QueryEvent};
 use types::SqlType;
 
@@ -33,6 +34,7 @@ pub struct DropSchemasQuery {
 pub struct ColumnInfo {
     pub name: String,
     pub sql_type: SqlType,
+    pub identity: Option<ColumnIdentity>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -70,6 +72,81 @@ pub struct CreateIndexQuery {
     pub column_names: Vec<String>,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct CreateSequenceQuery {
+    pub full_sequence_name: FullTableName,
+    pub start: i64,
+    pub increment: i64,
+    pub min_value: i64,
+    pub max_value: i64,
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CreateViewQuery {
+    pub full_view_name: FullTableName,
+    pub columns: Vec<String>,
+    pub definition: String,
+    pub source: FullTableName,
+    pub used_functions: Vec<FullTableName>,
+    pub or_replace: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CreateMaterializedViewQuery {
+    pub full_view_name: FullTableName,
+    pub column_defs: Vec<ColumnInfo>,
+    pub definition: String,
+    pub source: FullTableName,
+    pub used_functions: Vec<FullTableName>,
+    pub rows: Vec<Vec<BinaryValue>>,
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RefreshMaterializedViewQuery {
+    pub full_view_name: FullTableName,
+    pub rows: Vec<Vec<BinaryValue>>,
+    pub concurrently: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DropViewsQuery {
+    pub full_view_names: Vec<FullTableName>,
+    pub cascade: bool,
+    pub if_exists: bool,
+    pub materialized: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CreateFunctionQuery {
+    pub full_function_name: FullTableName,
+    pub parameters: Vec<(String, SqlType)>,
+    pub return_type: SqlType,
+    pub body: String,
+    pub used_functions: Vec<FullTableName>,
+    pub or_replace: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DropFunctionsQuery {
+    pub full_function_names: Vec<FullTableName>,
+    pub cascade: bool,
+    pub if_exists: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AnalyzeTablesQuery {
+    pub full_table_names: Vec<FullTableName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TruncateTablesQuery {
+    pub full_table_names: Vec<FullTableName>,
+    pub restart_identity: bool,
+    pub cascade: bool,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum SchemaChange {
     CreateSchema(CreateSchemaQuery),
@@ -77,6 +154,15 @@ pub enum SchemaChange {
     CreateTable(CreateTableQuery),
     DropTables(DropTablesQuery),
     CreateIndex(CreateIndexQuery),
+    CreateSequence(CreateSequenceQuery),
+    CreateView(CreateViewQuery),
+    DropViews(DropViewsQuery),
+    CreateMaterializedView(CreateMaterializedViewQuery),
+    RefreshMaterializedView(RefreshMaterializedViewQuery),
+    CreateFunction(CreateFunctionQuery),
+    DropFunctions(DropFunctionsQuery),
+    AnalyzeTables(AnalyzeTablesQuery),
+    TruncateTables(TruncateTablesQuery),
 }
 
 #[derive(Debug, PartialEq)]
@@ -86,6 +172,16 @@ pub enum ExecutionOutcome {
     TableCreated,
     TableDropped,
     IndexCreated,
+    SequenceCreated,
+    ViewCreated,
+    ViewDropped,
+    MaterializedViewCreated(usize),
+    MaterializedViewRefreshed,
+    MaterializedViewDropped,
+    FunctionCreated,
+    FunctionDropped,
+    TablesAnalyzed,
+    TablesTruncated,
 }
 
 impl From<ExecutionOutcome> for QueryEvent {
@@ -96,6 +192,16 @@ impl From<ExecutionOutcome> for QueryEvent {
             ExecutionOutcome::TableCreated => QueryEvent::TableCreated,
             ExecutionOutcome::TableDropped => QueryEvent::TableDropped,
             ExecutionOutcome::IndexCreated => QueryEvent::IndexCreated,
+            ExecutionOutcome::SequenceCreated => QueryEvent::SequenceCreated,
+            ExecutionOutcome::ViewCreated => QueryEvent::ViewCreated,
+            ExecutionOutcome::ViewDropped => QueryEvent::ViewDropped,
+            ExecutionOutcome::MaterializedViewCreated(records) => QueryEvent::MaterializedViewCreated(records),
+            ExecutionOutcome::MaterializedViewRefreshed => QueryEvent::MaterializedViewRefreshed,
+            ExecutionOutcome::MaterializedViewDropped => QueryEvent::MaterializedViewDropped,
+            ExecutionOutcome::FunctionCreated => QueryEvent::FunctionCreated,
+            ExecutionOutcome::FunctionDropped => QueryEvent::FunctionDropped,
+            ExecutionOutcome::TablesAnalyzed => QueryEvent::TablesAnalyzed,
+            ExecutionOutcome::TablesTruncated => QueryEvent::TablesTruncated,
         }
     }
 }
@@ -106,7 +212,15 @@ pub enum ExecutionError {
     SchemaDoesNotExist(String),
     TableAlreadyExists(String, String),
     TableDoesNotExist(String, String),
+    SequenceAlreadyExists(String, String),
+    ViewAlreadyExists(String, String),
+    ViewDoesNotExist(String, String),
+    MaterializedViewDoesNotExist(String, String),
+    FunctionAlreadyExists(String, String),
+    FunctionDoesNotExist(String, String),
+    InvalidFunctionDefinition(String),
     SchemaHasDependentObjects(String),
+    DependentObjectsStillExist(String),
     ColumnNotFound(String),
 }
 
@@ -117,7 +231,15 @@ impl From<ExecutionError> for QueryError {
             ExecutionError::SchemaDoesNotExist(schema) => QueryError::schema_does_not_exist(schema),
             ExecutionError::TableAlreadyExists(schema, table) => QueryError::table_already_exists(schema + "." + table.as_str()),
             ExecutionError::TableDoesNotExist(schema, table) => QueryError::table_does_not_exist(schema + "." + table.as_str()),
+            ExecutionError::SequenceAlreadyExists(schema, sequence) => QueryError::sequence_already_exists(schema + "." + sequence.as_str()),
+            ExecutionError::ViewAlreadyExists(schema, view) => QueryError::view_already_exists(schema + "." + view.as_str()),
+            ExecutionError::ViewDoesNotExist(schema, view) => QueryError::view_does_not_exist(schema + "." + view.as_str()),
+            ExecutionError::MaterializedViewDoesNotExist(schema, view) => QueryError::materialized_view_does_not_exist(schema + "." + view.as_str()),
+            ExecutionError::FunctionAlreadyExists(schema, function) => QueryError::function_already_exists(schema + "." + function.as_str()),
+            ExecutionError::FunctionDoesNotExist(schema, function) => QueryError::function_does_not_exist(schema + "." + function.as_str()),
+            ExecutionError::InvalidFunctionDefinition(message) => QueryError::invalid_function_definition(message),
             ExecutionError::SchemaHasDependentObjects(schema) => QueryError::schema_has_dependent_objects(schema),
+            ExecutionError::DependentObjectsStillExist(object) => QueryError::dependent_objects_still_exist(object),
             ExecutionError::ColumnNotFound(column) => QueryError::column_does_not_exist(column),
         }
     }
@@ -0,0 +1,385 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use data_manipulation_query_result::QueryExecutionError;
+use scalar::ScalarValue;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+use types::SqlTypeFamily;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ScalarFunction {
+    Lower,
+    Upper,
+    Length,
+    Substring,
+    BTrim,
+    LTrim,
+    RTrim,
+    Position,
+    Replace,
+    Concat,
+    Round,
+    Floor,
+    Ceil,
+    Power,
+    Mod,
+    Now,
+    LikeEscape,
+}
+
+impl ScalarFunction {
+    /// stable functions return the same result within a transaction, so they are evaluated
+    /// every time when query is executed and never folded into constant
+    pub fn is_stable(&self) -> bool {
+        matches!(self, ScalarFunction::Now)
+    }
+
+    /// `transaction_timestamp` is microseconds since the Unix epoch at which the current transaction started
+    pub fn eval(self, args: Vec<ScalarValue>, transaction_timestamp: i64) -> Result<ScalarValue, QueryExecutionError> {
+        // as in PostgreSQL all functions except `concat` are strict and return NULL on NULL argument
+        if self != ScalarFunction::Concat && args.contains(&ScalarValue::Null) {
+            return Ok(ScalarValue::Null);
+        }
+        match (self, args.as_slice()) {
+            (ScalarFunction::Lower, [ScalarValue::String(value)]) => Ok(ScalarValue::String(value.to_lowercase())),
+            (ScalarFunction::Upper, [ScalarValue::String(value)]) => Ok(ScalarValue::String(value.to_uppercase())),
+            (ScalarFunction::Length, [ScalarValue::String(value)]) => Ok(integer(value.chars().count())),
+            (ScalarFunction::Substring, [ScalarValue::String(value), ScalarValue::Num { value: start, .. }]) => {
+                substring(value, to_i64(self, start, 2)?, None)
+            }
+            (
+                ScalarFunction::Substring,
+                [ScalarValue::String(value), ScalarValue::Num { value: start, .. }, ScalarValue::Num { value: length, .. }],
+            ) => substring(value, to_i64(self, start, 2)?, Some(to_i64(self, length, 3)?)),
+            (ScalarFunction::BTrim, [ScalarValue::String(value)]) => Ok(ScalarValue::String(value.trim_matches(' ').to_owned())),
+            (ScalarFunction::BTrim, [ScalarValue::String(value), ScalarValue::String(chars)]) => {
+                Ok(ScalarValue::String(value.trim_matches(|c| chars.contains(c)).to_owned()))
+            }
+            (ScalarFunction::LTrim, [ScalarValue::String(value)]) => Ok(ScalarValue::String(value.trim_start_matches(' ').to_owned())),
+            (ScalarFunction::LTrim, [ScalarValue::String(value), ScalarValue::String(chars)]) => {
+                Ok(ScalarValue::String(value.trim_start_matches(|c| chars.contains(c)).to_owned()))
+            }
+            (ScalarFunction::RTrim, [ScalarValue::String(value)]) => Ok(ScalarValue::String(value.trim_end_matches(' ').to_owned())),
+            (ScalarFunction::RTrim, [ScalarValue::String(value), ScalarValue::String(chars)]) => {
+                Ok(ScalarValue::String(value.trim_end_matches(|c| chars.contains(c)).to_owned()))
+            }
+            (ScalarFunction::Position, [ScalarValue::String(value), ScalarValue::String(substring)]) => match value.find(substring.as_str()) {
+                Some(byte_index) => Ok(integer(value[..byte_index].chars().count() + 1)),
+                None => Ok(integer(0)),
+            },
+            (ScalarFunction::Replace, [ScalarValue::String(value), ScalarValue::String(from), ScalarValue::String(to)]) => {
+                if from.is_empty() {
+                    Ok(ScalarValue::String(value.clone()))
+                } else {
+                    Ok(ScalarValue::String(value.replace(from.as_str(), to)))
+                }
+            }
+            (ScalarFunction::Concat, args) => Ok(ScalarValue::String(
+                args.iter().filter(|arg| arg != &&ScalarValue::Null).map(ScalarValue::as_text).collect(),
+            )),
+            (ScalarFunction::Round, [ScalarValue::Num { value, .. }]) => Ok(double(value.round(0))),
+            (ScalarFunction::Round, [ScalarValue::Num { value, .. }, ScalarValue::Num { value: digits, .. }]) => {
+                Ok(double(value.round(to_i64(self, digits, 2)?)))
+            }
+            (ScalarFunction::Floor, [ScalarValue::Num { value, .. }]) => {
+                let truncated = value.with_scale(0);
+                if &truncated > value {
+                    Ok(double(truncated - BigDecimal::from(1)))
+                } else {
+                    Ok(double(truncated))
+                }
+            }
+            (ScalarFunction::Ceil, [ScalarValue::Num { value, .. }]) => {
+                let truncated = value.with_scale(0);
+                if &truncated < value {
+                    Ok(double(truncated + BigDecimal::from(1)))
+                } else {
+                    Ok(double(truncated))
+                }
+            }
+            (ScalarFunction::Power, [ScalarValue::Num { value: base, .. }, ScalarValue::Num { value: exponent, .. }]) => {
+                if base.is_zero() && exponent < &BigDecimal::zero() || base < &BigDecimal::zero() && !exponent.is_integer() {
+                    return Err(QueryExecutionError::InvalidArgumentForPowerFunction);
+                }
+                let result = base.to_f64().unwrap().powf(exponent.to_f64().unwrap());
+                match BigDecimal::from_str(&result.to_string()) {
+                    Ok(value) if result.is_finite() => Ok(double(value)),
+                    _ => Err(QueryExecutionError::out_of_range(SqlTypeFamily::Double, self, 1)),
+                }
+            }
+            (
+                ScalarFunction::Mod,
+                [ScalarValue::Num {
+                    value: left,
+                    type_family: left_type,
+                }, ScalarValue::Num {
+                    value: right,
+                    type_family: right_type,
+                }],
+            ) => {
+                if right.is_zero() {
+                    return Err(QueryExecutionError::DivisionByZero);
+                }
+                Ok(ScalarValue::Num {
+                    value: left % right,
+                    type_family: left_type.compare(right_type).unwrap_or(SqlTypeFamily::BigInt),
+                })
+            }
+            (ScalarFunction::Now, []) => Ok(ScalarValue::TimestampTz(transaction_timestamp)),
+            (ScalarFunction::LikeEscape, [ScalarValue::String(pattern), ScalarValue::String(escape)]) => like_escape(pattern, escape),
+            (function, args) => Err(QueryExecutionError::function_does_not_exist(format!(
+                "{}({})",
+                function,
+                args.iter()
+                    .map(|arg| arg.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+}
+
+impl Display for ScalarFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScalarFunction::Lower => write!(f, "lower"),
+            ScalarFunction::Upper => write!(f, "upper"),
+            ScalarFunction::Length => write!(f, "length"),
+            ScalarFunction::Substring => write!(f, "substring"),
+            ScalarFunction::BTrim => write!(f, "btrim"),
+            ScalarFunction::LTrim => write!(f, "ltrim"),
+            ScalarFunction::RTrim => write!(f, "rtrim"),
+            ScalarFunction::Position => write!(f, "position"),
+            ScalarFunction::Replace => write!(f, "replace"),
+            ScalarFunction::Concat => write!(f, "concat"),
+            ScalarFunction::Round => write!(f, "round"),
+            ScalarFunction::Floor => write!(f, "floor"),
+            ScalarFunction::Ceil => write!(f, "ceil"),
+            ScalarFunction::Power => write!(f, "power"),
+            ScalarFunction::Mod => write!(f, "mod"),
+            ScalarFunction::Now => write!(f, "now"),
+            ScalarFunction::LikeEscape => write!(f, "like_escape"),
+        }
+    }
+}
+
+fn integer(value: usize) -> ScalarValue {
+    ScalarValue::Num {
+        value: BigDecimal::from(value as i64),
+        type_family: SqlTypeFamily::Integer,
+    }
+}
+
+fn double(value: BigDecimal) -> ScalarValue {
+    ScalarValue::Num {
+        value,
+        type_family: SqlTypeFamily::Double,
+    }
+}
+
+fn to_i64(function: ScalarFunction, value: &BigDecimal, arg_index: usize) -> Result<i64, QueryExecutionError> {
+    value
+        .round(0)
+        .to_i64()
+        .ok_or_else(|| QueryExecutionError::out_of_range(SqlTypeFamily::Integer, function, arg_index))
+}
+
+// characters are counted from 1 and the start could be out of the string bounds
+fn substring(value: &str, start: i64, length: Option<i64>) -> Result<ScalarValue, QueryExecutionError> {
+    let end = match length {
+        Some(length) if length < 0 => return Err(QueryExecutionError::NegativeSubstringLength),
+        Some(length) => start.saturating_add(length),
+        None => i64::MAX,
+    };
+    let start = start.max(1);
+    if end <= start {
+        return Ok(ScalarValue::String(String::new()));
+    }
+    Ok(ScalarValue::String(
+        value.chars().skip((start - 1) as usize).take((end - start) as usize).collect(),
+    ))
+}
+
+// rewrites the pattern to use `\` as the escape character, an empty escape string disables escaping
+fn like_escape(pattern: &str, escape: &str) -> Result<ScalarValue, QueryExecutionError> {
+    let mut escape_chars = escape.chars();
+    let escape = match (escape_chars.next(), escape_chars.next()) {
+        (None, _) => None,
+        (Some(escape), None) => Some(escape),
+        _ => return Err(QueryExecutionError::InvalidEscapeCharacter),
+    };
+    let mut result = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if Some(c) == escape {
+            match chars.next() {
+                Some(escaped) => {
+                    result.push('\\');
+                    result.push(escaped);
+                }
+                None => {
+                    return Err(QueryExecutionError::InvalidEscapeSequence(
+                        "LIKE pattern must not end with escape character".to_owned(),
+                    ))
+                }
+            }
+        } else if c == '\\' {
+            result.push_str("\\\\");
+        } else {
+            result.push(c);
+        }
+    }
+    Ok(ScalarValue::String(result))
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Params {
+    Exact(Vec<SqlTypeFamily>),
+    Variadic,
+}
+
+impl Params {
+    fn accepts(&self, arity: usize) -> bool {
+        match self {
+            Params::Exact(params) => params.len() == arity,
+            Params::Variadic => true,
+        }
+    }
+
+    // arguments of unknown type, e.g. string literals or NULLs, match any parameter
+    fn matches(&self, arg_types: &[Option<SqlTypeFamily>], convert: fn(&SqlTypeFamily, &SqlTypeFamily) -> bool) -> bool {
+        match self {
+            Params::Exact(params) => {
+                params.len() == arg_types.len()
+                    && params
+                        .iter()
+                        .zip(arg_types.iter())
+                        .all(|(param, arg)| arg.as_ref().map(|arg| convert(arg, param)).unwrap_or(true))
+            }
+            Params::Variadic => true,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Overload {
+    function: ScalarFunction,
+    params: Params,
+    returns: SqlTypeFamily,
+}
+
+/// Built-in scalar functions looked up by name and overloaded by argument types
+pub struct FunctionRegistry {
+    functions: HashMap<&'static str, Vec<Overload>>,
+}
+
+impl Default for FunctionRegistry {
+    fn default() -> FunctionRegistry {
+        use SqlTypeFamily::*;
+
+        let mut registry = FunctionRegistry { functions: HashMap::new() };
+        registry.register("lower", ScalarFunction::Lower, vec![String], String);
+        registry.register("upper", ScalarFunction::Upper, vec![String], String);
+        for name in &["length", "char_length"] {
+            registry.register(name, ScalarFunction::Length, vec![String], Integer);
+        }
+        for name in &["substring", "substr"] {
+            registry.register(name, ScalarFunction::Substring, vec![String, Integer], String);
+            registry.register(name, ScalarFunction::Substring, vec![String, Integer, Integer], String);
+        }
+        // `TRIM([BOTH | LEADING | TRAILING] [characters] FROM string)` is parsed into these functions
+        for (name, function) in &[
+            ("btrim", ScalarFunction::BTrim),
+            ("ltrim", ScalarFunction::LTrim),
+            ("rtrim", ScalarFunction::RTrim),
+        ] {
+            registry.register(name, *function, vec![String], String);
+            registry.register(name, *function, vec![String, String], String);
+        }
+        // `POSITION(substring IN string)` is parsed into `position(string, substring)`
+        for name in &["position", "strpos"] {
+            registry.register(name, ScalarFunction::Position, vec![String, String], Integer);
+        }
+        registry.register("replace", ScalarFunction::Replace, vec![String, String, String], String);
+        registry.register_variadic("concat", ScalarFunction::Concat, String);
+        registry.register("round", ScalarFunction::Round, vec![Double], Double);
+        registry.register("round", ScalarFunction::Round, vec![Double, Integer], Double);
+        registry.register("floor", ScalarFunction::Floor, vec![Double], Double);
+        for name in &["ceil", "ceiling"] {
+            registry.register(name, ScalarFunction::Ceil, vec![Double], Double);
+        }
+        for name in &["power", "pow"] {
+            registry.register(name, ScalarFunction::Power, vec![Double, Double], Double);
+        }
+        for type_family in &[SmallInt, Integer, BigInt] {
+            registry.register("mod", ScalarFunction::Mod, vec![*type_family, *type_family], *type_family);
+        }
+        registry.register("now", ScalarFunction::Now, vec![], TimestampTz);
+        // `LIKE pattern ESCAPE escape` is parsed into `LIKE like_escape(pattern, escape)`
+        registry.register("like_escape", ScalarFunction::LikeEscape, vec![String, String], String);
+        registry
+    }
+}
+
+impl FunctionRegistry {
+    fn register(&mut self, name: &'static str, function: ScalarFunction, params: Vec<SqlTypeFamily>, returns: SqlTypeFamily) {
+        self.functions.entry(name).or_default().push(Overload {
+            function,
+            params: Params::Exact(params),
+            returns,
+        });
+    }
+
+    fn register_variadic(&mut self, name: &'static str, function: ScalarFunction, returns: SqlTypeFamily) {
+        self.functions.entry(name).or_default().push(Overload {
+            function,
+            params: Params::Variadic,
+            returns,
+        });
+    }
+
+    pub fn contains(&self, name: &str, arity: usize) -> bool {
+        self.functions
+            .get(name)
+            .map(|overloads| overloads.iter().any(|overload| overload.params.accepts(arity)))
+            .unwrap_or(false)
+    }
+
+    /// resolves function overload and its return type, overload with exactly matching parameters
+    /// is preferred over one to which arguments could be implicitly converted;
+    /// when no overload matches argument types the first one with the same number of parameters
+    /// is returned, so that mismatched arguments are reported when the function is evaluated
+    pub fn resolve(&self, name: &str, arg_types: &[Option<SqlTypeFamily>]) -> Option<(ScalarFunction, SqlTypeFamily)> {
+        let overloads = self.functions.get(name)?;
+        overloads
+            .iter()
+            .find(|overload| overload.params.matches(arg_types, |arg, param| arg == param))
+            .or_else(|| overloads.iter().find(|overload| overload.params.matches(arg_types, implicitly_converts)))
+            .or_else(|| overloads.iter().find(|overload| overload.params.accepts(arg_types.len())))
+            .map(|overload| (overload.function, overload.returns))
+    }
+}
+
+// numbers are converted to wider numeric types, e.g. `smallint` to `integer` or `integer` to `double precision`
+fn implicitly_converts(arg: &SqlTypeFamily, param: &SqlTypeFamily) -> bool {
+    matches!(arg.compare(param), Ok(ty) if &ty == param)
+}
+
+#[cfg(test)]
+mod tests;
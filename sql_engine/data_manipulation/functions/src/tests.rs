@@ -0,0 +1,278 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn string(value: &str) -> ScalarValue {
+    ScalarValue::String(value.to_owned())
+}
+
+fn num(value: i64, type_family: SqlTypeFamily) -> ScalarValue {
+    ScalarValue::Num {
+        value: BigDecimal::from(value),
+        type_family,
+    }
+}
+
+#[cfg(test)]
+mod resolution {
+    use super::*;
+
+    #[test]
+    fn unknown_function() {
+        let registry = FunctionRegistry::default();
+
+        assert!(!registry.contains("unknown", 0));
+        assert_eq!(registry.resolve("unknown", &[]), None);
+    }
+
+    #[test]
+    fn wrong_number_of_arguments() {
+        let registry = FunctionRegistry::default();
+
+        assert!(!registry.contains("lower", 2));
+        assert_eq!(registry.resolve("lower", &[None, None]), None);
+    }
+
+    #[test]
+    fn exact_match() {
+        let registry = FunctionRegistry::default();
+
+        assert_eq!(
+            registry.resolve("mod", &[Some(SqlTypeFamily::SmallInt), Some(SqlTypeFamily::SmallInt)]),
+            Some((ScalarFunction::Mod, SqlTypeFamily::SmallInt))
+        );
+    }
+
+    #[test]
+    fn implicit_conversion_to_wider_type() {
+        let registry = FunctionRegistry::default();
+
+        assert_eq!(
+            registry.resolve("mod", &[Some(SqlTypeFamily::SmallInt), Some(SqlTypeFamily::BigInt)]),
+            Some((ScalarFunction::Mod, SqlTypeFamily::BigInt))
+        );
+        assert_eq!(
+            registry.resolve("round", &[Some(SqlTypeFamily::Integer)]),
+            Some((ScalarFunction::Round, SqlTypeFamily::Double))
+        );
+    }
+
+    #[test]
+    fn unknown_argument_types() {
+        let registry = FunctionRegistry::default();
+
+        assert_eq!(
+            registry.resolve("substring", &[None, Some(SqlTypeFamily::SmallInt)]),
+            Some((ScalarFunction::Substring, SqlTypeFamily::String))
+        );
+    }
+
+    #[test]
+    fn variadic_function() {
+        let registry = FunctionRegistry::default();
+
+        assert!(registry.contains("concat", 0));
+        assert_eq!(
+            registry.resolve("concat", &[Some(SqlTypeFamily::String), Some(SqlTypeFamily::Integer), None]),
+            Some((ScalarFunction::Concat, SqlTypeFamily::String))
+        );
+    }
+
+    #[test]
+    fn mismatched_argument_types() {
+        let registry = FunctionRegistry::default();
+
+        assert_eq!(
+            registry.resolve("lower", &[Some(SqlTypeFamily::Integer)]),
+            Some((ScalarFunction::Lower, SqlTypeFamily::String))
+        );
+    }
+}
+
+#[cfg(test)]
+mod string_functions {
+    use super::*;
+
+    #[test]
+    fn change_case() {
+        assert_eq!(ScalarFunction::Lower.eval(vec![string("AbC")], 0), Ok(string("abc")));
+        assert_eq!(ScalarFunction::Upper.eval(vec![string("AbC")], 0), Ok(string("ABC")));
+    }
+
+    #[test]
+    fn length() {
+        assert_eq!(ScalarFunction::Length.eval(vec![string("añb")], 0), Ok(num(3, SqlTypeFamily::Integer)));
+    }
+
+    #[test]
+    fn substring() {
+        assert_eq!(
+            ScalarFunction::Substring.eval(vec![string("abcdef"), num(3, SqlTypeFamily::Integer)], 0),
+            Ok(string("cdef"))
+        );
+        assert_eq!(
+            ScalarFunction::Substring.eval(vec![string("abcdef"), num(2, SqlTypeFamily::Integer), num(3, SqlTypeFamily::Integer)], 0),
+            Ok(string("bcd"))
+        );
+        assert_eq!(
+            ScalarFunction::Substring.eval(vec![string("abcdef"), num(0, SqlTypeFamily::Integer), num(2, SqlTypeFamily::Integer)], 0),
+            Ok(string("a"))
+        );
+        assert_eq!(
+            ScalarFunction::Substring.eval(vec![string("abcdef"), num(1, SqlTypeFamily::Integer), num(-1, SqlTypeFamily::Integer)], 0),
+            Err(QueryExecutionError::NegativeSubstringLength)
+        );
+    }
+
+    #[test]
+    fn trim() {
+        assert_eq!(ScalarFunction::BTrim.eval(vec![string("  abc  ")], 0), Ok(string("abc")));
+        assert_eq!(ScalarFunction::LTrim.eval(vec![string("  abc  ")], 0), Ok(string("abc  ")));
+        assert_eq!(ScalarFunction::RTrim.eval(vec![string("  abc  ")], 0), Ok(string("  abc")));
+        assert_eq!(ScalarFunction::BTrim.eval(vec![string("xyabcyx"), string("xy")], 0), Ok(string("abc")));
+    }
+
+    #[test]
+    fn position() {
+        assert_eq!(
+            ScalarFunction::Position.eval(vec![string("añbc"), string("bc")], 0),
+            Ok(num(3, SqlTypeFamily::Integer))
+        );
+        assert_eq!(
+            ScalarFunction::Position.eval(vec![string("abc"), string("d")], 0),
+            Ok(num(0, SqlTypeFamily::Integer))
+        );
+    }
+
+    #[test]
+    fn replace() {
+        assert_eq!(
+            ScalarFunction::Replace.eval(vec![string("abcabc"), string("b"), string("xx")], 0),
+            Ok(string("axxcaxxc"))
+        );
+    }
+
+    #[test]
+    fn like_escape() {
+        assert_eq!(
+            ScalarFunction::LikeEscape.eval(vec![string("a#%b\\c"), string("#")], 0),
+            Ok(string("a\\%b\\\\c"))
+        );
+        assert_eq!(ScalarFunction::LikeEscape.eval(vec![string("a\\%"), string("")], 0), Ok(string("a\\\\%")));
+        assert_eq!(
+            ScalarFunction::LikeEscape.eval(vec![string("abc#"), string("#")], 0),
+            Err(QueryExecutionError::InvalidEscapeSequence(
+                "LIKE pattern must not end with escape character".to_owned()
+            ))
+        );
+        assert_eq!(
+            ScalarFunction::LikeEscape.eval(vec![string("abc"), string("##")], 0),
+            Err(QueryExecutionError::InvalidEscapeCharacter)
+        );
+    }
+
+    #[test]
+    fn concat_skips_nulls() {
+        assert_eq!(
+            ScalarFunction::Concat.eval(
+                vec![string("a"), ScalarValue::Null, num(1, SqlTypeFamily::Integer), ScalarValue::Bool(true)],
+                0
+            ),
+            Ok(string("a1t"))
+        );
+    }
+
+    #[test]
+    fn null_argument() {
+        assert_eq!(ScalarFunction::Upper.eval(vec![ScalarValue::Null], 0), Ok(ScalarValue::Null));
+    }
+
+    #[test]
+    fn mismatched_argument_type() {
+        assert_eq!(
+            ScalarFunction::Lower.eval(vec![num(1, SqlTypeFamily::Integer)], 0),
+            Err(QueryExecutionError::function_does_not_exist("lower(integer)"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod math_functions {
+    use super::*;
+
+    fn double(value: &str) -> ScalarValue {
+        ScalarValue::Num {
+            value: BigDecimal::from_str(value).unwrap(),
+            type_family: SqlTypeFamily::Double,
+        }
+    }
+
+    #[test]
+    fn round() {
+        assert_eq!(ScalarFunction::Round.eval(vec![double("2.5")], 0), Ok(double("3")));
+        assert_eq!(ScalarFunction::Round.eval(vec![double("-2.5")], 0), Ok(double("-3")));
+        assert_eq!(
+            ScalarFunction::Round.eval(vec![double("3.14159"), num(2, SqlTypeFamily::Integer)], 0),
+            Ok(double("3.14"))
+        );
+    }
+
+    #[test]
+    fn floor_and_ceil() {
+        assert_eq!(ScalarFunction::Floor.eval(vec![double("2.7")], 0), Ok(double("2")));
+        assert_eq!(ScalarFunction::Floor.eval(vec![double("-2.3")], 0), Ok(double("-3")));
+        assert_eq!(ScalarFunction::Ceil.eval(vec![double("2.3")], 0), Ok(double("3")));
+        assert_eq!(ScalarFunction::Ceil.eval(vec![double("-2.7")], 0), Ok(double("-2")));
+    }
+
+    #[test]
+    fn power() {
+        assert_eq!(
+            ScalarFunction::Power.eval(vec![num(2, SqlTypeFamily::Integer), num(10, SqlTypeFamily::Integer)], 0),
+            Ok(double("1024"))
+        );
+        assert_eq!(
+            ScalarFunction::Power.eval(vec![num(0, SqlTypeFamily::Integer), num(-1, SqlTypeFamily::Integer)], 0),
+            Err(QueryExecutionError::InvalidArgumentForPowerFunction)
+        );
+    }
+
+    #[test]
+    fn modulo() {
+        assert_eq!(
+            ScalarFunction::Mod.eval(vec![num(7, SqlTypeFamily::SmallInt), num(3, SqlTypeFamily::Integer)], 0),
+            Ok(num(1, SqlTypeFamily::Integer))
+        );
+        assert_eq!(
+            ScalarFunction::Mod.eval(vec![num(7, SqlTypeFamily::SmallInt), num(0, SqlTypeFamily::SmallInt)], 0),
+            Err(QueryExecutionError::DivisionByZero)
+        );
+    }
+}
+
+#[test]
+fn now_is_stable() {
+    assert!(ScalarFunction::Now.is_stable());
+    assert!(!ScalarFunction::Upper.is_stable());
+}
+
+#[test]
+fn now_is_transaction_timestamp() {
+    assert_eq!(ScalarFunction::Now.eval(vec![], 42), Ok(ScalarValue::TimestampTz(42)));
+    assert_eq!(
+        FunctionRegistry::default().resolve("now", &[]),
+        Some((ScalarFunction::Now, SqlTypeFamily::TimestampTz))
+    );
+}
@@ -14,10 +14,12 @@
 
 use bigdecimal::{BigDecimal, ToPrimitive, Zero};
 use data_manipulation_query_result::QueryExecutionError;
-use query_ast::{BinaryOperator, UnaryOperator};
+use query_ast::{BinaryOperator, SetOperator as AstSetOperator, UnaryOperator, WindowFrame as AstWindowFrame, WindowFrameBound, WindowFrameUnits};
 use regex::Regex;
 use scalar::ScalarValue;
 use std::{
+    cell::RefCell,
+    collections::HashMap,
     fmt::{self, Debug, Display, Formatter},
     str::FromStr,
 };
@@ -34,13 +36,14 @@ pub enum BiArithmetic {
 }
 
 impl BiArithmetic {
-    fn eval(&self, left: BigDecimal, right: BigDecimal) -> BigDecimal {
+    fn eval(&self, left: BigDecimal, right: BigDecimal) -> Result<BigDecimal, QueryExecutionError> {
         match self {
-            BiArithmetic::Add => left + right,
-            BiArithmetic::Sub => left - right,
-            BiArithmetic::Mul => left * right,
-            BiArithmetic::Div => left / right,
-            BiArithmetic::Mod => left % right,
+            BiArithmetic::Add => Ok(left + right),
+            BiArithmetic::Sub => Ok(left - right),
+            BiArithmetic::Mul => Ok(left * right),
+            BiArithmetic::Div | BiArithmetic::Mod if right.is_zero() => Err(QueryExecutionError::DivisionByZero),
+            BiArithmetic::Div => Ok(left / right),
+            BiArithmetic::Mod => Ok(left % right),
             BiArithmetic::Exp => {
                 fn exp(x: &BigDecimal, n: &BigDecimal) -> BigDecimal {
                     if n < &BigDecimal::from(0) {
@@ -56,7 +59,7 @@ impl BiArithmetic {
                     }
                 }
 
-                exp(&left, &right)
+                Ok(exp(&left, &right))
             }
         }
     }
@@ -83,6 +86,8 @@ pub enum Comparison {
     GtEq,
     Lt,
     Gt,
+    IsDistinctFrom,
+    IsNotDistinctFrom,
 }
 
 impl Comparison {
@@ -95,6 +100,33 @@ impl Comparison {
             Comparison::GtEq => left_value >= right_value,
             Comparison::Lt => left_value < right_value,
             Comparison::Gt => left_value > right_value,
+            Comparison::IsDistinctFrom => left_value != right_value,
+            Comparison::IsNotDistinctFrom => left_value == right_value,
+        }
+    }
+
+    /// comparison that gives the same result when operands are swapped
+    pub fn flip(&self) -> Comparison {
+        match self {
+            Comparison::LtEq => Comparison::GtEq,
+            Comparison::GtEq => Comparison::LtEq,
+            Comparison::Lt => Comparison::Gt,
+            Comparison::Gt => Comparison::Lt,
+            other => *other,
+        }
+    }
+
+    /// comparison that gives the opposite result for the same operands
+    pub fn negate(&self) -> Comparison {
+        match self {
+            Comparison::NotEq => Comparison::Eq,
+            Comparison::Eq => Comparison::NotEq,
+            Comparison::LtEq => Comparison::Gt,
+            Comparison::GtEq => Comparison::Lt,
+            Comparison::Lt => Comparison::GtEq,
+            Comparison::Gt => Comparison::LtEq,
+            Comparison::IsDistinctFrom => Comparison::IsNotDistinctFrom,
+            Comparison::IsNotDistinctFrom => Comparison::IsDistinctFrom,
         }
     }
 }
@@ -108,6 +140,8 @@ impl Display for Comparison {
             Comparison::GtEq => write!(f, ">="),
             Comparison::Lt => write!(f, "<"),
             Comparison::Gt => write!(f, ">"),
+            Comparison::IsDistinctFrom => write!(f, "IS DISTINCT FROM"),
+            Comparison::IsNotDistinctFrom => write!(f, "IS NOT DISTINCT FROM"),
         }
     }
 }
@@ -152,10 +186,14 @@ pub enum BiLogical {
 }
 
 impl BiLogical {
-    fn eval(&self, left_value: bool, right_value: bool) -> bool {
-        match self {
-            BiLogical::Or => left_value || right_value,
-            BiLogical::And => left_value && right_value,
+    /// Kleene logic where `None` is the unknown truth value of NULL
+    fn eval(&self, left_value: Option<bool>, right_value: Option<bool>) -> Option<bool> {
+        match (self, left_value, right_value) {
+            (BiLogical::Or, Some(true), _) | (BiLogical::Or, _, Some(true)) => Some(true),
+            (BiLogical::And, Some(false), _) | (BiLogical::And, _, Some(false)) => Some(false),
+            (BiLogical::Or, Some(left_value), Some(right_value)) => Some(left_value || right_value),
+            (BiLogical::And, Some(left_value), Some(right_value)) => Some(left_value && right_value),
+            _ => None,
         }
     }
 }
@@ -169,22 +207,136 @@ impl Display for BiLogical {
     }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum Matching {
     Like,
     NotLike,
+    ILike,
+    NotILike,
+    SimilarTo,
+    NotSimilarTo,
+    RegexMatch,
+    RegexIMatch,
+    NotRegexMatch,
+    NotRegexIMatch,
+}
+
+const PATTERN_CACHE_CAPACITY: usize = 64;
+
+thread_local! {
+    // patterns are compiled once and reused for every evaluated row
+    static PATTERN_CACHE: RefCell<HashMap<Matching, HashMap<String, Regex>>> = RefCell::new(HashMap::new());
 }
 
 impl Matching {
-    fn eval(&self, left: String, right: String) -> bool {
-        let matches = Regex::new(left.replace("%", ".*").replace("_", ".+").as_str())
-            .unwrap()
-            .is_match(right.as_str());
-        match self {
-            Matching::Like => matches,
-            Matching::NotLike => !matches,
+    fn eval(&self, value: &str, pattern: &str) -> Result<bool, QueryExecutionError> {
+        let (kind, negated) = match self {
+            Matching::NotLike => (Matching::Like, true),
+            Matching::NotILike => (Matching::ILike, true),
+            Matching::NotSimilarTo => (Matching::SimilarTo, true),
+            Matching::NotRegexMatch => (Matching::RegexMatch, true),
+            Matching::NotRegexIMatch => (Matching::RegexIMatch, true),
+            other => (*other, false),
+        };
+        let matches = PATTERN_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let patterns = cache.entry(kind).or_default();
+            if let Some(regex) = patterns.get(pattern) {
+                return Ok(regex.is_match(value));
+            }
+            let regex = kind.compile(pattern)?;
+            let matches = regex.is_match(value);
+            if patterns.len() >= PATTERN_CACHE_CAPACITY {
+                patterns.clear();
+            }
+            patterns.insert(pattern.to_owned(), regex);
+            Ok(matches)
+        })?;
+        Ok(matches != negated)
+    }
+
+    fn compile(&self, pattern: &str) -> Result<Regex, QueryExecutionError> {
+        let regex = match self {
+            Matching::Like | Matching::NotLike => format!("(?s)^{}$", like_to_regex(pattern)?),
+            Matching::ILike | Matching::NotILike => format!("(?si)^{}$", like_to_regex(pattern)?),
+            Matching::SimilarTo | Matching::NotSimilarTo => format!("(?s)^(?:{})$", similar_to_regex(pattern)?),
+            Matching::RegexMatch | Matching::NotRegexMatch => pattern.to_owned(),
+            Matching::RegexIMatch | Matching::NotRegexIMatch => format!("(?i){}", pattern),
+        };
+        Regex::new(&regex).map_err(|error| {
+            let message = error.to_string();
+            // syntax errors are reported on several lines with the cause on the last one
+            let cause = message.lines().last().unwrap_or_default().trim_start_matches("error: ").to_owned();
+            QueryExecutionError::InvalidRegularExpression(cause)
+        })
+    }
+}
+
+// `\` escapes the next character, other escape characters are replaced by `like_escape` function
+fn like_to_regex(pattern: &str) -> Result<String, QueryExecutionError> {
+    let mut regex = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '\\' => match chars.next() {
+                Some(escaped) => push_literal(&mut regex, escaped),
+                None => {
+                    return Err(QueryExecutionError::InvalidEscapeSequence(
+                        "LIKE pattern must not end with escape character".to_owned(),
+                    ))
+                }
+            },
+            other => push_literal(&mut regex, other),
         }
     }
+    Ok(regex)
+}
+
+// SIMILAR TO patterns are regular expressions where `%` and `_` are wildcards as in LIKE
+// and `.`, `^` and `$` are ordinary characters
+fn similar_to_regex(pattern: &str) -> Result<String, QueryExecutionError> {
+    let mut regex = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    let mut in_brackets = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) => push_literal(&mut regex, escaped),
+                None => {
+                    return Err(QueryExecutionError::InvalidEscapeSequence(
+                        "SIMILAR TO pattern must not end with escape character".to_owned(),
+                    ))
+                }
+            },
+            ']' if in_brackets => {
+                in_brackets = false;
+                regex.push(c);
+            }
+            other if in_brackets => regex.push(other),
+            '[' => {
+                in_brackets = true;
+                regex.push(c);
+                if chars.peek() == Some(&'^') {
+                    regex.push(chars.next().unwrap());
+                }
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                    regex.push_str("\\]");
+                }
+            }
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '|' | '*' | '+' | '?' | '{' | '}' | '(' | ')' => regex.push(c),
+            other => push_literal(&mut regex, other),
+        }
+    }
+    Ok(regex)
+}
+
+fn push_literal(regex: &mut String, c: char) {
+    regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4])));
 }
 
 impl Display for Matching {
@@ -192,6 +344,14 @@ impl Display for Matching {
         match self {
             Matching::Like => write!(f, "LIKE"),
             Matching::NotLike => write!(f, "NOT LIKE"),
+            Matching::ILike => write!(f, "ILIKE"),
+            Matching::NotILike => write!(f, "NOT ILIKE"),
+            Matching::SimilarTo => write!(f, "SIMILAR TO"),
+            Matching::NotSimilarTo => write!(f, "NOT SIMILAR TO"),
+            Matching::RegexMatch => write!(f, "~"),
+            Matching::RegexIMatch => write!(f, "~*"),
+            Matching::NotRegexMatch => write!(f, "!~"),
+            Matching::NotRegexIMatch => write!(f, "!~*"),
         }
     }
 }
@@ -224,9 +384,30 @@ pub enum BiOperator {
 impl BiOperator {
     pub fn eval(self, left: ScalarValue, right: ScalarValue) -> Result<ScalarValue, QueryExecutionError> {
         match self {
+            BiOperator::Logical(op) => match (left, right) {
+                (ScalarValue::Bool(left_value), ScalarValue::Bool(right_value)) => Ok(truth_value(op.eval(Some(left_value), Some(right_value)))),
+                (ScalarValue::Bool(left_value), ScalarValue::Null) => Ok(truth_value(op.eval(Some(left_value), None))),
+                (ScalarValue::Null, ScalarValue::Bool(right_value)) => Ok(truth_value(op.eval(None, Some(right_value)))),
+                (ScalarValue::Null, ScalarValue::Null) => Ok(ScalarValue::Null),
+                (other_left, other_right) => Err(QueryExecutionError::undefined_bi_function(
+                    self,
+                    other_left.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
+                    other_right.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
+                )),
+            },
+            BiOperator::Comparison(Comparison::IsDistinctFrom) | BiOperator::Comparison(Comparison::IsNotDistinctFrom)
+                if left == ScalarValue::Null || right == ScalarValue::Null =>
+            {
+                let distinct = left != right;
+                Ok(ScalarValue::Bool(
+                    distinct == (self == BiOperator::Comparison(Comparison::IsDistinctFrom)),
+                ))
+            }
+            // any other operator gives NULL when one of its operands is NULL
+            _ if left == ScalarValue::Null || right == ScalarValue::Null => Ok(ScalarValue::Null),
             BiOperator::Arithmetic(op) => match (left, right) {
                 (ScalarValue::Num { value: left_value, .. }, ScalarValue::Num { value: right_value, .. }) => Ok(ScalarValue::Num {
-                    value: op.eval(left_value, right_value),
+                    value: op.eval(left_value, right_value)?,
                     type_family: SqlTypeFamily::BigInt,
                 }),
                 (ScalarValue::Num { type_family, .. }, ScalarValue::String(value)) => {
@@ -257,6 +438,9 @@ impl BiOperator {
                 (ScalarValue::Num { value: left_value, .. }, ScalarValue::Num { value: right_value, .. }) => {
                     Ok(ScalarValue::Bool(op.eval(left_value, right_value)))
                 }
+                (ScalarValue::TimestampTz(left_value), ScalarValue::TimestampTz(right_value)) => {
+                    Ok(ScalarValue::Bool(op.eval(left_value, right_value)))
+                }
                 (other_left, other_right) => Err(QueryExecutionError::undefined_bi_function(
                     self,
                     other_left.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
@@ -379,16 +563,8 @@ impl BiOperator {
                     other_right.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
                 )),
             },
-            BiOperator::Logical(op) => match (left, right) {
-                (ScalarValue::Bool(left_value), ScalarValue::Bool(right_value)) => Ok(ScalarValue::Bool(op.eval(left_value, right_value))),
-                (other_left, other_right) => Err(QueryExecutionError::undefined_bi_function(
-                    self,
-                    other_left.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
-                    other_right.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
-                )),
-            },
             BiOperator::Matching(op) => match (left, right) {
-                (ScalarValue::String(left_value), ScalarValue::String(right_value)) => Ok(ScalarValue::Bool(op.eval(left_value, right_value))),
+                (ScalarValue::String(value), ScalarValue::String(pattern)) => op.eval(&value, &pattern).map(ScalarValue::Bool),
                 (other_left, other_right) => Err(QueryExecutionError::undefined_bi_function(
                     self,
                     other_left.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
@@ -407,6 +583,10 @@ impl BiOperator {
     }
 }
 
+fn truth_value(value: Option<bool>) -> ScalarValue {
+    value.map(ScalarValue::Bool).unwrap_or(ScalarValue::Null)
+}
+
 impl From<BinaryOperator> for BiOperator {
     fn from(operator: BinaryOperator) -> Self {
         match operator {
@@ -427,11 +607,21 @@ impl From<BinaryOperator> for BiOperator {
             BinaryOperator::Or => BiOperator::Logical(BiLogical::Or),
             BinaryOperator::Like => BiOperator::Matching(Matching::Like),
             BinaryOperator::NotLike => BiOperator::Matching(Matching::NotLike),
+            BinaryOperator::ILike => BiOperator::Matching(Matching::ILike),
+            BinaryOperator::NotILike => BiOperator::Matching(Matching::NotILike),
+            BinaryOperator::SimilarTo => BiOperator::Matching(Matching::SimilarTo),
+            BinaryOperator::NotSimilarTo => BiOperator::Matching(Matching::NotSimilarTo),
+            BinaryOperator::RegexMatch => BiOperator::Matching(Matching::RegexMatch),
+            BinaryOperator::RegexIMatch => BiOperator::Matching(Matching::RegexIMatch),
+            BinaryOperator::NotRegexMatch => BiOperator::Matching(Matching::NotRegexMatch),
+            BinaryOperator::NotRegexIMatch => BiOperator::Matching(Matching::NotRegexIMatch),
             BinaryOperator::BitwiseOr => BiOperator::Bitwise(Bitwise::Or),
             BinaryOperator::BitwiseAnd => BiOperator::Bitwise(Bitwise::And),
             BinaryOperator::BitwiseXor => BiOperator::Bitwise(Bitwise::Xor),
             BinaryOperator::BitwiseShiftLeft => BiOperator::Bitwise(Bitwise::ShiftLeft),
             BinaryOperator::BitwiseShiftRight => BiOperator::Bitwise(Bitwise::ShiftRight),
+            BinaryOperator::IsDistinctFrom => BiOperator::Comparison(Comparison::IsDistinctFrom),
+            BinaryOperator::IsNotDistinctFrom => BiOperator::Comparison(Comparison::IsNotDistinctFrom),
         }
     }
 }
@@ -455,11 +645,14 @@ pub enum UnOperator {
     LogicalNot,
     BitwiseNot,
     Cast(SqlType),
+    Is(IsPredicate),
 }
 
 impl UnOperator {
     pub fn eval(self, value: ScalarValue) -> Result<ScalarValue, QueryExecutionError> {
         match self {
+            UnOperator::Is(predicate) => predicate.eval(value),
+            UnOperator::Arithmetic(_) | UnOperator::LogicalNot | UnOperator::BitwiseNot if value == ScalarValue::Null => Ok(ScalarValue::Null),
             UnOperator::Arithmetic(operator) => match value {
                 ScalarValue::Num { value, type_family } => operator.eval(value, type_family),
                 other => Err(QueryExecutionError::undefined_function(
@@ -531,6 +724,10 @@ impl UnOperator {
                         Err(_) => Err(QueryExecutionError::invalid_text_representation(other, value)),
                     },
                 },
+                value @ ScalarValue::TimestampTz(_) => match type_family {
+                    SqlType::Str { .. } => Ok(ScalarValue::String(value.as_text())),
+                    other => Err(QueryExecutionError::cannot_coerce(SqlTypeFamily::TimestampTz, other)),
+                },
             },
         }
     }
@@ -548,6 +745,14 @@ impl From<UnaryOperator> for UnOperator {
             UnaryOperator::PostfixFactorial => UnOperator::Arithmetic(UnArithmetic::Factorial),
             UnaryOperator::PrefixFactorial => UnOperator::Arithmetic(UnArithmetic::Factorial),
             UnaryOperator::Abs => UnOperator::Arithmetic(UnArithmetic::Abs),
+            UnaryOperator::IsNull => UnOperator::Is(IsPredicate::Null),
+            UnaryOperator::IsNotNull => UnOperator::Is(IsPredicate::NotNull),
+            UnaryOperator::IsTrue => UnOperator::Is(IsPredicate::True),
+            UnaryOperator::IsNotTrue => UnOperator::Is(IsPredicate::NotTrue),
+            UnaryOperator::IsFalse => UnOperator::Is(IsPredicate::False),
+            UnaryOperator::IsNotFalse => UnOperator::Is(IsPredicate::NotFalse),
+            UnaryOperator::IsUnknown => UnOperator::Is(IsPredicate::Unknown),
+            UnaryOperator::IsNotUnknown => UnOperator::Is(IsPredicate::NotUnknown),
         }
     }
 }
@@ -559,6 +764,63 @@ impl Display for UnOperator {
             UnOperator::LogicalNot => write!(f, "NOT"),
             UnOperator::BitwiseNot => write!(f, "~"),
             UnOperator::Cast(type_family) => write!(f, "::{}", type_family),
+            UnOperator::Is(predicate) => write!(f, "{}", predicate),
+        }
+    }
+}
+
+/// `IS [NOT] NULL` and `IS [NOT] TRUE/FALSE/UNKNOWN` tests, they never evaluate to NULL
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum IsPredicate {
+    Null,
+    NotNull,
+    True,
+    NotTrue,
+    False,
+    NotFalse,
+    Unknown,
+    NotUnknown,
+}
+
+impl IsPredicate {
+    fn eval(&self, value: ScalarValue) -> Result<ScalarValue, QueryExecutionError> {
+        let truth = match (self, value) {
+            (IsPredicate::Null, value) => Some(value == ScalarValue::Null),
+            (IsPredicate::NotNull, value) => Some(value != ScalarValue::Null),
+            (_, ScalarValue::Bool(value)) => Some(value),
+            (_, ScalarValue::Null) => None,
+            (_, other) => {
+                return Err(QueryExecutionError::datatype_mismatch(
+                    self,
+                    SqlTypeFamily::Bool,
+                    other.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
+                ))
+            }
+        };
+        let result = match self {
+            IsPredicate::Null | IsPredicate::NotNull => truth == Some(true),
+            IsPredicate::True => truth == Some(true),
+            IsPredicate::NotTrue => truth != Some(true),
+            IsPredicate::False => truth == Some(false),
+            IsPredicate::NotFalse => truth != Some(false),
+            IsPredicate::Unknown => truth.is_none(),
+            IsPredicate::NotUnknown => truth.is_some(),
+        };
+        Ok(ScalarValue::Bool(result))
+    }
+}
+
+impl Display for IsPredicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            IsPredicate::Null => write!(f, "IS NULL"),
+            IsPredicate::NotNull => write!(f, "IS NOT NULL"),
+            IsPredicate::True => write!(f, "IS TRUE"),
+            IsPredicate::NotTrue => write!(f, "IS NOT TRUE"),
+            IsPredicate::False => write!(f, "IS FALSE"),
+            IsPredicate::NotFalse => write!(f, "IS NOT FALSE"),
+            IsPredicate::Unknown => write!(f, "IS UNKNOWN"),
+            IsPredicate::NotUnknown => write!(f, "IS NOT UNKNOWN"),
         }
     }
 }
@@ -623,5 +885,246 @@ impl Display for UnArithmetic {
     }
 }
 
+/// how results of comparing a value with every item of a list are combined,
+/// `IN` is the same as `= ANY` and `NOT IN` as `<> ALL`
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Quantifier {
+    Any,
+    All,
+}
+
+impl Display for Quantifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Quantifier::Any => write!(f, "ANY"),
+            Quantifier::All => write!(f, "ALL"),
+        }
+    }
+}
+
+/// functions advancing or setting state of a sequence
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SequenceFunction {
+    NextVal,
+    CurrVal,
+    SetVal,
+}
+
+impl Display for SequenceFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceFunction::NextVal => write!(f, "nextval"),
+            SequenceFunction::CurrVal => write!(f, "currval"),
+            SequenceFunction::SetVal => write!(f, "setval"),
+        }
+    }
+}
+
+/// conditional expressions with function call syntax that evaluate their arguments lazily
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ConditionalFunction {
+    Coalesce,
+    NullIf,
+    Greatest,
+    Least,
+}
+
+impl Display for ConditionalFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConditionalFunction::Coalesce => write!(f, "coalesce"),
+            ConditionalFunction::NullIf => write!(f, "nullif"),
+            ConditionalFunction::Greatest => write!(f, "greatest"),
+            ConditionalFunction::Least => write!(f, "least"),
+        }
+    }
+}
+
+/// functions computed over a window of rows related to the current one
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum WindowFunction {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag,
+    Lead,
+    FirstValue,
+    LastValue,
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl WindowFunction {
+    /// `count` without arguments is `count(*)`
+    pub fn resolve(name: &str, arity: usize) -> Option<WindowFunction> {
+        let (function, arities) = match name {
+            "row_number" => (WindowFunction::RowNumber, 0..=0),
+            "rank" => (WindowFunction::Rank, 0..=0),
+            "dense_rank" => (WindowFunction::DenseRank, 0..=0),
+            "lag" => (WindowFunction::Lag, 1..=3),
+            "lead" => (WindowFunction::Lead, 1..=3),
+            "first_value" => (WindowFunction::FirstValue, 1..=1),
+            "last_value" => (WindowFunction::LastValue, 1..=1),
+            "count" => (WindowFunction::Count, 0..=1),
+            "sum" => (WindowFunction::Sum, 1..=1),
+            "avg" => (WindowFunction::Avg, 1..=1),
+            "min" => (WindowFunction::Min, 1..=1),
+            "max" => (WindowFunction::Max, 1..=1),
+            _ => return None,
+        };
+        if arities.contains(&arity) {
+            Some(function)
+        } else {
+            None
+        }
+    }
+
+    /// aggregates and `first_value`, `last_value` are computed over the window frame,
+    /// other functions use the whole partition
+    pub fn uses_frame(&self) -> bool {
+        !matches!(
+            self,
+            WindowFunction::RowNumber | WindowFunction::Rank | WindowFunction::DenseRank | WindowFunction::Lag | WindowFunction::Lead
+        )
+    }
+}
+
+impl Display for WindowFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFunction::RowNumber => write!(f, "row_number"),
+            WindowFunction::Rank => write!(f, "rank"),
+            WindowFunction::DenseRank => write!(f, "dense_rank"),
+            WindowFunction::Lag => write!(f, "lag"),
+            WindowFunction::Lead => write!(f, "lead"),
+            WindowFunction::FirstValue => write!(f, "first_value"),
+            WindowFunction::LastValue => write!(f, "last_value"),
+            WindowFunction::Count => write!(f, "count"),
+            WindowFunction::Sum => write!(f, "sum"),
+            WindowFunction::Avg => write!(f, "avg"),
+            WindowFunction::Min => write!(f, "min"),
+            WindowFunction::Max => write!(f, "max"),
+        }
+    }
+}
+
+/// rows of a partition that window functions are computed over,
+/// `RANGE` frame includes peers of the current row, i.e. rows with the same sort keys
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct WindowFrame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+impl Default for WindowFrame {
+    /// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`
+    fn default() -> WindowFrame {
+        WindowFrame {
+            units: FrameUnits::Range,
+            start: FrameBound::UnboundedPreceding,
+            end: FrameBound::CurrentRow,
+        }
+    }
+}
+
+impl From<AstWindowFrame> for WindowFrame {
+    fn from(frame: AstWindowFrame) -> WindowFrame {
+        WindowFrame {
+            units: match frame.units {
+                WindowFrameUnits::Rows => FrameUnits::Rows,
+                WindowFrameUnits::Range => FrameUnits::Range,
+            },
+            start: FrameBound::from(frame.start),
+            end: FrameBound::from(frame.end),
+        }
+    }
+}
+
+impl Display for WindowFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} BETWEEN {} AND {}", self.units, self.start, self.end)
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum FrameUnits {
+    Rows,
+    Range,
+}
+
+impl Display for FrameUnits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameUnits::Rows => write!(f, "ROWS"),
+            FrameUnits::Range => write!(f, "RANGE"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+impl From<WindowFrameBound> for FrameBound {
+    fn from(bound: WindowFrameBound) -> FrameBound {
+        match bound {
+            WindowFrameBound::UnboundedPreceding => FrameBound::UnboundedPreceding,
+            WindowFrameBound::Preceding(offset) => FrameBound::Preceding(offset),
+            WindowFrameBound::CurrentRow => FrameBound::CurrentRow,
+            WindowFrameBound::Following(offset) => FrameBound::Following(offset),
+            WindowFrameBound::UnboundedFollowing => FrameBound::UnboundedFollowing,
+        }
+    }
+}
+
+impl Display for FrameBound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameBound::UnboundedPreceding => write!(f, "UNBOUNDED PRECEDING"),
+            FrameBound::Preceding(offset) => write!(f, "{} PRECEDING", offset),
+            FrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            FrameBound::Following(offset) => write!(f, "{} FOLLOWING", offset),
+            FrameBound::UnboundedFollowing => write!(f, "UNBOUNDED FOLLOWING"),
+        }
+    }
+}
+
+/// combines rows of two queries, duplicates are removed unless the operation is `ALL`
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SetOperator {
+    Union,
+    Intersect,
+    Except,
+}
+
+impl From<AstSetOperator> for SetOperator {
+    fn from(operator: AstSetOperator) -> SetOperator {
+        match operator {
+            AstSetOperator::Union => SetOperator::Union,
+            AstSetOperator::Intersect => SetOperator::Intersect,
+            AstSetOperator::Except => SetOperator::Except,
+        }
+    }
+}
+
+impl Display for SetOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SetOperator::Union => write!(f, "UNION"),
+            SetOperator::Intersect => write!(f, "INTERSECT"),
+            SetOperator::Except => write!(f, "EXCEPT"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
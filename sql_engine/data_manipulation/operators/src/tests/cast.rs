@@ -131,3 +131,24 @@ mod numbers {
         );
     }
 }
+
+#[cfg(test)]
+mod timestamp {
+    use super::*;
+
+    #[test]
+    fn cast_to_string() {
+        assert_eq!(
+            UnOperator::Cast(SqlType::var_char(255)).eval(ScalarValue::TimestampTz(951_782_400_000_123)),
+            Ok(ScalarValue::String("2000-02-29 00:00:00.000123+00".to_owned()))
+        );
+    }
+
+    #[test]
+    fn cast_to_numbers() {
+        assert_eq!(
+            UnOperator::Cast(SqlType::Num(Num::BigInt)).eval(ScalarValue::TimestampTz(0)),
+            Err(QueryExecutionError::cannot_coerce(SqlTypeFamily::TimestampTz, SqlTypeFamily::BigInt))
+        );
+    }
+}
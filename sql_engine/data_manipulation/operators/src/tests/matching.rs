@@ -0,0 +1,78 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn matches(op: Matching, value: &str, pattern: &str) -> Result<ScalarValue, QueryExecutionError> {
+    BiOperator::Matching(op).eval(ScalarValue::String(value.to_owned()), ScalarValue::String(pattern.to_owned()))
+}
+
+#[rstest::rstest(
+    op,
+    value,
+    pattern,
+    result,
+    case::percent_matches_any_string(Matching::Like, "abc", "a%", true),
+    case::percent_matches_empty_string(Matching::Like, "a", "a%", true),
+    case::underscore_matches_single_character(Matching::Like, "abc", "a_c", true),
+    case::underscore_does_not_match_several_characters(Matching::Like, "abbc", "a_c", false),
+    case::pattern_is_anchored(Matching::Like, "xabcx", "abc", false),
+    case::regex_characters_are_literals(Matching::Like, "abc", "a.c", false),
+    case::escaped_wildcard(Matching::Like, "a%c", "a\\%c", true),
+    case::escaped_wildcard_is_literal(Matching::Like, "abc", "a\\%c", false),
+    case::percent_matches_new_line(Matching::Like, "a\nb", "a%b", true),
+    case::like_is_case_sensitive(Matching::Like, "ABC", "abc", false),
+    case::not_like(Matching::NotLike, "abc", "b%", true),
+    case::ilike(Matching::ILike, "ABC", "a_c", true),
+    case::not_ilike(Matching::NotILike, "ABC", "a%", false),
+    case::similar_to_alternation(Matching::SimilarTo, "abd", "%(b|d)%", true),
+    case::similar_to_is_anchored(Matching::SimilarTo, "abc", "(b|c)", false),
+    case::similar_to_dot_is_literal(Matching::SimilarTo, "abc", "a.c", false),
+    case::similar_to_repetition(Matching::SimilarTo, "aaa", "a+", true),
+    case::similar_to_brackets(Matching::SimilarTo, "a1", "[a-z][0-9]", true),
+    case::not_similar_to(Matching::NotSimilarTo, "abc", "_b_", false),
+    case::regex_match_is_not_anchored(Matching::RegexMatch, "xabcx", "b.", true),
+    case::regex_match_is_case_sensitive(Matching::RegexMatch, "ABC", "^abc$", false),
+    case::regex_i_match(Matching::RegexIMatch, "ABC", "^abc$", true),
+    case::not_regex_match(Matching::NotRegexMatch, "abc", "^b", true),
+    case::not_regex_i_match(Matching::NotRegexIMatch, "ABC", "^a", false)
+)]
+fn string_matching(op: Matching, value: &str, pattern: &str, result: bool) {
+    assert_eq!(matches(op, value, pattern), Ok(ScalarValue::Bool(result)));
+}
+
+#[test]
+fn cached_pattern_is_reused_for_different_values() {
+    assert_eq!(matches(Matching::Like, "abc", "a_c"), Ok(ScalarValue::Bool(true)));
+    assert_eq!(matches(Matching::Like, "abbc", "a_c"), Ok(ScalarValue::Bool(false)));
+    assert_eq!(matches(Matching::ILike, "ABC", "a_c"), Ok(ScalarValue::Bool(true)));
+}
+
+#[test]
+fn like_pattern_ending_with_escape_character() {
+    assert_eq!(
+        matches(Matching::Like, "abc", "abc\\"),
+        Err(QueryExecutionError::InvalidEscapeSequence(
+            "LIKE pattern must not end with escape character".to_owned()
+        ))
+    );
+}
+
+#[test]
+fn invalid_regular_expression() {
+    assert!(matches!(
+        matches(Matching::RegexMatch, "abc", "(abc"),
+        Err(QueryExecutionError::InvalidRegularExpression(_))
+    ));
+}
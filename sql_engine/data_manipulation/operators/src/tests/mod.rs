@@ -17,4 +17,8 @@ use super::*;
 #[cfg(test)]
 mod cast;
 #[cfg(test)]
+mod matching;
+#[cfg(test)]
+mod null_semantics;
+#[cfg(test)]
 mod operators;
@@ -0,0 +1,116 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn number(value: i32) -> ScalarValue {
+    ScalarValue::Num {
+        value: BigDecimal::from(value),
+        type_family: SqlTypeFamily::Integer,
+    }
+}
+
+fn string(value: &str) -> ScalarValue {
+    ScalarValue::String(value.to_owned())
+}
+
+#[rstest::rstest(
+    op,
+    left,
+    right,
+    case::arithmetic(BiOperator::Arithmetic(BiArithmetic::Add), number(1), ScalarValue::Null),
+    case::division_by_null(BiOperator::Arithmetic(BiArithmetic::Div), ScalarValue::Null, number(0)),
+    case::comparison(BiOperator::Comparison(Comparison::Eq), ScalarValue::Null, ScalarValue::Null),
+    case::bitwise(BiOperator::Bitwise(Bitwise::And), number(1), ScalarValue::Null),
+    case::concatenation(BiOperator::StringOp(Concat), string("abc"), ScalarValue::Null),
+    case::matching(BiOperator::Matching(Matching::Like), ScalarValue::Null, string("abc"))
+)]
+fn null_operand_gives_null(op: BiOperator, left: ScalarValue, right: ScalarValue) {
+    assert_eq!(op.eval(left, right), Ok(ScalarValue::Null));
+}
+
+#[rstest::rstest(
+    op,
+    left,
+    right,
+    expected,
+    case::true_and_null(BiLogical::And, ScalarValue::Bool(true), ScalarValue::Null, ScalarValue::Null),
+    case::false_and_null(BiLogical::And, ScalarValue::Bool(false), ScalarValue::Null, ScalarValue::Bool(false)),
+    case::null_and_false(BiLogical::And, ScalarValue::Null, ScalarValue::Bool(false), ScalarValue::Bool(false)),
+    case::true_or_null(BiLogical::Or, ScalarValue::Bool(true), ScalarValue::Null, ScalarValue::Bool(true)),
+    case::null_or_true(BiLogical::Or, ScalarValue::Null, ScalarValue::Bool(true), ScalarValue::Bool(true)),
+    case::false_or_null(BiLogical::Or, ScalarValue::Bool(false), ScalarValue::Null, ScalarValue::Null),
+    case::null_or_null(BiLogical::Or, ScalarValue::Null, ScalarValue::Null, ScalarValue::Null)
+)]
+fn kleene_logic(op: BiLogical, left: ScalarValue, right: ScalarValue, expected: ScalarValue) {
+    assert_eq!(BiOperator::Logical(op).eval(left, right), Ok(expected));
+}
+
+#[test]
+fn unary_operators_propagate_null() {
+    assert_eq!(UnOperator::LogicalNot.eval(ScalarValue::Null), Ok(ScalarValue::Null));
+    assert_eq!(UnOperator::Arithmetic(UnArithmetic::Neg).eval(ScalarValue::Null), Ok(ScalarValue::Null));
+    assert_eq!(UnOperator::BitwiseNot.eval(ScalarValue::Null), Ok(ScalarValue::Null));
+}
+
+#[rstest::rstest(
+    left,
+    right,
+    distinct,
+    case::both_null(ScalarValue::Null, ScalarValue::Null, false),
+    case::one_null(number(1), ScalarValue::Null, true),
+    case::equal(number(1), number(1), false),
+    case::different(number(1), number(2), true)
+)]
+fn distinct_from(left: ScalarValue, right: ScalarValue, distinct: bool) {
+    assert_eq!(
+        BiOperator::Comparison(Comparison::IsDistinctFrom).eval(left.clone(), right.clone()),
+        Ok(ScalarValue::Bool(distinct))
+    );
+    assert_eq!(
+        BiOperator::Comparison(Comparison::IsNotDistinctFrom).eval(left, right),
+        Ok(ScalarValue::Bool(!distinct))
+    );
+}
+
+#[rstest::rstest(
+    predicate,
+    value,
+    expected,
+    case::null_is_null(IsPredicate::Null, ScalarValue::Null, true),
+    case::number_is_null(IsPredicate::Null, number(1), false),
+    case::null_is_not_null(IsPredicate::NotNull, ScalarValue::Null, false),
+    case::true_is_true(IsPredicate::True, ScalarValue::Bool(true), true),
+    case::null_is_true(IsPredicate::True, ScalarValue::Null, false),
+    case::null_is_not_true(IsPredicate::NotTrue, ScalarValue::Null, true),
+    case::false_is_false(IsPredicate::False, ScalarValue::Bool(false), true),
+    case::null_is_not_false(IsPredicate::NotFalse, ScalarValue::Null, true),
+    case::null_is_unknown(IsPredicate::Unknown, ScalarValue::Null, true),
+    case::true_is_not_unknown(IsPredicate::NotUnknown, ScalarValue::Bool(true), true)
+)]
+fn is_predicates(predicate: IsPredicate, value: ScalarValue, expected: bool) {
+    assert_eq!(UnOperator::Is(predicate).eval(value), Ok(ScalarValue::Bool(expected)));
+}
+
+#[test]
+fn truth_test_of_not_boolean_value() {
+    assert_eq!(
+        UnOperator::Is(IsPredicate::True).eval(number(1)),
+        Err(QueryExecutionError::datatype_mismatch(
+            IsPredicate::True,
+            SqlTypeFamily::Bool,
+            SqlTypeFamily::Integer
+        ))
+    );
+}
@@ -25,7 +25,9 @@ use super::*;
     case::cube_root(UnaryOperator::CubeRoot, UnOperator::Arithmetic(UnArithmetic::CubeRoot)),
     case::prefix_factorial(UnaryOperator::PrefixFactorial, UnOperator::Arithmetic(UnArithmetic::Factorial)),
     case::prefix_factorial(UnaryOperator::PostfixFactorial, UnOperator::Arithmetic(UnArithmetic::Factorial)),
-    case::abs(UnaryOperator::Abs, UnOperator::Arithmetic(UnArithmetic::Abs))
+    case::abs(UnaryOperator::Abs, UnOperator::Arithmetic(UnArithmetic::Abs)),
+    case::is_null(UnaryOperator::IsNull, UnOperator::Is(IsPredicate::Null)),
+    case::is_not_unknown(UnaryOperator::IsNotUnknown, UnOperator::Is(IsPredicate::NotUnknown))
 )]
 fn unary_op(ast_operator: UnaryOperator, expected: UnOperator) {
     assert_eq!(UnOperator::from(ast_operator), expected);
@@ -51,12 +53,38 @@ fn unary_op(ast_operator: UnaryOperator, expected: UnOperator) {
     case::or(BinaryOperator::Or, BiOperator::Logical(BiLogical::Or)),
     case::like(BinaryOperator::Like, BiOperator::Matching(Matching::Like)),
     case::not_like(BinaryOperator::NotLike, BiOperator::Matching(Matching::NotLike)),
+    case::ilike(BinaryOperator::ILike, BiOperator::Matching(Matching::ILike)),
+    case::not_ilike(BinaryOperator::NotILike, BiOperator::Matching(Matching::NotILike)),
+    case::similar_to(BinaryOperator::SimilarTo, BiOperator::Matching(Matching::SimilarTo)),
+    case::not_similar_to(BinaryOperator::NotSimilarTo, BiOperator::Matching(Matching::NotSimilarTo)),
+    case::regex_match(BinaryOperator::RegexMatch, BiOperator::Matching(Matching::RegexMatch)),
+    case::regex_i_match(BinaryOperator::RegexIMatch, BiOperator::Matching(Matching::RegexIMatch)),
+    case::not_regex_match(BinaryOperator::NotRegexMatch, BiOperator::Matching(Matching::NotRegexMatch)),
+    case::not_regex_i_match(BinaryOperator::NotRegexIMatch, BiOperator::Matching(Matching::NotRegexIMatch)),
     case::bitwise_or(BinaryOperator::BitwiseOr, BiOperator::Bitwise(Bitwise::Or)),
     case::bitwise_and(BinaryOperator::BitwiseAnd, BiOperator::Bitwise(Bitwise::And)),
     case::bitwise_xor(BinaryOperator::BitwiseXor, BiOperator::Bitwise(Bitwise::Xor)),
     case::bitwise_shift_left(BinaryOperator::BitwiseShiftLeft, BiOperator::Bitwise(Bitwise::ShiftLeft)),
-    case::bitwise_shift_right(BinaryOperator::BitwiseShiftRight, BiOperator::Bitwise(Bitwise::ShiftRight))
+    case::bitwise_shift_right(BinaryOperator::BitwiseShiftRight, BiOperator::Bitwise(Bitwise::ShiftRight)),
+    case::is_distinct_from(BinaryOperator::IsDistinctFrom, BiOperator::Comparison(Comparison::IsDistinctFrom)),
+    case::is_not_distinct_from(BinaryOperator::IsNotDistinctFrom, BiOperator::Comparison(Comparison::IsNotDistinctFrom))
 )]
 fn binary_op(ast_operator: BinaryOperator, expected: BiOperator) {
     assert_eq!(BiOperator::from(ast_operator), expected);
 }
+
+#[rstest::rstest(
+    name,
+    arity,
+    expected,
+    case::row_number("row_number", 0, Some(WindowFunction::RowNumber)),
+    case::row_number_with_argument("row_number", 1, None),
+    case::lag_with_offset_and_default("lag", 3, Some(WindowFunction::Lag)),
+    case::lead_without_value("lead", 0, None),
+    case::count_star("count", 0, Some(WindowFunction::Count)),
+    case::sum("sum", 1, Some(WindowFunction::Sum)),
+    case::unknown("ntile", 1, None)
+)]
+fn window_function(name: &str, arity: usize, expected: Option<WindowFunction>) {
+    assert_eq!(WindowFunction::resolve(name, arity), expected);
+}
@@ -0,0 +1,194 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ExecutionStats {
+    pub rows: usize,
+    pub loops: usize,
+    pub time: Duration,
+}
+
+impl ExecutionStats {
+    pub(crate) fn measure<T>(&mut self, produce: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = produce();
+        self.time += start.elapsed();
+        result
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PlanNode {
+    pub node_type: &'static str,
+    pub relation: Option<String>,
+    pub estimated_rows: usize,
+    pub properties: Vec<(&'static str, String)>,
+    pub output: Vec<String>,
+    pub actual: Option<ExecutionStats>,
+    pub children: Vec<PlanNode>,
+}
+
+impl PlanNode {
+    pub fn new(node_type: &'static str, estimated_rows: usize) -> PlanNode {
+        PlanNode {
+            node_type,
+            relation: None,
+            estimated_rows,
+            properties: vec![],
+            output: vec![],
+            actual: None,
+            children: vec![],
+        }
+    }
+
+    pub fn on(mut self, relation: &str) -> PlanNode {
+        self.relation = Some(relation.to_owned());
+        self
+    }
+
+    pub fn with_property(mut self, name: &'static str, value: String) -> PlanNode {
+        self.properties.push((name, value));
+        self
+    }
+
+    pub fn with_output(mut self, output: Vec<String>) -> PlanNode {
+        self.output = output;
+        self
+    }
+
+    pub fn with_child(mut self, child: PlanNode) -> PlanNode {
+        self.children.push(child);
+        self
+    }
+
+    /// renders the tree in the same layout as PostgreSQL does, one line per `QUERY PLAN` row
+    pub fn to_text(&self, verbose: bool, execution_time: Option<Duration>) -> Vec<String> {
+        let mut lines = vec![];
+        self.write_text(&mut lines, 0, verbose);
+        if let Some(execution_time) = execution_time {
+            lines.push(format!("Execution Time: {:.3} ms", millis(execution_time)));
+        }
+        lines
+    }
+
+    fn write_text(&self, lines: &mut Vec<String>, depth: usize, verbose: bool) {
+        let mut header = String::new();
+        if depth > 0 {
+            write!(header, "{:width$}->  ", "", width = 2 + (depth - 1) * 6).unwrap();
+        }
+        header.push_str(self.node_type);
+        if let Some(relation) = &self.relation {
+            write!(header, " on {}", relation).unwrap();
+        }
+        write!(header, "  (rows={})", self.estimated_rows).unwrap();
+        match self.actual {
+            Some(ExecutionStats { loops: 0, .. }) => header.push_str(" (never executed)"),
+            Some(ExecutionStats { rows, loops, time }) => write!(header, " (actual time={:.3} rows={} loops={})", millis(time), rows, loops).unwrap(),
+            None => {}
+        }
+        lines.push(header);
+
+        let details = 2 + depth * 6;
+        if verbose && !self.output.is_empty() {
+            lines.push(format!("{:width$}Output: {}", "", self.output.join(", "), width = details));
+        }
+        for (name, value) in self.properties.iter() {
+            lines.push(format!("{:width$}{}: {}", "", name, value, width = details));
+        }
+        for child in self.children.iter() {
+            child.write_text(lines, depth + 1, verbose);
+        }
+    }
+
+    pub fn to_json(&self, verbose: bool, execution_time: Option<Duration>) -> String {
+        let mut json = String::new();
+        json.push_str("[\n  {\n    \"Plan\": ");
+        self.write_json(&mut json, 4, verbose);
+        if let Some(execution_time) = execution_time {
+            write!(json, ",\n    \"Execution Time\": {:.3}", millis(execution_time)).unwrap();
+        }
+        json.push_str("\n  }\n]");
+        json
+    }
+
+    fn write_json(&self, json: &mut String, indent: usize, verbose: bool) {
+        let mut fields = vec![("Node Type".to_owned(), quote(self.node_type))];
+        if let Some(relation) = &self.relation {
+            fields.push(("Relation Name".to_owned(), quote(relation)));
+        }
+        fields.push(("Plan Rows".to_owned(), self.estimated_rows.to_string()));
+        if let Some(ExecutionStats { rows, loops, time }) = self.actual {
+            fields.push(("Actual Total Time".to_owned(), format!("{:.3}", millis(time))));
+            fields.push(("Actual Rows".to_owned(), rows.to_string()));
+            fields.push(("Actual Loops".to_owned(), loops.to_string()));
+        }
+        if verbose && !self.output.is_empty() {
+            let output = self.output.iter().map(|column| quote(column)).collect::<Vec<String>>();
+            fields.push(("Output".to_owned(), format!("[{}]", output.join(", "))));
+        }
+        for (name, value) in self.properties.iter() {
+            fields.push((name.to_string(), quote(value)));
+        }
+
+        json.push_str("{\n");
+        for (index, (name, value)) in fields.iter().enumerate() {
+            if index > 0 {
+                json.push_str(",\n");
+            }
+            write!(json, "{:width$}{}: {}", "", quote(name), value, width = indent + 2).unwrap();
+        }
+        if !self.children.is_empty() {
+            write!(json, ",\n{:width$}\"Plans\": [", "", width = indent + 2).unwrap();
+            for (index, child) in self.children.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                write!(json, "\n{:width$}", "", width = indent + 4).unwrap();
+                child.write_json(json, indent + 4, verbose);
+            }
+            write!(json, "\n{:width$}]", "", width = indent + 2).unwrap();
+        }
+        write!(json, "\n{:width$}}}", "", width = indent).unwrap();
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests;
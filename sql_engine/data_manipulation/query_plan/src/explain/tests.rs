@@ -0,0 +1,139 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn select_plan() -> PlanNode {
+    PlanNode::new("Select", 1)
+        .with_output(vec!["col1".to_owned(), "col2".to_owned()])
+        .with_child(
+            PlanNode::new("Filter", 1)
+                .with_property("Filter", "(col1 > 5)".to_owned())
+                .with_child(PlanNode::new("Projection", 3).with_child(PlanNode::new("Full Table Scan", 3).on("schema_name.table_name"))),
+        )
+}
+
+#[test]
+fn text() {
+    assert_eq!(
+        select_plan().to_text(false, None),
+        vec![
+            "Select  (rows=1)",
+            "  ->  Filter  (rows=1)",
+            "        Filter: (col1 > 5)",
+            "        ->  Projection  (rows=3)",
+            "              ->  Full Table Scan on schema_name.table_name  (rows=3)",
+        ]
+    );
+}
+
+#[test]
+fn verbose_text() {
+    assert_eq!(
+        select_plan().to_text(true, None),
+        vec![
+            "Select  (rows=1)",
+            "  Output: col1, col2",
+            "  ->  Filter  (rows=1)",
+            "        Filter: (col1 > 5)",
+            "        ->  Projection  (rows=3)",
+            "              ->  Full Table Scan on schema_name.table_name  (rows=3)",
+        ]
+    );
+}
+
+#[test]
+fn text_with_actual_statistics() {
+    let mut plan = PlanNode::new("Delete", 2)
+        .on("schema_name.table_name")
+        .with_child(PlanNode::new("Table Record Keys", 2));
+    plan.actual = Some(ExecutionStats {
+        rows: 2,
+        loops: 1,
+        time: Duration::from_micros(1500),
+    });
+    plan.children[0].actual = Some(ExecutionStats::default());
+
+    assert_eq!(
+        plan.to_text(false, Some(Duration::from_micros(2250))),
+        vec![
+            "Delete on schema_name.table_name  (rows=2) (actual time=1.500 rows=2 loops=1)",
+            "  ->  Table Record Keys  (rows=2) (never executed)",
+            "Execution Time: 2.250 ms",
+        ]
+    );
+}
+
+#[test]
+fn json() {
+    assert_eq!(
+        select_plan().to_json(true, None),
+        r#"[
+  {
+    "Plan": {
+      "Node Type": "Select",
+      "Plan Rows": 1,
+      "Output": ["col1", "col2"],
+      "Plans": [
+        {
+          "Node Type": "Filter",
+          "Plan Rows": 1,
+          "Filter": "(col1 > 5)",
+          "Plans": [
+            {
+              "Node Type": "Projection",
+              "Plan Rows": 3,
+              "Plans": [
+                {
+                  "Node Type": "Full Table Scan",
+                  "Relation Name": "schema_name.table_name",
+                  "Plan Rows": 3
+                }
+              ]
+            }
+          ]
+        }
+      ]
+    }
+  }
+]"#
+    );
+}
+
+#[test]
+fn json_with_actual_statistics() {
+    let mut plan = PlanNode::new("Filter", 1).with_property("Filter", "(col1 = 'a\"b')".to_owned());
+    plan.actual = Some(ExecutionStats {
+        rows: 1,
+        loops: 1,
+        time: Duration::from_micros(10),
+    });
+
+    assert_eq!(
+        plan.to_json(false, Some(Duration::from_micros(20))),
+        r#"[
+  {
+    "Plan": {
+      "Node Type": "Filter",
+      "Plan Rows": 1,
+      "Actual Total Time": 0.010,
+      "Actual Rows": 1,
+      "Actual Loops": 1,
+      "Filter": "(col1 = 'a\"b')"
+    },
+    "Execution Time": 0.020
+  }
+]"#
+    );
+}
@@ -14,40 +14,42 @@
 
 #![allow(clippy::type_complexity)]
 
-use bigdecimal::{BigDecimal, FromPrimitive};
+mod explain;
+
+pub use explain::{ExecutionStats, PlanNode};
+
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
 use binary::BinaryValue;
+use data_manipulation_operators::{FrameBound, FrameUnits, SetOperator, WindowFunction};
 use data_manipulation_query_result::QueryExecutionError;
-use data_manipulation_typed_tree::TypedTree;
-use definition::ColumnDef;
+use data_manipulation_typed_tree::{EvaluationContext, TypedSortKey, TypedTree, TypedWindow};
 use query_response::QueryEvent;
 use scalar::ScalarValue;
-use std::collections::HashMap;
-use storage::{Cursor, TableRef};
-use types::{SqlType, SqlTypeFamily};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+    vec::IntoIter,
+};
+use storage::{Cursor, Sequence, TableRef};
+use types::SqlTypeFamily;
 
 #[derive(Debug, PartialEq)]
 pub enum QueryExecutionResult {
     Inserted(usize),
     Deleted(usize),
     Updated(usize),
-    Selected((Vec<(String, u32)>, Vec<Vec<ScalarValue>>)),
+    Selected(usize),
 }
 
-impl From<QueryExecutionResult> for Vec<QueryEvent> {
-    fn from(plan_result: QueryExecutionResult) -> Vec<QueryEvent> {
+impl From<QueryExecutionResult> for QueryEvent {
+    fn from(plan_result: QueryExecutionResult) -> QueryEvent {
         match plan_result {
-            QueryExecutionResult::Inserted(inserted) => vec![QueryEvent::RecordsInserted(inserted)],
-            QueryExecutionResult::Deleted(inserted) => vec![QueryEvent::RecordsDeleted(inserted)],
-            QueryExecutionResult::Updated(inserted) => vec![QueryEvent::RecordsUpdated(inserted)],
-            QueryExecutionResult::Selected((desc, data)) => {
-                let mut events = vec![QueryEvent::RowDescription(desc)];
-                let len = data.len();
-                for row in data {
-                    events.push(QueryEvent::DataRow(row.into_iter().map(|scalar| scalar.as_text()).collect()));
-                }
-                events.push(QueryEvent::RecordsSelected(len));
-                events
-            }
+            QueryExecutionResult::Inserted(inserted) => QueryEvent::RecordsInserted(inserted),
+            QueryExecutionResult::Deleted(deleted) => QueryEvent::RecordsDeleted(deleted),
+            QueryExecutionResult::Updated(updated) => QueryEvent::RecordsUpdated(updated),
+            QueryExecutionResult::Selected(selected) => QueryEvent::RecordsSelected(selected),
         }
     }
 }
@@ -61,35 +63,147 @@ pub enum QueryPlan {
 }
 
 impl QueryPlan {
-    pub fn execute(self, param_values: Vec<ScalarValue>) -> Result<QueryExecutionResult, QueryExecutionError> {
+    pub fn execute(&mut self, context: EvaluationContext, events: &mut dyn FnMut(QueryEvent)) -> Result<QueryExecutionResult, QueryExecutionError> {
         match self {
-            QueryPlan::Insert(insert_query_plan) => insert_query_plan.execute(param_values).map(QueryExecutionResult::Inserted),
-            QueryPlan::Delete(delete_query_plan) => delete_query_plan.execute(param_values).map(QueryExecutionResult::Deleted),
-            QueryPlan::Update(update_query_plan) => update_query_plan.execute(param_values).map(QueryExecutionResult::Updated),
-            QueryPlan::Select(select_query_plan) => select_query_plan.execute(param_values).map(QueryExecutionResult::Selected),
+            QueryPlan::Insert(insert_query_plan) => insert_query_plan.execute(context).map(QueryExecutionResult::Inserted),
+            QueryPlan::Delete(delete_query_plan) => delete_query_plan.execute(context).map(QueryExecutionResult::Deleted),
+            QueryPlan::Update(update_query_plan) => update_query_plan.execute(context).map(QueryExecutionResult::Updated),
+            QueryPlan::Select(select_query_plan) => select_query_plan.execute(context, events).map(QueryExecutionResult::Selected),
         }
     }
+
+    /// prepares already executed plan to be executed once again
+    pub fn reset(&mut self) {
+        match self {
+            QueryPlan::Insert(insert_query_plan) => insert_query_plan.reset(),
+            QueryPlan::Delete(delete_query_plan) => delete_query_plan.reset(),
+            QueryPlan::Update(update_query_plan) => update_query_plan.reset(),
+            QueryPlan::Select(select_query_plan) => select_query_plan.reset(),
+        }
+    }
+
+    pub fn explain(&self) -> PlanNode {
+        match self {
+            QueryPlan::Insert(insert_query_plan) => insert_query_plan.explain(),
+            QueryPlan::Delete(delete_query_plan) => delete_query_plan.explain(),
+            QueryPlan::Update(update_query_plan) => update_query_plan.explain(),
+            QueryPlan::Select(select_query_plan) => select_query_plan.explain(),
+        }
+    }
+
+    /// executes the plan discarding selected rows, so that it could be explained with actual statistics
+    pub fn explain_analyze(mut self, context: EvaluationContext) -> Result<(PlanNode, Duration), QueryExecutionError> {
+        let start = Instant::now();
+        let rows = match &mut self {
+            QueryPlan::Insert(insert_query_plan) => insert_query_plan.execute(context)?,
+            QueryPlan::Delete(delete_query_plan) => delete_query_plan.execute(context)?,
+            QueryPlan::Update(update_query_plan) => update_query_plan.execute(context)?,
+            QueryPlan::Select(select_query_plan) => select_query_plan.execute(context, &mut |_| {})?,
+        };
+        let time = start.elapsed();
+        let mut node = self.explain();
+        node.actual = Some(ExecutionStats { rows, loops: 1, time });
+        Ok((node, time))
+    }
+}
+
+/// Time by which the statement that is being executed has to finish, plans of a session share it
+#[derive(Debug, Clone, Default)]
+pub struct Deadline {
+    at: Arc<Mutex<Option<Instant>>>,
 }
 
-pub trait Flow {
+impl Deadline {
+    /// starts counting `timeout` for the next statement, `None` lets it run for as long as it needs
+    pub fn start(&self, timeout: Option<Duration>) {
+        *self.at.lock().unwrap() = timeout.map(|timeout| Instant::now() + timeout);
+    }
+
+    pub fn check(&self) -> Result<(), QueryExecutionError> {
+        match *self.at.lock().unwrap() {
+            Some(at) if Instant::now() >= at => Err(QueryExecutionError::query_canceled()),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub trait Flow: Send {
     type Output;
 
-    fn next_tuple(&mut self, param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError>;
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError>;
+
+    fn explain(&self) -> PlanNode;
+
+    /// rewinds the operator so that the plan it belongs to could be executed once again
+    fn reset(&mut self);
+}
+
+/// Wraps an operator to count rows, loops and time spent in `next_tuple`, including time spent in its sources
+pub struct Instrumented<O> {
+    source: Box<dyn Flow<Output = O>>,
+    stats: ExecutionStats,
+}
+
+impl<O> Instrumented<O> {
+    pub fn new(source: Box<dyn Flow<Output = O>>) -> Box<Instrumented<O>> {
+        Box::new(Instrumented {
+            source,
+            stats: ExecutionStats::default(),
+        })
+    }
+}
+
+impl<O> Flow for Instrumented<O> {
+    type Output = O;
+
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        self.stats.loops = 1;
+        let source = &mut self.source;
+        let tuple = self.stats.measure(|| source.next_tuple(context))?;
+        if tuple.is_some() {
+            self.stats.rows += 1;
+        }
+        Ok(tuple)
+    }
+
+    fn reset(&mut self) {
+        self.source.reset();
+        self.stats = ExecutionStats::default();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let mut node = self.source.explain();
+        node.actual = Some(self.stats);
+        node
+    }
 }
 
-pub struct StaticValues(Box<dyn Iterator<Item = Vec<Option<TypedTree>>>>);
+pub struct StaticValues {
+    values: Vec<Vec<Option<TypedTree>>>,
+    position: usize,
+}
 
 impl StaticValues {
     pub fn new(values: Vec<Vec<Option<TypedTree>>>) -> Box<StaticValues> {
-        Box::new(StaticValues(Box::new(values.into_iter())))
+        Box::new(StaticValues { values, position: 0 })
     }
 }
 
 impl Flow for StaticValues {
     type Output = Vec<Option<TypedTree>>;
 
-    fn next_tuple(&mut self, _param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError> {
-        Ok(self.0.next())
+    fn next_tuple(&mut self, _context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        let tuple = self.values.get(self.position).cloned();
+        self.position += 1;
+        Ok(tuple)
+    }
+
+    fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    fn explain(&self) -> PlanNode {
+        PlanNode::new("Static Values", self.values.len())
     }
 }
 
@@ -106,13 +220,13 @@ impl StaticExpressionEval {
 impl Flow for StaticExpressionEval {
     type Output = (Vec<ScalarValue>, Vec<Option<ScalarValue>>);
 
-    fn next_tuple(&mut self, param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError> {
-        if let Ok(Some(tuple)) = self.source.next_tuple(param_values) {
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        if let Ok(Some(tuple)) = self.source.next_tuple(context) {
             let mut next_tuple = vec![];
             for value in tuple {
                 let typed_value = match value {
                     None => None,
-                    Some(value) => match value.eval(param_values, &[]) {
+                    Some(value) => match value.eval(context, &[]) {
                         Err(error) => return Err(error),
                         Ok(value) => Some(value),
                     },
@@ -124,6 +238,15 @@ impl Flow for StaticExpressionEval {
             Ok(None)
         }
     }
+
+    fn reset(&mut self) {
+        self.source.reset();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        PlanNode::new("Static Expression Eval", source.estimated_rows).with_child(source)
+    }
 }
 
 pub struct ConstraintValidator {
@@ -143,8 +266,8 @@ impl ConstraintValidator {
 impl Flow for ConstraintValidator {
     type Output = (Vec<ScalarValue>, Vec<Option<ScalarValue>>);
 
-    fn next_tuple(&mut self, param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError> {
-        if let Some((key, tuple)) = self.source.next_tuple(param_values)? {
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        if let Some((key, tuple)) = self.source.next_tuple(context)? {
             log::debug!("ConstraintValidator key - {:?}", key);
             let mut data = vec![];
             for (index, value) in tuple.into_iter().enumerate() {
@@ -202,63 +325,827 @@ impl Flow for ConstraintValidator {
             Ok(None)
         }
     }
+
+    fn reset(&mut self) {
+        self.source.reset();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        PlanNode::new("Constraint Validator", source.estimated_rows)
+            .with_output(self.column_types.iter().map(|(name, _)| name.clone()).collect())
+            .with_child(source)
+    }
 }
 
 pub struct InsertQueryPlan {
     source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<Option<ScalarValue>>)>>,
     table: TableRef,
+    defaults: Vec<Option<(Sequence, SqlTypeFamily)>>,
 }
 
 impl InsertQueryPlan {
-    pub fn new(source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<Option<ScalarValue>>)>>, table: TableRef) -> InsertQueryPlan {
-        InsertQueryPlan { source, table }
+    /// `defaults` has a sequence for every column which value is generated when it is omitted in the insert
+    pub fn new(
+        source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<Option<ScalarValue>>)>>,
+        table: TableRef,
+        defaults: Vec<Option<(Sequence, SqlTypeFamily)>>,
+    ) -> InsertQueryPlan {
+        InsertQueryPlan { source, table, defaults }
     }
 
-    pub fn execute(mut self, param_values: Vec<ScalarValue>) -> Result<usize, QueryExecutionError> {
+    pub fn execute(&mut self, context: EvaluationContext) -> Result<usize, QueryExecutionError> {
         let mut len = 0;
-        while let Some((_, data)) = self.source.next_tuple(&param_values)? {
-            self.table.write(
-                data.into_iter()
-                    .map(|v| v.map(|v| v.convert()).unwrap_or_else(BinaryValue::null))
-                    .collect::<Vec<BinaryValue>>(),
-            );
+        while let Some((_, data)) = self.source.next_tuple(&context)? {
+            let mut record = vec![];
+            for (index, value) in data.into_iter().enumerate() {
+                let value = match (value, self.defaults.get(index)) {
+                    (Some(value), _) => value.convert(),
+                    (None, Some(Some((sequence, type_family)))) => match sequence.next_value() {
+                        Some(value) => ScalarValue::Num {
+                            value: BigDecimal::from(value),
+                            type_family: *type_family,
+                        }
+                        .convert(),
+                        None => {
+                            let (min_value, max_value) = sequence.bounds();
+                            let ascending = sequence.increment() > 0;
+                            return Err(QueryExecutionError::sequence_limit_reached(
+                                sequence.name(),
+                                ascending,
+                                if ascending { max_value } else { min_value },
+                            ));
+                        }
+                    },
+                    (None, _) => BinaryValue::null(),
+                };
+                record.push(value);
+            }
+            self.table.write(record);
             len += 1;
         }
         Ok(len)
     }
+
+    pub fn reset(&mut self) {
+        self.source.reset();
+    }
+
+    pub fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        PlanNode::new("Insert", source.estimated_rows).on(self.table.name()).with_child(source)
+    }
 }
 
+/// Passes rows for which all `conditions` are true, conditions are evaluated in the given
+/// order and the rest of them are skipped as soon as one is not true
 pub struct Filter {
     source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
-    predicate: Option<TypedTree>,
+    conditions: Vec<TypedTree>,
+    selectivity: f64,
 }
 
 impl Filter {
-    pub fn new(source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>, predicate: Option<TypedTree>) -> Box<Filter> {
-        Box::new(Filter { source, predicate })
+    pub fn new(source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>, conditions: Vec<TypedTree>, selectivity: f64) -> Box<Filter> {
+        Box::new(Filter {
+            source,
+            conditions,
+            selectivity,
+        })
+    }
+
+    fn passes(&self, context: &EvaluationContext, value: &[ScalarValue]) -> Result<bool, QueryExecutionError> {
+        for condition in self.conditions.iter() {
+            // rows for which predicate is NULL are filtered out as if it was false
+            match condition.clone().eval(context, value)? {
+                ScalarValue::Bool(true) => {}
+                ScalarValue::Bool(false) | ScalarValue::Null => return Ok(false),
+                other => {
+                    return Err(QueryExecutionError::datatype_mismatch(
+                        "WHERE",
+                        SqlTypeFamily::Bool,
+                        other.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
+                    ))
+                }
+            }
+        }
+        Ok(true)
     }
 }
 
 impl Flow for Filter {
     type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
 
-    fn next_tuple(&mut self, param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError> {
-        while let Some((key, value)) = self.source.next_tuple(param_values)? {
-            match &self.predicate {
-                None => return Ok(Some((key, value))),
-                Some(predicate) => {
-                    log::debug!("Filter before: {:?}, {:?}", key, value);
-                    let result = predicate.clone().eval(param_values, &value);
-                    log::debug!("Filter after: {:?}", result);
-                    if let Ok(ScalarValue::Bool(true)) = result {
-                        log::debug!("Filter filtered key - {:?}", key);
-                        return Ok(Some((key, value)));
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        while let Some((key, value)) = self.source.next_tuple(context)? {
+            log::debug!("Filter before: {:?}, {:?}", key, value);
+            if self.passes(context, &value)? {
+                log::debug!("Filter filtered key - {:?}", key);
+                return Ok(Some((key, value)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn reset(&mut self) {
+        self.source.reset();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        match self.conditions.as_slice() {
+            [] => PlanNode::new("Filter", source.estimated_rows).with_child(source),
+            [condition] => PlanNode::new("Filter", (source.estimated_rows as f64 * self.selectivity).ceil() as usize)
+                .with_property("Filter", condition.to_string())
+                .with_child(source),
+            conditions => PlanNode::new("Filter", (source.estimated_rows as f64 * self.selectivity).ceil() as usize)
+                .with_property(
+                    "Filter",
+                    format!("({})", conditions.iter().map(ToString::to_string).collect::<Vec<_>>().join(" AND ")),
+                )
+                .with_child(source),
+        }
+    }
+}
+
+/// Removes rows with duplicated keys keeping the first of them, keys of already passed rows are kept in a hash set
+pub struct HashDistinct {
+    source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
+    keys: Vec<TypedTree>,
+    seen: HashSet<Vec<ScalarValue>>,
+}
+
+impl HashDistinct {
+    pub fn new(source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>, keys: Vec<TypedTree>) -> Box<HashDistinct> {
+        Box::new(HashDistinct {
+            source,
+            keys,
+            seen: HashSet::new(),
+        })
+    }
+}
+
+impl Flow for HashDistinct {
+    type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
+
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        while let Some((key, value)) = self.source.next_tuple(context)? {
+            if self.seen.insert(eval_keys(&self.keys, context, &value)?) {
+                return Ok(Some((key, value)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn reset(&mut self) {
+        self.source.reset();
+        self.seen.clear();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        PlanNode::new("Hash Distinct", source.estimated_rows)
+            .with_property("Distinct Key", describe_keys(&self.keys))
+            .with_child(source)
+    }
+}
+
+/// Sorts rows by keys and keeps the first row of every group with equal keys,
+/// so that rows are returned in the order of the keys as PostgreSQL does for `DISTINCT ON`
+pub struct SortDistinct {
+    source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
+    keys: Vec<TypedTree>,
+    sorted: Option<IntoIter<(Vec<ScalarValue>, Vec<ScalarValue>)>>,
+}
+
+impl SortDistinct {
+    pub fn new(source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>, keys: Vec<TypedTree>) -> Box<SortDistinct> {
+        Box::new(SortDistinct { source, keys, sorted: None })
+    }
+
+    fn sort(&mut self, context: &EvaluationContext) -> Result<IntoIter<(Vec<ScalarValue>, Vec<ScalarValue>)>, QueryExecutionError> {
+        let mut rows = vec![];
+        while let Some((key, value)) = self.source.next_tuple(context)? {
+            rows.push((eval_keys(&self.keys, context, &value)?, (key, value)));
+        }
+        // sort is stable, hence the first row of a group is the first one that was read from the source
+        rows.sort_by(|(left, _), (right, _)| compare_keys(left, right));
+        rows.dedup_by(|(next, _), (first, _)| compare_keys(next, first) == Ordering::Equal);
+        Ok(rows.into_iter().map(|(_, row)| row).collect::<Vec<_>>().into_iter())
+    }
+}
+
+impl Flow for SortDistinct {
+    type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
+
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        if self.sorted.is_none() {
+            self.sorted = Some(self.sort(context)?);
+        }
+        Ok(self.sorted.as_mut().and_then(Iterator::next))
+    }
+
+    fn reset(&mut self) {
+        self.source.reset();
+        self.sorted = None;
+    }
+
+    fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        PlanNode::new("Sort Distinct", source.estimated_rows)
+            .with_property("Sort Key", describe_keys(&self.keys))
+            .with_child(source)
+    }
+}
+
+/// Returns rows of every source one after another
+pub struct Append {
+    sources: Vec<Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>>,
+    current: usize,
+}
+
+impl Append {
+    pub fn new(sources: Vec<Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>>) -> Box<Append> {
+        Box::new(Append { sources, current: 0 })
+    }
+}
+
+impl Flow for Append {
+    type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
+
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        while let Some(source) = self.sources.get_mut(self.current) {
+            match source.next_tuple(context)? {
+                None => self.current += 1,
+                tuple => return Ok(tuple),
+            }
+        }
+        Ok(None)
+    }
+
+    fn reset(&mut self) {
+        for source in self.sources.iter_mut() {
+            source.reset();
+        }
+        self.current = 0;
+    }
+
+    fn explain(&self) -> PlanNode {
+        let children = self.sources.iter().map(|source| source.explain()).collect::<Vec<_>>();
+        let node = PlanNode::new("Append", children.iter().map(|child| child.estimated_rows).sum());
+        children.into_iter().fold(node, PlanNode::with_child)
+    }
+}
+
+/// Keeps rows of the left source that are (`INTERSECT`) or are not (`EXCEPT`) produced by the right source,
+/// the right source is read into a hash table with number of occurrences of every row beforehand
+pub struct HashSetOp {
+    left: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
+    right: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
+    op: SetOperator,
+    all: bool,
+    counts: Option<HashMap<Vec<ScalarValue>, usize>>,
+    returned: HashSet<Vec<ScalarValue>>,
+}
+
+impl HashSetOp {
+    pub fn new(
+        left: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
+        right: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
+        op: SetOperator,
+        all: bool,
+    ) -> Box<HashSetOp> {
+        Box::new(HashSetOp {
+            left,
+            right,
+            op,
+            all,
+            counts: None,
+            returned: HashSet::new(),
+        })
+    }
+
+    fn count(&mut self, context: &EvaluationContext) -> Result<HashMap<Vec<ScalarValue>, usize>, QueryExecutionError> {
+        let mut counts = HashMap::new();
+        while let Some((_key, value)) = self.right.next_tuple(context)? {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+}
+
+impl Flow for HashSetOp {
+    type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
+
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        if self.counts.is_none() {
+            self.counts = Some(self.count(context)?);
+        }
+        let counts = self.counts.as_mut().unwrap();
+        while let Some((key, value)) = self.left.next_tuple(context)? {
+            // `ALL` pairs every row of the left side with a single occurrence on the right side
+            let matched = match counts.get_mut(&value) {
+                Some(count) if *count > 0 => {
+                    if self.all {
+                        *count -= 1;
                     }
+                    true
                 }
+                _ => false,
+            };
+            let keep = match self.op {
+                SetOperator::Intersect => matched,
+                SetOperator::Except => !matched,
+                SetOperator::Union => unreachable!("UNION is planned as Append"),
+            };
+            if keep && (self.all || self.returned.insert(value.clone())) {
+                return Ok(Some((key, value)));
             }
         }
         Ok(None)
     }
+
+    fn reset(&mut self) {
+        self.left.reset();
+        self.right.reset();
+        self.counts = None;
+        self.returned.clear();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let left = self.left.explain();
+        let right = self.right.explain();
+        let command = if self.all { format!("{} ALL", self.op) } else { self.op.to_string() };
+        PlanNode::new("Hash SetOp", left.estimated_rows)
+            .with_property("Command", command)
+            .with_child(left)
+            .with_child(right)
+    }
+}
+
+/// Returns result rows of a query that is a side of a set operation
+pub struct SubqueryScan {
+    plan: SelectQueryPlan,
+}
+
+impl SubqueryScan {
+    pub fn new(plan: SelectQueryPlan) -> Box<SubqueryScan> {
+        Box::new(SubqueryScan { plan })
+    }
+}
+
+impl Flow for SubqueryScan {
+    type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
+
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        Ok(self.plan.next_row(context)?.map(|row| (vec![], row)))
+    }
+
+    fn reset(&mut self) {
+        self.plan.reset();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let plan = self.plan.explain();
+        PlanNode::new("Subquery Scan", plan.estimated_rows).with_child(plan)
+    }
+}
+
+/// Computes window functions over all rows of the source and appends their results to every row,
+/// for every window rows are sorted by its partition and order keys, so that partitions and
+/// peers, i.e. rows with equal order keys, are adjacent
+pub struct WindowAgg {
+    source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
+    windows: Vec<TypedWindow>,
+    rows: Option<IntoIter<(Vec<ScalarValue>, Vec<ScalarValue>)>>,
+}
+
+impl WindowAgg {
+    pub fn new(source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>, windows: Vec<TypedWindow>) -> Box<WindowAgg> {
+        Box::new(WindowAgg { source, windows, rows: None })
+    }
+
+    fn compute(&mut self, context: &EvaluationContext) -> Result<IntoIter<(Vec<ScalarValue>, Vec<ScalarValue>)>, QueryExecutionError> {
+        let mut rows = vec![];
+        while let Some(row) = self.source.next_tuple(context)? {
+            rows.push(row);
+        }
+        for window in self.windows.iter() {
+            let order_by = window.order_by.iter().map(|key| key.expr.clone()).collect::<Vec<_>>();
+            let mut sorted = vec![];
+            for (key, value) in rows {
+                let partition_keys = eval_keys(&window.partition_by, context, &value)?;
+                let order_keys = eval_keys(&order_by, context, &value)?;
+                let args = eval_keys(&window.args, context, &value)?;
+                sorted.push((partition_keys, WindowRow { order_keys, args }, (key, value)));
+            }
+            sorted.sort_by(|(left_partition, left, _), (right_partition, right, _)| {
+                compare_keys(left_partition, right_partition).then_with(|| compare_sort_keys(&window.order_by, &left.order_keys, &right.order_keys))
+            });
+            let mut results = Vec::with_capacity(sorted.len());
+            let mut start = 0;
+            while start < sorted.len() {
+                let end = start
+                    + sorted[start..]
+                        .iter()
+                        .take_while(|(partition, _, _)| compare_keys(partition, &sorted[start].0) == Ordering::Equal)
+                        .count();
+                let partition = sorted[start..end].iter().map(|(_, row, _)| row).collect::<Vec<_>>();
+                results.extend(window_results(window, &partition)?);
+                start = end;
+            }
+            rows = sorted
+                .into_iter()
+                .zip(results)
+                .map(|((_, _, (key, mut value)), result)| {
+                    value.push(result);
+                    (key, value)
+                })
+                .collect();
+        }
+        Ok(rows.into_iter())
+    }
+}
+
+impl Flow for WindowAgg {
+    type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
+
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        if self.rows.is_none() {
+            self.rows = Some(self.compute(context)?);
+        }
+        Ok(self.rows.as_mut().and_then(Iterator::next))
+    }
+
+    fn reset(&mut self) {
+        self.source.reset();
+        self.rows = None;
+    }
+
+    fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        self.windows
+            .iter()
+            .fold(PlanNode::new("WindowAgg", source.estimated_rows), |node, window| {
+                node.with_property("Window", window.to_string())
+            })
+            .with_child(source)
+    }
+}
+
+struct WindowRow {
+    order_keys: Vec<ScalarValue>,
+    args: Vec<ScalarValue>,
+}
+
+// arguments of window functions are evaluated on their own rows beforehand,
+// including default of `lag` and `lead` that is evaluated on the current row
+fn window_results(window: &TypedWindow, partition: &[&WindowRow]) -> Result<Vec<ScalarValue>, QueryExecutionError> {
+    // dense rank of the peer group, its start and end for every row
+    let mut peers = Vec::with_capacity(partition.len());
+    let mut group = 0;
+    let mut group_start = 0;
+    for index in 0..partition.len() {
+        if compare_sort_keys(&window.order_by, &partition[group_start].order_keys, &partition[index].order_keys) != Ordering::Equal {
+            group += 1;
+            group_start = index;
+        }
+        peers.push((group, group_start, partition.len()));
+    }
+    for index in (1..partition.len()).rev() {
+        peers[index - 1].2 = if peers[index - 1].0 == peers[index].0 { peers[index].2 } else { index };
+    }
+
+    let mut aggregate = SlidingAggregate::new(window, partition);
+    let mut previous: Option<((usize, usize), ScalarValue)> = None;
+    let mut results = Vec::with_capacity(partition.len());
+    for (index, row) in partition.iter().enumerate() {
+        let (group, group_start, group_end) = peers[index];
+        let result = match window.function {
+            WindowFunction::RowNumber => big_int(index + 1),
+            WindowFunction::Rank => big_int(group_start + 1),
+            WindowFunction::DenseRank => big_int(group + 1),
+            WindowFunction::Lag | WindowFunction::Lead => {
+                let offset = match row.args.get(1) {
+                    None => Some(1),
+                    Some(ScalarValue::Null) => None,
+                    Some(ScalarValue::Num { value, .. }) if value.is_integer() => value.to_i64(),
+                    Some(other) => {
+                        return Err(QueryExecutionError::undefined_function(
+                            window.function,
+                            other.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
+                        ))
+                    }
+                };
+                match offset {
+                    None => ScalarValue::Null,
+                    Some(offset) => {
+                        let target = if window.function == WindowFunction::Lag {
+                            index as i64 - offset
+                        } else {
+                            index as i64 + offset
+                        };
+                        if target >= 0 && (target as usize) < partition.len() {
+                            partition[target as usize].args[0].clone()
+                        } else {
+                            row.args.get(2).cloned().unwrap_or(ScalarValue::Null)
+                        }
+                    }
+                }
+            }
+            _ => {
+                let (start, end) = frame_bounds(window, partition, index, group_start, group_end)?;
+                let end = end.max(start);
+                match previous.take() {
+                    // e.g. the whole partition or the peer group is the frame of every its row
+                    Some((bounds, result)) if bounds == (start, end) => {
+                        previous = Some((bounds, result.clone()));
+                        result
+                    }
+                    _ => {
+                        aggregate.slide(start, end)?;
+                        let result = aggregate.result();
+                        previous = Some(((start, end), result.clone()));
+                        result
+                    }
+                }
+            }
+        };
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// State of an aggregate over a frame that moves forward through a partition.
+/// Rows are added at the frame end and removed from its start, so every row
+/// is visited at most twice while both bounds of the frame do not go back.
+struct SlidingAggregate<'p> {
+    window: &'p TypedWindow,
+    partition: &'p [&'p WindowRow],
+    start: usize,
+    end: usize,
+    sum: BigDecimal,
+    count: usize,
+    // indexes of candidates to `min` or `max`, the current one is in front
+    extremes: VecDeque<usize>,
+}
+
+impl<'p> SlidingAggregate<'p> {
+    fn new(window: &'p TypedWindow, partition: &'p [&'p WindowRow]) -> SlidingAggregate<'p> {
+        SlidingAggregate {
+            window,
+            partition,
+            start: 0,
+            end: 0,
+            sum: BigDecimal::from(0),
+            count: 0,
+            extremes: VecDeque::new(),
+        }
+    }
+
+    fn slide(&mut self, start: usize, end: usize) -> Result<(), QueryExecutionError> {
+        if start < self.start || end < self.end {
+            self.start = start;
+            self.end = start;
+            self.sum = BigDecimal::from(0);
+            self.count = 0;
+            self.extremes.clear();
+        }
+        while self.end < end {
+            self.add(self.end)?;
+            self.end += 1;
+        }
+        while self.start < start {
+            self.remove(self.start);
+            self.start += 1;
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, index: usize) -> Result<(), QueryExecutionError> {
+        let value = match self.partition[index].args.first() {
+            None | Some(ScalarValue::Null) => return Ok(()),
+            Some(value) => value,
+        };
+        match self.window.function {
+            WindowFunction::Sum | WindowFunction::Avg => match value {
+                ScalarValue::Num { value, .. } => {
+                    self.sum += value;
+                    self.count += 1;
+                }
+                other => {
+                    return Err(QueryExecutionError::undefined_function(
+                        self.window.function,
+                        other.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
+                    ))
+                }
+            },
+            WindowFunction::Min | WindowFunction::Max => {
+                let replaced = if self.window.function == WindowFunction::Min {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                };
+                while let Some(last) = self.extremes.back() {
+                    if compare_values(&self.partition[*last].args[0], value) == replaced {
+                        self.extremes.pop_back();
+                    } else {
+                        break;
+                    }
+                }
+                self.extremes.push_back(index);
+            }
+            _ => self.count += 1,
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) {
+        let value = match self.partition[index].args.first() {
+            None | Some(ScalarValue::Null) => return,
+            Some(value) => value,
+        };
+        match (self.window.function, value) {
+            (WindowFunction::Sum, ScalarValue::Num { value, .. }) | (WindowFunction::Avg, ScalarValue::Num { value, .. }) => {
+                self.sum -= value;
+                self.count -= 1;
+            }
+            (WindowFunction::Min, _) | (WindowFunction::Max, _) => {
+                if self.extremes.front() == Some(&index) {
+                    self.extremes.pop_front();
+                }
+            }
+            _ => self.count -= 1,
+        }
+    }
+
+    fn result(&self) -> ScalarValue {
+        let frame = &self.partition[self.start..self.end];
+        match self.window.function {
+            WindowFunction::FirstValue => frame.first().map(|row| row.args[0].clone()).unwrap_or(ScalarValue::Null),
+            WindowFunction::LastValue => frame.last().map(|row| row.args[0].clone()).unwrap_or(ScalarValue::Null),
+            WindowFunction::Count if self.window.args.is_empty() => big_int(frame.len()),
+            WindowFunction::Count => big_int(self.count),
+            WindowFunction::Min | WindowFunction::Max => self
+                .extremes
+                .front()
+                .map(|index| self.partition[*index].args[0].clone())
+                .unwrap_or(ScalarValue::Null),
+            WindowFunction::Sum | WindowFunction::Avg if self.count == 0 => ScalarValue::Null,
+            WindowFunction::Avg => ScalarValue::Num {
+                value: &self.sum / BigDecimal::from(self.count as u64),
+                type_family: SqlTypeFamily::Double,
+            },
+            WindowFunction::Sum => ScalarValue::Num {
+                value: self.sum.clone(),
+                type_family: self.window.type_family.unwrap_or(SqlTypeFamily::BigInt),
+            },
+            function => unreachable!("{} is not computed over a window frame", function),
+        }
+    }
+}
+
+// returns the range of partition rows in the frame of the current row, the range is empty
+// when the frame start is after its end, e.g. for `ROWS BETWEEN 1 FOLLOWING AND 1 PRECEDING`
+fn frame_bounds(
+    window: &TypedWindow,
+    partition: &[&WindowRow],
+    index: usize,
+    peers_start: usize,
+    peers_end: usize,
+) -> Result<(usize, usize), QueryExecutionError> {
+    let len = partition.len();
+    let bounds = match window.frame.units {
+        FrameUnits::Rows => {
+            let start = match window.frame.start {
+                FrameBound::UnboundedPreceding => 0,
+                FrameBound::Preceding(offset) => index.saturating_sub(offset as usize),
+                FrameBound::CurrentRow => index,
+                FrameBound::Following(offset) => index.saturating_add(offset as usize).min(len),
+                FrameBound::UnboundedFollowing => len,
+            };
+            let end = match window.frame.end {
+                FrameBound::UnboundedPreceding => 0,
+                FrameBound::Preceding(offset) => (index + 1).saturating_sub(offset as usize),
+                FrameBound::CurrentRow => index + 1,
+                FrameBound::Following(offset) => index.saturating_add(offset as usize).saturating_add(1).min(len),
+                FrameBound::UnboundedFollowing => len,
+            };
+            (start, end)
+        }
+        FrameUnits::Range => {
+            let start = match window.frame.start {
+                FrameBound::UnboundedPreceding => 0,
+                FrameBound::CurrentRow => peers_start,
+                FrameBound::UnboundedFollowing => len,
+                offset => match range_bound(window, partition, index, offset)? {
+                    None => peers_start,
+                    Some(bound) => partition
+                        .partition_point(|row| compare_sort_keys(&window.order_by, &row.order_keys, std::slice::from_ref(&bound)) == Ordering::Less),
+                },
+            };
+            let end = match window.frame.end {
+                FrameBound::UnboundedPreceding => 0,
+                FrameBound::CurrentRow => peers_end,
+                FrameBound::UnboundedFollowing => len,
+                offset => match range_bound(window, partition, index, offset)? {
+                    None => peers_end,
+                    Some(bound) => partition.partition_point(|row| {
+                        compare_sort_keys(&window.order_by, &row.order_keys, std::slice::from_ref(&bound)) != Ordering::Greater
+                    }),
+                },
+            };
+            (start, end)
+        }
+    };
+    Ok(bounds)
+}
+
+// `RANGE` offset is applied to the only order key in the direction of the sort,
+// the frame of a row with NULL key consists of its peers
+fn range_bound(window: &TypedWindow, partition: &[&WindowRow], index: usize, bound: FrameBound) -> Result<Option<ScalarValue>, QueryExecutionError> {
+    let (value, type_family) = match &partition[index].order_keys[0] {
+        ScalarValue::Null => return Ok(None),
+        ScalarValue::Num { value, type_family } => (value, *type_family),
+        other => {
+            return Err(QueryExecutionError::undefined_function(
+                "RANGE with offset PRECEDING/FOLLOWING",
+                other.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
+            ))
+        }
+    };
+    let (offset, preceding) = match bound {
+        FrameBound::Preceding(offset) => (BigDecimal::from(offset), true),
+        FrameBound::Following(offset) => (BigDecimal::from(offset), false),
+        other => unreachable!("{} is not an offset", other),
+    };
+    let value = if preceding == window.order_by[0].ascending {
+        value - offset
+    } else {
+        value + offset
+    };
+    Ok(Some(ScalarValue::Num { value, type_family }))
+}
+
+fn big_int(value: usize) -> ScalarValue {
+    ScalarValue::Num {
+        value: BigDecimal::from(value as u64),
+        type_family: SqlTypeFamily::BigInt,
+    }
+}
+
+fn eval_keys(keys: &[TypedTree], context: &EvaluationContext, value: &[ScalarValue]) -> Result<Vec<ScalarValue>, QueryExecutionError> {
+    keys.iter().map(|key| key.clone().eval(context, value)).collect()
+}
+
+fn describe_keys(keys: &[TypedTree]) -> String {
+    keys.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+}
+
+// direction and placement of NULLs are specified for every key of `ORDER BY` in a window
+fn compare_sort_keys(keys: &[TypedSortKey], left: &[ScalarValue], right: &[ScalarValue]) -> Ordering {
+    keys.iter()
+        .zip(left.iter().zip(right.iter()))
+        .map(|(key, (left, right))| match (left, right) {
+            (ScalarValue::Null, ScalarValue::Null) => Ordering::Equal,
+            (ScalarValue::Null, _) if key.nulls_first => Ordering::Less,
+            (ScalarValue::Null, _) => Ordering::Greater,
+            (_, ScalarValue::Null) if key.nulls_first => Ordering::Greater,
+            (_, ScalarValue::Null) => Ordering::Less,
+            (left, right) if key.ascending => compare_values(left, right),
+            (left, right) => compare_values(right, left),
+        })
+        .find(|ordering| ordering != &Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+fn compare_keys(left: &[ScalarValue], right: &[ScalarValue]) -> Ordering {
+    left.iter()
+        .zip(right.iter())
+        .map(|(left, right)| compare_values(left, right))
+        .find(|ordering| ordering != &Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+// NULLs are placed after other values as in ascending sort of PostgreSQL
+fn compare_values(left: &ScalarValue, right: &ScalarValue) -> Ordering {
+    fn rank(value: &ScalarValue) -> u8 {
+        match value {
+            ScalarValue::Bool(_) => 0,
+            ScalarValue::Num { .. } => 1,
+            ScalarValue::String(_) => 2,
+            ScalarValue::TimestampTz(_) => 3,
+            ScalarValue::Null => 4,
+        }
+    }
+
+    match (left, right) {
+        (ScalarValue::Num { value: left, .. }, ScalarValue::Num { value: right, .. }) => left.cmp(right),
+        (ScalarValue::String(left), ScalarValue::String(right)) => left.cmp(right),
+        (ScalarValue::Bool(left), ScalarValue::Bool(right)) => left.cmp(right),
+        (ScalarValue::TimestampTz(left), ScalarValue::TimestampTz(right)) => left.cmp(right),
+        (left, right) => rank(left).cmp(&rank(right)),
+    }
 }
 
 pub struct Projection {
@@ -274,7 +1161,7 @@ impl Projection {
 impl Flow for Projection {
     type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
 
-    fn next_tuple(&mut self, param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError> {
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
         fn mapper(datum: &BinaryValue) -> ScalarValue {
             match datum {
                 BinaryValue::Null => ScalarValue::Null,
@@ -303,7 +1190,7 @@ impl Flow for Projection {
             }
         }
 
-        if let Some(row) = self.source.next_tuple(&param_values)? {
+        if let Some(row) = self.source.next_tuple(&context)? {
             let key = row.0;
             let value = row.1;
             Ok(Some((
@@ -314,26 +1201,87 @@ impl Flow for Projection {
             Ok(None)
         }
     }
+
+    fn reset(&mut self) {
+        self.source.reset();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        PlanNode::new("Projection", source.estimated_rows).with_child(source)
+    }
 }
 
+/// Reads every record of a table, it is where rows of a statement come from,
+/// so the statement is canceled here when it runs out of time
 pub struct FullTableScan {
     source: Cursor,
+    table: TableRef,
+    records: usize,
+    deadline: Deadline,
 }
 
 impl FullTableScan {
-    pub fn new(source: &TableRef) -> Box<FullTableScan> {
-        Box::new(FullTableScan { source: source.scan() })
+    pub fn new(table: &TableRef, deadline: &Deadline) -> Box<FullTableScan> {
+        Box::new(FullTableScan {
+            source: table.scan(),
+            table: table.clone(),
+            records: table.len(),
+            deadline: deadline.clone(),
+        })
     }
 }
 
 impl Flow for FullTableScan {
     type Output = (Vec<BinaryValue>, Vec<BinaryValue>);
 
-    fn next_tuple(&mut self, _param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError> {
+    fn next_tuple(&mut self, _context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        self.deadline.check()?;
         let record = self.source.next();
         log::debug!("TABLE RECORD {:?}", record);
         Ok(record)
     }
+
+    fn reset(&mut self) {
+        self.source = self.table.scan();
+        self.records = self.table.len();
+    }
+
+    fn explain(&self) -> PlanNode {
+        PlanNode::new("Full Table Scan", self.records).on(self.table.name())
+    }
+}
+
+/// Source of a query without `FROM` clause that produces exactly one empty row
+pub struct SingleRow {
+    produced: bool,
+}
+
+impl SingleRow {
+    pub fn new() -> Box<SingleRow> {
+        Box::new(SingleRow { produced: false })
+    }
+}
+
+impl Flow for SingleRow {
+    type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
+
+    fn next_tuple(&mut self, _context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        if self.produced {
+            Ok(None)
+        } else {
+            self.produced = true;
+            Ok(Some((vec![], vec![])))
+        }
+    }
+
+    fn reset(&mut self) {
+        self.produced = false;
+    }
+
+    fn explain(&self) -> PlanNode {
+        PlanNode::new("Result", 1)
+    }
 }
 
 pub struct TableRecordKeys {
@@ -349,13 +1297,22 @@ impl TableRecordKeys {
 impl Flow for TableRecordKeys {
     type Output = Vec<BinaryValue>;
 
-    fn next_tuple(&mut self, param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError> {
-        if let Some((key, _value)) = self.source.next_tuple(param_values)? {
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        if let Some((key, _value)) = self.source.next_tuple(context)? {
             Ok(Some(key.into_iter().map(|v| v.convert()).collect::<Vec<BinaryValue>>()))
         } else {
             Ok(None)
         }
     }
+
+    fn reset(&mut self) {
+        self.source.reset();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        PlanNode::new("Table Record Keys", source.estimated_rows).with_child(source)
+    }
 }
 
 pub struct DeleteQueryPlan {
@@ -368,14 +1325,23 @@ impl DeleteQueryPlan {
         DeleteQueryPlan { source, table }
     }
 
-    pub fn execute(mut self, param_values: Vec<ScalarValue>) -> Result<usize, QueryExecutionError> {
+    pub fn execute(&mut self, context: EvaluationContext) -> Result<usize, QueryExecutionError> {
         let mut len = 0;
-        while let Some(key) = self.source.next_tuple(&param_values)? {
+        while let Some(key) = self.source.next_tuple(&context)? {
             self.table.write_key(key, None);
             len += 1;
         }
         Ok(len)
     }
+
+    pub fn reset(&mut self) {
+        self.source.reset();
+    }
+
+    pub fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        PlanNode::new("Delete", source.estimated_rows).on(self.table.name()).with_child(source)
+    }
 }
 
 pub struct Repeater {
@@ -391,9 +1357,15 @@ impl Repeater {
 impl Flow for Repeater {
     type Output = Vec<Option<TypedTree>>;
 
-    fn next_tuple(&mut self, _param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError> {
+    fn next_tuple(&mut self, _context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
         Ok(Some(self.source.clone()))
     }
+
+    fn reset(&mut self) {}
+
+    fn explain(&self) -> PlanNode {
+        PlanNode::new("Repeater", 1)
+    }
 }
 
 pub struct DynamicValues {
@@ -413,16 +1385,16 @@ impl DynamicValues {
 impl Flow for DynamicValues {
     type Output = (Vec<ScalarValue>, Vec<Option<ScalarValue>>);
 
-    fn next_tuple(&mut self, param_values: &[ScalarValue]) -> Result<Option<Self::Output>, QueryExecutionError> {
-        if let Some((key, value)) = self.records.next_tuple(param_values)? {
+    fn next_tuple(&mut self, context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        if let Some((key, value)) = self.records.next_tuple(context)? {
             let table_row = value;
             log::trace!("[DynamicValues] VALUES - {:?}", table_row);
-            if let Some(tuple) = self.source.next_tuple(param_values)? {
+            if let Some(tuple) = self.source.next_tuple(context)? {
                 let mut next_tuple = vec![];
                 for value in tuple {
                     let value = match value {
                         None => None,
-                        Some(tree) => match tree.eval(param_values, &table_row) {
+                        Some(tree) => match tree.eval(context, &table_row) {
                             Err(error) => return Err(error),
                             Ok(value) => Some(value),
                         },
@@ -437,6 +1409,18 @@ impl Flow for DynamicValues {
             Ok(None)
         }
     }
+
+    fn reset(&mut self) {
+        self.source.reset();
+        self.records.reset();
+    }
+
+    fn explain(&self) -> PlanNode {
+        let records = self.records.explain();
+        PlanNode::new("Dynamic Values", records.estimated_rows)
+            .with_child(self.source.explain())
+            .with_child(records)
+    }
 }
 
 pub struct UpdateQueryPlan {
@@ -454,7 +1438,7 @@ impl UpdateQueryPlan {
         UpdateQueryPlan { values, records, table }
     }
 
-    pub fn execute(mut self, param_values: Vec<ScalarValue>) -> Result<usize, QueryExecutionError> {
+    pub fn execute(&mut self, context: EvaluationContext) -> Result<usize, QueryExecutionError> {
         fn mapper(datum: BinaryValue) -> ScalarValue {
             match datum {
                 BinaryValue::Null => ScalarValue::Null,
@@ -485,10 +1469,10 @@ impl UpdateQueryPlan {
 
         let mut len = 0;
         let mut values = HashMap::new();
-        while let Some((updated_key, value)) = self.values.next_tuple(&param_values)? {
+        while let Some((updated_key, value)) = self.values.next_tuple(&context)? {
             values.insert(updated_key, value);
         }
-        while let Some((key, row)) = self.records.next_tuple(&param_values)? {
+        while let Some((key, row)) = self.records.next_tuple(&context)? {
             let mut unpacked = row;
             let unpacked_key = key.clone().into_iter().map(mapper).collect::<Vec<ScalarValue>>();
             if let Some(value) = values.remove(&unpacked_key) {
@@ -506,51 +1490,78 @@ impl UpdateQueryPlan {
         }
         Ok(len)
     }
+
+    pub fn reset(&mut self) {
+        self.values.reset();
+        self.records.reset();
+    }
+
+    pub fn explain(&self) -> PlanNode {
+        let values = self.values.explain();
+        PlanNode::new("Update", values.estimated_rows)
+            .on(self.table.name())
+            .with_child(values)
+            .with_child(self.records.explain())
+    }
 }
 
 pub struct SelectQueryPlan {
     source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
-    columns: Vec<String>,
-    column_types: Vec<(String, SqlType)>,
+    column_defs: Vec<(String, u32)>,
+    projection_items: Vec<TypedTree>,
 }
 
 impl SelectQueryPlan {
     pub fn new(
         source: Box<dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>>,
-        columns: Vec<String>,
-        column_types: Vec<(String, SqlType)>,
+        projection_items: Vec<TypedTree>,
+        column_defs: Vec<(String, u32)>,
     ) -> SelectQueryPlan {
+        log::debug!("COLUMNS METADATA {:?}", column_defs);
         SelectQueryPlan {
             source,
-            columns,
-            column_types,
+            column_defs,
+            projection_items,
         }
     }
 
-    pub fn execute(mut self, param_values: Vec<ScalarValue>) -> Result<(Vec<(String, u32)>, Vec<Vec<ScalarValue>>), QueryExecutionError> {
-        log::debug!("COLUMNS TO SELECT {:?}", self.columns);
-        let mut column_defs = vec![];
-        let columns = self
-            .column_types
-            .iter()
-            .enumerate()
-            .map(|(index, (name, sql_type))| (name.clone(), ColumnDef::new(name.clone(), *sql_type, index)))
-            .collect::<HashMap<String, ColumnDef>>();
-        for name in self.columns.iter() {
-            let column = columns.get(name).unwrap();
-            column_defs.push((column.name().to_owned(), (&column.sql_type()).into()));
+    pub fn column_defs(&self) -> &[(String, u32)] {
+        &self.column_defs
+    }
+
+    pub fn next_row(&mut self, context: &EvaluationContext) -> Result<Option<Vec<ScalarValue>>, QueryExecutionError> {
+        match self.source.next_tuple(context)? {
+            None => Ok(None),
+            Some((_key, value)) => Ok(Some(
+                self.projection_items
+                    .iter()
+                    .map(|item| item.clone().eval(context, &value))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
         }
-        log::debug!("COLUMNS METADATA {:?}", column_defs);
-        let mut set = vec![];
-        while let Some((_key, value)) = self.source.next_tuple(&param_values)? {
-            let mut data = vec![];
-            for name in self.columns.iter() {
-                let index = columns.get(name).unwrap().index();
-                let value = value[index].clone();
-                data.push(value);
-            }
-            set.push(data);
+    }
+
+    pub fn execute(&mut self, context: EvaluationContext, events: &mut dyn FnMut(QueryEvent)) -> Result<usize, QueryExecutionError> {
+        events(QueryEvent::RowDescription(self.column_defs.clone()));
+        let mut selected = 0;
+        while let Some(row) = self.next_row(&context)? {
+            events(QueryEvent::DataRow(row.into_iter().map(|scalar| scalar.as_text()).collect()));
+            selected += 1;
         }
-        Ok((column_defs, set))
+        Ok(selected)
+    }
+
+    pub fn reset(&mut self) {
+        self.source.reset();
+    }
+
+    pub fn explain(&self) -> PlanNode {
+        let source = self.source.explain();
+        PlanNode::new("Select", source.estimated_rows)
+            .with_output(self.column_defs.iter().map(|(name, _)| name.clone()).collect())
+            .with_child(source)
     }
 }
+
+#[cfg(test)]
+mod tests;
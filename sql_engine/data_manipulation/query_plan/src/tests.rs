@@ -0,0 +1,418 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_manipulation_operators::WindowFrame;
+use data_manipulation_typed_tree::{TypedItem, TypedValue};
+
+struct Rows {
+    rows: Vec<Vec<ScalarValue>>,
+    position: usize,
+}
+
+impl Rows {
+    fn new(rows: Vec<Vec<ScalarValue>>) -> Box<Rows> {
+        Box::new(Rows { rows, position: 0 })
+    }
+}
+
+impl Flow for Rows {
+    type Output = (Vec<ScalarValue>, Vec<ScalarValue>);
+
+    fn next_tuple(&mut self, _context: &EvaluationContext) -> Result<Option<Self::Output>, QueryExecutionError> {
+        let row = self.rows.get(self.position).cloned();
+        self.position += 1;
+        Ok(row.map(|row| (vec![], row)))
+    }
+
+    fn explain(&self) -> PlanNode {
+        PlanNode::new("Rows", self.rows.len())
+    }
+
+    fn reset(&mut self) {
+        self.position = 0;
+    }
+}
+
+fn rows() -> Box<Rows> {
+    Rows::new(vec![
+        vec![string("b"), number(1)],
+        vec![ScalarValue::Null, number(2)],
+        vec![string("a"), number(3)],
+        vec![string("b"), number(4)],
+        vec![ScalarValue::Null, number(5)],
+    ])
+}
+
+fn string(value: &str) -> ScalarValue {
+    ScalarValue::String(value.to_owned())
+}
+
+fn number(value: i32) -> ScalarValue {
+    ScalarValue::Num {
+        value: BigDecimal::from(value),
+        type_family: SqlTypeFamily::Integer,
+    }
+}
+
+fn first_column() -> TypedTree {
+    TypedTree::Item(TypedItem::Column {
+        name: "col1".to_owned(),
+        sql_type: SqlTypeFamily::String,
+        index: 0,
+    })
+}
+
+fn collect(flow: &mut dyn Flow<Output = (Vec<ScalarValue>, Vec<ScalarValue>)>) -> Vec<Vec<ScalarValue>> {
+    let mut rows = vec![];
+    while let Some((_key, row)) = flow.next_tuple(&EvaluationContext::default()).unwrap() {
+        rows.push(row);
+    }
+    rows
+}
+
+#[test]
+fn hash_distinct_keeps_first_row_in_source_order() {
+    let mut distinct = HashDistinct::new(rows(), vec![first_column()]);
+
+    assert_eq!(
+        collect(distinct.as_mut()),
+        vec![
+            vec![string("b"), number(1)],
+            vec![ScalarValue::Null, number(2)],
+            vec![string("a"), number(3)],
+        ]
+    );
+}
+
+#[test]
+fn sort_distinct_orders_rows_by_keys_with_nulls_last() {
+    let mut distinct = SortDistinct::new(rows(), vec![first_column()]);
+
+    assert_eq!(
+        collect(distinct.as_mut()),
+        vec![
+            vec![string("a"), number(3)],
+            vec![string("b"), number(1)],
+            vec![ScalarValue::Null, number(2)],
+        ]
+    );
+}
+
+#[test]
+fn distinct_is_reset_with_its_source() {
+    let mut distinct = HashDistinct::new(rows(), vec![first_column()]);
+    collect(distinct.as_mut());
+    distinct.reset();
+
+    assert_eq!(collect(distinct.as_mut()).len(), 3);
+}
+
+fn numbers(values: &[i32]) -> Box<Rows> {
+    Rows::new(values.iter().map(|value| vec![number(*value)]).collect())
+}
+
+#[test]
+fn append_returns_rows_of_every_source() {
+    let mut append = Append::new(vec![numbers(&[1, 2]), numbers(&[]), numbers(&[2, 3])]);
+
+    assert_eq!(
+        collect(append.as_mut()),
+        vec![vec![number(1)], vec![number(2)], vec![number(2)], vec![number(3)]]
+    );
+    assert_eq!(append.explain().estimated_rows, 4);
+}
+
+#[rstest::rstest(
+    op,
+    all,
+    expected,
+    case::intersect(SetOperator::Intersect, false, vec![2, 3]),
+    case::intersect_all(SetOperator::Intersect, true, vec![2, 2, 3]),
+    case::except(SetOperator::Except, false, vec![1]),
+    case::except_all(SetOperator::Except, true, vec![1, 2, 1])
+)]
+fn hash_set_op(op: SetOperator, all: bool, expected: Vec<i32>) {
+    let mut set_op = HashSetOp::new(numbers(&[1, 2, 2, 3, 2, 1]), numbers(&[2, 3, 2, 4]), op, all);
+
+    assert_eq!(
+        collect(set_op.as_mut()),
+        expected.into_iter().map(|value| vec![number(value)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn hash_set_op_treats_nulls_as_equal() {
+    let mut set_op = HashSetOp::new(
+        Rows::new(vec![vec![ScalarValue::Null], vec![number(1)]]),
+        Rows::new(vec![vec![ScalarValue::Null]]),
+        SetOperator::Except,
+        false,
+    );
+
+    assert_eq!(collect(set_op.as_mut()), vec![vec![number(1)]]);
+}
+
+#[test]
+fn hash_set_op_is_reset_with_its_sources() {
+    let mut set_op = HashSetOp::new(numbers(&[1, 2, 2]), numbers(&[2]), SetOperator::Intersect, true);
+    collect(set_op.as_mut());
+    set_op.reset();
+
+    assert_eq!(collect(set_op.as_mut()), vec![vec![number(2)]]);
+}
+
+fn window(
+    function: WindowFunction,
+    args: Vec<TypedTree>,
+    partition_by: Vec<TypedTree>,
+    order_by: Vec<TypedSortKey>,
+    frame: WindowFrame,
+) -> TypedWindow {
+    TypedWindow {
+        type_family: Some(SqlTypeFamily::BigInt),
+        function,
+        args,
+        partition_by,
+        order_by,
+        frame,
+    }
+}
+
+fn column(index: usize, sql_type: SqlTypeFamily) -> TypedTree {
+    TypedTree::Item(TypedItem::Column {
+        name: format!("col{}", index + 1),
+        sql_type,
+        index,
+    })
+}
+
+fn ascending(expr: TypedTree) -> TypedSortKey {
+    TypedSortKey {
+        expr,
+        ascending: true,
+        nulls_first: false,
+    }
+}
+
+fn window_results(window: TypedWindow, source: Box<Rows>) -> Vec<ScalarValue> {
+    let mut window_agg = WindowAgg::new(source, vec![window]);
+    collect(window_agg.as_mut()).into_iter().map(|mut row| row.pop().unwrap()).collect()
+}
+
+#[rstest::rstest(
+    function,
+    expected,
+    case::row_number(WindowFunction::RowNumber, vec![1, 2, 3, 4]),
+    case::rank(WindowFunction::Rank, vec![1, 2, 2, 4]),
+    case::dense_rank(WindowFunction::DenseRank, vec![1, 2, 2, 3])
+)]
+fn ranking_functions(function: WindowFunction, expected: Vec<usize>) {
+    let window = window(
+        function,
+        vec![],
+        vec![],
+        vec![ascending(column(0, SqlTypeFamily::Integer))],
+        WindowFrame::default(),
+    );
+
+    assert_eq!(
+        window_results(window, numbers(&[4, 2, 1, 2])),
+        expected.into_iter().map(big_int).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn window_agg_sorts_rows_by_partitions_and_order() {
+    let mut window_agg = WindowAgg::new(
+        rows(),
+        vec![window(
+            WindowFunction::RowNumber,
+            vec![],
+            vec![first_column()],
+            vec![TypedSortKey {
+                expr: column(1, SqlTypeFamily::Integer),
+                ascending: false,
+                nulls_first: true,
+            }],
+            WindowFrame::default(),
+        )],
+    );
+
+    assert_eq!(
+        collect(window_agg.as_mut()),
+        vec![
+            vec![string("a"), number(3), big_int(1)],
+            vec![string("b"), number(4), big_int(1)],
+            vec![string("b"), number(1), big_int(2)],
+            vec![ScalarValue::Null, number(5), big_int(1)],
+            vec![ScalarValue::Null, number(2), big_int(2)],
+        ]
+    );
+}
+
+#[test]
+fn lag_and_lead_return_default_outside_of_partition() {
+    let order_by = vec![ascending(column(0, SqlTypeFamily::Integer))];
+    let lag = window(
+        WindowFunction::Lag,
+        vec![column(0, SqlTypeFamily::Integer)],
+        vec![],
+        order_by.clone(),
+        WindowFrame::default(),
+    );
+    let lead = window(
+        WindowFunction::Lead,
+        vec![
+            column(0, SqlTypeFamily::Integer),
+            TypedTree::Item(TypedItem::Const(TypedValue::Num {
+                value: BigDecimal::from(2),
+                type_family: SqlTypeFamily::SmallInt,
+            })),
+            TypedTree::Item(TypedItem::Const(TypedValue::Num {
+                value: BigDecimal::from(0),
+                type_family: SqlTypeFamily::Integer,
+            })),
+        ],
+        vec![],
+        order_by,
+        WindowFrame::default(),
+    );
+
+    assert_eq!(window_results(lag, numbers(&[3, 1, 2])), vec![ScalarValue::Null, number(1), number(2)]);
+    assert_eq!(window_results(lead, numbers(&[3, 1, 2])), vec![number(3), number(0), number(0)]);
+}
+
+#[rstest::rstest(
+    units,
+    start,
+    end,
+    expected,
+    case::rows_around_current(FrameUnits::Rows, FrameBound::Preceding(1), FrameBound::Following(1), vec![Some(3), Some(5), Some(8), Some(6)]),
+    case::range_up_to_peers(FrameUnits::Range, FrameBound::UnboundedPreceding, FrameBound::CurrentRow, vec![Some(1), Some(5), Some(5), Some(9)]),
+    case::range_with_offsets(FrameUnits::Range, FrameBound::Preceding(1), FrameBound::Following(1), vec![Some(5), Some(5), Some(5), Some(4)]),
+    case::whole_partition(FrameUnits::Rows, FrameBound::UnboundedPreceding, FrameBound::UnboundedFollowing, vec![Some(9), Some(9), Some(9), Some(9)]),
+    case::empty_frame(FrameUnits::Rows, FrameBound::Following(1), FrameBound::UnboundedFollowing, vec![Some(8), Some(6), Some(4), None])
+)]
+fn sum_over_frame(units: FrameUnits, start: FrameBound, end: FrameBound, expected: Vec<Option<usize>>) {
+    let window = window(
+        WindowFunction::Sum,
+        vec![column(0, SqlTypeFamily::Integer)],
+        vec![],
+        vec![ascending(column(0, SqlTypeFamily::Integer))],
+        WindowFrame { units, start, end },
+    );
+
+    assert_eq!(
+        window_results(window, numbers(&[2, 1, 4, 2])),
+        expected
+            .into_iter()
+            .map(|sum| sum.map(big_int).unwrap_or(ScalarValue::Null))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn all_rows_are_peers_without_order() {
+    let count = window(WindowFunction::Count, vec![], vec![], vec![], WindowFrame::default());
+    let last_value = window(
+        WindowFunction::LastValue,
+        vec![column(0, SqlTypeFamily::Integer)],
+        vec![],
+        vec![],
+        WindowFrame::default(),
+    );
+
+    assert_eq!(window_results(count, numbers(&[2, 1, 3])), vec![big_int(3), big_int(3), big_int(3)]);
+    assert_eq!(window_results(last_value, numbers(&[2, 1, 3])), vec![number(3), number(3), number(3)]);
+}
+
+#[test]
+fn aggregates_skip_nulls() {
+    let source = || Rows::new(vec![vec![number(2)], vec![ScalarValue::Null], vec![number(4)]]);
+    let aggregate = |function| window(function, vec![column(0, SqlTypeFamily::Integer)], vec![], vec![], WindowFrame::default());
+
+    assert_eq!(window_results(aggregate(WindowFunction::Count), source())[0], big_int(2));
+    assert_eq!(window_results(aggregate(WindowFunction::Min), source())[0], number(2));
+    assert_eq!(
+        window_results(aggregate(WindowFunction::Avg), source())[0],
+        ScalarValue::Num {
+            value: BigDecimal::from(3),
+            type_family: SqlTypeFamily::Double
+        }
+    );
+}
+
+#[rstest::rstest(
+    function,
+    expected,
+    case::min(WindowFunction::Min, vec![1, 1, 1, 1, 1, 2, 2]),
+    case::max(WindowFunction::Max, vec![3, 4, 4, 5, 9, 9, 9])
+)]
+fn extremes_over_sliding_frame(function: WindowFunction, expected: Vec<i32>) {
+    let window = window(
+        function,
+        vec![column(0, SqlTypeFamily::Integer)],
+        vec![],
+        vec![],
+        WindowFrame {
+            units: FrameUnits::Rows,
+            start: FrameBound::Preceding(1),
+            end: FrameBound::Following(1),
+        },
+    );
+
+    assert_eq!(
+        window_results(window, numbers(&[3, 1, 4, 1, 5, 9, 2])),
+        expected.into_iter().map(number).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn average_is_not_rounded() {
+    let window = window(
+        WindowFunction::Avg,
+        vec![column(0, SqlTypeFamily::Integer)],
+        vec![],
+        vec![],
+        WindowFrame::default(),
+    );
+
+    assert_eq!(
+        window_results(window, numbers(&[1, 1, 2]))[0],
+        ScalarValue::Num {
+            value: BigDecimal::from(4) / BigDecimal::from(3),
+            type_family: SqlTypeFamily::Double
+        }
+    );
+}
+
+#[test]
+fn window_agg_is_reset_with_its_source() {
+    let mut window_agg = WindowAgg::new(
+        numbers(&[1, 2]),
+        vec![window(WindowFunction::RowNumber, vec![], vec![], vec![], WindowFrame::default())],
+    );
+    collect(window_agg.as_mut());
+    window_agg.reset();
+
+    assert_eq!(collect(window_agg.as_mut()).len(), 2);
+    assert_eq!(
+        window_agg.explain().properties,
+        vec![(
+            "Window",
+            "row_number() OVER (RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)".to_owned()
+        )]
+    );
+}
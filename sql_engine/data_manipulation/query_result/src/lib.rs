@@ -32,10 +32,20 @@ pub enum QueryExecutionError {
     UndefinedBiFunction(String, String, String),
     DatatypeMismatch(String, String, String),
     InvalidArgumentForPowerFunction,
+    DivisionByZero,
     InvalidTextRepresentation(String, String),
     MostSpecificTypeMismatch(String, String, String, usize),
     CannotCoerce(String, String),
     NumberOutOfRange(String, String, usize),
+    SequenceLimitReached(String, bool, i64),
+    SequenceCurrentValueNotDefined(String),
+    SequenceValueOutOfBounds(String, i64, i64, i64),
+    FunctionDoesNotExist(String),
+    NegativeSubstringLength,
+    InvalidEscapeSequence(String),
+    InvalidEscapeCharacter,
+    InvalidRegularExpression(String),
+    QueryCanceled,
 }
 
 impl QueryExecutionError {
@@ -75,6 +85,26 @@ impl QueryExecutionError {
     pub fn out_of_range<T: ToString, S: ToString>(pg_type: T, column_name: S, row_index: usize) -> QueryExecutionError {
         QueryExecutionError::NumberOutOfRange(pg_type.to_string(), column_name.to_string(), row_index)
     }
+
+    pub fn sequence_limit_reached<S: ToString>(sequence_name: S, ascending: bool, limit: i64) -> QueryExecutionError {
+        QueryExecutionError::SequenceLimitReached(sequence_name.to_string(), ascending, limit)
+    }
+
+    pub fn sequence_current_value_not_defined<S: ToString>(sequence_name: S) -> QueryExecutionError {
+        QueryExecutionError::SequenceCurrentValueNotDefined(sequence_name.to_string())
+    }
+
+    pub fn sequence_value_out_of_bounds<S: ToString>(sequence_name: S, value: i64, min_value: i64, max_value: i64) -> QueryExecutionError {
+        QueryExecutionError::SequenceValueOutOfBounds(sequence_name.to_string(), value, min_value, max_value)
+    }
+
+    pub fn function_does_not_exist<F: ToString>(function: F) -> QueryExecutionError {
+        QueryExecutionError::FunctionDoesNotExist(function.to_string())
+    }
+
+    pub fn query_canceled() -> QueryExecutionError {
+        QueryExecutionError::QueryCanceled
+    }
 }
 
 impl From<QueryExecutionError> for query_response::QueryError {
@@ -86,12 +116,24 @@ impl From<QueryExecutionError> for query_response::QueryError {
             QueryExecutionError::UndefinedBiFunction(func, left_type, right_type) => QueryError::undefined_function(func, left_type, right_type),
             QueryExecutionError::DatatypeMismatch(op, target_type, actual_type) => QueryError::datatype_mismatch(op, target_type, actual_type),
             QueryExecutionError::InvalidArgumentForPowerFunction => QueryError::invalid_argument_for_power_function(),
+            QueryExecutionError::DivisionByZero => QueryError::division_by_zero(),
             QueryExecutionError::InvalidTextRepresentation(sql_type, value) => QueryError::invalid_text_representation_2(sql_type, value),
             QueryExecutionError::MostSpecificTypeMismatch(value, sql_type, column_name, index) => {
                 QueryError::most_specific_type_mismatch2(value, sql_type, column_name, index)
             }
             QueryExecutionError::CannotCoerce(from_type, to_type) => QueryError::cannot_coerce(from_type, to_type),
             QueryExecutionError::NumberOutOfRange(sql_type, column, index) => QueryError::out_of_range_2(sql_type, column, index),
+            QueryExecutionError::SequenceLimitReached(sequence, ascending, limit) => QueryError::sequence_limit_reached(sequence, ascending, limit),
+            QueryExecutionError::SequenceCurrentValueNotDefined(sequence) => QueryError::sequence_current_value_not_defined(sequence),
+            QueryExecutionError::SequenceValueOutOfBounds(sequence, value, min_value, max_value) => {
+                QueryError::sequence_value_out_of_bounds(sequence, value, min_value, max_value)
+            }
+            QueryExecutionError::FunctionDoesNotExist(function) => QueryError::function_does_not_exist(function),
+            QueryExecutionError::NegativeSubstringLength => QueryError::negative_substring_length(),
+            QueryExecutionError::InvalidEscapeSequence(message) => QueryError::invalid_escape_sequence(message),
+            QueryExecutionError::InvalidEscapeCharacter => QueryError::invalid_escape_character(),
+            QueryExecutionError::InvalidRegularExpression(message) => QueryError::invalid_regular_expression(message),
+            QueryExecutionError::QueryCanceled => QueryError::query_canceled(),
         }
     }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use data_manipulation_functions::*;
 pub use data_manipulation_operators::*;
 pub use data_manipulation_query_plan::*;
 pub use data_manipulation_query_result::*;
@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use data_manipulation_operators::SetOperator;
 use data_manipulation_typed_tree::TypedTree;
 use definition::FullTableName;
+use types::SqlTypeFamily;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct TypedInsertQuery {
@@ -36,9 +38,36 @@ pub struct TypedUpdateQuery {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct TypedSelectQuery {
-    pub full_table_name: FullTableName,
+    /// `None` for queries without `FROM` clause
+    pub full_table_name: Option<FullTableName>,
+    /// query which returns whole rows of the table to be read instead of the table itself,
+    /// e.g. rows of a view that are kept by its `DISTINCT ON` before the query filters them
+    pub subquery: Option<Box<TypedSelectQuery>>,
     pub projection_items: Vec<TypedTree>,
+    /// names of the result columns, one per projection item
+    pub column_names: Vec<String>,
     pub filter: Option<TypedTree>,
+    pub distinct: Option<TypedDistinct>,
+}
+
+/// rows are de-duplicated by the keys, for `SELECT DISTINCT` these are the projection items
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypedDistinct {
+    Rows(Vec<TypedTree>),
+    On(Vec<TypedTree>),
+}
+
+/// each side is either a `SELECT` or another set operation with the same number of columns
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypedSetOperation {
+    pub op: SetOperator,
+    pub all: bool,
+    pub left: Box<TypedQuery>,
+    pub right: Box<TypedQuery>,
+    /// names of the result columns are taken from the left query
+    pub column_names: Vec<String>,
+    /// types of the result columns that columns of both sides are cast to
+    pub column_types: Vec<SqlTypeFamily>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -47,4 +76,5 @@ pub enum TypedQuery {
     Delete(TypedDeleteQuery),
     Update(TypedUpdateQuery),
     Select(TypedSelectQuery),
+    SetOperation(TypedSetOperation),
 }
@@ -13,12 +13,38 @@
 // limitations under the License.
 
 use bigdecimal::BigDecimal;
-use data_manipulation_operators::{BiOperator, UnOperator};
+use bigdecimal::ToPrimitive;
+use data_manipulation_functions::ScalarFunction;
+use data_manipulation_operators::{
+    BiLogical, BiOperator, Comparison, ConditionalFunction, Quantifier, SequenceFunction, UnOperator, WindowFrame, WindowFunction,
+};
 use data_manipulation_query_result::QueryExecutionError;
 use scalar::ScalarValue;
 use std::fmt::{self, Display, Formatter};
+use storage::Sequence;
 use types::SqlTypeFamily;
 
+/// Values that stay the same during a single execution of a query
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct EvaluationContext {
+    params: Vec<ScalarValue>,
+    transaction_timestamp: i64,
+}
+
+impl EvaluationContext {
+    /// `transaction_timestamp` is microseconds since the Unix epoch at which the current transaction started
+    pub fn new(params: Vec<ScalarValue>, transaction_timestamp: i64) -> EvaluationContext {
+        EvaluationContext {
+            params,
+            transaction_timestamp,
+        }
+    }
+
+    pub fn params(&self) -> &[ScalarValue] {
+        &self.params
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum TypedTree {
     BiOp {
@@ -31,6 +57,43 @@ pub enum TypedTree {
         op: UnOperator,
         item: Box<TypedTree>,
     },
+    Sequence {
+        function: SequenceFunction,
+        sequence: Sequence,
+        args: Vec<TypedTree>,
+    },
+    Function {
+        type_family: SqlTypeFamily,
+        function: ScalarFunction,
+        args: Vec<TypedTree>,
+    },
+    Case {
+        type_family: Option<SqlTypeFamily>,
+        operand: Option<Box<TypedTree>>,
+        conditions: Vec<TypedTree>,
+        results: Vec<TypedTree>,
+        else_result: Option<Box<TypedTree>>,
+    },
+    Conditional {
+        type_family: Option<SqlTypeFamily>,
+        function: ConditionalFunction,
+        args: Vec<TypedTree>,
+    },
+    Quantified {
+        type_family: SqlTypeFamily,
+        left: Box<TypedTree>,
+        op: BiOperator,
+        quantifier: Quantifier,
+        list: Vec<TypedTree>,
+    },
+    Between {
+        type_family: SqlTypeFamily,
+        item: Box<TypedTree>,
+        low: Box<TypedTree>,
+        high: Box<TypedTree>,
+        negated: bool,
+    },
+    Window(TypedWindow),
     Item(TypedItem),
 }
 
@@ -40,21 +103,716 @@ impl TypedTree {
             TypedTree::Item(item) => item.type_family(),
             TypedTree::BiOp { type_family, .. } => Some(*type_family),
             TypedTree::UnOp { item, .. } => item.type_family(),
+            TypedTree::Sequence { .. } => Some(SqlTypeFamily::BigInt),
+            TypedTree::Function { type_family, .. } => Some(*type_family),
+            TypedTree::Case { type_family, .. } => *type_family,
+            TypedTree::Conditional { type_family, .. } => *type_family,
+            TypedTree::Quantified { type_family, .. } => Some(*type_family),
+            TypedTree::Between { type_family, .. } => Some(*type_family),
+            TypedTree::Window(window) => window.type_family,
+        }
+    }
+
+    /// replaces window function calls with references to the columns that
+    /// window operator appends after `first_index` source columns
+    pub fn extract_windows(self, windows: &mut Vec<TypedWindow>, first_index: usize) -> TypedTree {
+        let extract_all = |trees: Vec<TypedTree>, windows: &mut Vec<TypedWindow>| {
+            trees
+                .into_iter()
+                .map(|tree| tree.extract_windows(windows, first_index))
+                .collect::<Vec<_>>()
+        };
+        let extract_boxed = |tree: Box<TypedTree>, windows: &mut Vec<TypedWindow>| Box::new(tree.extract_windows(windows, first_index));
+        match self {
+            TypedTree::Window(window) => {
+                let position = match windows.iter().position(|existing| existing == &window) {
+                    Some(position) => position,
+                    None => {
+                        windows.push(window.clone());
+                        windows.len() - 1
+                    }
+                };
+                TypedTree::Item(TypedItem::Column {
+                    name: window.function.to_string(),
+                    sql_type: window.type_family.unwrap_or(SqlTypeFamily::String),
+                    index: first_index + position,
+                })
+            }
+            TypedTree::BiOp {
+                type_family,
+                left,
+                op,
+                right,
+            } => TypedTree::BiOp {
+                type_family,
+                left: extract_boxed(left, windows),
+                op,
+                right: extract_boxed(right, windows),
+            },
+            TypedTree::UnOp { op, item } => TypedTree::UnOp {
+                op,
+                item: extract_boxed(item, windows),
+            },
+            TypedTree::Sequence { function, sequence, args } => TypedTree::Sequence {
+                function,
+                sequence,
+                args: extract_all(args, windows),
+            },
+            TypedTree::Function { type_family, function, args } => TypedTree::Function {
+                type_family,
+                function,
+                args: extract_all(args, windows),
+            },
+            TypedTree::Case {
+                type_family,
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => TypedTree::Case {
+                type_family,
+                operand: operand.map(|operand| extract_boxed(operand, windows)),
+                conditions: extract_all(conditions, windows),
+                results: extract_all(results, windows),
+                else_result: else_result.map(|else_result| extract_boxed(else_result, windows)),
+            },
+            TypedTree::Conditional { type_family, function, args } => TypedTree::Conditional {
+                type_family,
+                function,
+                args: extract_all(args, windows),
+            },
+            TypedTree::Quantified {
+                type_family,
+                left,
+                op,
+                quantifier,
+                list,
+            } => TypedTree::Quantified {
+                type_family,
+                left: extract_boxed(left, windows),
+                op,
+                quantifier,
+                list: extract_all(list, windows),
+            },
+            TypedTree::Between {
+                type_family,
+                item,
+                low,
+                high,
+                negated,
+            } => TypedTree::Between {
+                type_family,
+                item: extract_boxed(item, windows),
+                low: extract_boxed(low, windows),
+                high: extract_boxed(high, windows),
+                negated,
+            },
+            TypedTree::Item(item) => TypedTree::Item(item),
         }
     }
 
-    pub fn eval(self, param_values: &[ScalarValue], table_row: &[ScalarValue]) -> Result<ScalarValue, QueryExecutionError> {
+    pub fn eval(self, context: &EvaluationContext, table_row: &[ScalarValue]) -> Result<ScalarValue, QueryExecutionError> {
         match self {
             TypedTree::Item(TypedItem::Const(value)) => Ok(value.eval()),
             TypedTree::Item(TypedItem::Column { index, .. }) => Ok(table_row[index].clone()),
-            TypedTree::Item(TypedItem::Param { index, .. }) => Ok(param_values[index].clone()),
+            TypedTree::Item(TypedItem::Param { index, .. }) => Ok(context.params[index].clone()),
             TypedTree::Item(TypedItem::Null(_)) => Ok(ScalarValue::Null),
-            TypedTree::UnOp { op, item } => op.eval(item.eval(param_values, table_row)?),
-            TypedTree::BiOp { left, op, right, .. } => op.eval(left.eval(param_values, table_row)?, right.eval(param_values, table_row)?),
+            TypedTree::UnOp { op, item } => op.eval(item.eval(context, table_row)?),
+            TypedTree::BiOp { left, op, right, .. } => op.eval(left.eval(context, table_row)?, right.eval(context, table_row)?),
+            TypedTree::Function { function, args, .. } => function.eval(
+                args.into_iter().map(|arg| arg.eval(context, table_row)).collect::<Result<Vec<_>, _>>()?,
+                context.transaction_timestamp,
+            ),
+            // only the taken branch is evaluated
+            TypedTree::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+                ..
+            } => {
+                let operand = operand.map(|operand| operand.eval(context, table_row)).transpose()?;
+                for (condition, result) in conditions.into_iter().zip(results) {
+                    let matched = match (&operand, condition.eval(context, table_row)?) {
+                        (_, ScalarValue::Null) | (Some(ScalarValue::Null), _) => false,
+                        (None, ScalarValue::Bool(value)) => value,
+                        (None, other) => {
+                            return Err(QueryExecutionError::datatype_mismatch(
+                                "CASE/WHEN",
+                                SqlTypeFamily::Bool,
+                                other.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
+                            ))
+                        }
+                        (Some(operand), value) => BiOperator::Comparison(Comparison::Eq).eval(operand.clone(), value)? == ScalarValue::Bool(true),
+                    };
+                    if matched {
+                        return result.eval(context, table_row);
+                    }
+                }
+                match else_result {
+                    Some(else_result) => else_result.eval(context, table_row),
+                    None => Ok(ScalarValue::Null),
+                }
+            }
+            TypedTree::Conditional { function, args, .. } => {
+                let mut args = args.into_iter();
+                match function {
+                    ConditionalFunction::Coalesce => {
+                        for arg in args {
+                            match arg.eval(context, table_row)? {
+                                ScalarValue::Null => continue,
+                                value => return Ok(value),
+                            }
+                        }
+                        Ok(ScalarValue::Null)
+                    }
+                    ConditionalFunction::NullIf => {
+                        let left = args
+                            .next()
+                            .map(|arg| arg.eval(context, table_row))
+                            .transpose()?
+                            .unwrap_or(ScalarValue::Null);
+                        let right = args
+                            .next()
+                            .map(|arg| arg.eval(context, table_row))
+                            .transpose()?
+                            .unwrap_or(ScalarValue::Null);
+                        match (left, right) {
+                            (ScalarValue::Null, _) => Ok(ScalarValue::Null),
+                            (left, ScalarValue::Null) => Ok(left),
+                            (left, right) => {
+                                if BiOperator::Comparison(Comparison::Eq).eval(left.clone(), right)? == ScalarValue::Bool(true) {
+                                    Ok(ScalarValue::Null)
+                                } else {
+                                    Ok(left)
+                                }
+                            }
+                        }
+                    }
+                    ConditionalFunction::Greatest | ConditionalFunction::Least => {
+                        let comparison = if function == ConditionalFunction::Greatest {
+                            Comparison::Gt
+                        } else {
+                            Comparison::Lt
+                        };
+                        let mut current = ScalarValue::Null;
+                        for arg in args {
+                            current = match (current, arg.eval(context, table_row)?) {
+                                (current, ScalarValue::Null) => current,
+                                (ScalarValue::Null, value) => value,
+                                (current, value) => {
+                                    if BiOperator::Comparison(comparison).eval(value.clone(), current.clone())? == ScalarValue::Bool(true) {
+                                        value
+                                    } else {
+                                        current
+                                    }
+                                }
+                            };
+                        }
+                        Ok(current)
+                    }
+                }
+            }
+            // the result is NULL when no item decides it and some of comparisons is NULL
+            TypedTree::Quantified {
+                left, op, quantifier, list, ..
+            } => {
+                let left = left.eval(context, table_row)?;
+                let decisive = quantifier == Quantifier::Any;
+                let mut result = ScalarValue::Bool(!decisive);
+                for item in list {
+                    match op.eval(left.clone(), item.eval(context, table_row)?)? {
+                        ScalarValue::Bool(value) if value == decisive => return Ok(ScalarValue::Bool(decisive)),
+                        ScalarValue::Bool(_) => {}
+                        ScalarValue::Null => result = ScalarValue::Null,
+                        other => {
+                            return Err(QueryExecutionError::datatype_mismatch(
+                                format!("{} {}", op, quantifier),
+                                SqlTypeFamily::Bool,
+                                other.type_family().map(|ty| ty.to_string()).unwrap_or_else(|| "unknown".to_owned()),
+                            ))
+                        }
+                    }
+                }
+                Ok(result)
+            }
+            TypedTree::Between {
+                item, low, high, negated, ..
+            } => {
+                let item = item.eval(context, table_row)?;
+                let low = BiOperator::Comparison(Comparison::GtEq).eval(item.clone(), low.eval(context, table_row)?)?;
+                let high = BiOperator::Comparison(Comparison::LtEq).eval(item, high.eval(context, table_row)?)?;
+                let between = BiOperator::Logical(BiLogical::And).eval(low, high)?;
+                if negated {
+                    UnOperator::LogicalNot.eval(between)
+                } else {
+                    Ok(between)
+                }
+            }
+            TypedTree::Window(window) => unreachable!("{} has to be computed by window operator", window),
+            TypedTree::Sequence { function, sequence, args } => {
+                let value = match function {
+                    SequenceFunction::NextVal => sequence.next_value().ok_or_else(|| {
+                        let (min_value, max_value) = sequence.bounds();
+                        if sequence.increment() > 0 {
+                            QueryExecutionError::sequence_limit_reached(sequence.name(), true, max_value)
+                        } else {
+                            QueryExecutionError::sequence_limit_reached(sequence.name(), false, min_value)
+                        }
+                    })?,
+                    SequenceFunction::CurrVal => sequence
+                        .current_value()
+                        .ok_or_else(|| QueryExecutionError::sequence_current_value_not_defined(sequence.name()))?,
+                    SequenceFunction::SetVal => {
+                        let value = match args.into_iter().next().map(|arg| arg.eval(context, table_row)).transpose()? {
+                            Some(ScalarValue::Num { value, .. }) if value.is_integer() => match value.to_i64() {
+                                Some(value) => value,
+                                None => return Err(QueryExecutionError::out_of_range(SqlTypeFamily::BigInt, "setval", 1)),
+                            },
+                            Some(ScalarValue::Null) => return Ok(ScalarValue::Null),
+                            other => {
+                                return Err(QueryExecutionError::undefined_function(
+                                    function,
+                                    other.and_then(|value| value.type_family()).map(|ty| ty.to_string()).unwrap_or_default(),
+                                ))
+                            }
+                        };
+                        if !sequence.set_value(value) {
+                            let (min_value, max_value) = sequence.bounds();
+                            return Err(QueryExecutionError::sequence_value_out_of_bounds(
+                                sequence.name(),
+                                value,
+                                min_value,
+                                max_value,
+                            ));
+                        }
+                        value
+                    }
+                };
+                Ok(ScalarValue::Num {
+                    value: BigDecimal::from(value),
+                    type_family: SqlTypeFamily::BigInt,
+                })
+            }
+        }
+    }
+
+    /// folds constant subtrees and simplifies boolean logic so that they are not evaluated for every row
+    pub fn simplify(self) -> Result<TypedTree, QueryExecutionError> {
+        match self {
+            TypedTree::Item(item) => Ok(TypedTree::Item(item)),
+            // sequence functions change sequence state, so they are never folded
+            TypedTree::Sequence { function, sequence, args } => Ok(TypedTree::Sequence {
+                function,
+                sequence,
+                args: args.into_iter().map(TypedTree::simplify).collect::<Result<_, _>>()?,
+            }),
+            TypedTree::Function { type_family, function, args } => {
+                let args = args.into_iter().map(TypedTree::simplify).collect::<Result<Vec<_>, _>>()?;
+                let constant = !function.is_stable() && args.iter().all(TypedTree::is_constant);
+                let tree = TypedTree::Function { type_family, function, args };
+                if constant {
+                    tree.fold()
+                } else {
+                    Ok(tree)
+                }
+            }
+            // branches are simplified lazily, those that fail are left as is to raise an error only when taken
+            TypedTree::Case {
+                type_family,
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                let operand = operand.map(|operand| Box::new(operand.simplify_lazily()));
+                let conditions = conditions.into_iter().map(TypedTree::simplify_lazily).collect::<Vec<_>>();
+                let results = results.into_iter().map(TypedTree::simplify_lazily).collect::<Vec<_>>();
+                let else_result = else_result.map(|else_result| Box::new(else_result.simplify_lazily()));
+                let constant = operand.iter().chain(else_result.iter()).all(|tree| tree.is_constant())
+                    && conditions.iter().chain(results.iter()).all(TypedTree::is_constant);
+                let tree = TypedTree::Case {
+                    type_family,
+                    operand,
+                    conditions,
+                    results,
+                    else_result,
+                };
+                if constant {
+                    tree.fold()
+                } else {
+                    Ok(tree)
+                }
+            }
+            TypedTree::Conditional { type_family, function, args } => {
+                let args = args.into_iter().map(TypedTree::simplify_lazily).collect::<Vec<_>>();
+                let constant = args.iter().all(TypedTree::is_constant);
+                let tree = TypedTree::Conditional { type_family, function, args };
+                if constant {
+                    tree.fold()
+                } else {
+                    Ok(tree)
+                }
+            }
+            TypedTree::Quantified {
+                type_family,
+                left,
+                op,
+                quantifier,
+                list,
+            } => {
+                let left = left.simplify()?;
+                let list = list.into_iter().map(TypedTree::simplify).collect::<Result<Vec<_>, _>>()?;
+                let constant = left.is_constant() && list.iter().all(TypedTree::is_constant);
+                let tree = TypedTree::Quantified {
+                    type_family,
+                    left: Box::new(left),
+                    op,
+                    quantifier,
+                    list,
+                };
+                if constant {
+                    tree.fold()
+                } else {
+                    Ok(tree)
+                }
+            }
+            TypedTree::Between {
+                type_family,
+                item,
+                low,
+                high,
+                negated,
+            } => {
+                let item = item.simplify()?;
+                let low = low.simplify()?;
+                let high = high.simplify()?;
+                let constant = item.is_constant() && low.is_constant() && high.is_constant();
+                let tree = TypedTree::Between {
+                    type_family,
+                    item: Box::new(item),
+                    low: Box::new(low),
+                    high: Box::new(high),
+                    negated,
+                };
+                if constant {
+                    tree.fold()
+                } else {
+                    Ok(tree)
+                }
+            }
+            // window functions depend on other rows, so only their arguments are folded
+            TypedTree::Window(window) => Ok(TypedTree::Window(TypedWindow {
+                args: window.args.into_iter().map(TypedTree::simplify).collect::<Result<_, _>>()?,
+                partition_by: window.partition_by.into_iter().map(TypedTree::simplify).collect::<Result<_, _>>()?,
+                order_by: window
+                    .order_by
+                    .into_iter()
+                    .map(|key| {
+                        Ok(TypedSortKey {
+                            expr: key.expr.simplify()?,
+                            ..key
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+                ..window
+            })),
+            TypedTree::UnOp { op, item } => match (op, item.simplify()?) {
+                (
+                    UnOperator::LogicalNot,
+                    TypedTree::UnOp {
+                        op: UnOperator::LogicalNot,
+                        item,
+                    },
+                ) if item.is_bool() => Ok(*item),
+                (
+                    UnOperator::LogicalNot,
+                    TypedTree::BiOp {
+                        type_family,
+                        left,
+                        op: BiOperator::Comparison(comparison),
+                        right,
+                    },
+                ) => Ok(TypedTree::BiOp {
+                    type_family,
+                    left,
+                    op: BiOperator::Comparison(comparison.negate()),
+                    right,
+                }),
+                (op, item) => {
+                    let constant = item.is_constant();
+                    let tree = TypedTree::UnOp { op, item: Box::new(item) };
+                    if constant {
+                        tree.fold()
+                    } else {
+                        Ok(tree)
+                    }
+                }
+            },
+            TypedTree::BiOp {
+                type_family,
+                left,
+                op,
+                right,
+            } => {
+                let left = left.simplify()?;
+                let right = right.simplify()?;
+                if left.is_constant() && right.is_constant() {
+                    return TypedTree::BiOp {
+                        type_family,
+                        left: Box::new(left),
+                        op,
+                        right: Box::new(right),
+                    }
+                    .fold();
+                }
+                match op {
+                    BiOperator::Logical(logical) if left.is_bool() && right.is_bool() => match (logical, left.as_bool(), right.as_bool()) {
+                        (BiLogical::And, Some(true), _) | (BiLogical::Or, Some(false), _) => return Ok(right),
+                        (BiLogical::And, _, Some(true)) | (BiLogical::Or, _, Some(false)) => return Ok(left),
+                        (BiLogical::And, Some(false), _) | (BiLogical::Or, Some(true), _) => return Ok(left),
+                        (BiLogical::And, _, Some(false)) | (BiLogical::Or, _, Some(true)) => return Ok(right),
+                        _ => {}
+                    },
+                    // constants go to the right side of comparisons, e.g. `5 < col` becomes `col > 5`
+                    BiOperator::Comparison(comparison) if left.is_constant() => {
+                        return Ok(TypedTree::BiOp {
+                            type_family,
+                            left: Box::new(right),
+                            op: BiOperator::Comparison(comparison.flip()),
+                            right: Box::new(left),
+                        })
+                    }
+                    _ => {}
+                }
+                Ok(TypedTree::BiOp {
+                    type_family,
+                    left: Box::new(left),
+                    op,
+                    right: Box::new(right),
+                })
+            }
+        }
+    }
+
+    fn simplify_lazily(self) -> TypedTree {
+        self.clone().simplify().unwrap_or(self)
+    }
+
+    fn fold(self) -> Result<TypedTree, QueryExecutionError> {
+        let type_family = self.type_family();
+        // constant trees read neither parameters nor transaction timestamp
+        let value = match self.clone().eval(&EvaluationContext::default(), &[])? {
+            ScalarValue::Num { value, type_family } => TypedValue::Num { value, type_family },
+            ScalarValue::String(value) => TypedValue::String(value),
+            ScalarValue::Bool(value) => TypedValue::Bool(value),
+            ScalarValue::TimestampTz(_) => return Ok(self),
+            ScalarValue::Null => return Ok(TypedTree::Item(TypedItem::Null(type_family))),
+        };
+        Ok(TypedTree::Item(TypedItem::Const(value)))
+    }
+
+    fn is_constant(&self) -> bool {
+        matches!(self, TypedTree::Item(TypedItem::Const(_)) | TypedTree::Item(TypedItem::Null(_)))
+    }
+
+    /// type of the evaluated value, `BiOp` of comparison carries type of its operands,
+    /// so the result type is derived from the operator
+    pub fn result_type(&self) -> Option<SqlTypeFamily> {
+        match self {
+            TypedTree::BiOp {
+                op: BiOperator::Comparison(_),
+                ..
+            }
+            | TypedTree::BiOp {
+                op: BiOperator::Logical(_), ..
+            }
+            | TypedTree::BiOp {
+                op: BiOperator::Matching(_), ..
+            }
+            | TypedTree::UnOp {
+                op: UnOperator::LogicalNot, ..
+            }
+            | TypedTree::UnOp { op: UnOperator::Is(_), .. }
+            | TypedTree::Quantified { .. }
+            | TypedTree::Between { .. } => Some(SqlTypeFamily::Bool),
+            TypedTree::BiOp {
+                op: BiOperator::StringOp(_), ..
+            } => Some(SqlTypeFamily::String),
+            TypedTree::UnOp {
+                op: UnOperator::Cast(sql_type),
+                ..
+            } => Some(sql_type.family()),
+            other => other.type_family(),
+        }
+    }
+
+    fn is_bool(&self) -> bool {
+        self.result_type() == Some(SqlTypeFamily::Bool)
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            TypedTree::Item(TypedItem::Const(TypedValue::Bool(value))) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TypedTree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypedTree::BiOp { left, op, right, .. } => write!(f, "({} {} {})", left, op, right),
+            TypedTree::UnOp {
+                op: UnOperator::LogicalNot,
+                item,
+            } => write!(f, "(NOT {})", item),
+            TypedTree::UnOp {
+                op: op @ UnOperator::Cast(_),
+                item,
+            } => write!(f, "({}{})", item, op),
+            TypedTree::UnOp {
+                op: op @ UnOperator::Is(_),
+                item,
+            } => write!(f, "({} {})", item, op),
+            TypedTree::UnOp { op, item } => write!(f, "({}{})", op, item),
+            TypedTree::Sequence { function, sequence, args } => {
+                write!(f, "{}('{}'", function, sequence.name())?;
+                for arg in args {
+                    write!(f, ", {}", arg)?;
+                }
+                write!(f, ")")
+            }
+            TypedTree::Function { function, args, .. } => {
+                write!(f, "{}(", function)?;
+                for (index, arg) in args.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+            TypedTree::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+                ..
+            } => {
+                write!(f, "CASE")?;
+                if let Some(operand) = operand {
+                    write!(f, " {}", operand)?;
+                }
+                for (condition, result) in conditions.iter().zip(results.iter()) {
+                    write!(f, " WHEN {} THEN {}", condition, result)?;
+                }
+                if let Some(else_result) = else_result {
+                    write!(f, " ELSE {}", else_result)?;
+                }
+                write!(f, " END")
+            }
+            TypedTree::Quantified {
+                left, op, quantifier, list, ..
+            } => {
+                write!(f, "({} {} {}(", left, op, quantifier)?;
+                for (index, item) in list.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "))")
+            }
+            TypedTree::Between {
+                item, low, high, negated, ..
+            } => {
+                let not = if *negated { "NOT " } else { "" };
+                write!(f, "({} {}BETWEEN {} AND {})", item, not, low, high)
+            }
+            TypedTree::Conditional { function, args, .. } => {
+                write!(f, "{}(", function)?;
+                for (index, arg) in args.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+            TypedTree::Window(window) => write!(f, "{}", window),
+            TypedTree::Item(item) => write!(f, "{}", item),
         }
     }
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypedWindow {
+    pub type_family: Option<SqlTypeFamily>,
+    pub function: WindowFunction,
+    pub args: Vec<TypedTree>,
+    pub partition_by: Vec<TypedTree>,
+    pub order_by: Vec<TypedSortKey>,
+    pub frame: WindowFrame,
+}
+
+impl Display for TypedWindow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.function)?;
+        if self.function == WindowFunction::Count && self.args.is_empty() {
+            write!(f, "*")?;
+        }
+        for (index, arg) in self.args.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", arg)?;
+        }
+        write!(f, ") OVER (")?;
+        if !self.partition_by.is_empty() {
+            write!(f, "PARTITION BY ")?;
+            for (index, key) in self.partition_by.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", key)?;
+            }
+            write!(f, " ")?;
+        }
+        if !self.order_by.is_empty() {
+            write!(f, "ORDER BY ")?;
+            for (index, key) in self.order_by.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", key)?;
+            }
+            write!(f, " ")?;
+        }
+        write!(f, "{})", self.frame)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypedSortKey {
+    pub expr: TypedTree,
+    pub ascending: bool,
+    pub nulls_first: bool,
+}
+
+impl Display for TypedSortKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let direction = if self.ascending { "" } else { " DESC" };
+        // only the order of NULLs that differs from the default one is shown
+        let nulls = match (self.ascending, self.nulls_first) {
+            (true, true) => " NULLS FIRST",
+            (false, false) => " NULLS LAST",
+            _ => "",
+        };
+        write!(f, "{}{}{}", self.expr, direction, nulls)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum TypedItem {
     Const(TypedValue),
@@ -74,6 +832,18 @@ impl TypedItem {
     }
 }
 
+impl Display for TypedItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypedItem::Const(TypedValue::String(value)) => write!(f, "'{}'", value),
+            TypedItem::Const(value) => write!(f, "{}", value),
+            TypedItem::Param { index, .. } => write!(f, "${}", index + 1),
+            TypedItem::Null(_) => write!(f, "NULL"),
+            TypedItem::Column { name, .. } => write!(f, "{}", name),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum TypedValue {
     Num { value: BigDecimal, type_family: SqlTypeFamily },
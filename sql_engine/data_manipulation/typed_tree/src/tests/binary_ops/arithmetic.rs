@@ -41,7 +41,7 @@ fn number_and_number(operator: BiOperator, left: u32, right: u32, result: u32) {
                 type_family: SqlTypeFamily::SmallInt
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Num {
             value: BigDecimal::from(result),
             type_family: SqlTypeFamily::BigInt
@@ -49,6 +49,30 @@ fn number_and_number(operator: BiOperator, left: u32, right: u32, result: u32) {
     );
 }
 
+#[rstest::rstest(
+    operator,
+    case::div(BiOperator::Arithmetic(BiArithmetic::Div)),
+    case::modulo(BiOperator::Arithmetic(BiArithmetic::Mod))
+)]
+fn number_by_zero(operator: BiOperator) {
+    assert_eq!(
+        TypedTree::BiOp {
+            type_family: SqlTypeFamily::SmallInt,
+            left: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Num {
+                value: BigDecimal::from(5),
+                type_family: SqlTypeFamily::SmallInt
+            }))),
+            op: operator,
+            right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Num {
+                value: BigDecimal::from(0),
+                type_family: SqlTypeFamily::SmallInt
+            }))),
+        }
+        .eval(&EvaluationContext::default(), &[]),
+        Err(QueryExecutionError::DivisionByZero)
+    );
+}
+
 #[rstest::rstest(
     operator,
     case::add(BiOperator::Arithmetic(BiArithmetic::Add)),
@@ -69,7 +93,7 @@ fn number_and_boolean(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Integer,
@@ -87,7 +111,7 @@ fn number_and_boolean(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -116,7 +140,7 @@ fn number_and_string(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::invalid_text_representation(SqlTypeFamily::Integer, &"abc"))
     );
 
@@ -130,7 +154,7 @@ fn number_and_string(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::invalid_text_representation(SqlTypeFamily::Integer, &"abc"))
     );
 }
@@ -152,7 +176,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::String,
@@ -167,7 +191,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -40,7 +40,7 @@ fn integer_and_integer(operator: BiOperator, left: u32, right: u32, result: u32)
                 type_family: SqlTypeFamily::SmallInt
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Num {
             value: BigDecimal::from(result),
             type_family: SqlTypeFamily::BigInt
@@ -70,7 +70,7 @@ fn integer_and_float(operator: BiOperator) {
                 type_family: SqlTypeFamily::Real
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::SmallInt,
@@ -98,7 +98,7 @@ fn integer_and_boolean(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Integer,
@@ -116,7 +116,7 @@ fn integer_and_boolean(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -144,7 +144,7 @@ fn integer_and_string(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::invalid_text_representation(SqlTypeFamily::Integer, &"abc"))
     );
 
@@ -158,7 +158,7 @@ fn integer_and_string(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::invalid_text_representation(SqlTypeFamily::Integer, &"abc"))
     );
 }
@@ -179,7 +179,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::String,
@@ -194,7 +194,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -215,7 +215,7 @@ fn others(operator: BiOperator) {
                 type_family: SqlTypeFamily::Real
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Real,
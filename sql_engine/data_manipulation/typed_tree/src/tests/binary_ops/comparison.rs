@@ -41,7 +41,7 @@ fn number_and_number(operator: BiOperator, left: u32, right: u32, result: bool)
                 type_family: SqlTypeFamily::SmallInt
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Bool(result))
     );
 }
@@ -66,7 +66,7 @@ fn string_and_string(operator: BiOperator, left: &str, right: &str, result: bool
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String(right.to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Bool(result))
     );
 }
@@ -91,7 +91,7 @@ fn boolean_and_boolean(operator: BiOperator, left: bool, right: bool, result: bo
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(right)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Bool(result))
     );
 }
@@ -116,7 +116,7 @@ fn others(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::String,
@@ -134,7 +134,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Integer,
@@ -148,7 +148,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::String,
@@ -163,7 +163,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -180,7 +180,7 @@ fn others(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -198,7 +198,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Integer,
@@ -31,7 +31,7 @@ fn boolean_and_boolean(operator: BiOperator, left: bool, right: bool, result: bo
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(right)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Bool(result))
     );
 }
@@ -48,7 +48,7 @@ fn number_and_boolean(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Integer,
@@ -66,7 +66,7 @@ fn number_and_boolean(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -84,7 +84,7 @@ fn boolean_and_string(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -99,7 +99,7 @@ fn boolean_and_string(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::String,
@@ -120,7 +120,7 @@ fn others(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::String,
@@ -138,7 +138,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Integer,
@@ -20,8 +20,11 @@ use data_manipulation_operators::Matching;
     left,
     right,
     result,
-    case::like(BiOperator::Matching(Matching::Like), "1%", "123", true),
-    case::not_like(BiOperator::Matching(Matching::NotLike), "1%", "234", true)
+    case::like(BiOperator::Matching(Matching::Like), "123", "1%", true),
+    case::not_like(BiOperator::Matching(Matching::NotLike), "234", "1%", true),
+    case::ilike(BiOperator::Matching(Matching::ILike), "ABC", "a%", true),
+    case::similar_to(BiOperator::Matching(Matching::SimilarTo), "abc", "a(b|c)+", true),
+    case::regex_match(BiOperator::Matching(Matching::RegexMatch), "abc", "^a.c$", true)
 )]
 fn string_and_string(operator: BiOperator, left: &str, right: &str, result: bool) {
     assert_eq!(
@@ -31,7 +34,7 @@ fn string_and_string(operator: BiOperator, left: &str, right: &str, result: bool
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String(right.to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Bool(result))
     );
 }
@@ -49,7 +52,7 @@ fn string_and_boolean(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::String,
@@ -64,7 +67,7 @@ fn string_and_boolean(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -89,7 +92,7 @@ fn string_and_number(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Integer,
@@ -107,7 +110,7 @@ fn string_and_number(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::String,
@@ -129,7 +132,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(false)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -146,7 +149,7 @@ fn others(operator: BiOperator) {
             op: operator,
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(false)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Integer,
@@ -163,7 +166,7 @@ fn others(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             })))
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Bool,
@@ -184,7 +187,7 @@ fn others(operator: BiOperator) {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             operator,
             SqlTypeFamily::Integer,
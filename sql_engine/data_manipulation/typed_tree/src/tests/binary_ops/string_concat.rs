@@ -24,7 +24,7 @@ fn string_and_string() {
             op: BiOperator::StringOp(Concat),
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("2".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::String("12".to_owned()))
     );
 }
@@ -38,7 +38,7 @@ fn string_and_boolean() {
             op: BiOperator::StringOp(Concat),
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             BiOperator::StringOp(Concat),
             SqlTypeFamily::String,
@@ -53,7 +53,7 @@ fn string_and_boolean() {
             op: BiOperator::StringOp(Concat),
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             BiOperator::StringOp(Concat),
             SqlTypeFamily::Bool,
@@ -74,7 +74,7 @@ fn string_and_number() {
             op: BiOperator::StringOp(Concat),
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             BiOperator::StringOp(Concat),
             SqlTypeFamily::Integer,
@@ -92,7 +92,7 @@ fn string_and_number() {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             BiOperator::StringOp(Concat),
             SqlTypeFamily::String,
@@ -110,7 +110,7 @@ fn others() {
             op: BiOperator::StringOp(Concat),
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(false)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             BiOperator::StringOp(Concat),
             SqlTypeFamily::Bool,
@@ -127,7 +127,7 @@ fn others() {
             op: BiOperator::StringOp(Concat),
             right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(false)))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             BiOperator::StringOp(Concat),
             SqlTypeFamily::Integer,
@@ -144,7 +144,7 @@ fn others() {
                 type_family: SqlTypeFamily::Integer
             })))
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             BiOperator::StringOp(Concat),
             SqlTypeFamily::Bool,
@@ -165,7 +165,7 @@ fn others() {
                 type_family: SqlTypeFamily::Integer
             }))),
         }
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Err(QueryExecutionError::undefined_bi_function(
             BiOperator::StringOp(Concat),
             SqlTypeFamily::Integer,
@@ -0,0 +1,239 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_manipulation_operators::BiArithmetic;
+
+fn number(value: i32) -> TypedTree {
+    TypedTree::Item(TypedItem::Const(TypedValue::Num {
+        value: BigDecimal::from(value),
+        type_family: SqlTypeFamily::Integer,
+    }))
+}
+
+fn boolean(value: bool) -> TypedTree {
+    TypedTree::Item(TypedItem::Const(TypedValue::Bool(value)))
+}
+
+fn null() -> TypedTree {
+    TypedTree::Item(TypedItem::Null(None))
+}
+
+fn column(index: usize) -> TypedTree {
+    TypedTree::Item(TypedItem::Column {
+        name: "col".to_owned(),
+        sql_type: SqlTypeFamily::Integer,
+        index,
+    })
+}
+
+fn division_by_zero() -> TypedTree {
+    TypedTree::BiOp {
+        type_family: SqlTypeFamily::Integer,
+        left: Box::new(number(1)),
+        op: BiOperator::Arithmetic(BiArithmetic::Div),
+        right: Box::new(number(0)),
+    }
+}
+
+fn scalar(value: i32) -> ScalarValue {
+    ScalarValue::Num {
+        value: BigDecimal::from(value),
+        type_family: SqlTypeFamily::Integer,
+    }
+}
+
+fn conditional(function: ConditionalFunction, args: Vec<TypedTree>) -> TypedTree {
+    TypedTree::Conditional {
+        type_family: Some(SqlTypeFamily::Integer),
+        function,
+        args,
+    }
+}
+
+#[cfg(test)]
+mod case {
+    use super::*;
+
+    fn searched(conditions: Vec<TypedTree>, results: Vec<TypedTree>, else_result: Option<TypedTree>) -> TypedTree {
+        TypedTree::Case {
+            type_family: Some(SqlTypeFamily::Integer),
+            operand: None,
+            conditions,
+            results,
+            else_result: else_result.map(Box::new),
+        }
+    }
+
+    #[test]
+    fn first_true_condition_is_taken() {
+        assert_eq!(
+            searched(
+                vec![boolean(false), boolean(true), boolean(true)],
+                vec![number(1), number(2), number(3)],
+                None
+            )
+            .eval(&EvaluationContext::default(), &[]),
+            Ok(scalar(2))
+        );
+    }
+
+    #[test]
+    fn null_condition_is_not_taken() {
+        assert_eq!(
+            searched(vec![null()], vec![number(1)], Some(number(2))).eval(&EvaluationContext::default(), &[]),
+            Ok(scalar(2))
+        );
+    }
+
+    #[test]
+    fn without_else_evaluates_to_null() {
+        assert_eq!(
+            searched(vec![boolean(false)], vec![number(1)], None).eval(&EvaluationContext::default(), &[]),
+            Ok(ScalarValue::Null)
+        );
+    }
+
+    #[test]
+    fn non_boolean_condition() {
+        assert_eq!(
+            searched(vec![number(1)], vec![number(1)], None).eval(&EvaluationContext::default(), &[]),
+            Err(QueryExecutionError::datatype_mismatch(
+                "CASE/WHEN",
+                SqlTypeFamily::Bool,
+                SqlTypeFamily::Integer
+            ))
+        );
+    }
+
+    #[test]
+    fn simple_case_compares_operand() {
+        let tree = TypedTree::Case {
+            type_family: Some(SqlTypeFamily::Integer),
+            operand: Some(Box::new(column(0))),
+            conditions: vec![number(1), number(2)],
+            results: vec![number(10), number(20)],
+            else_result: None,
+        };
+
+        assert_eq!(tree.clone().eval(&EvaluationContext::default(), &[scalar(2)]), Ok(scalar(20)));
+        assert_eq!(tree.eval(&EvaluationContext::default(), &[ScalarValue::Null]), Ok(ScalarValue::Null));
+    }
+
+    #[test]
+    fn untaken_branches_are_not_evaluated() {
+        assert_eq!(
+            searched(
+                vec![boolean(true), division_by_zero()],
+                vec![number(1), division_by_zero()],
+                Some(division_by_zero())
+            )
+            .eval(&EvaluationContext::default(), &[]),
+            Ok(scalar(1))
+        );
+    }
+
+    #[test]
+    fn untaken_branches_do_not_fail_simplification() {
+        let tree = searched(
+            vec![TypedTree::BiOp {
+                type_family: SqlTypeFamily::Integer,
+                left: Box::new(column(0)),
+                op: BiOperator::Comparison(Comparison::Eq),
+                right: Box::new(number(0)),
+            }],
+            vec![number(0)],
+            Some(division_by_zero()),
+        );
+
+        assert_eq!(tree.clone().simplify(), Ok(tree));
+    }
+
+    #[test]
+    fn constant_case_is_folded() {
+        assert_eq!(searched(vec![boolean(true)], vec![number(1)], Some(number(2))).simplify(), Ok(number(1)));
+        assert_eq!(searched(vec![boolean(false)], vec![number(1)], Some(number(2))).simplify(), Ok(number(2)));
+    }
+}
+
+#[cfg(test)]
+mod coalesce {
+    use super::*;
+
+    #[test]
+    fn first_not_null_argument() {
+        assert_eq!(
+            conditional(ConditionalFunction::Coalesce, vec![null(), number(1), division_by_zero()]).eval(&EvaluationContext::default(), &[]),
+            Ok(scalar(1))
+        );
+    }
+
+    #[test]
+    fn all_nulls() {
+        assert_eq!(
+            conditional(ConditionalFunction::Coalesce, vec![null(), null()]).eval(&EvaluationContext::default(), &[]),
+            Ok(ScalarValue::Null)
+        );
+    }
+}
+
+#[cfg(test)]
+mod null_if {
+    use super::*;
+
+    #[test]
+    fn equal_arguments() {
+        assert_eq!(
+            conditional(ConditionalFunction::NullIf, vec![number(1), number(1)]).eval(&EvaluationContext::default(), &[]),
+            Ok(ScalarValue::Null)
+        );
+    }
+
+    #[test]
+    fn different_arguments() {
+        assert_eq!(
+            conditional(ConditionalFunction::NullIf, vec![number(1), number(2)]).eval(&EvaluationContext::default(), &[]),
+            Ok(scalar(1))
+        );
+        assert_eq!(
+            conditional(ConditionalFunction::NullIf, vec![number(1), null()]).eval(&EvaluationContext::default(), &[]),
+            Ok(scalar(1))
+        );
+    }
+}
+
+#[cfg(test)]
+mod greatest_and_least {
+    use super::*;
+
+    #[test]
+    fn nulls_are_ignored() {
+        assert_eq!(
+            conditional(ConditionalFunction::Greatest, vec![number(1), null(), number(3), number(2)]).eval(&EvaluationContext::default(), &[]),
+            Ok(scalar(3))
+        );
+        assert_eq!(
+            conditional(ConditionalFunction::Least, vec![number(2), null(), number(1), number(3)]).eval(&EvaluationContext::default(), &[]),
+            Ok(scalar(1))
+        );
+    }
+
+    #[test]
+    fn all_nulls() {
+        assert_eq!(
+            conditional(ConditionalFunction::Greatest, vec![null(), null()]).eval(&EvaluationContext::default(), &[]),
+            Ok(ScalarValue::Null)
+        );
+    }
+}
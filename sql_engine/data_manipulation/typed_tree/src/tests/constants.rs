@@ -21,7 +21,7 @@ fn small_int() {
             value: BigDecimal::from(0),
             type_family: SqlTypeFamily::SmallInt,
         }))
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Num {
             value: BigDecimal::from(0),
             type_family: SqlTypeFamily::SmallInt
@@ -36,7 +36,7 @@ fn integer() {
             value: BigDecimal::from(0),
             type_family: SqlTypeFamily::Integer,
         }))
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Num {
             value: BigDecimal::from(0),
             type_family: SqlTypeFamily::Integer
@@ -51,7 +51,7 @@ fn big_int() {
             value: BigDecimal::from(0),
             type_family: SqlTypeFamily::BigInt,
         }))
-        .eval(&[], &[]),
+        .eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Num {
             value: BigDecimal::from(0),
             type_family: SqlTypeFamily::BigInt
@@ -62,7 +62,7 @@ fn big_int() {
 #[test]
 fn bool() {
     assert_eq!(
-        TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))).eval(&[], &[]),
+        TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))).eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::Bool(true))
     );
 }
@@ -70,7 +70,7 @@ fn bool() {
 #[test]
 fn string() {
     assert_eq!(
-        TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned()))).eval(&[], &[]),
+        TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned()))).eval(&EvaluationContext::default(), &[]),
         Ok(ScalarValue::String("str".to_owned()))
     );
 }
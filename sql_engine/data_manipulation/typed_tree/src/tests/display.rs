@@ -0,0 +1,80 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_manipulation_operators::{BiLogical, Comparison, IsPredicate};
+use types::SqlType;
+
+fn column(name: &str) -> Box<TypedTree> {
+    Box::new(TypedTree::Item(TypedItem::Column {
+        name: name.to_owned(),
+        sql_type: SqlTypeFamily::Integer,
+        index: 0,
+    }))
+}
+
+#[test]
+fn comparison_with_constant() {
+    let tree = TypedTree::BiOp {
+        type_family: SqlTypeFamily::Bool,
+        left: column("col1"),
+        op: BiOperator::Comparison(Comparison::Gt),
+        right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Num {
+            value: BigDecimal::from(5),
+            type_family: SqlTypeFamily::Integer,
+        }))),
+    };
+
+    assert_eq!(tree.to_string(), "(col1 > 5)");
+}
+
+#[test]
+fn string_constants_are_quoted() {
+    let tree = TypedTree::BiOp {
+        type_family: SqlTypeFamily::Bool,
+        left: column("col1"),
+        op: BiOperator::Comparison(Comparison::Eq),
+        right: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))),
+    };
+
+    assert_eq!(tree.to_string(), "(col1 = 'abc')");
+}
+
+#[test]
+fn params_and_logical_operators() {
+    let tree = TypedTree::BiOp {
+        type_family: SqlTypeFamily::Bool,
+        left: Box::new(TypedTree::UnOp {
+            op: UnOperator::LogicalNot,
+            item: column("col1"),
+        }),
+        op: BiOperator::Logical(BiLogical::And),
+        right: Box::new(TypedTree::UnOp {
+            op: UnOperator::Cast(SqlType::bool()),
+            item: Box::new(TypedTree::Item(TypedItem::Param { index: 0, type_family: None })),
+        }),
+    };
+
+    assert_eq!(tree.to_string(), "((NOT col1) AND ($1::bool))");
+}
+
+#[test]
+fn null_test() {
+    let tree = TypedTree::UnOp {
+        op: UnOperator::Is(IsPredicate::NotNull),
+        item: column("col1"),
+    };
+
+    assert_eq!(tree.to_string(), "(col1 IS NOT NULL)");
+}
@@ -18,6 +18,14 @@ use bigdecimal::BigDecimal;
 #[cfg(test)]
 mod binary_ops;
 #[cfg(test)]
+mod conditionals;
+#[cfg(test)]
 mod constants;
 #[cfg(test)]
+mod display;
+#[cfg(test)]
+mod predicates;
+#[cfg(test)]
+mod simplification;
+#[cfg(test)]
 mod unary_op;
@@ -0,0 +1,147 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn number(value: i32) -> TypedTree {
+    TypedTree::Item(TypedItem::Const(TypedValue::Num {
+        value: BigDecimal::from(value),
+        type_family: SqlTypeFamily::Integer,
+    }))
+}
+
+fn null() -> TypedTree {
+    TypedTree::Item(TypedItem::Null(None))
+}
+
+fn quantified(left: TypedTree, comparison: Comparison, quantifier: Quantifier, list: Vec<TypedTree>) -> TypedTree {
+    TypedTree::Quantified {
+        type_family: SqlTypeFamily::Integer,
+        left: Box::new(left),
+        op: BiOperator::Comparison(comparison),
+        quantifier,
+        list,
+    }
+}
+
+fn between(item: TypedTree, low: TypedTree, high: TypedTree, negated: bool) -> TypedTree {
+    TypedTree::Between {
+        type_family: SqlTypeFamily::Integer,
+        item: Box::new(item),
+        low: Box::new(low),
+        high: Box::new(high),
+        negated,
+    }
+}
+
+#[cfg(test)]
+mod in_list {
+    use super::*;
+
+    #[rstest::rstest(
+        item,
+        list,
+        expected,
+        case::found(number(2), vec![number(1), number(2)], ScalarValue::Bool(true)),
+        case::not_found(number(3), vec![number(1), number(2)], ScalarValue::Bool(false)),
+        case::found_with_null(number(1), vec![null(), number(1)], ScalarValue::Bool(true)),
+        case::not_found_with_null(number(3), vec![number(1), null()], ScalarValue::Null),
+        case::null_item(null(), vec![number(1)], ScalarValue::Null)
+    )]
+    fn any(item: TypedTree, list: Vec<TypedTree>, expected: ScalarValue) {
+        assert_eq!(
+            quantified(item, Comparison::Eq, Quantifier::Any, list).eval(&EvaluationContext::default(), &[]),
+            Ok(expected)
+        );
+    }
+
+    #[rstest::rstest(
+        item,
+        list,
+        expected,
+        case::not_in(number(3), vec![number(1), number(2)], ScalarValue::Bool(true)),
+        case::in_list(number(1), vec![number(1), number(2)], ScalarValue::Bool(false)),
+        case::not_in_with_null(number(3), vec![number(1), null()], ScalarValue::Null),
+        case::in_list_with_null(number(1), vec![null(), number(1)], ScalarValue::Bool(false))
+    )]
+    fn all(item: TypedTree, list: Vec<TypedTree>, expected: ScalarValue) {
+        assert_eq!(
+            quantified(item, Comparison::NotEq, Quantifier::All, list).eval(&EvaluationContext::default(), &[]),
+            Ok(expected)
+        );
+    }
+}
+
+#[cfg(test)]
+mod between {
+    use super::*;
+
+    #[rstest::rstest(
+        item,
+        low,
+        high,
+        expected,
+        case::inside(number(5), number(1), number(10), ScalarValue::Bool(true)),
+        case::on_bound(number(10), number(1), number(10), ScalarValue::Bool(true)),
+        case::outside(number(11), number(1), number(10), ScalarValue::Bool(false)),
+        case::null_item(null(), number(1), number(10), ScalarValue::Null),
+        case::outside_with_null_bound(number(0), number(1), null(), ScalarValue::Bool(false)),
+        case::inside_with_null_bound(number(5), number(1), null(), ScalarValue::Null)
+    )]
+    fn between(item: TypedTree, low: TypedTree, high: TypedTree, expected: ScalarValue) {
+        assert_eq!(
+            super::between(item, low, high, false).eval(&EvaluationContext::default(), &[]),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn not_between() {
+        assert_eq!(
+            super::between(number(11), number(1), number(10), true).eval(&EvaluationContext::default(), &[]),
+            Ok(ScalarValue::Bool(true))
+        );
+        assert_eq!(
+            super::between(number(0), number(1), null(), true).eval(&EvaluationContext::default(), &[]),
+            Ok(ScalarValue::Bool(true))
+        );
+    }
+}
+
+#[test]
+fn constant_predicates_are_folded() {
+    assert_eq!(
+        quantified(number(1), Comparison::Eq, Quantifier::Any, vec![number(1), number(2)]).simplify(),
+        Ok(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))))
+    );
+    assert_eq!(
+        between(number(1), number(2), number(3), false).simplify(),
+        Ok(TypedTree::Item(TypedItem::Const(TypedValue::Bool(false))))
+    );
+}
+
+#[test]
+fn display() {
+    let column = TypedTree::Item(TypedItem::Column {
+        name: "col".to_owned(),
+        sql_type: SqlTypeFamily::Integer,
+        index: 0,
+    });
+
+    assert_eq!(
+        quantified(column.clone(), Comparison::Eq, Quantifier::Any, vec![number(1), number(2)]).to_string(),
+        "(col = ANY(1, 2))"
+    );
+    assert_eq!(between(column, number(1), number(2), true).to_string(), "(col NOT BETWEEN 1 AND 2)");
+}
@@ -0,0 +1,222 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_manipulation_operators::{BiArithmetic, BiLogical, Comparison, IsPredicate};
+
+fn number(value: i32) -> Box<TypedTree> {
+    Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Num {
+        value: BigDecimal::from(value),
+        type_family: SqlTypeFamily::Integer,
+    })))
+}
+
+fn boolean(value: bool) -> Box<TypedTree> {
+    Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(value))))
+}
+
+fn column(sql_type: SqlTypeFamily) -> Box<TypedTree> {
+    Box::new(TypedTree::Item(TypedItem::Column {
+        name: "col".to_owned(),
+        sql_type,
+        index: 0,
+    }))
+}
+
+fn bi_op(left: Box<TypedTree>, op: BiOperator, right: Box<TypedTree>) -> Box<TypedTree> {
+    Box::new(TypedTree::BiOp {
+        type_family: SqlTypeFamily::Integer,
+        left,
+        op,
+        right,
+    })
+}
+
+fn not(item: Box<TypedTree>) -> Box<TypedTree> {
+    Box::new(TypedTree::UnOp {
+        op: UnOperator::LogicalNot,
+        item,
+    })
+}
+
+fn greater_than_five() -> Box<TypedTree> {
+    bi_op(column(SqlTypeFamily::Integer), BiOperator::Comparison(Comparison::Gt), number(5))
+}
+
+#[test]
+fn constant_subtree_is_folded() {
+    assert_eq!(
+        bi_op(
+            bi_op(number(1), BiOperator::Arithmetic(BiArithmetic::Add), number(2)),
+            BiOperator::Arithmetic(BiArithmetic::Mul),
+            number(3)
+        )
+        .simplify(),
+        Ok(TypedTree::Item(TypedItem::Const(TypedValue::Num {
+            value: BigDecimal::from(9),
+            type_family: SqlTypeFamily::BigInt
+        })))
+    );
+}
+
+#[test]
+fn constant_subtree_of_column_expression_is_folded() {
+    assert_eq!(
+        bi_op(
+            column(SqlTypeFamily::Integer),
+            BiOperator::Arithmetic(BiArithmetic::Add),
+            bi_op(number(2), BiOperator::Arithmetic(BiArithmetic::Mul), number(3))
+        )
+        .simplify(),
+        Ok(*bi_op(
+            column(SqlTypeFamily::Integer),
+            BiOperator::Arithmetic(BiArithmetic::Add),
+            Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Num {
+                value: BigDecimal::from(6),
+                type_family: SqlTypeFamily::BigInt
+            })))
+        ))
+    );
+}
+
+#[test]
+fn params_are_not_folded() {
+    let tree = bi_op(
+        Box::new(TypedTree::Item(TypedItem::Param {
+            index: 0,
+            type_family: Some(SqlTypeFamily::Integer),
+        })),
+        BiOperator::Arithmetic(BiArithmetic::Add),
+        number(1),
+    );
+
+    assert_eq!(tree.clone().simplify(), Ok(*tree));
+}
+
+#[rstest::rstest(
+    left,
+    op,
+    right,
+    expected,
+    case::true_and(boolean(true), BiLogical::And, greater_than_five(), *greater_than_five()),
+    case::and_true(greater_than_five(), BiLogical::And, boolean(true), *greater_than_five()),
+    case::false_and(boolean(false), BiLogical::And, greater_than_five(), *boolean(false)),
+    case::and_false(greater_than_five(), BiLogical::And, boolean(false), *boolean(false)),
+    case::true_or(boolean(true), BiLogical::Or, greater_than_five(), *boolean(true)),
+    case::or_true(greater_than_five(), BiLogical::Or, boolean(true), *boolean(true)),
+    case::false_or(boolean(false), BiLogical::Or, greater_than_five(), *greater_than_five()),
+    case::or_false(greater_than_five(), BiLogical::Or, boolean(false), *greater_than_five())
+)]
+fn logical_with_constant(left: Box<TypedTree>, op: BiLogical, right: Box<TypedTree>, expected: TypedTree) {
+    assert_eq!(bi_op(left, BiOperator::Logical(op), right).simplify(), Ok(expected));
+}
+
+#[test]
+fn logical_with_non_boolean_is_not_simplified() {
+    let tree = bi_op(boolean(true), BiOperator::Logical(BiLogical::And), column(SqlTypeFamily::Integer));
+
+    assert_eq!(tree.clone().simplify(), Ok(*tree));
+}
+
+#[test]
+fn double_negation() {
+    assert_eq!(not(not(column(SqlTypeFamily::Bool))).simplify(), Ok(*column(SqlTypeFamily::Bool)));
+}
+
+#[test]
+fn negated_comparison() {
+    assert_eq!(
+        not(greater_than_five()).simplify(),
+        Ok(*bi_op(
+            column(SqlTypeFamily::Integer),
+            BiOperator::Comparison(Comparison::LtEq),
+            number(5)
+        ))
+    );
+}
+
+#[test]
+fn constant_moves_to_the_right_of_comparison() {
+    assert_eq!(
+        bi_op(number(5), BiOperator::Comparison(Comparison::Lt), column(SqlTypeFamily::Integer)).simplify(),
+        Ok(*greater_than_five())
+    );
+}
+
+#[test]
+fn division_by_zero_is_reported() {
+    assert_eq!(
+        bi_op(
+            column(SqlTypeFamily::Integer),
+            BiOperator::Comparison(Comparison::Gt),
+            bi_op(number(1), BiOperator::Arithmetic(BiArithmetic::Div), number(0))
+        )
+        .simplify(),
+        Err(QueryExecutionError::DivisionByZero)
+    );
+}
+
+#[test]
+fn null_test_of_constant_is_folded() {
+    assert_eq!(
+        TypedTree::UnOp {
+            op: UnOperator::Is(IsPredicate::Null),
+            item: Box::new(TypedTree::Item(TypedItem::Null(None))),
+        }
+        .simplify(),
+        Ok(*boolean(true))
+    );
+}
+
+#[test]
+fn false_and_null_is_false() {
+    assert_eq!(
+        bi_op(
+            boolean(false),
+            BiOperator::Logical(BiLogical::And),
+            Box::new(TypedTree::Item(TypedItem::Null(Some(SqlTypeFamily::Bool))))
+        )
+        .simplify(),
+        Ok(*boolean(false))
+    );
+}
+
+#[test]
+fn negated_distinct_from() {
+    assert_eq!(
+        not(bi_op(
+            column(SqlTypeFamily::Integer),
+            BiOperator::Comparison(Comparison::IsDistinctFrom),
+            number(5)
+        ))
+        .simplify(),
+        Ok(*bi_op(
+            column(SqlTypeFamily::Integer),
+            BiOperator::Comparison(Comparison::IsNotDistinctFrom),
+            number(5)
+        ))
+    );
+}
+
+#[test]
+fn stable_function_is_not_folded() {
+    let now = TypedTree::Function {
+        type_family: SqlTypeFamily::TimestampTz,
+        function: ScalarFunction::Now,
+        args: vec![],
+    };
+
+    assert_eq!(now.clone().simplify(), Ok(now.clone()));
+    assert_eq!(now.eval(&EvaluationContext::new(vec![], 42), &[]), Ok(ScalarValue::TimestampTz(42)));
+}
@@ -29,7 +29,7 @@ mod unary_minus {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(-32767),
                 type_family: SqlTypeFamily::Integer
@@ -44,7 +44,7 @@ mod unary_minus {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(-32768),
                 type_family: SqlTypeFamily::Integer
@@ -65,7 +65,7 @@ mod unary_minus {
                     }))),
                 })
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(32767),
                 type_family: SqlTypeFamily::Integer
@@ -83,7 +83,7 @@ mod unary_minus {
                     }))),
                 })
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(32768),
                 type_family: SqlTypeFamily::Integer
@@ -98,7 +98,7 @@ mod unary_minus {
                 op: UnOperator::Arithmetic(UnArithmetic::Neg),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned()))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Neg),
                 SqlTypeFamily::String
@@ -113,7 +113,7 @@ mod unary_minus {
                 op: UnOperator::Arithmetic(UnArithmetic::Neg),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Neg),
                 SqlTypeFamily::Bool
@@ -136,7 +136,7 @@ mod unary_plus {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(32767),
                 type_family: SqlTypeFamily::Integer
@@ -151,7 +151,7 @@ mod unary_plus {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(32768),
                 type_family: SqlTypeFamily::Integer
@@ -166,7 +166,7 @@ mod unary_plus {
                 op: UnOperator::Arithmetic(UnArithmetic::Pos),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned()))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Pos),
                 SqlTypeFamily::String
@@ -181,7 +181,7 @@ mod unary_plus {
                 op: UnOperator::Arithmetic(UnArithmetic::Pos),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Pos),
                 SqlTypeFamily::Bool
@@ -201,7 +201,7 @@ mod unary_not {
                 op: UnOperator::LogicalNot,
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Bool(false))
         );
     }
@@ -216,7 +216,7 @@ mod unary_not {
                     type_family: SqlTypeFamily::SmallInt
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::datatype_mismatch(
                 UnOperator::LogicalNot,
                 SqlTypeFamily::Bool,
@@ -232,7 +232,7 @@ mod unary_not {
                 op: UnOperator::LogicalNot,
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned())))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::datatype_mismatch(
                 UnOperator::LogicalNot,
                 SqlTypeFamily::Bool,
@@ -256,7 +256,7 @@ mod unary_bitwise_not {
                     type_family: SqlTypeFamily::SmallInt
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(!1),
                 type_family: SqlTypeFamily::SmallInt
@@ -271,7 +271,7 @@ mod unary_bitwise_not {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(!(i32::MAX - i16::MAX as i32)),
                 type_family: SqlTypeFamily::Integer
@@ -286,7 +286,7 @@ mod unary_bitwise_not {
                     type_family: SqlTypeFamily::BigInt
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(!(i64::MAX - i32::MAX as i64)),
                 type_family: SqlTypeFamily::BigInt
@@ -304,7 +304,7 @@ mod unary_bitwise_not {
                     type_family: SqlTypeFamily::Real
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(UnOperator::BitwiseNot, SqlTypeFamily::Real))
         );
 
@@ -316,7 +316,7 @@ mod unary_bitwise_not {
                     type_family: SqlTypeFamily::Double
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(UnOperator::BitwiseNot, SqlTypeFamily::Double))
         );
     }
@@ -328,7 +328,7 @@ mod unary_bitwise_not {
                 op: UnOperator::BitwiseNot,
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned())))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(UnOperator::BitwiseNot, SqlTypeFamily::String))
         );
     }
@@ -340,7 +340,7 @@ mod unary_bitwise_not {
                 op: UnOperator::BitwiseNot,
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(UnOperator::BitwiseNot, SqlTypeFamily::Bool))
         );
     }
@@ -360,7 +360,7 @@ mod square_root {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(32768).sqrt().unwrap(),
                 type_family: SqlTypeFamily::Double
@@ -378,7 +378,7 @@ mod square_root {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::InvalidArgumentForPowerFunction)
         );
     }
@@ -390,7 +390,7 @@ mod square_root {
                 op: UnOperator::Arithmetic(UnArithmetic::SquareRoot),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned()))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::SquareRoot),
                 SqlTypeFamily::String
@@ -405,7 +405,7 @@ mod square_root {
                 op: UnOperator::Arithmetic(UnArithmetic::SquareRoot),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::SquareRoot),
                 SqlTypeFamily::Bool
@@ -428,7 +428,7 @@ mod cube_root {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(32768).cbrt(),
                 type_family: SqlTypeFamily::Double
@@ -443,7 +443,7 @@ mod cube_root {
                 op: UnOperator::Arithmetic(UnArithmetic::CubeRoot),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned()))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::CubeRoot),
                 SqlTypeFamily::String
@@ -458,7 +458,7 @@ mod cube_root {
                 op: UnOperator::Arithmetic(UnArithmetic::CubeRoot),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::CubeRoot),
                 SqlTypeFamily::Bool
@@ -481,7 +481,7 @@ mod factorial {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(6),
                 type_family: SqlTypeFamily::BigInt
@@ -499,7 +499,7 @@ mod factorial {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(1),
                 type_family: SqlTypeFamily::BigInt
@@ -517,7 +517,7 @@ mod factorial {
                     type_family: SqlTypeFamily::Real
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Factorial),
                 SqlTypeFamily::Real
@@ -532,7 +532,7 @@ mod factorial {
                     type_family: SqlTypeFamily::Double
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Factorial),
                 SqlTypeFamily::Double
@@ -547,7 +547,7 @@ mod factorial {
                 op: UnOperator::Arithmetic(UnArithmetic::Factorial),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned()))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Factorial),
                 SqlTypeFamily::String
@@ -562,7 +562,7 @@ mod factorial {
                 op: UnOperator::Arithmetic(UnArithmetic::Factorial),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Factorial),
                 SqlTypeFamily::Bool
@@ -585,7 +585,7 @@ mod absolute_value {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(3),
                 type_family: SqlTypeFamily::Integer
@@ -603,7 +603,7 @@ mod absolute_value {
                     type_family: SqlTypeFamily::Integer
                 }))),
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Ok(ScalarValue::Num {
                 value: BigDecimal::from(3),
                 type_family: SqlTypeFamily::Integer
@@ -618,7 +618,7 @@ mod absolute_value {
                 op: UnOperator::Arithmetic(UnArithmetic::Abs),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::String("str".to_owned()))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Abs),
                 SqlTypeFamily::String
@@ -633,7 +633,7 @@ mod absolute_value {
                 op: UnOperator::Arithmetic(UnArithmetic::Abs),
                 item: Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Bool(true))))
             }
-            .eval(&[], &[]),
+            .eval(&EvaluationContext::default(), &[]),
             Err(QueryExecutionError::undefined_function(
                 UnOperator::Arithmetic(UnArithmetic::Abs),
                 SqlTypeFamily::Bool
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use data_manipulation_operators::SetOperator;
 use data_manipulation_untyped_tree::UntypedTree;
 use definition::FullTableName;
 
@@ -30,9 +31,23 @@ pub struct UntypedUpdateQuery {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct UntypedSelectQuery {
-    pub full_table_name: FullTableName,
+    /// `None` for queries without `FROM` clause
+    pub full_table_name: Option<FullTableName>,
+    /// query which returns whole rows of the table to be read instead of the table itself,
+    /// e.g. rows of a view that are kept by its `DISTINCT ON` before the query filters them
+    pub subquery: Option<Box<UntypedSelectQuery>>,
     pub projection_items: Vec<UntypedTree>,
+    /// names of the result columns, one per projection item
+    pub column_names: Vec<String>,
     pub filter: Option<UntypedTree>,
+    pub distinct: Option<UntypedDistinct>,
+}
+
+/// rows are de-duplicated by the keys, for `SELECT DISTINCT` these are the projection items
+#[derive(Debug, PartialEq, Clone)]
+pub enum UntypedDistinct {
+    Rows(Vec<UntypedTree>),
+    On(Vec<UntypedTree>),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -41,10 +56,22 @@ pub struct UntypedDeleteQuery {
     pub filter: Option<UntypedTree>,
 }
 
+/// each side is either a `SELECT` or another set operation with the same number of columns
+#[derive(Debug, PartialEq, Clone)]
+pub struct UntypedSetOperation {
+    pub op: SetOperator,
+    pub all: bool,
+    pub left: Box<UntypedQuery>,
+    pub right: Box<UntypedQuery>,
+    /// names of the result columns are taken from the left query
+    pub column_names: Vec<String>,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum UntypedQuery {
     Insert(UntypedInsertQuery),
     Update(UntypedUpdateQuery),
     Delete(UntypedDeleteQuery),
     Select(UntypedSelectQuery),
+    SetOperation(UntypedSetOperation),
 }
@@ -13,11 +13,12 @@
 // limitations under the License.
 
 use bigdecimal::BigDecimal;
-use data_manipulation_operators::{BiOperator, UnOperator};
+use data_manipulation_operators::{BiOperator, ConditionalFunction, Quantifier, SequenceFunction, UnOperator, WindowFrame};
 use std::{
     fmt,
     fmt::{Display, Formatter},
 };
+use storage::Sequence;
 use types::{Bool, SqlType, SqlTypeFamily};
 
 #[derive(Debug, PartialEq, Clone)]
@@ -75,5 +76,54 @@ pub enum UntypedTree {
         op: UnOperator,
         item: Box<UntypedTree>,
     },
+    Function {
+        name: String,
+        args: Vec<UntypedTree>,
+    },
+    Sequence {
+        function: SequenceFunction,
+        sequence: Sequence,
+        args: Vec<UntypedTree>,
+    },
+    Case {
+        operand: Option<Box<UntypedTree>>,
+        conditions: Vec<UntypedTree>,
+        results: Vec<UntypedTree>,
+        else_result: Option<Box<UntypedTree>>,
+    },
+    Conditional {
+        function: ConditionalFunction,
+        args: Vec<UntypedTree>,
+    },
+    Quantified {
+        left: Box<UntypedTree>,
+        op: BiOperator,
+        quantifier: Quantifier,
+        list: Vec<UntypedTree>,
+    },
+    Between {
+        item: Box<UntypedTree>,
+        low: Box<UntypedTree>,
+        high: Box<UntypedTree>,
+        negated: bool,
+    },
+    Window(UntypedWindow),
     Item(UntypedItem),
 }
+
+/// window function is resolved by its name and number of arguments during type inference
+#[derive(Debug, PartialEq, Clone)]
+pub struct UntypedWindow {
+    pub name: String,
+    pub args: Vec<UntypedTree>,
+    pub partition_by: Vec<UntypedTree>,
+    pub order_by: Vec<UntypedSortKey>,
+    pub frame: WindowFrame,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct UntypedSortKey {
+    pub expr: UntypedTree,
+    pub ascending: bool,
+    pub nulls_first: bool,
+}
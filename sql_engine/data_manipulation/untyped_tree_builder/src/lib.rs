@@ -13,10 +13,10 @@
 // limitations under the License.
 
 use bigdecimal::BigDecimal;
-use data_manipulation_operators::{BiOperator, UnOperator};
-use data_manipulation_untyped_tree::{UntypedItem, UntypedTree, UntypedValue};
+use data_manipulation_operators::{BiOperator, ConditionalFunction, Quantifier, UnOperator, WindowFrame};
+use data_manipulation_untyped_tree::{UntypedItem, UntypedSortKey, UntypedTree, UntypedValue, UntypedWindow};
 use definition::ColumnDef;
-use query_ast::{BinaryOperator, Expr, Value};
+use query_ast::{BinaryOperator, Expr, Value, WindowSpec};
 use std::str::FromStr;
 use types::SqlType;
 
@@ -44,6 +44,14 @@ impl TreeBuilder {
                 item: Box::new(Self::inner_dynamic(*expr, table_columns)?),
             }),
             Expr::Param(index) => Ok(UntypedTree::Item(UntypedItem::Param((index - 1) as usize))),
+            Expr::Function { name, args } => Ok(UntypedTree::Function {
+                name,
+                args: args
+                    .into_iter()
+                    .map(|arg| Self::inner_dynamic(arg, table_columns))
+                    .collect::<Result<_, _>>()?,
+            }),
+            composite => Self::composite(composite, &|expr| Self::inner_dynamic(expr, table_columns)),
         }
     }
 
@@ -89,9 +97,92 @@ impl TreeBuilder {
                 item: Box::new(Self::inner_insert_position(*expr)?),
             }),
             Expr::Param(index) => Ok(UntypedTree::Item(UntypedItem::Param((index - 1) as usize))),
+            Expr::Function { name, args } => Ok(UntypedTree::Function {
+                name,
+                args: args.into_iter().map(Self::inner_insert_position).collect::<Result<_, _>>()?,
+            }),
+            composite => Self::composite(composite, &Self::inner_insert_position),
         }
     }
 
+    // conditional expressions and predicates with many operands are built the same way in any context
+    fn composite(expr: Expr, build: &dyn Fn(Expr) -> Result<UntypedTree, UntypedExpressionError>) -> Result<UntypedTree, UntypedExpressionError> {
+        let (function, args) = match expr {
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                return Ok(UntypedTree::Case {
+                    operand: operand.map(|operand| build(*operand)).transpose()?.map(Box::new),
+                    conditions: conditions.into_iter().map(build).collect::<Result<_, _>>()?,
+                    results: results.into_iter().map(build).collect::<Result<_, _>>()?,
+                    else_result: else_result.map(|else_result| build(*else_result)).transpose()?.map(Box::new),
+                })
+            }
+            Expr::InList { expr, list, negated: false } => return Self::quantified(*expr, BinaryOperator::Eq, Quantifier::Any, list, build),
+            Expr::InList { expr, list, negated: true } => return Self::quantified(*expr, BinaryOperator::NotEq, Quantifier::All, list, build),
+            Expr::Any { left, op, right } => return Self::quantified(*left, op, Quantifier::Any, right, build),
+            Expr::All { left, op, right } => return Self::quantified(*left, op, Quantifier::All, right, build),
+            Expr::Between { expr, low, high, negated } => {
+                return Ok(UntypedTree::Between {
+                    item: Box::new(build(*expr)?),
+                    low: Box::new(build(*low)?),
+                    high: Box::new(build(*high)?),
+                    negated,
+                })
+            }
+            Expr::Window { name, args, over } => {
+                let WindowSpec {
+                    partition_by,
+                    order_by,
+                    frame,
+                } = *over;
+                return Ok(UntypedTree::Window(UntypedWindow {
+                    name,
+                    args: args.into_iter().map(build).collect::<Result<_, _>>()?,
+                    partition_by: partition_by.into_iter().map(build).collect::<Result<_, _>>()?,
+                    order_by: order_by
+                        .into_iter()
+                        .map(|key| {
+                            Ok(UntypedSortKey {
+                                expr: build(key.expr)?,
+                                ascending: key.ascending,
+                                nulls_first: key.nulls_first,
+                            })
+                        })
+                        .collect::<Result<_, _>>()?,
+                    frame: frame.map(WindowFrame::from).unwrap_or_default(),
+                }));
+            }
+            Expr::Coalesce(args) => (ConditionalFunction::Coalesce, args),
+            Expr::NullIf { left, right } => (ConditionalFunction::NullIf, vec![*left, *right]),
+            Expr::Greatest(args) => (ConditionalFunction::Greatest, args),
+            Expr::Least(args) => (ConditionalFunction::Least, args),
+            _ => unreachable!(),
+        };
+        Ok(UntypedTree::Conditional {
+            function,
+            args: args.into_iter().map(build).collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn quantified(
+        left: Expr,
+        op: BinaryOperator,
+        quantifier: Quantifier,
+        list: Vec<Expr>,
+        build: &dyn Fn(Expr) -> Result<UntypedTree, UntypedExpressionError>,
+    ) -> Result<UntypedTree, UntypedExpressionError> {
+        Ok(UntypedTree::Quantified {
+            left: Box::new(build(left)?),
+            op: BiOperator::from(op),
+            quantifier,
+            list: list.into_iter().map(build).collect::<Result<_, _>>()?,
+        })
+    }
+
     fn static_binary_op(operator: BinaryOperator, left: Expr, right: Expr) -> Result<UntypedTree, UntypedExpressionError> {
         let left = Self::inner_insert_position(left)?;
         let right = Self::inner_insert_position(right)?;
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::*;
+use data_manipulation_operators::Comparison;
 use query_ast::Value;
 
 #[test]
@@ -72,3 +73,23 @@ fn numeric() {
         ))))
     );
 }
+
+#[test]
+fn not_in_list() {
+    assert_eq!(
+        TreeBuilder::insert_position(Expr::InList {
+            expr: Box::new(Expr::Value(Value::Int(1))),
+            list: vec![Expr::Value(Value::Int(2)), Expr::Value(Value::Null)],
+            negated: true
+        }),
+        Ok(UntypedTree::Quantified {
+            left: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(1)))),
+            op: BiOperator::Comparison(Comparison::NotEq),
+            quantifier: Quantifier::All,
+            list: vec![
+                UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(2))),
+                UntypedTree::Item(UntypedItem::Const(UntypedValue::Null))
+            ]
+        })
+    );
+}
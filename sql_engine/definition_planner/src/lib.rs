@@ -12,29 +12,63 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use catalog::CatalogHandler;
+use binary::BinaryValue;
+use catalog::{is_system_schema, CatalogHandler};
 use data_definition_execution_plan::{
-    ColumnInfo, CreateIndexQuery, CreateSchemaQuery, CreateTableQuery, DropSchemasQuery, DropTablesQuery, SchemaChange,
+    AnalyzeTablesQuery, ColumnInfo, CreateFunctionQuery, CreateIndexQuery, CreateMaterializedViewQuery, CreateSchemaQuery, CreateSequenceQuery,
+    CreateTableQuery, CreateViewQuery, DropFunctionsQuery, DropSchemasQuery, DropTablesQuery, DropViewsQuery, RefreshMaterializedViewQuery,
+    SchemaChange, TruncateTablesQuery,
 };
-use definition::{FullTableName, SchemaName};
-use query_ast::{ColumnDef, Definition};
+use data_manipulation_query_plan::QueryPlan;
+use data_manipulation_query_result::QueryExecutionError;
+use data_manipulation_typed_queries::{TypedDistinct, TypedQuery, TypedSelectQuery};
+use data_manipulation_typed_tree::{EvaluationContext, TypedTree};
+use data_manipulation_untyped_queries::{UntypedDistinct, UntypedQuery, UntypedSelectQuery};
+use data_manipulation_untyped_tree::{UntypedItem, UntypedTree};
+use definition::{ColumnIdentity, FullTableName, SchemaName, SearchPath};
+use query_analyzer::{AnalysisError, QueryAnalyzer};
+use query_ast::{ColumnDef, DataType, Definition, Identity, Query, SelectItem, SelectQuery};
+use query_planner::QueryPlanner;
+use query_processing::{Simplifier, TypeChecker, TypeCoercion, TypeInference};
 use query_response::QueryError;
+use std::cell::RefCell;
 use storage::Transaction;
-use types::SqlType;
+use types::{SqlType, SqlTypeFamily};
 
 pub struct DefinitionPlanner<'p> {
     catalog: CatalogHandler<'p>,
+    analyzer: QueryAnalyzer<'p>,
+    type_inference: TypeInference,
+    type_checker: TypeChecker,
+    type_coercion: TypeCoercion,
+    simplifier: Simplifier,
+    query_planner: QueryPlanner<'p>,
+    search_path: RefCell<SearchPath>,
+    transaction_timestamp: i64,
 }
 
 impl<'p> From<Transaction<'p>> for DefinitionPlanner<'p> {
     fn from(transaction: Transaction<'p>) -> DefinitionPlanner {
         DefinitionPlanner {
-            catalog: CatalogHandler::from(transaction),
+            catalog: CatalogHandler::from(transaction.clone()),
+            analyzer: QueryAnalyzer::from(transaction.clone()),
+            type_inference: TypeInference::default(),
+            type_checker: TypeChecker,
+            type_coercion: TypeCoercion,
+            simplifier: Simplifier,
+            transaction_timestamp: transaction.started_at(),
+            query_planner: QueryPlanner::from(transaction),
+            search_path: RefCell::new(SearchPath::default()),
         }
     }
 }
 
 impl<'p> DefinitionPlanner<'p> {
+    pub fn set_search_path(&self, search_path: SearchPath) {
+        self.analyzer.set_search_path(search_path.clone());
+        self.search_path.replace(search_path);
+    }
+
     pub fn plan(&self, statement: Definition) -> Result<SchemaChange, SchemaPlanError> {
         match statement {
             Definition::CreateTable {
@@ -43,17 +77,35 @@ impl<'p> DefinitionPlanner<'p> {
                 columns,
                 if_not_exists,
             } => {
+                let schema_name = self.creation_schema(schema_name)?;
                 if !(self.catalog.schema_exists(&SchemaName::from(&schema_name))) {
                     Err(SchemaPlanError::schema_does_not_exist(&schema_name))
+                } else if is_system_schema(&schema_name) {
+                    Err(SchemaPlanError::permission_denied_to_create(&schema_name, &table_name))
                 } else {
                     let full_table_name = FullTableName::from((&schema_name, &table_name));
-                    let column_defs = columns
-                        .into_iter()
-                        .map(|ColumnDef { name, data_type }| ColumnInfo {
+                    let mut column_defs = vec![];
+                    for ColumnDef { name, data_type, identity } in columns {
+                        let sql_type = SqlType::from(data_type);
+                        if identity.is_some()
+                            && !matches!(
+                                sql_type.family(),
+                                SqlTypeFamily::SmallInt | SqlTypeFamily::Integer | SqlTypeFamily::BigInt
+                            )
+                        {
+                            return Err(SchemaPlanError::invalid_parameter_value(
+                                "identity column type must be smallint, integer, or bigint",
+                            ));
+                        }
+                        column_defs.push(ColumnInfo {
                             name,
-                            sql_type: SqlType::from(data_type),
-                        })
-                        .collect::<Vec<_>>();
+                            sql_type,
+                            identity: identity.map(|identity| match identity {
+                                Identity::Always => ColumnIdentity::Always,
+                                Identity::ByDefault => ColumnIdentity::ByDefault,
+                            }),
+                        });
+                    }
                     Ok(SchemaChange::CreateTable(CreateTableQuery {
                         full_table_name,
                         column_defs,
@@ -71,10 +123,11 @@ impl<'p> DefinitionPlanner<'p> {
                 table_name,
                 column_names,
             } => {
-                let full_table_name = FullTableName::from((&schema_name, &table_name));
+                let full_table_name = self.qualify(schema_name, &table_name)?;
                 match self.catalog.table_definition(full_table_name.clone()) {
                     None => Err(SchemaPlanError::schema_does_not_exist(full_table_name.schema())),
                     Some(None) => Err(SchemaPlanError::table_does_not_exist(full_table_name)),
+                    Some(Some(_)) if is_system_schema(full_table_name.schema()) => Err(SchemaPlanError::system_catalog(&table_name)),
                     Some(Some(table_info)) => {
                         let table_columns = table_info.column_names();
                         for column in column_names.iter() {
@@ -90,14 +143,285 @@ impl<'p> DefinitionPlanner<'p> {
                     }
                 }
             }
+            Definition::CreateSequence {
+                schema_name,
+                sequence_name,
+                start,
+                increment,
+                min_value,
+                max_value,
+                if_not_exists,
+            } => {
+                let schema_name = self.creation_schema(schema_name)?;
+                if !(self.catalog.schema_exists(&SchemaName::from(&schema_name))) {
+                    return Err(SchemaPlanError::schema_does_not_exist(&schema_name));
+                }
+                if is_system_schema(&schema_name) {
+                    return Err(SchemaPlanError::permission_denied_to_create(&schema_name, &sequence_name));
+                }
+                let increment = increment.unwrap_or(1);
+                if increment == 0 {
+                    return Err(SchemaPlanError::invalid_parameter_value("INCREMENT must not be zero"));
+                }
+                let min_value = min_value.unwrap_or(if increment > 0 { 1 } else { i64::MIN });
+                let max_value = max_value.unwrap_or(if increment > 0 { i64::MAX } else { -1 });
+                if min_value >= max_value {
+                    return Err(SchemaPlanError::invalid_parameter_value(format!(
+                        "MINVALUE ({}) must be less than MAXVALUE ({})",
+                        min_value, max_value
+                    )));
+                }
+                let start = start.unwrap_or(if increment > 0 { min_value } else { max_value });
+                if start < min_value {
+                    return Err(SchemaPlanError::invalid_parameter_value(format!(
+                        "START value ({}) cannot be less than MINVALUE ({})",
+                        start, min_value
+                    )));
+                }
+                if start > max_value {
+                    return Err(SchemaPlanError::invalid_parameter_value(format!(
+                        "START value ({}) cannot be greater than MAXVALUE ({})",
+                        start, max_value
+                    )));
+                }
+                Ok(SchemaChange::CreateSequence(CreateSequenceQuery {
+                    full_sequence_name: FullTableName::from((&schema_name, &sequence_name)),
+                    start,
+                    increment,
+                    min_value,
+                    max_value,
+                    if_not_exists,
+                }))
+            }
+            Definition::CreateView {
+                schema_name,
+                view_name,
+                columns,
+                mut query,
+                or_replace,
+            } => {
+                let schema_name = self.creation_schema(schema_name)?;
+                if !(self.catalog.schema_exists(&SchemaName::from(&schema_name))) {
+                    return Err(SchemaPlanError::schema_does_not_exist(&schema_name));
+                }
+                if is_system_schema(&schema_name) {
+                    return Err(SchemaPlanError::permission_denied_to_create(&schema_name, &view_name));
+                }
+                let source = self.qualify_view_source(&mut query)?;
+                let (_select, columns) = self.analyze_view(&query, columns)?;
+                Ok(SchemaChange::CreateView(CreateViewQuery {
+                    full_view_name: FullTableName::from((&schema_name, &view_name)),
+                    columns,
+                    definition: serde_json::to_string(&query).unwrap(),
+                    source,
+                    used_functions: self.analyzer.take_inlined_functions(),
+                    or_replace,
+                }))
+            }
+            Definition::CreateMaterializedView {
+                schema_name,
+                view_name,
+                columns,
+                mut query,
+                if_not_exists,
+            } => {
+                let schema_name = self.creation_schema(schema_name)?;
+                if !(self.catalog.schema_exists(&SchemaName::from(&schema_name))) {
+                    return Err(SchemaPlanError::schema_does_not_exist(&schema_name));
+                }
+                if is_system_schema(&schema_name) {
+                    return Err(SchemaPlanError::permission_denied_to_create(&schema_name, &view_name));
+                }
+                let source = self.qualify_view_source(&mut query)?;
+                let (select, columns) = self.analyze_view(&query, columns)?;
+                let used_functions = self.analyzer.take_inlined_functions();
+                let declared_types = select
+                    .projection_items
+                    .iter()
+                    .map(|item| match item {
+                        UntypedTree::Item(UntypedItem::Column { sql_type, .. }) => Some(*sql_type),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let select = self.process_select(select)?;
+                let column_defs = columns
+                    .into_iter()
+                    .zip(declared_types)
+                    .zip(select.projection_items.iter())
+                    .map(|((name, declared_type), item)| ColumnInfo {
+                        name,
+                        sql_type: declared_type.unwrap_or_else(|| column_type(item.result_type())),
+                        identity: None,
+                    })
+                    .collect();
+                let rows = self.materialize(select)?;
+                Ok(SchemaChange::CreateMaterializedView(CreateMaterializedViewQuery {
+                    full_view_name: FullTableName::from((&schema_name, &view_name)),
+                    column_defs,
+                    definition: serde_json::to_string(&query).unwrap(),
+                    source,
+                    used_functions,
+                    rows,
+                    if_not_exists,
+                }))
+            }
+            Definition::RefreshMaterializedView {
+                schema_name,
+                view_name,
+                concurrently,
+            } => {
+                let full_view_name = self.qualify(schema_name, &view_name)?;
+                if !(self.catalog.schema_exists(&SchemaName::from(&full_view_name.schema()))) {
+                    return Err(SchemaPlanError::schema_does_not_exist(full_view_name.schema()));
+                }
+                let view_def = match self.catalog.view_definition(&full_view_name) {
+                    Some(view_def) if view_def.is_materialized() => view_def,
+                    _ => return Err(SchemaPlanError::materialized_view_does_not_exist(full_view_name)),
+                };
+                let query = serde_json::from_str::<SelectQuery>(view_def.definition()).unwrap();
+                let (select, _columns) = self.analyze_view(&query, vec![])?;
+                let rows = self.materialize(self.process_select(select)?)?;
+                Ok(SchemaChange::RefreshMaterializedView(RefreshMaterializedViewQuery {
+                    full_view_name,
+                    rows,
+                    concurrently,
+                }))
+            }
+            Definition::CreateFunction {
+                schema_name,
+                function_name,
+                parameters,
+                return_type,
+                body,
+                or_replace,
+            } => {
+                let schema_name = self.creation_schema(schema_name)?;
+                if !(self.catalog.schema_exists(&SchemaName::from(&schema_name))) {
+                    return Err(SchemaPlanError::schema_does_not_exist(&schema_name));
+                }
+                if is_system_schema(&schema_name) {
+                    return Err(SchemaPlanError::permission_denied_to_create(&schema_name, &function_name));
+                }
+                let full_function_name = FullTableName::from((&schema_name, &function_name));
+                let mut names = vec![];
+                for (name, _data_type) in parameters.iter() {
+                    match name {
+                        Some(name) if names.contains(&name) => {
+                            return Err(SchemaPlanError::invalid_function_definition(format!(
+                                "parameter name \"{}\" used more than once",
+                                name
+                            )))
+                        }
+                        Some(name) => names.push(name),
+                        None => {}
+                    }
+                }
+                let parameters = parameters
+                    .into_iter()
+                    .map(|(name, data_type)| (name.unwrap_or_default(), SqlType::from(data_type)))
+                    .collect::<Vec<_>>();
+                let body = match body {
+                    SelectQuery {
+                        distinct: None,
+                        mut select_items,
+                        table_name: None,
+                        where_clause: None,
+                        ..
+                    } if select_items.len() == 1 => select_items.pop(),
+                    _ => None,
+                };
+                let body = match body {
+                    Some(SelectItem::UnnamedExpr(expr)) | Some(SelectItem::ExprWithAlias { expr, .. }) => expr,
+                    _ => {
+                        return Err(SchemaPlanError::FeatureNotSupported(
+                            "SQL function body other than a single expression without FROM clause".to_owned(),
+                        ))
+                    }
+                };
+                self.analyzer.take_inlined_functions();
+                self.analyzer.analyze_function_body(&parameters, body.clone())?;
+                let used_functions = self.analyzer.take_inlined_functions();
+                // the body is inlined into its callers, so it could not call the function itself
+                if used_functions.contains(&full_function_name) {
+                    return Err(SchemaPlanError::FeatureNotSupported("recursive SQL functions".to_owned()));
+                }
+                Ok(SchemaChange::CreateFunction(CreateFunctionQuery {
+                    full_function_name,
+                    parameters,
+                    return_type: SqlType::from(return_type),
+                    body: serde_json::to_string(&body).unwrap(),
+                    used_functions,
+                    or_replace,
+                }))
+            }
+            Definition::DropFunctions { names, if_exists, cascade } => {
+                let mut full_function_names = vec![];
+                for (schema_name, function_name) in names {
+                    let full_function_name = match schema_name {
+                        Some(schema_name) => FullTableName::from((&schema_name, &function_name)),
+                        None => match self.catalog.qualify_function(&self.search_path.borrow(), &function_name) {
+                            Some(full_function_name) => full_function_name,
+                            None if if_exists => continue,
+                            None => return Err(SchemaPlanError::function_does_not_exist(function_name)),
+                        },
+                    };
+                    if !self.catalog.schema_exists(&SchemaName::from(&full_function_name.schema())) {
+                        return Err(SchemaPlanError::schema_does_not_exist(full_function_name.schema()));
+                    } else if is_system_schema(full_function_name.schema()) {
+                        return Err(SchemaPlanError::permission_denied(format!(
+                            "permission denied for function {}",
+                            function_name
+                        )));
+                    } else {
+                        full_function_names.push(full_function_name)
+                    }
+                }
+                Ok(SchemaChange::DropFunctions(DropFunctionsQuery {
+                    full_function_names,
+                    cascade,
+                    if_exists,
+                }))
+            }
+            Definition::DropViews {
+                names,
+                if_exists,
+                cascade,
+                materialized,
+            } => {
+                let mut full_view_names = vec![];
+                for (schema_name, view_name) in names {
+                    let full_view_name = match self.qualify(schema_name, &view_name) {
+                        Err(_) if if_exists => continue,
+                        full_view_name => full_view_name?,
+                    };
+                    if !self.catalog.schema_exists(&SchemaName::from(&full_view_name.schema())) {
+                        return Err(SchemaPlanError::schema_does_not_exist(full_view_name.schema()));
+                    } else if is_system_schema(full_view_name.schema()) {
+                        return Err(SchemaPlanError::system_catalog(&view_name));
+                    } else {
+                        full_view_names.push(full_view_name)
+                    }
+                }
+                Ok(SchemaChange::DropViews(DropViewsQuery {
+                    full_view_names,
+                    cascade,
+                    if_exists,
+                    materialized,
+                }))
+            }
             Definition::DropTables { names, if_exists, cascade } => {
                 let mut full_table_names = vec![];
                 for (schema_name, table_name) in names {
-                    let full_table_name = FullTableName::from((&schema_name, &table_name));
-                    if self.catalog.schema_exists(&SchemaName::from(&schema_name)) {
-                        full_table_names.push(full_table_name)
+                    let full_table_name = match self.qualify(schema_name, &table_name) {
+                        Err(_) if if_exists => continue,
+                        full_table_name => full_table_name?,
+                    };
+                    if !self.catalog.schema_exists(&SchemaName::from(&full_table_name.schema())) {
+                        return Err(SchemaPlanError::schema_does_not_exist(full_table_name.schema()));
+                    } else if is_system_schema(full_table_name.schema()) {
+                        return Err(SchemaPlanError::system_catalog(&table_name));
                     } else {
-                        return Err(SchemaPlanError::schema_does_not_exist(&schema_name));
+                        full_table_names.push(full_table_name)
                     }
                 }
                 Ok(SchemaChange::DropTables(DropTablesQuery {
@@ -106,7 +430,51 @@ impl<'p> DefinitionPlanner<'p> {
                     if_exists,
                 }))
             }
+            Definition::Analyze { names } => {
+                let mut full_table_names = vec![];
+                for (schema_name, table_name) in names {
+                    let full_table_name = self.qualify(schema_name, &table_name)?;
+                    match self.catalog.table_definition(full_table_name.clone()) {
+                        None => return Err(SchemaPlanError::schema_does_not_exist(full_table_name.schema())),
+                        Some(None) => return Err(SchemaPlanError::table_does_not_exist(full_table_name)),
+                        Some(Some(_)) if is_system_schema(full_table_name.schema()) => return Err(SchemaPlanError::system_catalog(&table_name)),
+                        Some(Some(_)) => full_table_names.push(full_table_name),
+                    }
+                }
+                Ok(SchemaChange::AnalyzeTables(AnalyzeTablesQuery { full_table_names }))
+            }
+            Definition::Truncate {
+                names,
+                restart_identity,
+                cascade,
+            } => {
+                let mut full_table_names = vec![];
+                for (schema_name, table_name) in names {
+                    let full_table_name = self.qualify(schema_name, &table_name)?;
+                    match self.catalog.table_definition(full_table_name.clone()) {
+                        None => return Err(SchemaPlanError::schema_does_not_exist(full_table_name.schema())),
+                        Some(Some(_)) if is_system_schema(full_table_name.schema()) => return Err(SchemaPlanError::system_catalog(&table_name)),
+                        Some(Some(_)) if self.catalog.view_definition(&full_table_name).is_none() => {
+                            if !full_table_names.contains(&full_table_name) {
+                                full_table_names.push(full_table_name)
+                            }
+                        }
+                        _ => return Err(SchemaPlanError::table_does_not_exist(full_table_name)),
+                    }
+                }
+                Ok(SchemaChange::TruncateTables(TruncateTablesQuery {
+                    full_table_names,
+                    restart_identity,
+                    cascade,
+                }))
+            }
             Definition::DropSchemas { names, cascade, if_exists } => {
+                if let Some(schema_name) = names.iter().find(|schema_name| is_system_schema(schema_name)) {
+                    return Err(SchemaPlanError::permission_denied(format!(
+                        "permission denied for schema {}",
+                        schema_name
+                    )));
+                }
                 let schema_names = names.iter().map(SchemaName::from).collect::<Vec<_>>();
                 Ok(SchemaChange::DropSchemas(DropSchemasQuery {
                     schema_names,
@@ -116,6 +484,127 @@ impl<'p> DefinitionPlanner<'p> {
             }
         }
     }
+
+    // objects are created in the first existing schema of the search path
+    fn creation_schema(&self, schema_name: Option<String>) -> Result<String, SchemaPlanError> {
+        match schema_name {
+            Some(schema_name) => Ok(schema_name),
+            None => self
+                .catalog
+                .creation_schema(&self.search_path.borrow())
+                .ok_or(SchemaPlanError::NoSchemaSelected),
+        }
+    }
+
+    fn qualify(&self, schema_name: Option<String>, name: &str) -> Result<FullTableName, SchemaPlanError> {
+        match schema_name {
+            Some(schema_name) => Ok(FullTableName::from((&schema_name, &name))),
+            None => self
+                .catalog
+                .qualify(&self.search_path.borrow(), name)
+                .ok_or_else(|| SchemaPlanError::table_does_not_exist(name)),
+        }
+    }
+
+    // view definition is saved with qualified source so it does not depend on the search path
+    fn qualify_view_source(&self, query: &mut SelectQuery) -> Result<FullTableName, SchemaPlanError> {
+        let table_name = match &query.table_name {
+            None => return Err(SchemaPlanError::FeatureNotSupported("views without FROM clause".to_owned())),
+            Some(table_name) => table_name,
+        };
+        let source = self.qualify(query.schema_name.take(), table_name)?;
+        query.schema_name = Some(source.schema().to_owned());
+        Ok(source)
+    }
+
+    fn analyze_view(&self, query: &SelectQuery, columns: Vec<String>) -> Result<(UntypedSelectQuery, Vec<String>), SchemaPlanError> {
+        // functions inlined into the view are taken by the caller as the view dependencies
+        self.analyzer.take_inlined_functions();
+        let select = match self.analyzer.analyze(Query::Select(query.clone()))? {
+            UntypedQuery::Select(select) => select,
+            _ => unreachable!(),
+        };
+        if columns.len() > select.projection_items.len() {
+            return Err(SchemaPlanError::TooManyColumnNames);
+        }
+        let columns = select
+            .column_names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| columns.get(index).unwrap_or(name).clone())
+            .collect();
+        Ok((select, columns))
+    }
+
+    fn process_select(&self, select: UntypedSelectQuery) -> Result<TypedSelectQuery, SchemaPlanError> {
+        let projection_items = select
+            .projection_items
+            .into_iter()
+            .map(|item| self.type_inference.infer_type(item, &[]))
+            .map(|item| self.type_checker.type_check(item))
+            .map(|item| self.type_coercion.coerce(item))
+            .map(|item| self.simplifier.simplify(item))
+            .collect::<Result<Vec<TypedTree>, _>>()?;
+        let filter = select
+            .filter
+            .map(|filter| self.type_inference.infer_type(filter, &[]))
+            .map(|filter| self.type_checker.type_check(filter))
+            .map(|filter| self.type_coercion.coerce(filter))
+            .map(|filter| self.simplifier.simplify(filter))
+            .transpose()?;
+        let process = |keys: Vec<UntypedTree>| {
+            keys.into_iter()
+                .map(|key| self.type_inference.infer_type(key, &[]))
+                .map(|key| self.type_checker.type_check(key))
+                .map(|key| self.type_coercion.coerce(key))
+                .map(|key| self.simplifier.simplify(key))
+                .collect::<Result<Vec<TypedTree>, _>>()
+        };
+        let distinct = match select.distinct {
+            None => None,
+            Some(UntypedDistinct::Rows(keys)) => Some(TypedDistinct::Rows(process(keys)?)),
+            Some(UntypedDistinct::On(keys)) => Some(TypedDistinct::On(process(keys)?)),
+        };
+        let subquery = match select.subquery {
+            None => None,
+            Some(subquery) => Some(Box::new(self.process_select(*subquery)?)),
+        };
+        Ok(TypedSelectQuery {
+            full_table_name: select.full_table_name,
+            subquery,
+            projection_items,
+            column_names: select.column_names,
+            filter,
+            distinct,
+        })
+    }
+
+    fn materialize(&self, select: TypedSelectQuery) -> Result<Vec<Vec<BinaryValue>>, SchemaPlanError> {
+        let mut plan = match self.query_planner.plan(TypedQuery::Select(select)) {
+            QueryPlan::Select(plan) => plan,
+            _ => unreachable!(),
+        };
+        let context = EvaluationContext::new(vec![], self.transaction_timestamp);
+        let mut rows = vec![];
+        while let Some(row) = plan.next_row(&context)? {
+            rows.push(row.into_iter().map(|value| value.convert()).collect());
+        }
+        Ok(rows)
+    }
+}
+
+// materialized view columns that are computed from expressions have no declared type,
+// strings and timestamps are typed as `varchar` without length
+fn column_type(type_family: Option<SqlTypeFamily>) -> SqlType {
+    match type_family {
+        Some(SqlTypeFamily::Bool) => SqlType::bool(),
+        Some(SqlTypeFamily::SmallInt) => SqlType::small_int(),
+        Some(SqlTypeFamily::Integer) => SqlType::integer(),
+        Some(SqlTypeFamily::BigInt) => SqlType::big_int(),
+        Some(SqlTypeFamily::Real) => SqlType::real(),
+        Some(SqlTypeFamily::Double) => SqlType::double_precision(),
+        Some(SqlTypeFamily::String) | Some(SqlTypeFamily::TimestampTz) | None => SqlType::from(DataType::VarChar(None)),
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -123,6 +612,16 @@ pub enum SchemaPlanError {
     SchemaDoesNotExist(String),
     TableDoesNotExist(String),
     ColumnNotFound(String),
+    MaterializedViewDoesNotExist(String),
+    FunctionDoesNotExist(String),
+    InvalidParameterValue(String),
+    InvalidFunctionDefinition(String),
+    PermissionDenied(String),
+    NoSchemaSelected,
+    FeatureNotSupported(String),
+    TooManyColumnNames,
+    Analysis(AnalysisError),
+    Execution(QueryExecutionError),
 }
 
 impl SchemaPlanError {
@@ -137,6 +636,38 @@ impl SchemaPlanError {
     pub fn column_not_found<C: ToString>(column_name: C) -> SchemaPlanError {
         SchemaPlanError::ColumnNotFound(column_name.to_string())
     }
+
+    pub fn materialized_view_does_not_exist<V: ToString>(view_name: V) -> SchemaPlanError {
+        SchemaPlanError::MaterializedViewDoesNotExist(view_name.to_string())
+    }
+
+    pub fn function_does_not_exist<F: ToString>(function_name: F) -> SchemaPlanError {
+        SchemaPlanError::FunctionDoesNotExist(function_name.to_string())
+    }
+
+    pub fn invalid_parameter_value<M: ToString>(message: M) -> SchemaPlanError {
+        SchemaPlanError::InvalidParameterValue(message.to_string())
+    }
+
+    pub fn invalid_function_definition<M: ToString>(message: M) -> SchemaPlanError {
+        SchemaPlanError::InvalidFunctionDefinition(message.to_string())
+    }
+
+    pub fn permission_denied<M: ToString>(message: M) -> SchemaPlanError {
+        SchemaPlanError::PermissionDenied(message.to_string())
+    }
+
+    pub fn permission_denied_to_create<S: ToString, O: ToString>(schema_name: S, object_name: O) -> SchemaPlanError {
+        SchemaPlanError::permission_denied(format!(
+            "permission denied to create \"{}.{}\"",
+            schema_name.to_string(),
+            object_name.to_string()
+        ))
+    }
+
+    pub fn system_catalog<T: ToString>(table_name: T) -> SchemaPlanError {
+        SchemaPlanError::permission_denied(format!("permission denied: \"{}\" is a system catalog", table_name.to_string()))
+    }
 }
 
 impl From<SchemaPlanError> for QueryError {
@@ -145,9 +676,31 @@ impl From<SchemaPlanError> for QueryError {
             SchemaPlanError::SchemaDoesNotExist(schema) => QueryError::schema_does_not_exist(schema),
             SchemaPlanError::TableDoesNotExist(table) => QueryError::table_does_not_exist(table),
             SchemaPlanError::ColumnNotFound(column) => QueryError::column_does_not_exist(column),
+            SchemaPlanError::MaterializedViewDoesNotExist(view) => QueryError::materialized_view_does_not_exist(view),
+            SchemaPlanError::FunctionDoesNotExist(function) => QueryError::function_does_not_exist(function),
+            SchemaPlanError::InvalidParameterValue(message) => QueryError::invalid_parameter_value(message),
+            SchemaPlanError::InvalidFunctionDefinition(message) => QueryError::invalid_function_definition(message),
+            SchemaPlanError::PermissionDenied(message) => QueryError::insufficient_privilege(message),
+            SchemaPlanError::NoSchemaSelected => QueryError::no_schema_selected(),
+            SchemaPlanError::FeatureNotSupported(feature) => QueryError::feature_not_supported(feature),
+            SchemaPlanError::TooManyColumnNames => QueryError::syntax_error("CREATE VIEW specifies more column names than columns"),
+            SchemaPlanError::Analysis(error) => QueryError::from(error),
+            SchemaPlanError::Execution(error) => QueryError::from(error),
         }
     }
 }
 
+impl From<AnalysisError> for SchemaPlanError {
+    fn from(error: AnalysisError) -> SchemaPlanError {
+        SchemaPlanError::Analysis(error)
+    }
+}
+
+impl From<QueryExecutionError> for SchemaPlanError {
+    fn from(error: QueryExecutionError) -> SchemaPlanError {
+        SchemaPlanError::Execution(error)
+    }
+}
+
 #[cfg(test)]
 mod tests;
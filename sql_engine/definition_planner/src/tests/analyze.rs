@@ -0,0 +1,73 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::AnalyzeTablesQuery;
+
+fn analyze(names: Vec<(&str, &str)>) -> Definition {
+    Definition::Analyze {
+        names: names
+            .into_iter()
+            .map(|(schema_name, table_name)| (Some(schema_name.to_owned()), table_name.to_owned()))
+            .collect(),
+    }
+}
+
+#[test]
+fn analyze_all_tables() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(analyze(vec![])),
+        Ok(SchemaChange::AnalyzeTables(AnalyzeTablesQuery { full_table_names: vec![] }))
+    );
+}
+
+#[test]
+fn analyze_table_in_not_existent_schema() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(analyze(vec![("non_existent", TABLE)])),
+        Err(SchemaPlanError::schema_does_not_exist("non_existent"))
+    );
+}
+
+#[test]
+fn analyze_not_existent_table() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(analyze(vec![("public", "non_existent")])),
+        Err(SchemaPlanError::table_does_not_exist("public.non_existent"))
+    );
+}
+
+#[test]
+fn analyze_existing_table() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog
+        .apply(create_table_ops("public", TABLE, vec![("column", SqlType::small_int())]))
+        .unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(analyze(vec![("public", TABLE)])),
+        Ok(SchemaChange::AnalyzeTables(AnalyzeTablesQuery {
+            full_table_names: vec![FullTableName::from((&"public", &TABLE))]
+        }))
+    );
+}
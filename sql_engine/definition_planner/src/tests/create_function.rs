@@ -0,0 +1,288 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::{CreateFunctionQuery, CreateViewQuery};
+use query_analyzer::AnalysisError;
+use query_ast::{BinaryOperator, Expr, SelectItem, SelectQuery};
+
+const FUNCTION: &str = "function_name";
+
+fn create_function(function_name: &str, parameters: Vec<(Option<&str>, DataType)>, body: Expr, or_replace: bool) -> Definition {
+    Definition::CreateFunction {
+        schema_name: Some(SCHEMA.to_owned()),
+        function_name: function_name.to_owned(),
+        parameters: parameters
+            .into_iter()
+            .map(|(name, data_type)| (name.map(ToOwned::to_owned), data_type))
+            .collect(),
+        return_type: DataType::Int,
+        body: select(body),
+        or_replace,
+    }
+}
+
+fn select(expr: Expr) -> SelectQuery {
+    SelectQuery {
+        distinct: None,
+        select_items: vec![SelectItem::UnnamedExpr(expr)],
+        schema_name: None,
+        table_name: None,
+        where_clause: None,
+    }
+}
+
+fn plus(left: Expr, right: Expr) -> Expr {
+    Expr::BinaryOp {
+        left: Box::new(left),
+        op: BinaryOperator::Plus,
+        right: Box::new(right),
+    }
+}
+
+fn call(function_name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function {
+        name: function_name.to_owned(),
+        args,
+    }
+}
+
+fn column(name: &str) -> Expr {
+    Expr::Column(name.to_owned())
+}
+
+#[test]
+fn create_function_in_nonexistent_schema() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(create_function(FUNCTION, vec![(Some("a"), DataType::Int)], column("a"), false)),
+        Err(SchemaPlanError::schema_does_not_exist(SCHEMA))
+    );
+}
+
+#[test]
+fn create_function_with_named_and_positional_parameters() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_function(
+            FUNCTION,
+            vec![(Some("a"), DataType::Int), (None, DataType::SmallInt)],
+            plus(column("a"), Expr::Param(2)),
+            false
+        )),
+        Ok(SchemaChange::CreateFunction(CreateFunctionQuery {
+            full_function_name: FullTableName::from((&SCHEMA, &FUNCTION)),
+            parameters: vec![("a".to_owned(), SqlType::integer()), ("".to_owned(), SqlType::small_int())],
+            return_type: SqlType::integer(),
+            body: serde_json::to_string(&plus(column("a"), Expr::Param(2))).unwrap(),
+            used_functions: vec![],
+            or_replace: false,
+        }))
+    );
+}
+
+#[test]
+fn create_function_with_duplicate_parameter_names() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_function(
+            FUNCTION,
+            vec![(Some("a"), DataType::Int), (Some("a"), DataType::Int)],
+            column("a"),
+            false
+        )),
+        Err(SchemaPlanError::invalid_function_definition("parameter name \"a\" used more than once"))
+    );
+}
+
+#[test]
+fn create_function_referring_unknown_parameter() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_function(FUNCTION, vec![(Some("a"), DataType::Int)], column("b"), false)),
+        Err(SchemaPlanError::Analysis(AnalysisError::column_not_found("b")))
+    );
+    assert_eq!(
+        planner.plan(create_function(FUNCTION, vec![(Some("a"), DataType::Int)], Expr::Param(2), false)),
+        Err(SchemaPlanError::Analysis(AnalysisError::invalid_function_definition(
+            "there is no parameter $2"
+        )))
+    );
+}
+
+#[test]
+fn create_function_with_body_selecting_from_table() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(Definition::CreateFunction {
+            schema_name: Some(SCHEMA.to_owned()),
+            function_name: FUNCTION.to_owned(),
+            parameters: vec![],
+            return_type: DataType::Int,
+            body: SelectQuery {
+                table_name: Some(TABLE.to_owned()),
+                ..select(column("col_1"))
+            },
+            or_replace: false,
+        }),
+        Err(SchemaPlanError::FeatureNotSupported(
+            "SQL function body other than a single expression without FROM clause".to_owned()
+        ))
+    );
+}
+
+#[test]
+fn create_function_calling_other_function() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    planner.set_search_path(SearchPath::new(SCHEMA, ""));
+    catalog
+        .apply(
+            planner
+                .plan(create_function(
+                    "inc",
+                    vec![(Some("a"), DataType::Int)],
+                    plus(column("a"), Expr::Value(query_ast::Value::Int(1))),
+                    false,
+                ))
+                .unwrap(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        planner.plan(create_function(
+            FUNCTION,
+            vec![(Some("b"), DataType::Int)],
+            call("inc", vec![column("b")]),
+            false
+        )),
+        Ok(SchemaChange::CreateFunction(CreateFunctionQuery {
+            full_function_name: FullTableName::from((&SCHEMA, &FUNCTION)),
+            parameters: vec![("b".to_owned(), SqlType::integer())],
+            return_type: SqlType::integer(),
+            body: serde_json::to_string(&call("inc", vec![column("b")])).unwrap(),
+            used_functions: vec![FullTableName::from((&SCHEMA, &"inc"))],
+            or_replace: false,
+        }))
+    );
+}
+
+#[test]
+fn replace_function_with_recursive_call() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    planner.set_search_path(SearchPath::new(SCHEMA, ""));
+    catalog
+        .apply(
+            planner
+                .plan(create_function("first", vec![(Some("a"), DataType::Int)], column("a"), false))
+                .unwrap(),
+        )
+        .unwrap();
+    catalog
+        .apply(
+            planner
+                .plan(create_function(
+                    "second",
+                    vec![(Some("a"), DataType::Int)],
+                    call("first", vec![column("a")]),
+                    false,
+                ))
+                .unwrap(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        planner.plan(create_function(
+            "first",
+            vec![(Some("a"), DataType::Int)],
+            call("second", vec![column("a")]),
+            true
+        )),
+        Err(SchemaPlanError::FeatureNotSupported("recursive SQL functions".to_owned()))
+    );
+}
+
+#[test]
+fn create_view_depends_on_called_functions() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col_1", SqlType::integer())]))
+        .unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    planner.set_search_path(SearchPath::new(SCHEMA, ""));
+    catalog
+        .apply(
+            planner
+                .plan(create_function(FUNCTION, vec![(Some("a"), DataType::Int)], column("a"), false))
+                .unwrap(),
+        )
+        .unwrap();
+
+    let query = SelectQuery {
+        schema_name: Some(SCHEMA.to_owned()),
+        table_name: Some(TABLE.to_owned()),
+        ..select(call(FUNCTION, vec![column("col_1")]))
+    };
+    assert_eq!(
+        planner.plan(Definition::CreateView {
+            schema_name: Some(SCHEMA.to_owned()),
+            view_name: "view_name".to_owned(),
+            columns: vec![],
+            query: query.clone(),
+            or_replace: false,
+        }),
+        Ok(SchemaChange::CreateView(CreateViewQuery {
+            full_view_name: FullTableName::from((&SCHEMA, &"view_name")),
+            columns: vec![FUNCTION.to_owned()],
+            definition: serde_json::to_string(&query).unwrap(),
+            source: FullTableName::from((&SCHEMA, &TABLE)),
+            used_functions: vec![FullTableName::from((&SCHEMA, &FUNCTION))],
+            or_replace: false,
+        }))
+    );
+}
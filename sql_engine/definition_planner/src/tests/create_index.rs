@@ -20,7 +20,7 @@ const DEFAULT_SCHEMA: &str = "public";
 fn create_index(index_name: &str, schema_name: &str, table_name: &str, columns: Vec<&str>) -> Definition {
     Definition::CreateIndex {
         name: index_name.to_owned(),
-        schema_name: schema_name.to_owned(),
+        schema_name: Some(schema_name.to_owned()),
         table_name: table_name.to_owned(),
         column_names: columns.into_iter().map(ToOwned::to_owned).collect(),
     }
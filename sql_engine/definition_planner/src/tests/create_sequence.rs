@@ -0,0 +1,113 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::CreateSequenceQuery;
+
+const SEQUENCE: &str = "sequence_name";
+
+fn create_sequence(start: Option<i64>, increment: Option<i64>, min_value: Option<i64>, max_value: Option<i64>) -> Definition {
+    Definition::CreateSequence {
+        schema_name: Some(SCHEMA.to_owned()),
+        sequence_name: SEQUENCE.to_owned(),
+        start,
+        increment,
+        min_value,
+        max_value,
+        if_not_exists: false,
+    }
+}
+
+fn create_sequence_ops(start: i64, increment: i64, min_value: i64, max_value: i64) -> SchemaChange {
+    SchemaChange::CreateSequence(CreateSequenceQuery {
+        full_sequence_name: FullTableName::from((&SCHEMA, &SEQUENCE)),
+        start,
+        increment,
+        min_value,
+        max_value,
+        if_not_exists: false,
+    })
+}
+
+#[test]
+fn create_sequence_with_nonexistent_schema() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(create_sequence(None, None, None, None)),
+        Err(SchemaPlanError::schema_does_not_exist(SCHEMA))
+    );
+}
+
+#[test]
+fn create_ascending_sequence_with_defaults() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_sequence(None, None, None, None)),
+        Ok(create_sequence_ops(1, 1, 1, i64::MAX))
+    );
+}
+
+#[test]
+fn create_descending_sequence_with_defaults() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_sequence(None, Some(-2), None, None)),
+        Ok(create_sequence_ops(-1, -2, i64::MIN, -1))
+    );
+}
+
+#[test]
+fn create_sequence_with_zero_increment() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_sequence(None, Some(0), None, None)),
+        Err(SchemaPlanError::invalid_parameter_value("INCREMENT must not be zero"))
+    );
+}
+
+#[test]
+fn create_sequence_with_start_out_of_bounds() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_sequence(Some(100), None, Some(10), Some(20))),
+        Err(SchemaPlanError::invalid_parameter_value(
+            "START value (100) cannot be greater than MAXVALUE (20)"
+        ))
+    );
+    assert_eq!(
+        planner.plan(create_sequence(Some(20), None, Some(20), Some(10))),
+        Err(SchemaPlanError::invalid_parameter_value("MINVALUE (20) must be less than MAXVALUE (10)"))
+    );
+}
@@ -13,12 +13,14 @@
 // limitations under the License.
 
 use super::*;
-use query_ast::DataType;
+use definition::ColumnIdentity;
+use query_ast::{DataType, Identity};
 
 fn column(name: &str, data_type: DataType) -> ColumnDef {
     ColumnDef {
         name: name.to_owned(),
         data_type,
+        identity: None,
     }
 }
 
@@ -73,7 +75,8 @@ fn create_new_table_if_not_exist() {
             full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
             column_defs: vec![ColumnInfo {
                 name: "column_name".to_owned(),
-                sql_type: SqlType::small_int()
+                sql_type: SqlType::small_int(),
+                identity: None,
             }],
             if_not_exists: true,
         }))
@@ -94,9 +97,75 @@ fn successfully_create_table() {
             full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
             column_defs: vec![ColumnInfo {
                 name: "column_name".to_owned(),
-                sql_type: SqlType::small_int()
+                sql_type: SqlType::small_int(),
+                identity: None,
             }],
             if_not_exists: false,
         }))
     );
 }
+
+#[test]
+fn create_table_with_identity_column() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_table(
+            SCHEMA,
+            TABLE,
+            vec![ColumnDef {
+                name: "id".to_owned(),
+                data_type: DataType::BigInt,
+                identity: Some(Identity::Always),
+            }]
+        )),
+        Ok(SchemaChange::CreateTable(CreateTableQuery {
+            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            column_defs: vec![ColumnInfo {
+                name: "id".to_owned(),
+                sql_type: SqlType::big_int(),
+                identity: Some(ColumnIdentity::Always),
+            }],
+            if_not_exists: false,
+        }))
+    );
+}
+
+#[test]
+fn create_table_with_identity_column_of_not_integer_type() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_table(
+            SCHEMA,
+            TABLE,
+            vec![ColumnDef {
+                name: "id".to_owned(),
+                data_type: DataType::Real,
+                identity: Some(Identity::ByDefault),
+            }]
+        )),
+        Err(SchemaPlanError::invalid_parameter_value(
+            "identity column type must be smallint, integer, or bigint"
+        ))
+    );
+}
+
+#[test]
+fn create_table_in_system_schema() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+
+    assert_eq!(
+        planner.plan(create_table("pg_catalog", TABLE, vec![])),
+        Err(SchemaPlanError::permission_denied_to_create("pg_catalog", TABLE))
+    );
+}
@@ -0,0 +1,115 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::CreateViewQuery;
+use query_analyzer::AnalysisError;
+use query_ast::{Expr, SelectItem, SelectQuery};
+
+const VIEW: &str = "view_name";
+
+fn create_view(columns: Vec<&str>, query: SelectQuery) -> Definition {
+    Definition::CreateView {
+        schema_name: Some(SCHEMA.to_owned()),
+        view_name: VIEW.to_owned(),
+        columns: columns.into_iter().map(ToOwned::to_owned).collect(),
+        query,
+        or_replace: false,
+    }
+}
+
+fn select(columns: Vec<&str>) -> SelectQuery {
+    SelectQuery {
+        distinct: None,
+        select_items: columns
+            .into_iter()
+            .map(|column| SelectItem::UnnamedExpr(Expr::Column(column.to_owned())))
+            .collect(),
+        schema_name: Some(SCHEMA.to_owned()),
+        table_name: Some(TABLE.to_owned()),
+        where_clause: None,
+    }
+}
+
+#[test]
+fn create_view_in_nonexistent_schema() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(create_view(vec![], select(vec!["col_1"]))),
+        Err(SchemaPlanError::schema_does_not_exist(SCHEMA))
+    );
+}
+
+#[test]
+fn create_view_over_nonexistent_table() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_view(vec![], select(vec!["col_1"]))),
+        Err(SchemaPlanError::Analysis(AnalysisError::table_does_not_exist(format!(
+            "{}.{}",
+            SCHEMA, TABLE
+        ))))
+    );
+}
+
+#[test]
+fn create_view_names_columns_after_query() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::bool())],
+        ))
+        .unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_view(vec!["view_col"], select(vec!["col_2", "col_1"]))),
+        Ok(SchemaChange::CreateView(CreateViewQuery {
+            full_view_name: FullTableName::from((&SCHEMA, &VIEW)),
+            columns: vec!["view_col".to_owned(), "col_1".to_owned()],
+            definition: serde_json::to_string(&select(vec!["col_2", "col_1"])).unwrap(),
+            source: FullTableName::from((&SCHEMA, &TABLE)),
+            used_functions: vec![],
+            or_replace: false,
+        }))
+    );
+}
+
+#[test]
+fn create_view_with_more_column_names_than_columns() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col_1", SqlType::small_int())]))
+        .unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_view(vec!["view_col_1", "view_col_2"], select(vec!["col_1"]))),
+        Err(SchemaPlanError::TooManyColumnNames)
+    );
+}
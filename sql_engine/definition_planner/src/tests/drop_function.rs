@@ -0,0 +1,257 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::{CreateFunctionQuery, CreateViewQuery, DropFunctionsQuery, DropViewsQuery, ExecutionError, ExecutionOutcome};
+use query_ast::{Expr, SelectItem, SelectQuery};
+
+const FUNCTION: &str = "function_name";
+const VIEW: &str = "view_name";
+
+fn drop_functions(names: Vec<(Option<&str>, &str)>, if_exists: bool, cascade: bool) -> Definition {
+    Definition::DropFunctions {
+        names: names
+            .into_iter()
+            .map(|(schema, function)| (schema.map(ToOwned::to_owned), function.to_owned()))
+            .collect(),
+        if_exists,
+        cascade,
+    }
+}
+
+fn create_function_ops(function_name: &str, used_functions: Vec<&str>) -> SchemaChange {
+    SchemaChange::CreateFunction(CreateFunctionQuery {
+        full_function_name: FullTableName::from((&SCHEMA, &function_name)),
+        parameters: vec![("a".to_owned(), SqlType::integer())],
+        return_type: SqlType::integer(),
+        body: serde_json::to_string(&Expr::Column("a".to_owned())).unwrap(),
+        used_functions: used_functions
+            .into_iter()
+            .map(|function_name| FullTableName::from((&SCHEMA, &function_name)))
+            .collect(),
+        or_replace: false,
+    })
+}
+
+fn create_view_ops(used_functions: Vec<&str>) -> SchemaChange {
+    SchemaChange::CreateView(CreateViewQuery {
+        full_view_name: FullTableName::from((&SCHEMA, &VIEW)),
+        columns: vec![FUNCTION.to_owned()],
+        definition: serde_json::to_string(&SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::UnnamedExpr(Expr::Function {
+                name: FUNCTION.to_owned(),
+                args: vec![Expr::Column("col_1".to_owned())],
+            })],
+            schema_name: Some(SCHEMA.to_owned()),
+            table_name: Some(TABLE.to_owned()),
+            where_clause: None,
+        })
+        .unwrap(),
+        source: FullTableName::from((&SCHEMA, &TABLE)),
+        used_functions: used_functions
+            .into_iter()
+            .map(|function_name| FullTableName::from((&SCHEMA, &function_name)))
+            .collect(),
+        or_replace: false,
+    })
+}
+
+fn drop_schema_ops(cascade: bool) -> SchemaChange {
+    SchemaChange::DropSchemas(DropSchemasQuery {
+        schema_names: vec![SchemaName::from(&SCHEMA)],
+        cascade,
+        if_exists: false,
+    })
+}
+
+fn drop_functions_ops(function_names: Vec<&str>, cascade: bool) -> SchemaChange {
+    SchemaChange::DropFunctions(DropFunctionsQuery {
+        full_function_names: function_names
+            .into_iter()
+            .map(|function_name| FullTableName::from((&SCHEMA, &function_name)))
+            .collect(),
+        cascade,
+        if_exists: false,
+    })
+}
+
+#[test]
+fn drop_nonexistent_function() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    planner.set_search_path(SearchPath::new(SCHEMA, ""));
+    assert_eq!(
+        planner.plan(drop_functions(vec![(None, FUNCTION)], false, false)),
+        Err(SchemaPlanError::function_does_not_exist(FUNCTION))
+    );
+    assert_eq!(
+        planner.plan(drop_functions(vec![(None, FUNCTION)], true, false)),
+        Ok(SchemaChange::DropFunctions(DropFunctionsQuery {
+            full_function_names: vec![],
+            cascade: false,
+            if_exists: true,
+        }))
+    );
+    assert_eq!(
+        catalog.apply(drop_functions_ops(vec![FUNCTION], false)),
+        Err(ExecutionError::FunctionDoesNotExist(SCHEMA.to_owned(), FUNCTION.to_owned()))
+    );
+}
+
+#[test]
+fn drop_function_qualified_with_search_path() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_function_ops(FUNCTION, vec![])).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    planner.set_search_path(SearchPath::new(SCHEMA, ""));
+    assert_eq!(
+        planner.plan(drop_functions(vec![(None, FUNCTION)], false, true)),
+        Ok(SchemaChange::DropFunctions(DropFunctionsQuery {
+            full_function_names: vec![FullTableName::from((&SCHEMA, &FUNCTION))],
+            cascade: true,
+            if_exists: false,
+        }))
+    );
+    assert_eq!(
+        catalog.apply(drop_functions_ops(vec![FUNCTION], false)),
+        Ok(ExecutionOutcome::FunctionDropped)
+    );
+    assert_eq!(catalog.function_definition(&FullTableName::from((&SCHEMA, &FUNCTION))), None);
+}
+
+#[test]
+fn drop_function_used_by_view() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col_1", SqlType::integer())]))
+        .unwrap();
+    catalog.apply(create_function_ops(FUNCTION, vec![])).unwrap();
+    catalog.apply(create_view_ops(vec![FUNCTION])).unwrap();
+
+    assert_eq!(
+        catalog.apply(drop_functions_ops(vec![FUNCTION], false)),
+        Err(ExecutionError::DependentObjectsStillExist(format!("function {}.{}", SCHEMA, FUNCTION)))
+    );
+    assert_eq!(
+        catalog.apply(drop_functions_ops(vec![FUNCTION], true)),
+        Ok(ExecutionOutcome::FunctionDropped)
+    );
+    assert_eq!(catalog.view_definition(&FullTableName::from((&SCHEMA, &VIEW))), None);
+}
+
+#[test]
+fn drop_function_used_by_other_function() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col_1", SqlType::integer())]))
+        .unwrap();
+    catalog.apply(create_function_ops("inner_function", vec![])).unwrap();
+    catalog.apply(create_function_ops(FUNCTION, vec!["inner_function"])).unwrap();
+    catalog.apply(create_view_ops(vec![FUNCTION, "inner_function"])).unwrap();
+
+    assert_eq!(
+        catalog.apply(drop_functions_ops(vec!["inner_function"], false)),
+        Err(ExecutionError::DependentObjectsStillExist(format!("function {}.inner_function", SCHEMA)))
+    );
+    assert_eq!(
+        catalog.apply(drop_functions_ops(vec!["inner_function"], true)),
+        Ok(ExecutionOutcome::FunctionDropped)
+    );
+    assert_eq!(catalog.function_definition(&FullTableName::from((&SCHEMA, &FUNCTION))), None);
+    assert_eq!(catalog.view_definition(&FullTableName::from((&SCHEMA, &VIEW))), None);
+}
+
+#[test]
+fn dropped_view_does_not_depend_on_function() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col_1", SqlType::integer())]))
+        .unwrap();
+    catalog.apply(create_function_ops(FUNCTION, vec![])).unwrap();
+    catalog.apply(create_view_ops(vec![FUNCTION])).unwrap();
+    catalog
+        .apply(SchemaChange::DropViews(DropViewsQuery {
+            full_view_names: vec![FullTableName::from((&SCHEMA, &VIEW))],
+            cascade: false,
+            if_exists: false,
+            materialized: false,
+        }))
+        .unwrap();
+
+    assert_eq!(
+        catalog.apply(drop_functions_ops(vec![FUNCTION], false)),
+        Ok(ExecutionOutcome::FunctionDropped)
+    );
+}
+
+#[test]
+fn replace_function_with_other_return_type() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_function_ops(FUNCTION, vec![])).unwrap();
+
+    assert_eq!(
+        catalog.apply(create_function_ops(FUNCTION, vec![])),
+        Err(ExecutionError::FunctionAlreadyExists(SCHEMA.to_owned(), FUNCTION.to_owned()))
+    );
+    assert_eq!(
+        catalog.apply(SchemaChange::CreateFunction(CreateFunctionQuery {
+            full_function_name: FullTableName::from((&SCHEMA, &FUNCTION)),
+            parameters: vec![("a".to_owned(), SqlType::integer())],
+            return_type: SqlType::big_int(),
+            body: serde_json::to_string(&Expr::Column("a".to_owned())).unwrap(),
+            used_functions: vec![],
+            or_replace: true,
+        })),
+        Err(ExecutionError::InvalidFunctionDefinition(
+            "cannot change return type of existing function".to_owned()
+        ))
+    );
+}
+
+#[test]
+fn drop_schema_with_function() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_function_ops(FUNCTION, vec![])).unwrap();
+
+    assert_eq!(
+        catalog.apply(drop_schema_ops(false)),
+        Err(ExecutionError::SchemaHasDependentObjects(SCHEMA.to_owned()))
+    );
+    assert_eq!(catalog.apply(drop_schema_ops(true)), Ok(ExecutionOutcome::SchemaDropped));
+    assert_eq!(catalog.function_definition(&FullTableName::from((&SCHEMA, &FUNCTION))), None);
+}
@@ -104,3 +104,14 @@ fn drop_schema_if_exists() {
         }))
     );
 }
+
+#[test]
+fn drop_system_schema() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+
+    assert_eq!(
+        planner.plan(drop_cascade(vec!["information_schema"])),
+        Err(SchemaPlanError::permission_denied("permission denied for schema information_schema"))
+    );
+}
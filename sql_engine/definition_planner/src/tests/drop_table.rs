@@ -17,7 +17,10 @@ use super::*;
 fn inner_drop(names: Vec<(&str, &str)>, if_exists: bool, cascade: bool) -> Definition {
     Definition::DropTables {
         if_exists,
-        names: names.into_iter().map(|(schema, table)| (schema.to_owned(), table.to_owned())).collect(),
+        names: names
+            .into_iter()
+            .map(|(schema, table)| (Some(schema.to_owned()), table.to_owned()))
+            .collect(),
         cascade,
     }
 }
@@ -125,3 +128,14 @@ fn drop_table_cascade() {
         }))
     );
 }
+
+#[test]
+fn drop_system_view() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+
+    assert_eq!(
+        planner.plan(drop_table_stmt(vec![("pg_catalog", "pg_class")])),
+        Err(SchemaPlanError::system_catalog("pg_class"))
+    );
+}
@@ -0,0 +1,57 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::DropViewsQuery;
+
+fn drop_views(names: Vec<(&str, &str)>, if_exists: bool, cascade: bool) -> Definition {
+    Definition::DropViews {
+        names: names
+            .into_iter()
+            .map(|(schema, view)| (Some(schema.to_owned()), view.to_owned()))
+            .collect(),
+        if_exists,
+        cascade,
+        materialized: false,
+    }
+}
+
+#[test]
+fn drop_view_from_nonexistent_schema() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(drop_views(vec![("non_existent_schema", "view_name")], false, false)),
+        Err(SchemaPlanError::schema_does_not_exist("non_existent_schema"))
+    );
+}
+
+#[test]
+fn drop_views_cascade() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(drop_views(vec![(SCHEMA, "view_1"), (SCHEMA, "view_2")], true, true)),
+        Ok(SchemaChange::DropViews(DropViewsQuery {
+            full_view_names: vec![FullTableName::from((&SCHEMA, &"view_1")), FullTableName::from((&SCHEMA, &"view_2"))],
+            cascade: true,
+            if_exists: true,
+            materialized: false
+        }))
+    );
+}
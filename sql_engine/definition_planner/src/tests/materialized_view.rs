@@ -0,0 +1,282 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::{CreateMaterializedViewQuery, CreateViewQuery, RefreshMaterializedViewQuery};
+use query_ast::{BinaryOperator, Distinct, Expr, SelectItem, SelectQuery, Value};
+
+const VIEW: &str = "view_name";
+
+fn select(columns: Vec<&str>) -> SelectQuery {
+    SelectQuery {
+        distinct: None,
+        select_items: columns
+            .into_iter()
+            .map(|column| SelectItem::UnnamedExpr(Expr::Column(column.to_owned())))
+            .collect(),
+        schema_name: Some(SCHEMA.to_owned()),
+        table_name: Some(TABLE.to_owned()),
+        where_clause: None,
+    }
+}
+
+fn create_materialized_view(columns: Vec<&str>, query: SelectQuery) -> Definition {
+    Definition::CreateMaterializedView {
+        schema_name: Some(SCHEMA.to_owned()),
+        view_name: VIEW.to_owned(),
+        columns: columns.into_iter().map(ToOwned::to_owned).collect(),
+        query,
+        if_not_exists: false,
+    }
+}
+
+fn refresh_materialized_view(concurrently: bool) -> Definition {
+    Definition::RefreshMaterializedView {
+        schema_name: Some(SCHEMA.to_owned()),
+        view_name: VIEW.to_owned(),
+        concurrently,
+    }
+}
+
+#[test]
+fn create_materialized_view_in_nonexistent_schema() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(create_materialized_view(vec![], select(vec!["col_1"]))),
+        Err(SchemaPlanError::schema_does_not_exist(SCHEMA))
+    );
+}
+
+#[test]
+fn create_materialized_view_computes_rows() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::var_char(10))],
+        ))
+        .unwrap();
+    let table = transaction.lookup_table_ref(&FullTableName::from((&SCHEMA, &TABLE)));
+    table.write(vec![BinaryValue::from(1i16), BinaryValue::from("first")]);
+    table.write(vec![BinaryValue::from(2i16), BinaryValue::from("second")]);
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_materialized_view(vec!["view_col"], select(vec!["col_2", "col_1"]))),
+        Ok(SchemaChange::CreateMaterializedView(CreateMaterializedViewQuery {
+            full_view_name: FullTableName::from((&SCHEMA, &VIEW)),
+            column_defs: vec![
+                ColumnInfo {
+                    name: "view_col".to_owned(),
+                    sql_type: SqlType::var_char(10),
+                    identity: None,
+                },
+                ColumnInfo {
+                    name: "col_1".to_owned(),
+                    sql_type: SqlType::small_int(),
+                    identity: None,
+                }
+            ],
+            definition: serde_json::to_string(&select(vec!["col_2", "col_1"])).unwrap(),
+            source: FullTableName::from((&SCHEMA, &TABLE)),
+            used_functions: vec![],
+            rows: vec![
+                vec![BinaryValue::from("first"), BinaryValue::from(1i16)],
+                vec![BinaryValue::from("second"), BinaryValue::from(2i16)]
+            ],
+            if_not_exists: false,
+        }))
+    );
+}
+
+#[test]
+fn create_materialized_view_with_expression_columns() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col_1", SqlType::integer())]))
+        .unwrap();
+    let table = transaction.lookup_table_ref(&FullTableName::from((&SCHEMA, &TABLE)));
+    table.write(vec![BinaryValue::from(1i32)]);
+    table.write(vec![BinaryValue::from(2i32)]);
+
+    let query = SelectQuery {
+        distinct: None,
+        select_items: vec![SelectItem::ExprWithAlias {
+            expr: Expr::BinaryOp {
+                left: Box::new(Expr::Column("col_1".to_owned())),
+                op: BinaryOperator::Multiply,
+                right: Box::new(Expr::Value(Value::Int(10))),
+            },
+            alias: "tens".to_owned(),
+        }],
+        schema_name: Some(SCHEMA.to_owned()),
+        table_name: Some(TABLE.to_owned()),
+        where_clause: None,
+    };
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_materialized_view(vec![], query.clone())),
+        Ok(SchemaChange::CreateMaterializedView(CreateMaterializedViewQuery {
+            full_view_name: FullTableName::from((&SCHEMA, &VIEW)),
+            column_defs: vec![ColumnInfo {
+                name: "tens".to_owned(),
+                sql_type: SqlType::integer(),
+                identity: None,
+            }],
+            definition: serde_json::to_string(&query).unwrap(),
+            source: FullTableName::from((&SCHEMA, &TABLE)),
+            used_functions: vec![],
+            rows: vec![vec![BinaryValue::from(10i64)], vec![BinaryValue::from(20i64)]],
+            if_not_exists: false,
+        }))
+    );
+}
+
+#[test]
+fn refresh_nonexistent_materialized_view() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(refresh_materialized_view(false)),
+        Err(SchemaPlanError::materialized_view_does_not_exist(FullTableName::from((&SCHEMA, &VIEW))))
+    );
+}
+
+#[test]
+fn refresh_regular_view() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col_1", SqlType::small_int())]))
+        .unwrap();
+    catalog
+        .apply(SchemaChange::CreateView(CreateViewQuery {
+            full_view_name: FullTableName::from((&SCHEMA, &VIEW)),
+            columns: vec!["col_1".to_owned()],
+            definition: serde_json::to_string(&select(vec!["col_1"])).unwrap(),
+            source: FullTableName::from((&SCHEMA, &TABLE)),
+            used_functions: vec![],
+            or_replace: false,
+        }))
+        .unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(refresh_materialized_view(true)),
+        Err(SchemaPlanError::materialized_view_does_not_exist(FullTableName::from((&SCHEMA, &VIEW))))
+    );
+}
+
+#[test]
+fn refresh_materialized_view_recomputes_rows() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col_1", SqlType::small_int())]))
+        .unwrap();
+    let table = transaction.lookup_table_ref(&FullTableName::from((&SCHEMA, &TABLE)));
+    table.write(vec![BinaryValue::from(1i16)]);
+
+    let planner = DefinitionPlanner::from(transaction.clone());
+    catalog
+        .apply(planner.plan(create_materialized_view(vec![], select(vec!["col_1"]))).unwrap())
+        .unwrap();
+    table.write(vec![BinaryValue::from(2i16)]);
+
+    assert_eq!(
+        planner.plan(refresh_materialized_view(true)),
+        Ok(SchemaChange::RefreshMaterializedView(RefreshMaterializedViewQuery {
+            full_view_name: FullTableName::from((&SCHEMA, &VIEW)),
+            rows: vec![vec![BinaryValue::from(1i16)], vec![BinaryValue::from(2i16)]],
+            concurrently: true,
+        }))
+    );
+}
+
+#[test]
+fn create_materialized_view_filtering_view_with_distinct_on() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::small_int())],
+        ))
+        .unwrap();
+    let table = transaction.lookup_table_ref(&FullTableName::from((&SCHEMA, &TABLE)));
+    table.write(vec![BinaryValue::from(1i16), BinaryValue::from(4i16)]);
+    table.write(vec![BinaryValue::from(2i16), BinaryValue::from(5i16)]);
+    table.write(vec![BinaryValue::from(1i16), BinaryValue::from(7i16)]);
+    let mut first_rows = select(vec!["col_1", "col_2"]);
+    first_rows.distinct = Some(Distinct::On(vec![Expr::Column("col_1".to_owned())]));
+    catalog
+        .apply(SchemaChange::CreateView(CreateViewQuery {
+            full_view_name: FullTableName::from((&SCHEMA, &"first_rows")),
+            columns: vec!["col_1".to_owned(), "col_2".to_owned()],
+            definition: serde_json::to_string(&first_rows).unwrap(),
+            source: FullTableName::from((&SCHEMA, &TABLE)),
+            used_functions: vec![],
+            or_replace: false,
+        }))
+        .unwrap();
+
+    // (1, 7) is not a row of the view, so it is filtered out before the view keeps (1, 4)
+    let query = SelectQuery {
+        distinct: None,
+        select_items: vec![SelectItem::UnnamedExpr(Expr::Column("col_2".to_owned()))],
+        schema_name: Some(SCHEMA.to_owned()),
+        table_name: Some("first_rows".to_owned()),
+        where_clause: Some(Expr::BinaryOp {
+            left: Box::new(Expr::Column("col_2".to_owned())),
+            op: BinaryOperator::Gt,
+            right: Box::new(Expr::Value(Value::Int(4))),
+        }),
+    };
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(create_materialized_view(vec![], query.clone())),
+        Ok(SchemaChange::CreateMaterializedView(CreateMaterializedViewQuery {
+            full_view_name: FullTableName::from((&SCHEMA, &VIEW)),
+            column_defs: vec![ColumnInfo {
+                name: "col_2".to_owned(),
+                sql_type: SqlType::small_int(),
+                identity: None,
+            }],
+            definition: serde_json::to_string(&query).unwrap(),
+            source: FullTableName::from((&SCHEMA, &"first_rows")),
+            used_functions: vec![],
+            rows: vec![vec![BinaryValue::from(5i16)]],
+            if_not_exists: false,
+        }))
+    );
+}
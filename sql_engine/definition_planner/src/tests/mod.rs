@@ -16,23 +16,41 @@ use super::*;
 use data_definition_execution_plan::{ColumnInfo, CreateSchemaQuery, CreateTableQuery, DropSchemasQuery, SchemaChange};
 use storage::Database;
 
+#[cfg(test)]
+mod analyze;
+#[cfg(test)]
+mod create_function;
 #[cfg(test)]
 mod create_index;
 #[cfg(test)]
 mod create_schema;
 #[cfg(test)]
+mod create_sequence;
+#[cfg(test)]
 mod create_table;
 #[cfg(test)]
+mod create_view;
+#[cfg(test)]
+mod drop_function;
+#[cfg(test)]
 mod drop_schema;
 #[cfg(test)]
 mod drop_table;
+#[cfg(test)]
+mod drop_view;
+#[cfg(test)]
+mod materialized_view;
+#[cfg(test)]
+mod search_path;
+#[cfg(test)]
+mod truncate;
 
 const SCHEMA: &str = "schema_name";
 const TABLE: &str = "table_name";
 
 fn create_table_if_not_exists(schema_name: &str, table_name: &str, columns: Vec<ColumnDef>, if_not_exists: bool) -> Definition {
     Definition::CreateTable {
-        schema_name: schema_name.to_owned(),
+        schema_name: Some(schema_name.to_owned()),
         table_name: table_name.to_owned(),
         columns,
         if_not_exists,
@@ -51,6 +69,7 @@ fn create_table_ops(schema_name: &str, table_name: &str, columns: Vec<(&str, Sql
             .map(|(name, sql_type)| ColumnInfo {
                 name: name.to_owned(),
                 sql_type,
+                identity: None,
             })
             .collect(),
         if_not_exists: true,
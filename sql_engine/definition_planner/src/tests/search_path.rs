@@ -0,0 +1,114 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::DropTablesQuery;
+
+fn create_unqualified_table(table_name: &str) -> Definition {
+    Definition::CreateTable {
+        schema_name: None,
+        table_name: table_name.to_owned(),
+        columns: vec![],
+        if_not_exists: false,
+    }
+}
+
+#[test]
+fn create_table_in_first_existing_schema() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    planner.set_search_path(SearchPath::new("\"$user\", schema_name, public", "username"));
+
+    assert_eq!(
+        planner.plan(create_unqualified_table(TABLE)),
+        Ok(SchemaChange::CreateTable(CreateTableQuery {
+            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            column_defs: vec![],
+            if_not_exists: false,
+        }))
+    );
+}
+
+#[test]
+fn create_table_in_public_schema_by_default() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+
+    assert_eq!(
+        planner.plan(create_unqualified_table(TABLE)),
+        Ok(SchemaChange::CreateTable(CreateTableQuery {
+            full_table_name: FullTableName::from((&"public", &TABLE)),
+            column_defs: vec![],
+            if_not_exists: false,
+        }))
+    );
+}
+
+#[test]
+fn no_schema_selected_to_create_in() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    planner.set_search_path(SearchPath::new("non_existent", ""));
+
+    assert_eq!(planner.plan(create_unqualified_table(TABLE)), Err(SchemaPlanError::NoSchemaSelected));
+}
+
+#[test]
+fn drop_table_found_in_search_path() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![])).unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    planner.set_search_path(SearchPath::new("public, schema_name", ""));
+
+    assert_eq!(
+        planner.plan(Definition::DropTables {
+            names: vec![(None, TABLE.to_owned())],
+            if_exists: false,
+            cascade: false,
+        }),
+        Ok(SchemaChange::DropTables(DropTablesQuery {
+            full_table_names: vec![FullTableName::from((&SCHEMA, &TABLE))],
+            cascade: false,
+            if_exists: false,
+        }))
+    );
+}
+
+#[test]
+fn drop_table_if_exists_without_existing_schema_in_search_path() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    planner.set_search_path(SearchPath::new("non_existent", ""));
+
+    assert_eq!(
+        planner.plan(Definition::DropTables {
+            names: vec![(None, TABLE.to_owned())],
+            if_exists: true,
+            cascade: false,
+        }),
+        Ok(SchemaChange::DropTables(DropTablesQuery {
+            full_table_names: vec![],
+            cascade: false,
+            if_exists: true,
+        }))
+    );
+}
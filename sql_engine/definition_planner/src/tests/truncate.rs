@@ -0,0 +1,67 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::TruncateTablesQuery;
+
+fn truncate(names: Vec<(&str, &str)>, restart_identity: bool) -> Definition {
+    Definition::Truncate {
+        names: names
+            .into_iter()
+            .map(|(schema_name, table_name)| (Some(schema_name.to_owned()), table_name.to_owned()))
+            .collect(),
+        restart_identity,
+        cascade: false,
+    }
+}
+
+#[test]
+fn truncate_table_in_not_existent_schema() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(truncate(vec![("non_existent", TABLE)], false)),
+        Err(SchemaPlanError::schema_does_not_exist("non_existent"))
+    );
+}
+
+#[test]
+fn truncate_not_existent_table() {
+    let db = Database::new("");
+    let planner = DefinitionPlanner::from(db.transaction());
+    assert_eq!(
+        planner.plan(truncate(vec![("public", "non_existent")], false)),
+        Err(SchemaPlanError::table_does_not_exist("public.non_existent"))
+    );
+}
+
+#[test]
+fn truncate_existing_table() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog
+        .apply(create_table_ops("public", TABLE, vec![("column", SqlType::small_int())]))
+        .unwrap();
+
+    let planner = DefinitionPlanner::from(transaction);
+    assert_eq!(
+        planner.plan(truncate(vec![("public", TABLE), ("public", TABLE)], true)),
+        Ok(SchemaChange::TruncateTables(TruncateTablesQuery {
+            full_table_names: vec![FullTableName::from((&"public", &TABLE))],
+            restart_identity: true,
+            cascade: false,
+        }))
+    );
+}
@@ -12,29 +12,76 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use catalog::CatalogHandler;
-use data_manipulation_untyped_queries::{UntypedDeleteQuery, UntypedInsertQuery, UntypedQuery, UntypedSelectQuery, UntypedUpdateQuery};
-use data_manipulation_untyped_tree::{UntypedItem, UntypedTree};
+use catalog::{is_system_schema, CatalogHandler};
+use data_manipulation_functions::FunctionRegistry;
+use data_manipulation_operators::{BiLogical, BiOperator, FrameBound, FrameUnits, SequenceFunction, SetOperator, UnOperator, WindowFunction};
+use data_manipulation_untyped_queries::{
+    UntypedDeleteQuery, UntypedDistinct, UntypedInsertQuery, UntypedQuery, UntypedSelectQuery, UntypedSetOperation, UntypedUpdateQuery,
+};
+use data_manipulation_untyped_tree::{UntypedItem, UntypedSortKey, UntypedTree, UntypedValue, UntypedWindow};
 use data_manipulation_untyped_tree_builder::{TreeBuilder, UntypedExpressionError};
-use definition::FullTableName;
-use query_ast::{Assignment, DeleteQuery, InsertQuery, InsertSource, Query, SelectItem, SelectQuery, UpdateQuery, Values};
+use definition::{ColumnDef, ColumnIdentity, FullTableName, SearchPath, ViewDef};
+use query_ast::{
+    Assignment, DeleteQuery, Distinct, Expr, InsertQuery, InsertSource, Query, SelectItem, SelectQuery, SetOperation, UpdateQuery, Values,
+};
 use query_response::QueryError;
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap};
 use storage::Transaction;
+use types::{SqlType, SqlTypeFamily};
 
 pub struct QueryAnalyzer<'a> {
     catalog: CatalogHandler<'a>,
+    search_path: RefCell<SearchPath>,
+    functions: FunctionRegistry,
+    inlined_functions: RefCell<Vec<FullTableName>>,
 }
 
 impl<'a> From<Transaction<'a>> for QueryAnalyzer<'a> {
     fn from(transaction: Transaction<'a>) -> QueryAnalyzer<'a> {
         QueryAnalyzer {
             catalog: CatalogHandler::from(transaction),
+            search_path: RefCell::new(SearchPath::default()),
+            functions: FunctionRegistry::default(),
+            inlined_functions: RefCell::new(vec![]),
         }
     }
 }
 
 impl<'a> QueryAnalyzer<'a> {
+    pub fn set_search_path(&self, search_path: SearchPath) {
+        self.search_path.replace(search_path);
+    }
+
+    pub fn search_path(&self) -> SearchPath {
+        self.search_path.borrow().clone()
+    }
+
+    /// SQL functions that were inlined since the last call, views and functions depend on them
+    pub fn take_inlined_functions(&self) -> Vec<FullTableName> {
+        self.inlined_functions.take()
+    }
+
+    /// analyzes a body of SQL function as if it is called with its parameters as arguments
+    pub fn analyze_function_body(&self, parameters: &[(String, SqlType)], body: Expr) -> Result<UntypedTree, AnalysisError> {
+        let parameters = parameters
+            .iter()
+            .enumerate()
+            .map(|(index, (name, sql_type))| ColumnDef::new(name.clone(), *sql_type, index, None))
+            .collect::<Vec<_>>();
+        let args = parameters
+            .iter()
+            .map(|parameter| {
+                UntypedTree::Item(UntypedItem::Column {
+                    name: parameter.name().to_owned(),
+                    sql_type: parameter.sql_type(),
+                    index: parameter.index(),
+                })
+            })
+            .collect::<Vec<_>>();
+        let body = self.resolve_scalar(TreeBuilder::build_dynamic(body, &parameters)?, "SQL function body")?;
+        inline_parameters(body, &args)
+    }
+
     pub fn analyze(&self, query: Query) -> Result<UntypedQuery, AnalysisError> {
         match query {
             Query::Insert(InsertQuery {
@@ -43,10 +90,13 @@ impl<'a> QueryAnalyzer<'a> {
                 source,
                 columns,
             }) => {
-                let full_table_name = FullTableName::from((&schema_name, &table_name));
+                let full_table_name = self.qualify(schema_name, table_name)?;
                 match self.catalog.table_definition(full_table_name.clone()) {
                     None => Err(AnalysisError::schema_does_not_exist(full_table_name.schema())),
                     Some(None) => Err(AnalysisError::table_does_not_exist(full_table_name)),
+                    Some(Some(_)) if is_system_schema(full_table_name.schema()) => {
+                        Err(AnalysisError::permission_denied_for_table(full_table_name.table()))
+                    }
                     Some(Some(table_info)) => {
                         let table_columns = table_info.columns();
                         let column_names = if columns.is_empty() {
@@ -75,8 +125,11 @@ impl<'a> QueryAnalyzer<'a> {
                                     let mut row = vec![];
                                     for table_column in table_columns {
                                         let value = match column_map.get(table_column.name()) {
+                                            Some(_) if table_column.identity() == Some(ColumnIdentity::Always) => {
+                                                return Err(AnalysisError::cannot_insert_into_generated_column(table_column.name()));
+                                            }
                                             Some(index) if index < &insert_row.len() => {
-                                                Some(TreeBuilder::insert_position(insert_row[*index].clone())?)
+                                                Some(self.resolve_scalar(TreeBuilder::insert_position(insert_row[*index].clone())?, "VALUES")?)
                                             }
                                             _ => None,
                                         };
@@ -97,10 +150,13 @@ impl<'a> QueryAnalyzer<'a> {
                 assignments: stmt_assignments,
                 where_clause,
             }) => {
-                let full_table_name = FullTableName::from((&schema_name, &table_name));
+                let full_table_name = self.qualify(schema_name, table_name)?;
                 match self.catalog.table_definition(full_table_name.clone()) {
                     None => Err(AnalysisError::schema_does_not_exist(full_table_name.schema())),
                     Some(None) => Err(AnalysisError::table_does_not_exist(full_table_name)),
+                    Some(Some(_)) if is_system_schema(full_table_name.schema()) => {
+                        Err(AnalysisError::permission_denied_for_table(full_table_name.table()))
+                    }
                     Some(Some(table_info)) => {
                         let table_columns = table_info.columns();
                         let mut temp_column_names = vec![];
@@ -108,6 +164,9 @@ impl<'a> QueryAnalyzer<'a> {
                             let mut found = false;
                             for stmt_assignment in stmt_assignments.iter() {
                                 if table_column.name() == stmt_assignment.column.as_str() {
+                                    if table_column.identity() == Some(ColumnIdentity::Always) {
+                                        return Err(AnalysisError::cannot_update_generated_column(table_column.name()));
+                                    }
                                     temp_column_names.push(Some(stmt_assignment.value.clone()));
                                     found = true;
                                     break;
@@ -128,12 +187,12 @@ impl<'a> QueryAnalyzer<'a> {
                             match temp_column_name {
                                 None => assignments.push(None),
                                 Some(value) => {
-                                    assignments.push(Some(TreeBuilder::build_dynamic(value, &table_columns)?));
+                                    assignments.push(Some(self.resolve_scalar(TreeBuilder::build_dynamic(value, &table_columns)?, "UPDATE")?));
                                 }
                             }
                         }
                         let filter = match where_clause {
-                            Some(expr) => Some(TreeBuilder::build_dynamic(expr, &table_columns)?),
+                            Some(expr) => Some(self.resolve_scalar(TreeBuilder::build_dynamic(expr, &table_columns)?, "WHERE")?),
                             None => None,
                         };
                         Ok(UntypedQuery::Update(UntypedUpdateQuery {
@@ -144,66 +203,666 @@ impl<'a> QueryAnalyzer<'a> {
                     }
                 }
             }
-            Query::Select(SelectQuery {
-                select_items,
+            Query::Select(query) => Ok(UntypedQuery::Select(self.analyze_select(query)?)),
+            Query::SetOperation(set_operation) => Ok(UntypedQuery::SetOperation(self.analyze_set_operation(set_operation)?)),
+            Query::Delete(DeleteQuery {
                 schema_name,
                 table_name,
                 where_clause,
             }) => {
-                let full_table_name = FullTableName::from((&schema_name, &table_name));
+                let full_table_name = self.qualify(schema_name, table_name)?;
                 match self.catalog.table_definition(full_table_name.clone()) {
                     None => Err(AnalysisError::schema_does_not_exist(full_table_name.schema())),
                     Some(None) => Err(AnalysisError::table_does_not_exist(full_table_name)),
+                    Some(Some(_)) if is_system_schema(full_table_name.schema()) => {
+                        Err(AnalysisError::permission_denied_for_table(full_table_name.table()))
+                    }
                     Some(Some(table_info)) => {
                         let table_columns = table_info.columns();
-                        let mut projection_items = vec![];
-                        for item in select_items {
-                            match item {
-                                SelectItem::Wildcard => {
-                                    for (index, table_column) in table_columns.iter().enumerate() {
-                                        projection_items.push(UntypedTree::Item(UntypedItem::Column {
-                                            name: table_column.name().to_lowercase(),
-                                            index,
-                                            sql_type: table_column.sql_type(),
-                                        }));
-                                    }
-                                }
-                                SelectItem::UnnamedExpr(expr) => projection_items.push(TreeBuilder::build_dynamic(expr, &table_columns)?),
-                            }
-                        }
                         let filter = match where_clause {
-                            Some(expr) => Some(TreeBuilder::build_dynamic(expr, &table_columns)?),
+                            Some(expr) => Some(self.resolve_scalar(TreeBuilder::build_dynamic(expr, &table_columns)?, "WHERE")?),
                             None => None,
                         };
-                        Ok(UntypedQuery::Select(UntypedSelectQuery {
-                            full_table_name,
-                            projection_items,
-                            filter,
-                        }))
+                        Ok(UntypedQuery::Delete(UntypedDeleteQuery { full_table_name, filter }))
                     }
                 }
             }
-            Query::Delete(DeleteQuery {
-                schema_name,
-                table_name,
-                where_clause,
-            }) => {
-                let full_table_name = FullTableName::from((&schema_name, &table_name));
-                match self.catalog.table_definition(full_table_name.clone()) {
-                    None => Err(AnalysisError::schema_does_not_exist(full_table_name.schema())),
-                    Some(None) => Err(AnalysisError::table_does_not_exist(full_table_name)),
-                    Some(Some(table_info)) => {
-                        let table_columns = table_info.columns();
-                        let filter = match where_clause {
-                            Some(expr) => Some(TreeBuilder::build_dynamic(expr, &table_columns)?),
-                            None => None,
-                        };
-                        Ok(UntypedQuery::Delete(UntypedDeleteQuery { full_table_name, filter }))
+        }
+    }
+
+    fn analyze_select(&self, query: SelectQuery) -> Result<UntypedSelectQuery, AnalysisError> {
+        let SelectQuery {
+            distinct,
+            select_items,
+            schema_name,
+            table_name,
+            where_clause,
+        } = query;
+        let table_name = match table_name {
+            None => return self.analyze_projection(None, &[], select_items, where_clause, distinct),
+            Some(table_name) => table_name,
+        };
+        let full_table_name = self.qualify(schema_name, table_name)?;
+        match self.catalog.table_definition(full_table_name.clone()) {
+            None => Err(AnalysisError::schema_does_not_exist(full_table_name.schema())),
+            Some(None) => match self.catalog.view_definition(&full_table_name) {
+                None => Err(AnalysisError::table_does_not_exist(full_table_name)),
+                Some(view_def) => self.analyze_view_select(view_def, select_items, where_clause, distinct),
+            },
+            Some(Some(table_info)) => self.analyze_projection(Some(full_table_name), table_info.columns(), select_items, where_clause, distinct),
+        }
+    }
+
+    fn analyze_set_operation(&self, set_operation: SetOperation) -> Result<UntypedSetOperation, AnalysisError> {
+        let SetOperation { op, all, left, right } = set_operation;
+        let op = SetOperator::from(op);
+        let left = self.analyze(*left)?;
+        let right = self.analyze(*right)?;
+        let column_names = result_column_names(&left).to_vec();
+        if column_names.len() != result_column_names(&right).len() {
+            return Err(AnalysisError::column_number_mismatch(op));
+        }
+        // types known before type inference have to be comparable as they are for
+        // the rest of columns after the inference
+        for types in result_types(&left).into_iter().zip(result_types(&right)) {
+            if let (Some(left_type), Some(right_type)) = types {
+                if left_type.compare(&right_type).is_err() {
+                    return Err(AnalysisError::set_operation_type_mismatch(op, left_type, right_type));
+                }
+            }
+        }
+        Ok(UntypedSetOperation {
+            op,
+            all,
+            left: Box::new(left),
+            right: Box::new(right),
+            column_names,
+        })
+    }
+
+    fn analyze_projection(
+        &self,
+        full_table_name: Option<FullTableName>,
+        table_columns: &[ColumnDef],
+        select_items: Vec<SelectItem>,
+        where_clause: Option<Expr>,
+        distinct: Option<Distinct>,
+    ) -> Result<UntypedSelectQuery, AnalysisError> {
+        let mut projection_items = vec![];
+        let mut column_names = vec![];
+        for item in select_items {
+            match item {
+                SelectItem::Wildcard => {
+                    for (index, table_column) in table_columns.iter().enumerate() {
+                        column_names.push(table_column.name().to_lowercase());
+                        projection_items.push(UntypedTree::Item(UntypedItem::Column {
+                            name: table_column.name().to_lowercase(),
+                            index,
+                            sql_type: table_column.sql_type(),
+                        }));
+                    }
+                }
+                SelectItem::UnnamedExpr(expr) => {
+                    column_names.push(column_name(&expr));
+                    projection_items.push(self.resolve_functions(TreeBuilder::build_dynamic(expr, table_columns)?)?)
+                }
+                SelectItem::ExprWithAlias { expr, alias } => {
+                    column_names.push(alias);
+                    projection_items.push(self.resolve_functions(TreeBuilder::build_dynamic(expr, table_columns)?)?)
+                }
+            }
+        }
+        let filter = match where_clause {
+            Some(expr) => Some(self.resolve_scalar(TreeBuilder::build_dynamic(expr, table_columns)?, "WHERE")?),
+            None => None,
+        };
+        let distinct = self.analyze_distinct(distinct, &projection_items, |expr| {
+            self.resolve_functions(TreeBuilder::build_dynamic(expr, table_columns)?)
+        })?;
+        Ok(UntypedSelectQuery {
+            full_table_name,
+            subquery: None,
+            projection_items,
+            column_names,
+            filter,
+            distinct,
+        })
+    }
+
+    // unqualified names are resolved with the search path
+    fn qualify(&self, schema_name: Option<String>, name: String) -> Result<FullTableName, AnalysisError> {
+        match schema_name {
+            Some(schema_name) => Ok(FullTableName::from((&schema_name, &name))),
+            None => self
+                .catalog
+                .qualify(&self.search_path.borrow(), &name)
+                .ok_or_else(|| AnalysisError::table_does_not_exist(name)),
+        }
+    }
+
+    // view is expanded into its definition: references to view columns are replaced with
+    // the view projection items and the view filter is combined with the query filter
+    fn analyze_view_select(
+        &self,
+        view_def: ViewDef,
+        select_items: Vec<SelectItem>,
+        where_clause: Option<Expr>,
+        distinct: Option<Distinct>,
+    ) -> Result<UntypedSelectQuery, AnalysisError> {
+        let view_query = serde_json::from_str::<SelectQuery>(view_def.definition()).unwrap();
+        let view = self.analyze_select(view_query)?;
+        let view_columns = view_def
+            .columns()
+            .iter()
+            .zip(view.projection_items.iter())
+            .enumerate()
+            .map(|(index, (name, item))| {
+                let sql_type = match item {
+                    UntypedTree::Item(UntypedItem::Column { sql_type, .. }) => *sql_type,
+                    // replaced by the view projection item, type is inferred later
+                    _ => SqlType::bool(),
+                };
+                ColumnDef::new(name.clone(), sql_type, index, None)
+            })
+            .collect::<Vec<_>>();
+
+        let mut projection_items = vec![];
+        let mut column_names = vec![];
+        let mut has_windows = false;
+        for item in select_items {
+            let (expr, name) = match item {
+                SelectItem::Wildcard => {
+                    for view_column in view_columns.iter() {
+                        column_names.push(view_column.name().to_owned());
+                        projection_items.push(view_column_item(view_column, &view.projection_items));
                     }
+                    continue;
+                }
+                SelectItem::UnnamedExpr(expr) => {
+                    let name = column_name(&expr);
+                    (expr, name)
                 }
+                SelectItem::ExprWithAlias { expr, alias } => (expr, alias),
+            };
+            column_names.push(name);
+            match TreeBuilder::build_dynamic(expr, &view_columns)? {
+                UntypedTree::Item(UntypedItem::Column { index, .. }) => {
+                    projection_items.push(view_column_item(&view_columns[index], &view.projection_items))
+                }
+                tree => {
+                    let tree = self.resolve_functions(tree)?;
+                    has_windows |= contains_window(&tree);
+                    projection_items.push(expand_view_columns(tree, &view.projection_items)?)
+                }
+            }
+        }
+        // windows of the query are computed over rows of the view, which are final
+        // only after the view windows and `DISTINCT` are applied
+        let view_has_windows = view.projection_items.iter().any(contains_window);
+        if has_windows && (view_has_windows || view.distinct.is_some()) {
+            return Err(AnalysisError::feature_not_supported(
+                "window functions over a view defined with window functions or DISTINCT",
+            ));
+        }
+        if view_has_windows && where_clause.is_some() {
+            return Err(AnalysisError::feature_not_supported("filtering a view defined with window functions"));
+        }
+        let distinct = self.analyze_distinct(distinct, &projection_items, |expr| {
+            expand_view_columns(
+                self.resolve_functions(TreeBuilder::build_dynamic(expr, &view_columns)?)?,
+                &view.projection_items,
+            )
+        })?;
+        let filter = match where_clause {
+            Some(expr) => Some(expand_view_columns(
+                self.resolve_scalar(TreeBuilder::build_dynamic(expr, &view_columns)?, "WHERE")?,
+                &view.projection_items,
+            )?),
+            None => None,
+        };
+        // rows kept by the view `DISTINCT ON` depend on which of them pass the query filter,
+        // so the query reads table rows that the view filter and `DISTINCT ON` have kept
+        if let Some(UntypedDistinct::On(_)) = view.distinct {
+            if filter.is_some() || distinct.is_some() {
+                if view_has_windows {
+                    return Err(AnalysisError::feature_not_supported(
+                        "DISTINCT over a view defined with window functions and DISTINCT ON",
+                    ));
+                }
+                let table_columns = match &view.full_table_name {
+                    Some(full_table_name) => match self.catalog.table_definition(full_table_name.clone()) {
+                        Some(Some(table_info)) => table_info.columns().to_vec(),
+                        _ => return Err(AnalysisError::table_does_not_exist(full_table_name)),
+                    },
+                    None => vec![],
+                };
+                let subquery = UntypedSelectQuery {
+                    full_table_name: view.full_table_name.clone(),
+                    subquery: view.subquery,
+                    projection_items: table_columns
+                        .iter()
+                        .enumerate()
+                        .map(|(index, table_column)| {
+                            UntypedTree::Item(UntypedItem::Column {
+                                name: table_column.name().to_lowercase(),
+                                index,
+                                sql_type: table_column.sql_type(),
+                            })
+                        })
+                        .collect(),
+                    column_names: table_columns.iter().map(|table_column| table_column.name().to_lowercase()).collect(),
+                    filter: view.filter,
+                    distinct: view.distinct,
+                };
+                return Ok(UntypedSelectQuery {
+                    full_table_name: view.full_table_name,
+                    subquery: Some(Box::new(subquery)),
+                    projection_items,
+                    column_names,
+                    filter,
+                    distinct,
+                });
             }
         }
+        // rows that are duplicates for the view are duplicates for the query too
+        let distinct = distinct.or(view.distinct);
+        let filter = match (view.filter, filter) {
+            (Some(view_filter), Some(filter)) => Some(UntypedTree::BiOp {
+                left: Box::new(view_filter),
+                op: BiOperator::Logical(BiLogical::And),
+                right: Box::new(filter),
+            }),
+            (view_filter, filter) => view_filter.or(filter),
+        };
+        Ok(UntypedSelectQuery {
+            full_table_name: view.full_table_name,
+            subquery: view.subquery,
+            projection_items,
+            column_names,
+            filter,
+            distinct,
+        })
     }
+
+    // `SELECT DISTINCT` de-duplicates rows by all projection items
+    fn analyze_distinct<B: Fn(Expr) -> Result<UntypedTree, AnalysisError>>(
+        &self,
+        distinct: Option<Distinct>,
+        projection_items: &[UntypedTree],
+        build: B,
+    ) -> Result<Option<UntypedDistinct>, AnalysisError> {
+        match distinct {
+            None => Ok(None),
+            Some(Distinct::Rows) => Ok(Some(UntypedDistinct::Rows(projection_items.to_vec()))),
+            Some(Distinct::On(exprs)) => Ok(Some(UntypedDistinct::On(exprs.into_iter().map(build).collect::<Result<Vec<_>, _>>()?))),
+        }
+    }
+
+    // sequence functions refer sequences by name given as a string literal,
+    // overloads of built-in functions are resolved during type inference
+    fn resolve_functions(&self, tree: UntypedTree) -> Result<UntypedTree, AnalysisError> {
+        match tree {
+            UntypedTree::BiOp { left, op, right } => Ok(UntypedTree::BiOp {
+                left: Box::new(self.resolve_functions(*left)?),
+                op,
+                right: Box::new(self.resolve_functions(*right)?),
+            }),
+            UntypedTree::UnOp { op, item } => Ok(UntypedTree::UnOp {
+                op,
+                item: Box::new(self.resolve_functions(*item)?),
+            }),
+            UntypedTree::Function { name, args } => {
+                let (function, arity) = match name.as_str() {
+                    "nextval" => (SequenceFunction::NextVal, 1),
+                    "currval" => (SequenceFunction::CurrVal, 1),
+                    "setval" => (SequenceFunction::SetVal, 2),
+                    _ if self.functions.contains(&name, args.len()) => {
+                        return Ok(UntypedTree::Function {
+                            name,
+                            args: args.into_iter().map(|arg| self.resolve_functions(arg)).collect::<Result<_, _>>()?,
+                        })
+                    }
+                    _ => return self.inline_function(name, args),
+                };
+                if args.len() != arity {
+                    return Err(AnalysisError::function_does_not_exist(name));
+                }
+                let mut args = args.into_iter().map(|arg| self.resolve_functions(arg)).collect::<Result<Vec<_>, _>>()?;
+                let sequence_name = match args.remove(0) {
+                    UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal(sequence_name))) => sequence_name,
+                    _ => return Err(AnalysisError::function_does_not_exist(name)),
+                };
+                let qualified_name = sequence_name.to_lowercase();
+                let full_sequence_name = match qualified_name.split_once('.') {
+                    Some((schema_name, name)) => Some(FullTableName::from((&schema_name, &name))),
+                    None => self.catalog.qualify(&self.search_path.borrow(), &qualified_name),
+                };
+                match full_sequence_name.and_then(|full_sequence_name| self.catalog.sequence(&full_sequence_name)) {
+                    Some(sequence) => Ok(UntypedTree::Sequence { function, sequence, args }),
+                    None => Err(AnalysisError::sequence_does_not_exist(sequence_name)),
+                }
+            }
+            UntypedTree::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => Ok(UntypedTree::Case {
+                operand: operand.map(|operand| self.resolve_functions(*operand)).transpose()?.map(Box::new),
+                conditions: conditions
+                    .into_iter()
+                    .map(|condition| self.resolve_functions(condition))
+                    .collect::<Result<_, _>>()?,
+                results: results
+                    .into_iter()
+                    .map(|result| self.resolve_functions(result))
+                    .collect::<Result<_, _>>()?,
+                else_result: else_result
+                    .map(|else_result| self.resolve_functions(*else_result))
+                    .transpose()?
+                    .map(Box::new),
+            }),
+            UntypedTree::Conditional { function, args } => Ok(UntypedTree::Conditional {
+                function,
+                args: args.into_iter().map(|arg| self.resolve_functions(arg)).collect::<Result<_, _>>()?,
+            }),
+            UntypedTree::Quantified { left, op, quantifier, list } => Ok(UntypedTree::Quantified {
+                left: Box::new(self.resolve_functions(*left)?),
+                op,
+                quantifier,
+                list: list.into_iter().map(|item| self.resolve_functions(item)).collect::<Result<_, _>>()?,
+            }),
+            UntypedTree::Between { item, low, high, negated } => Ok(UntypedTree::Between {
+                item: Box::new(self.resolve_functions(*item)?),
+                low: Box::new(self.resolve_functions(*low)?),
+                high: Box::new(self.resolve_functions(*high)?),
+                negated,
+            }),
+            UntypedTree::Window(window) => {
+                if WindowFunction::resolve(&window.name, window.args.len()).is_none() {
+                    return Err(AnalysisError::function_does_not_exist(window.name));
+                }
+                let nested = window
+                    .args
+                    .iter()
+                    .chain(window.partition_by.iter())
+                    .chain(window.order_by.iter().map(|key| &key.expr))
+                    .any(contains_window);
+                if nested {
+                    return Err(AnalysisError::windowing_error("window function calls cannot be nested"));
+                }
+                let has_offset = |bound: FrameBound| matches!(bound, FrameBound::Preceding(_) | FrameBound::Following(_));
+                if window.frame.units == FrameUnits::Range
+                    && (has_offset(window.frame.start) || has_offset(window.frame.end))
+                    && window.order_by.len() != 1
+                {
+                    return Err(AnalysisError::windowing_error(
+                        "RANGE with offset PRECEDING/FOLLOWING requires exactly one ORDER BY column",
+                    ));
+                }
+                Ok(UntypedTree::Window(UntypedWindow {
+                    args: window.args.into_iter().map(|arg| self.resolve_functions(arg)).collect::<Result<_, _>>()?,
+                    partition_by: window
+                        .partition_by
+                        .into_iter()
+                        .map(|key| self.resolve_functions(key))
+                        .collect::<Result<_, _>>()?,
+                    order_by: window
+                        .order_by
+                        .into_iter()
+                        .map(|key| {
+                            Ok(UntypedSortKey {
+                                expr: self.resolve_functions(key.expr)?,
+                                ..key
+                            })
+                        })
+                        .collect::<Result<_, AnalysisError>>()?,
+                    ..window
+                }))
+            }
+            tree => Ok(tree),
+        }
+    }
+
+    // SQL function call is replaced with its body where parameters are replaced with
+    // the call arguments cast to the parameter types
+    fn inline_function(&self, name: String, args: Vec<UntypedTree>) -> Result<UntypedTree, AnalysisError> {
+        let function_def = match self
+            .catalog
+            .qualify_function(&self.search_path.borrow(), &name)
+            .and_then(|full_function_name| self.catalog.function_definition(&full_function_name))
+        {
+            Some(function_def) if function_def.parameters().len() == args.len() => function_def,
+            _ => return Err(AnalysisError::function_does_not_exist(name)),
+        };
+        let args = function_def
+            .parameters()
+            .iter()
+            .zip(args)
+            .map(|((_name, sql_type), arg)| {
+                Ok(UntypedTree::UnOp {
+                    op: UnOperator::Cast(*sql_type),
+                    item: Box::new(self.resolve_functions(arg)?),
+                })
+            })
+            .collect::<Result<Vec<_>, AnalysisError>>()?;
+        let body = serde_json::from_str::<Expr>(function_def.body()).map_err(|_| {
+            AnalysisError::invalid_function_definition(format!("body of function {} could not be read", function_def.full_function_name()))
+        })?;
+        let body = self.analyze_function_body(function_def.parameters(), body)?;
+        let mut inlined_functions = self.inlined_functions.borrow_mut();
+        if !inlined_functions.contains(function_def.full_function_name()) {
+            inlined_functions.push(function_def.full_function_name().clone());
+        }
+        Ok(UntypedTree::UnOp {
+            op: UnOperator::Cast(function_def.return_type()),
+            item: Box::new(inline_parameters(body, &args)?),
+        })
+    }
+
+    // window functions are computed over the rows of a query result,
+    // so they could not be used where a single row is processed
+    fn resolve_scalar(&self, tree: UntypedTree, clause: &str) -> Result<UntypedTree, AnalysisError> {
+        let tree = self.resolve_functions(tree)?;
+        if contains_window(&tree) {
+            Err(AnalysisError::windowing_error(format!("window functions are not allowed in {}", clause)))
+        } else {
+            Ok(tree)
+        }
+    }
+}
+
+// result columns without alias are named as PostgreSQL does it
+fn column_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Column(name) => name.to_lowercase(),
+        Expr::Function { name, .. } => name.to_lowercase(),
+        Expr::Window { name, .. } => name.to_lowercase(),
+        Expr::Cast { expr, .. } => column_name(expr),
+        Expr::Case { .. } => "case".to_owned(),
+        Expr::Coalesce(_) => "coalesce".to_owned(),
+        Expr::NullIf { .. } => "nullif".to_owned(),
+        Expr::Greatest(_) => "greatest".to_owned(),
+        Expr::Least(_) => "least".to_owned(),
+        _ => "?column?".to_owned(),
+    }
+}
+
+// parser produces only queries that return rows as operands of set operations
+fn result_column_names(query: &UntypedQuery) -> &[String] {
+    match query {
+        UntypedQuery::Select(select) => &select.column_names,
+        UntypedQuery::SetOperation(set_operation) => &set_operation.column_names,
+        other => unreachable!("{:?} could not be used in a set operation", other),
+    }
+}
+
+// columns and typed constants have known types, types of expressions are inferred later
+fn result_types(query: &UntypedQuery) -> Vec<Option<SqlTypeFamily>> {
+    match query {
+        UntypedQuery::Select(select) => select
+            .projection_items
+            .iter()
+            .map(|item| match item {
+                UntypedTree::Item(UntypedItem::Column { sql_type, .. }) => Some(sql_type.family()),
+                UntypedTree::Item(UntypedItem::Const(value)) => value.kind(),
+                UntypedTree::UnOp {
+                    op: UnOperator::Cast(sql_type),
+                    ..
+                } => Some(sql_type.family()),
+                _ => None,
+            })
+            .collect(),
+        UntypedQuery::SetOperation(set_operation) => result_types(&set_operation.left)
+            .into_iter()
+            .zip(result_types(&set_operation.right))
+            .map(|types| match types {
+                (Some(left), Some(right)) => left.compare(&right).ok(),
+                (known, None) | (None, known) => known,
+            })
+            .collect(),
+        other => unreachable!("{:?} could not be used in a set operation", other),
+    }
+}
+
+fn view_column_item(view_column: &ColumnDef, view_items: &[UntypedTree]) -> UntypedTree {
+    match &view_items[view_column.index()] {
+        UntypedTree::Item(UntypedItem::Column { sql_type, index, .. }) => UntypedTree::Item(UntypedItem::Column {
+            name: view_column.name().to_owned(),
+            sql_type: *sql_type,
+            index: *index,
+        }),
+        item => item.clone(),
+    }
+}
+
+fn expand_view_columns(tree: UntypedTree, view_items: &[UntypedTree]) -> Result<UntypedTree, AnalysisError> {
+    map_items(tree, &|item| match item {
+        UntypedItem::Column { index, .. } => Ok(view_items[index].clone()),
+        item => Ok(UntypedTree::Item(item)),
+    })
+}
+
+// parameters of a function body are referenced either by name or by position, an argument
+// is evaluated as many times as its parameter is referenced, so volatile one is not inlined twice
+fn inline_parameters(body: UntypedTree, args: &[UntypedTree]) -> Result<UntypedTree, AnalysisError> {
+    let references = RefCell::new(vec![0; args.len()]);
+    let body = map_items(body, &|item| match item {
+        UntypedItem::Column { index, .. } | UntypedItem::Param(index) if index < args.len() => {
+            references.borrow_mut()[index] += 1;
+            Ok(args[index].clone())
+        }
+        UntypedItem::Param(index) => Err(AnalysisError::invalid_function_definition(format!(
+            "there is no parameter ${}",
+            index + 1
+        ))),
+        item => Ok(UntypedTree::Item(item)),
+    })?;
+    if args
+        .iter()
+        .zip(references.into_inner())
+        .any(|(arg, references)| references > 1 && contains_sequence(arg))
+    {
+        return Err(AnalysisError::feature_not_supported(
+            "SQL function that references a parameter with volatile argument more than once",
+        ));
+    }
+    Ok(body)
+}
+
+fn map_items<F: Fn(UntypedItem) -> Result<UntypedTree, AnalysisError>>(tree: UntypedTree, f: &F) -> Result<UntypedTree, AnalysisError> {
+    let map_all = |trees: Vec<UntypedTree>| trees.into_iter().map(|tree| map_items(tree, f)).collect::<Result<Vec<_>, _>>();
+    Ok(match tree {
+        UntypedTree::Item(item) => f(item)?,
+        UntypedTree::BiOp { left, op, right } => UntypedTree::BiOp {
+            left: Box::new(map_items(*left, f)?),
+            op,
+            right: Box::new(map_items(*right, f)?),
+        },
+        UntypedTree::UnOp { op, item } => UntypedTree::UnOp {
+            op,
+            item: Box::new(map_items(*item, f)?),
+        },
+        UntypedTree::Function { name, args } => UntypedTree::Function { name, args: map_all(args)? },
+        UntypedTree::Sequence { function, sequence, args } => UntypedTree::Sequence {
+            function,
+            sequence,
+            args: map_all(args)?,
+        },
+        UntypedTree::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => UntypedTree::Case {
+            operand: operand.map(|operand| map_items(*operand, f)).transpose()?.map(Box::new),
+            conditions: map_all(conditions)?,
+            results: map_all(results)?,
+            else_result: else_result.map(|else_result| map_items(*else_result, f)).transpose()?.map(Box::new),
+        },
+        UntypedTree::Conditional { function, args } => UntypedTree::Conditional {
+            function,
+            args: map_all(args)?,
+        },
+        UntypedTree::Quantified { left, op, quantifier, list } => UntypedTree::Quantified {
+            left: Box::new(map_items(*left, f)?),
+            op,
+            quantifier,
+            list: map_all(list)?,
+        },
+        UntypedTree::Between { item, low, high, negated } => UntypedTree::Between {
+            item: Box::new(map_items(*item, f)?),
+            low: Box::new(map_items(*low, f)?),
+            high: Box::new(map_items(*high, f)?),
+            negated,
+        },
+        UntypedTree::Window(window) => UntypedTree::Window(UntypedWindow {
+            args: map_all(window.args)?,
+            partition_by: map_all(window.partition_by)?,
+            order_by: window
+                .order_by
+                .into_iter()
+                .map(|key| {
+                    Ok(UntypedSortKey {
+                        expr: map_items(key.expr, f)?,
+                        ..key
+                    })
+                })
+                .collect::<Result<_, AnalysisError>>()?,
+            ..window
+        }),
+    })
+}
+
+fn contains_window(tree: &UntypedTree) -> bool {
+    contains(tree, &|tree| matches!(tree, UntypedTree::Window(_)))
+}
+
+// sequence functions are the only volatile ones
+fn contains_sequence(tree: &UntypedTree) -> bool {
+    contains(tree, &|tree| matches!(tree, UntypedTree::Sequence { .. }))
+}
+
+fn contains<P: Fn(&UntypedTree) -> bool>(tree: &UntypedTree, predicate: &P) -> bool {
+    let any = |trees: &[UntypedTree]| trees.iter().any(|tree| contains(tree, predicate));
+    predicate(tree)
+        || match tree {
+            UntypedTree::Window(window) => {
+                any(&window.args) || any(&window.partition_by) || window.order_by.iter().any(|key| contains(&key.expr, predicate))
+            }
+            UntypedTree::BiOp { left, right, .. } => contains(left, predicate) || contains(right, predicate),
+            UntypedTree::UnOp { item, .. } => contains(item, predicate),
+            UntypedTree::Sequence { args, .. } | UntypedTree::Function { args, .. } | UntypedTree::Conditional { args, .. } => any(args),
+            UntypedTree::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => operand.iter().chain(else_result.iter()).any(|tree| contains(tree, predicate)) || any(conditions) || any(results),
+            UntypedTree::Quantified { left, list, .. } => contains(left, predicate) || any(list),
+            UntypedTree::Between { item, low, high, .. } => contains(item, predicate) || contains(low, predicate) || contains(high, predicate),
+            UntypedTree::Item(_) => false,
+        }
 }
 
 #[derive(Debug, PartialEq)]
@@ -212,6 +871,16 @@ pub enum AnalysisError {
     TableDoesNotExist(String),
     ColumnNotFound(String),
     ColumnCantBeReferenced(String), // Error code: 42703
+    SequenceDoesNotExist(String),
+    FunctionDoesNotExist(String),
+    CannotInsertIntoGeneratedColumn(String),
+    CannotUpdateGeneratedColumn(String),
+    PermissionDeniedForTable(String),
+    FeatureNotSupported(String),
+    ColumnNumberMismatch(SetOperator),
+    SetOperationTypeMismatch(SetOperator, SqlTypeFamily, SqlTypeFamily), // Error code: 42804
+    WindowingError(String),                                              // Error code: 42P20
+    InvalidFunctionDefinition(String),
 }
 
 impl AnalysisError {
@@ -230,6 +899,46 @@ impl AnalysisError {
     pub fn column_cant_be_referenced<C: ToString>(column_name: C) -> AnalysisError {
         AnalysisError::ColumnCantBeReferenced(column_name.to_string())
     }
+
+    pub fn sequence_does_not_exist<S: ToString>(sequence_name: S) -> AnalysisError {
+        AnalysisError::SequenceDoesNotExist(sequence_name.to_string())
+    }
+
+    pub fn function_does_not_exist<F: ToString>(function_name: F) -> AnalysisError {
+        AnalysisError::FunctionDoesNotExist(function_name.to_string())
+    }
+
+    pub fn cannot_insert_into_generated_column<C: ToString>(column_name: C) -> AnalysisError {
+        AnalysisError::CannotInsertIntoGeneratedColumn(column_name.to_string())
+    }
+
+    pub fn cannot_update_generated_column<C: ToString>(column_name: C) -> AnalysisError {
+        AnalysisError::CannotUpdateGeneratedColumn(column_name.to_string())
+    }
+
+    pub fn permission_denied_for_table<T: ToString>(table_name: T) -> AnalysisError {
+        AnalysisError::PermissionDeniedForTable(table_name.to_string())
+    }
+
+    pub fn feature_not_supported<F: ToString>(feature_description: F) -> AnalysisError {
+        AnalysisError::FeatureNotSupported(feature_description.to_string())
+    }
+
+    pub fn column_number_mismatch(op: SetOperator) -> AnalysisError {
+        AnalysisError::ColumnNumberMismatch(op)
+    }
+
+    pub fn set_operation_type_mismatch(op: SetOperator, left: SqlTypeFamily, right: SqlTypeFamily) -> AnalysisError {
+        AnalysisError::SetOperationTypeMismatch(op, left, right)
+    }
+
+    pub fn windowing_error<M: ToString>(message: M) -> AnalysisError {
+        AnalysisError::WindowingError(message.to_string())
+    }
+
+    pub fn invalid_function_definition<M: ToString>(message: M) -> AnalysisError {
+        AnalysisError::InvalidFunctionDefinition(message.to_string())
+    }
 }
 
 impl From<AnalysisError> for QueryError {
@@ -239,6 +948,18 @@ impl From<AnalysisError> for QueryError {
             AnalysisError::TableDoesNotExist(table_name) => QueryError::table_does_not_exist(table_name),
             AnalysisError::ColumnNotFound(column_name) => QueryError::column_does_not_exist(column_name),
             AnalysisError::ColumnCantBeReferenced(column_name) => QueryError::column_does_not_exist(column_name),
+            AnalysisError::SequenceDoesNotExist(sequence_name) => QueryError::sequence_does_not_exist(sequence_name),
+            AnalysisError::FunctionDoesNotExist(function_name) => QueryError::function_does_not_exist(function_name),
+            AnalysisError::CannotInsertIntoGeneratedColumn(column_name) => QueryError::cannot_insert_into_generated_column(column_name),
+            AnalysisError::CannotUpdateGeneratedColumn(column_name) => QueryError::cannot_update_generated_column(column_name),
+            AnalysisError::PermissionDeniedForTable(table_name) => {
+                QueryError::insufficient_privilege(format!("permission denied for table {}", table_name))
+            }
+            AnalysisError::FeatureNotSupported(feature_description) => QueryError::feature_not_supported(feature_description),
+            AnalysisError::ColumnNumberMismatch(op) => QueryError::syntax_error(format!("each {} query must have the same number of columns", op)),
+            AnalysisError::SetOperationTypeMismatch(op, left, right) => QueryError::set_operation_type_mismatch(op, left, right),
+            AnalysisError::WindowingError(message) => QueryError::windowing_error(message),
+            AnalysisError::InvalidFunctionDefinition(message) => QueryError::invalid_function_definition(message),
         }
     }
 }
@@ -16,7 +16,7 @@ use super::*;
 
 fn delete_statement(schema_name: &str, table_name: &str) -> Query {
     Query::Delete(DeleteQuery {
-        schema_name: schema_name.to_owned(),
+        schema_name: Some(schema_name.to_owned()),
         table_name: table_name.to_owned(),
         where_clause: None,
     })
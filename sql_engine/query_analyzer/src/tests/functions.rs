@@ -0,0 +1,358 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::{CreateFunctionQuery, CreateSequenceQuery};
+use data_manipulation_operators::{BiArithmetic, UnArithmetic};
+use data_manipulation_untyped_tree::{UntypedItem, UntypedTree, UntypedValue};
+use query_ast::{SelectItem, SelectQuery};
+
+fn function(name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function { name: name.to_owned(), args }
+}
+
+fn select(select_items: Vec<SelectItem>) -> Query {
+    Query::Select(SelectQuery {
+        distinct: None,
+        select_items,
+        schema_name: Some(SCHEMA.to_owned()),
+        table_name: Some(TABLE.to_owned()),
+        where_clause: None,
+    })
+}
+
+#[test]
+fn built_in_function() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::var_char(10))]))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(select(vec![SelectItem::UnnamedExpr(function(
+            "substring",
+            vec![Expr::Column("col".to_owned()), Expr::Value(number(2))]
+        ))])),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![UntypedTree::Function {
+                name: "substring".to_owned(),
+                args: vec![
+                    UntypedTree::Item(UntypedItem::Column {
+                        name: "col".to_owned(),
+                        sql_type: SqlType::var_char(10),
+                        index: 0
+                    }),
+                    UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(2)))
+                ]
+            }],
+            column_names: vec!["substring".to_owned()],
+            filter: None,
+            distinct: None,
+        }))
+    );
+}
+
+#[test]
+fn built_in_function_with_wrong_number_of_arguments() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::var_char(10))]))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(select(vec![SelectItem::UnnamedExpr(function(
+            "lower",
+            vec![Expr::Column("col".to_owned()), Expr::Column("col".to_owned())]
+        ))])),
+        Err(AnalysisError::function_does_not_exist("lower"))
+    );
+}
+
+fn create_function_ops(function_name: &str, parameters: Vec<(&str, SqlType)>, return_type: SqlType, body: Expr) -> SchemaChange {
+    SchemaChange::CreateFunction(CreateFunctionQuery {
+        full_function_name: FullTableName::from((&SCHEMA, &function_name)),
+        parameters: parameters.into_iter().map(|(name, sql_type)| (name.to_owned(), sql_type)).collect(),
+        return_type,
+        body: serde_json::to_string(&body).unwrap(),
+        used_functions: vec![],
+        or_replace: false,
+    })
+}
+
+fn cast(tree: UntypedTree, sql_type: SqlType) -> UntypedTree {
+    UntypedTree::UnOp {
+        op: UnOperator::Cast(sql_type),
+        item: Box::new(tree),
+    }
+}
+
+#[test]
+fn sql_function_is_inlined() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::small_int())]))
+        .unwrap();
+    catalog
+        .apply(create_function_ops(
+            "add",
+            vec![("a", SqlType::integer()), ("", SqlType::integer())],
+            SqlType::big_int(),
+            Expr::BinaryOp {
+                left: Box::new(Expr::Column("a".to_owned())),
+                op: BinaryOperator::Plus,
+                right: Box::new(Expr::Param(2)),
+            },
+        ))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+    analyzer.set_search_path(SearchPath::new(SCHEMA, ""));
+
+    assert_eq!(
+        analyzer.analyze(select(vec![SelectItem::UnnamedExpr(function(
+            "add",
+            vec![Expr::Column("col".to_owned()), Expr::Value(number(2))]
+        ))])),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![cast(
+                UntypedTree::BiOp {
+                    left: Box::new(cast(
+                        UntypedTree::Item(UntypedItem::Column {
+                            name: "col".to_owned(),
+                            sql_type: SqlType::small_int(),
+                            index: 0
+                        }),
+                        SqlType::integer()
+                    )),
+                    op: BiOperator::Arithmetic(BiArithmetic::Add),
+                    right: Box::new(cast(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(2))), SqlType::integer())),
+                },
+                SqlType::big_int()
+            )],
+            column_names: vec!["add".to_owned()],
+            filter: None,
+            distinct: None,
+        }))
+    );
+    assert_eq!(analyzer.take_inlined_functions(), vec![FullTableName::from((&SCHEMA, &"add"))]);
+}
+
+#[test]
+fn sql_function_calling_sql_function() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::integer())])).unwrap();
+    catalog
+        .apply(create_function_ops(
+            "negate",
+            vec![("a", SqlType::integer())],
+            SqlType::integer(),
+            Expr::UnaryOp {
+                op: query_ast::UnaryOperator::Minus,
+                expr: Box::new(Expr::Column("a".to_owned())),
+            },
+        ))
+        .unwrap();
+    catalog
+        .apply(create_function_ops(
+            "twice_negated",
+            vec![("b", SqlType::integer())],
+            SqlType::integer(),
+            function("negate", vec![function("negate", vec![Expr::Column("b".to_owned())])]),
+        ))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+    analyzer.set_search_path(SearchPath::new(SCHEMA, ""));
+
+    let column = UntypedTree::Item(UntypedItem::Column {
+        name: "col".to_owned(),
+        sql_type: SqlType::integer(),
+        index: 0,
+    });
+    let negate = |tree: UntypedTree| {
+        cast(
+            UntypedTree::UnOp {
+                op: UnOperator::Arithmetic(UnArithmetic::Neg),
+                item: Box::new(cast(tree, SqlType::integer())),
+            },
+            SqlType::integer(),
+        )
+    };
+    assert_eq!(
+        analyzer.analyze(select(vec![SelectItem::UnnamedExpr(function(
+            "twice_negated",
+            vec![Expr::Column("col".to_owned())]
+        ))])),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![cast(negate(negate(cast(column, SqlType::integer()))), SqlType::integer())],
+            column_names: vec!["twice_negated".to_owned()],
+            filter: None,
+            distinct: None,
+        }))
+    );
+    assert_eq!(
+        analyzer.take_inlined_functions(),
+        vec![
+            FullTableName::from((&SCHEMA, &"negate")),
+            FullTableName::from((&SCHEMA, &"twice_negated"))
+        ]
+    );
+}
+
+#[test]
+fn sql_function_with_wrong_number_of_arguments() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::integer())])).unwrap();
+    catalog
+        .apply(create_function_ops(
+            "identity",
+            vec![("a", SqlType::integer())],
+            SqlType::integer(),
+            Expr::Column("a".to_owned()),
+        ))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+    analyzer.set_search_path(SearchPath::new(SCHEMA, ""));
+
+    assert_eq!(
+        analyzer.analyze(select(vec![SelectItem::UnnamedExpr(function("identity", vec![]))])),
+        Err(AnalysisError::function_does_not_exist("identity"))
+    );
+}
+
+#[test]
+fn sql_function_outside_of_search_path() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::integer())])).unwrap();
+    catalog
+        .apply(create_function_ops(
+            "identity",
+            vec![("a", SqlType::integer())],
+            SqlType::integer(),
+            Expr::Column("a".to_owned()),
+        ))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(select(vec![SelectItem::UnnamedExpr(function(
+            "identity",
+            vec![Expr::Column("col".to_owned())]
+        ))])),
+        Err(AnalysisError::function_does_not_exist("identity"))
+    );
+}
+
+#[test]
+fn sql_function_referencing_volatile_argument_twice() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::integer())])).unwrap();
+    catalog
+        .apply(SchemaChange::CreateSequence(CreateSequenceQuery {
+            full_sequence_name: FullTableName::from((&SCHEMA, &"sequence_name")),
+            start: 1,
+            increment: 1,
+            min_value: 1,
+            max_value: i64::MAX,
+            if_not_exists: false,
+        }))
+        .unwrap();
+    catalog
+        .apply(create_function_ops(
+            "double",
+            vec![("a", SqlType::big_int())],
+            SqlType::big_int(),
+            Expr::BinaryOp {
+                left: Box::new(Expr::Column("a".to_owned())),
+                op: BinaryOperator::Plus,
+                right: Box::new(Expr::Column("a".to_owned())),
+            },
+        ))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+    analyzer.set_search_path(SearchPath::new(SCHEMA, ""));
+
+    assert_eq!(
+        analyzer.analyze(select(vec![SelectItem::UnnamedExpr(function(
+            "double",
+            vec![function("nextval", vec![Expr::Value(Value::String("sequence_name".to_owned()))])]
+        ))])),
+        Err(AnalysisError::feature_not_supported(
+            "SQL function that references a parameter with volatile argument more than once"
+        ))
+    );
+}
+
+#[test]
+fn sql_function_with_unreadable_body() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::integer())])).unwrap();
+    catalog
+        .apply(SchemaChange::CreateFunction(CreateFunctionQuery {
+            full_function_name: FullTableName::from((&SCHEMA, &"broken")),
+            parameters: vec![],
+            return_type: SqlType::integer(),
+            body: "not an expression".to_owned(),
+            used_functions: vec![],
+            or_replace: false,
+        }))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+    analyzer.set_search_path(SearchPath::new(SCHEMA, ""));
+
+    assert_eq!(
+        analyzer.analyze(select(vec![SelectItem::UnnamedExpr(function("broken", vec![]))])),
+        Err(AnalysisError::invalid_function_definition(
+            "body of function schema_name.broken could not be read"
+        ))
+    );
+}
@@ -26,7 +26,7 @@ fn small_int(value: i16) -> Expr {
 
 fn inner_insert(schema_name: &str, table_name: &str, multiple_values: Vec<Vec<Expr>>, columns: Vec<&str>) -> Query {
     Query::Insert(InsertQuery {
-        schema_name: schema_name.to_owned(),
+        schema_name: Some(schema_name.to_owned()),
         table_name: table_name.to_owned(),
         columns: columns.into_iter().map(ToOwned::to_owned).collect(),
         source: InsertSource::Values(Values(multiple_values)),
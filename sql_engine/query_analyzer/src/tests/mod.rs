@@ -23,10 +23,18 @@ use types::SqlType;
 #[cfg(test)]
 mod delete;
 #[cfg(test)]
+mod functions;
+#[cfg(test)]
 mod insertions;
 #[cfg(test)]
+mod search_path;
+#[cfg(test)]
 mod selects;
 #[cfg(test)]
+mod sequences;
+#[cfg(test)]
+mod system_views;
+#[cfg(test)]
 mod updates;
 
 const SCHEMA: &str = "schema_name";
@@ -66,6 +74,7 @@ fn create_table_ops(schema_name: &str, table_name: &str, columns: Vec<(&str, Sql
             .map(|(name, sql_type)| ColumnInfo {
                 name: name.to_owned(),
                 sql_type,
+                identity: None,
             })
             .collect(),
         if_not_exists: true,
@@ -0,0 +1,100 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use catalog::PG_CATALOG;
+use query_ast::{SelectItem, SelectQuery};
+
+fn select_all_from(table_name: &str) -> Query {
+    Query::Select(SelectQuery {
+        distinct: None,
+        select_items: vec![SelectItem::Wildcard],
+        schema_name: None,
+        table_name: Some(table_name.to_owned()),
+        where_clause: None,
+    })
+}
+
+fn selected_table(query: Result<UntypedQuery, AnalysisError>) -> FullTableName {
+    match query {
+        Ok(UntypedQuery::Select(select)) => select.full_table_name.unwrap(),
+        other => panic!("select query expected but {:?}", other),
+    }
+}
+
+#[test]
+fn unqualified_name_is_resolved_with_first_schema_that_has_table() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::integer())])).unwrap();
+    catalog
+        .apply(create_table_ops("public", "other_table", vec![("col", SqlType::integer())]))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+    analyzer.set_search_path(SearchPath::new("schema_name, public", "username"));
+
+    assert_eq!(
+        selected_table(analyzer.analyze(select_all_from(TABLE))),
+        FullTableName::from((&SCHEMA, &TABLE))
+    );
+    assert_eq!(
+        selected_table(analyzer.analyze(select_all_from("other_table"))),
+        FullTableName::from((&"public", &"other_table"))
+    );
+}
+
+#[test]
+fn user_schema_is_searched_first() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops("username")).unwrap();
+    catalog
+        .apply(create_table_ops("username", TABLE, vec![("col", SqlType::integer())]))
+        .unwrap();
+    catalog
+        .apply(create_table_ops("public", TABLE, vec![("col", SqlType::integer())]))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+    analyzer.set_search_path(SearchPath::new("\"$user\", public", "username"));
+
+    assert_eq!(
+        selected_table(analyzer.analyze(select_all_from(TABLE))),
+        FullTableName::from((&"username", &TABLE))
+    );
+}
+
+#[test]
+fn pg_catalog_is_searched_implicitly() {
+    let db = Database::new("");
+    let analyzer = QueryAnalyzer::from(db.transaction());
+
+    assert_eq!(
+        selected_table(analyzer.analyze(select_all_from("pg_class"))),
+        FullTableName::from((&PG_CATALOG, &"pg_class"))
+    );
+}
+
+#[test]
+fn none_of_schemas_in_search_path_exists() {
+    let db = Database::new("");
+    let analyzer = QueryAnalyzer::from(db.transaction());
+    analyzer.set_search_path(SearchPath::new("non_existent", ""));
+
+    assert_eq!(analyzer.analyze(select_all_from(TABLE)), Err(AnalysisError::table_does_not_exist(TABLE)));
+}
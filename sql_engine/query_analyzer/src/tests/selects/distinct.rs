@@ -0,0 +1,103 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn column(name: &str, index: usize) -> UntypedTree {
+    UntypedTree::Item(UntypedItem::Column {
+        name: name.to_owned(),
+        index,
+        sql_type: SqlType::small_int(),
+    })
+}
+
+fn select_distinct(distinct: Distinct, select_items: Vec<SelectItem>) -> Query {
+    Query::Select(SelectQuery {
+        distinct: Some(distinct),
+        select_items,
+        schema_name: Some(SCHEMA.to_owned()),
+        table_name: Some(TABLE.to_owned()),
+        where_clause: None,
+    })
+}
+
+fn analyzer_with_table(db: &Database) -> QueryAnalyzer<'_> {
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::small_int())],
+        ))
+        .unwrap();
+    QueryAnalyzer::from(transaction)
+}
+
+#[test]
+fn distinct_rows_are_deduplicated_by_projection_items() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(select_distinct(
+            Distinct::Rows,
+            vec![SelectItem::UnnamedExpr(Expr::Column("col_2".to_owned()))]
+        )),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![column("col_2", 1)],
+            column_names: vec!["col_2".to_owned()],
+            filter: None,
+            distinct: Some(UntypedDistinct::Rows(vec![column("col_2", 1)])),
+        }))
+    );
+}
+
+#[test]
+fn distinct_on_expressions() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(select_distinct(
+            Distinct::On(vec![Expr::Column("col_1".to_owned())]),
+            vec![SelectItem::UnnamedExpr(Expr::Column("col_2".to_owned()))]
+        )),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![column("col_2", 1)],
+            column_names: vec!["col_2".to_owned()],
+            filter: None,
+            distinct: Some(UntypedDistinct::On(vec![column("col_1", 0)])),
+        }))
+    );
+}
+
+#[test]
+fn distinct_on_non_existent_column() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(select_distinct(
+            Distinct::On(vec![Expr::Column("col_3".to_owned())]),
+            vec![SelectItem::Wildcard]
+        )),
+        Err(AnalysisError::column_not_found("col_3"))
+    );
+}
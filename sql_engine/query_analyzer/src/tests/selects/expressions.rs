@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use data_manipulation_operators::ConditionalFunction;
 use data_manipulation_untyped_tree::{UntypedItem, UntypedTree, UntypedValue};
 
 use super::*;
@@ -31,13 +32,16 @@ fn select_all_columns_from_table() {
     assert_eq!(
         analyzer.analyze(select(SCHEMA, TABLE)),
         Ok(UntypedQuery::Select(UntypedSelectQuery {
-            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
             projection_items: vec![UntypedTree::Item(UntypedItem::Column {
                 name: "col1".to_owned(),
                 index: 0,
                 sql_type: SqlType::integer()
             })],
-            filter: None
+            column_names: vec!["col1".to_owned()],
+            filter: None,
+            distinct: None,
         }))
     );
 }
@@ -61,13 +65,16 @@ fn select_specified_column_from_table() {
             vec![SelectItem::UnnamedExpr(Expr::Column("col1".to_owned()))]
         )),
         Ok(UntypedQuery::Select(UntypedSelectQuery {
-            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
             projection_items: vec![UntypedTree::Item(UntypedItem::Column {
                 name: "col1".to_owned(),
                 index: 0,
                 sql_type: SqlType::integer()
             })],
-            filter: None
+            column_names: vec!["col1".to_owned()],
+            filter: None,
+            distinct: None,
         }))
     );
 }
@@ -109,9 +116,131 @@ fn select_from_table_with_constant() {
     assert_eq!(
         analyzer.analyze(select_with_columns(SCHEMA, TABLE, vec![SelectItem::UnnamedExpr(Expr::Value(number(1)))],)),
         Ok(UntypedQuery::Select(UntypedSelectQuery {
-            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
             projection_items: vec![UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(1)))],
-            filter: None
+            column_names: vec!["?column?".to_owned()],
+            filter: None,
+            distinct: None,
+        }))
+    );
+}
+
+#[test]
+fn select_expression_with_alias() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col1", SqlType::integer())]))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            TABLE,
+            vec![SelectItem::ExprWithAlias {
+                expr: Expr::BinaryOp {
+                    left: Box::new(Expr::Column("col1".to_owned())),
+                    op: BinaryOperator::Plus,
+                    right: Box::new(Expr::Value(number(1)))
+                },
+                alias: "col2".to_owned()
+            }]
+        )),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![UntypedTree::BiOp {
+                left: Box::new(UntypedTree::Item(UntypedItem::Column {
+                    name: "col1".to_owned(),
+                    index: 0,
+                    sql_type: SqlType::integer()
+                })),
+                op: BiOperator::Arithmetic(BiArithmetic::Add),
+                right: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(1))))
+            }],
+            column_names: vec!["col2".to_owned()],
+            filter: None,
+            distinct: None,
+        }))
+    );
+}
+
+#[test]
+fn select_conditional_expression() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col1", SqlType::integer())]))
+        .unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            TABLE,
+            vec![SelectItem::UnnamedExpr(Expr::Coalesce(vec![
+                Expr::Column("col1".to_owned()),
+                Expr::Value(number(0))
+            ]))]
+        )),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![UntypedTree::Conditional {
+                function: ConditionalFunction::Coalesce,
+                args: vec![
+                    UntypedTree::Item(UntypedItem::Column {
+                        name: "col1".to_owned(),
+                        index: 0,
+                        sql_type: SqlType::integer()
+                    }),
+                    UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(0)))
+                ]
+            }],
+            column_names: vec!["coalesce".to_owned()],
+            filter: None,
+            distinct: None,
+        }))
+    );
+}
+
+#[test]
+fn select_without_from_clause() {
+    let db = Database::new("");
+    let analyzer = QueryAnalyzer::from(db.transaction());
+
+    assert_eq!(
+        analyzer.analyze(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![
+                SelectItem::UnnamedExpr(Expr::Value(number(1))),
+                SelectItem::ExprWithAlias {
+                    expr: string("abc"),
+                    alias: "str".to_owned()
+                }
+            ],
+            schema_name: None,
+            table_name: None,
+            where_clause: None,
+        })),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: None,
+            subquery: None,
+            projection_items: vec![
+                UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(1))),
+                UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal("abc".to_owned())))
+            ],
+            column_names: vec!["?column?".to_owned(), "str".to_owned()],
+            filter: None,
+            distinct: None,
         }))
     );
 }
@@ -131,9 +260,12 @@ fn select_parameters_from_a_table() {
     assert_eq!(
         analyzer.analyze(select_with_columns(SCHEMA, TABLE, vec![SelectItem::UnnamedExpr(Expr::Param(1))],)),
         Ok(UntypedQuery::Select(UntypedSelectQuery {
-            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
             projection_items: vec![UntypedTree::Item(UntypedItem::Param(0))],
-            filter: None
+            column_names: vec!["?column?".to_owned()],
+            filter: None,
+            distinct: None,
         }))
     );
 }
@@ -175,13 +307,16 @@ mod multiple_values {
                 Expr::Value(number(1))
             )),
             Ok(UntypedQuery::Select(UntypedSelectQuery {
-                full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+                full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+                subquery: None,
                 projection_items: vec![UntypedTree::BiOp {
                     left: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal("1".to_owned())))),
                     op: BiOperator::Arithmetic(BiArithmetic::Add),
                     right: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(1))))
                 }],
-                filter: None
+                column_names: vec!["?column?".to_owned()],
+                filter: None,
+                distinct: None,
             }))
         );
     }
@@ -205,13 +340,16 @@ mod multiple_values {
                 string("str")
             )),
             Ok(UntypedQuery::Select(UntypedSelectQuery {
-                full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+                full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+                subquery: None,
                 projection_items: vec![UntypedTree::BiOp {
                     left: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal("str".to_owned())))),
                     op: BiOperator::StringOp(Concat),
                     right: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal("str".to_owned()))))
                 }],
-                filter: None
+                column_names: vec!["?column?".to_owned()],
+                filter: None,
+                distinct: None,
             }))
         );
     }
@@ -233,13 +371,16 @@ mod multiple_values {
                 Expr::Value(number(1))
             )),
             Ok(UntypedQuery::Select(UntypedSelectQuery {
-                full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+                full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+                subquery: None,
                 projection_items: vec![UntypedTree::BiOp {
                     left: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal("1".to_owned())))),
                     op: BiOperator::Comparison(Comparison::Gt),
                     right: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(1))))
                 }],
-                filter: None
+                column_names: vec!["?column?".to_owned()],
+                filter: None,
+                distinct: None,
             }))
         );
     }
@@ -261,7 +402,8 @@ mod multiple_values {
                 boolean(true),
             )),
             Ok(UntypedQuery::Select(UntypedSelectQuery {
-                full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+                full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+                subquery: None,
                 projection_items: vec![UntypedTree::BiOp {
                     left: Box::new(UntypedTree::UnOp {
                         op: UnOperator::Cast(SqlType::Bool),
@@ -273,7 +415,9 @@ mod multiple_values {
                         item: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal("t".to_owned()))))
                     }),
                 }],
-                filter: None
+                column_names: vec!["?column?".to_owned()],
+                filter: None,
+                distinct: None,
             }))
         );
     }
@@ -297,13 +441,16 @@ mod multiple_values {
                 Expr::Value(number(1))
             )),
             Ok(UntypedQuery::Select(UntypedSelectQuery {
-                full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+                full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+                subquery: None,
                 projection_items: vec![UntypedTree::BiOp {
                     left: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(1)))),
                     op: BiOperator::Bitwise(Bitwise::Or),
                     right: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(1))))
                 }],
-                filter: None
+                column_names: vec!["?column?".to_owned()],
+                filter: None,
+                distinct: None,
             }))
         );
     }
@@ -325,13 +472,16 @@ mod multiple_values {
                 string("str")
             )),
             Ok(UntypedQuery::Select(UntypedSelectQuery {
-                full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+                full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+                subquery: None,
                 projection_items: vec![UntypedTree::BiOp {
                     left: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal("s".to_owned())))),
                     op: BiOperator::Matching(Matching::Like),
                     right: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal("str".to_owned()))))
                 }],
-                filter: None
+                column_names: vec!["?column?".to_owned()],
+                filter: None,
+                distinct: None,
             }))
         );
     }
@@ -14,16 +14,25 @@
 
 use super::*;
 
+#[cfg(test)]
+mod distinct;
 #[cfg(test)]
 mod expressions;
 #[cfg(test)]
 mod general_cases;
+#[cfg(test)]
+mod set_operations;
+#[cfg(test)]
+mod views;
+#[cfg(test)]
+mod windows;
 
 fn select_with_columns(schema_name: &str, table_name: &str, select_items: Vec<SelectItem>) -> Query {
     Query::Select(SelectQuery {
+        distinct: None,
         select_items,
-        schema_name: schema_name.to_owned(),
-        table_name: table_name.to_owned(),
+        schema_name: Some(schema_name.to_owned()),
+        table_name: Some(table_name.to_owned()),
         where_clause: None,
     })
 }
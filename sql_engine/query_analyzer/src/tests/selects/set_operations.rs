@@ -0,0 +1,259 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use query_ast::SetOperator as AstSetOperator;
+
+const OTHER_SCHEMA: &str = "other_schema";
+const OTHER_TABLE: &str = "other_table";
+
+fn analyzer_with_tables(db: &Database) -> QueryAnalyzer<'_> {
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(SCHEMA, TABLE, vec![("col_1", SqlType::small_int())]))
+        .unwrap();
+    catalog.apply(create_schema_ops(OTHER_SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            OTHER_SCHEMA,
+            OTHER_TABLE,
+            vec![("col_2", SqlType::integer()), ("col_3", SqlType::bool())],
+        ))
+        .unwrap();
+    QueryAnalyzer::from(transaction)
+}
+
+fn column(name: &str) -> SelectItem {
+    SelectItem::UnnamedExpr(Expr::Column(name.to_owned()))
+}
+
+fn set_operation(op: AstSetOperator, left: Query, right: Query) -> Query {
+    Query::SetOperation(SetOperation {
+        op,
+        all: false,
+        left: Box::new(left),
+        right: Box::new(right),
+    })
+}
+
+#[test]
+fn result_columns_are_named_after_the_left_query() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_tables(&db);
+
+    assert_eq!(
+        analyzer.analyze(set_operation(
+            AstSetOperator::Union,
+            select_with_columns(SCHEMA, TABLE, vec![column("col_1")]),
+            select_with_columns(OTHER_SCHEMA, OTHER_TABLE, vec![column("col_2")]),
+        )),
+        Ok(UntypedQuery::SetOperation(UntypedSetOperation {
+            op: SetOperator::Union,
+            all: false,
+            left: Box::new(UntypedQuery::Select(UntypedSelectQuery {
+                full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+                subquery: None,
+                projection_items: vec![UntypedTree::Item(UntypedItem::Column {
+                    name: "col_1".to_owned(),
+                    index: 0,
+                    sql_type: SqlType::small_int(),
+                })],
+                column_names: vec!["col_1".to_owned()],
+                filter: None,
+                distinct: None,
+            })),
+            right: Box::new(UntypedQuery::Select(UntypedSelectQuery {
+                full_table_name: Some(FullTableName::from((&OTHER_SCHEMA, &OTHER_TABLE))),
+                subquery: None,
+                projection_items: vec![UntypedTree::Item(UntypedItem::Column {
+                    name: "col_2".to_owned(),
+                    index: 0,
+                    sql_type: SqlType::integer(),
+                })],
+                column_names: vec!["col_2".to_owned()],
+                filter: None,
+                distinct: None,
+            })),
+            column_names: vec!["col_1".to_owned()],
+        }))
+    );
+}
+
+#[test]
+fn sides_with_different_number_of_columns() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_tables(&db);
+
+    assert_eq!(
+        analyzer.analyze(set_operation(
+            AstSetOperator::Intersect,
+            select(SCHEMA, TABLE),
+            select(OTHER_SCHEMA, OTHER_TABLE),
+        )),
+        Err(AnalysisError::column_number_mismatch(SetOperator::Intersect))
+    );
+}
+
+#[test]
+fn nested_set_operations_are_checked_for_number_of_columns() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_tables(&db);
+
+    assert_eq!(
+        analyzer.analyze(set_operation(
+            AstSetOperator::Except,
+            set_operation(
+                AstSetOperator::Union,
+                select_with_columns(SCHEMA, TABLE, vec![column("col_1")]),
+                select_with_columns(OTHER_SCHEMA, OTHER_TABLE, vec![column("col_2")]),
+            ),
+            select(OTHER_SCHEMA, OTHER_TABLE),
+        )),
+        Err(AnalysisError::column_number_mismatch(SetOperator::Except))
+    );
+}
+
+#[test]
+fn side_of_set_operation_is_analyzed() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_tables(&db);
+
+    assert_eq!(
+        analyzer.analyze(set_operation(
+            AstSetOperator::Union,
+            select(SCHEMA, TABLE),
+            select_with_columns(OTHER_SCHEMA, OTHER_TABLE, vec![column("col_4")]),
+        )),
+        Err(AnalysisError::column_not_found("col_4"))
+    );
+}
+
+#[test]
+fn sides_with_incomparable_column_types() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_tables(&db);
+
+    assert_eq!(
+        analyzer.analyze(set_operation(
+            AstSetOperator::Union,
+            select_with_columns(SCHEMA, TABLE, vec![column("col_1")]),
+            select_with_columns(OTHER_SCHEMA, OTHER_TABLE, vec![column("col_3")]),
+        )),
+        Err(AnalysisError::set_operation_type_mismatch(
+            SetOperator::Union,
+            SqlTypeFamily::SmallInt,
+            SqlTypeFamily::Bool
+        ))
+    );
+}
+
+#[test]
+fn side_cast_to_incomparable_type() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_tables(&db);
+
+    assert_eq!(
+        analyzer.analyze(set_operation(
+            AstSetOperator::Union,
+            select_with_columns(SCHEMA, TABLE, vec![column("col_1")]),
+            select_with_columns(
+                OTHER_SCHEMA,
+                OTHER_TABLE,
+                vec![SelectItem::UnnamedExpr(Expr::Cast {
+                    expr: Box::new(Expr::Value(Value::String("a".to_owned()))),
+                    data_type: DataType::VarChar(None),
+                })]
+            ),
+        )),
+        Err(AnalysisError::set_operation_type_mismatch(
+            SetOperator::Union,
+            SqlTypeFamily::SmallInt,
+            SqlTypeFamily::String
+        ))
+    );
+}
+
+#[test]
+fn nested_set_operations_are_checked_for_column_types() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_tables(&db);
+
+    assert_eq!(
+        analyzer.analyze(set_operation(
+            AstSetOperator::Except,
+            set_operation(
+                AstSetOperator::Union,
+                select_with_columns(SCHEMA, TABLE, vec![column("col_1")]),
+                select_with_columns(OTHER_SCHEMA, OTHER_TABLE, vec![column("col_2")]),
+            ),
+            select_with_columns(OTHER_SCHEMA, OTHER_TABLE, vec![column("col_3")]),
+        )),
+        Err(AnalysisError::set_operation_type_mismatch(
+            SetOperator::Except,
+            SqlTypeFamily::Integer,
+            SqlTypeFamily::Bool
+        ))
+    );
+}
+
+#[test]
+fn side_cast_to_comparable_type() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_tables(&db);
+
+    assert_eq!(
+        analyzer.analyze(set_operation(
+            AstSetOperator::Union,
+            select_with_columns(SCHEMA, TABLE, vec![column("col_1")]),
+            select_with_columns(
+                OTHER_SCHEMA,
+                OTHER_TABLE,
+                vec![SelectItem::UnnamedExpr(Expr::Cast {
+                    expr: Box::new(Expr::Value(Value::String("1".to_owned()))),
+                    data_type: DataType::BigInt,
+                })]
+            ),
+        )),
+        Ok(UntypedQuery::SetOperation(UntypedSetOperation {
+            op: SetOperator::Union,
+            all: false,
+            left: Box::new(UntypedQuery::Select(UntypedSelectQuery {
+                full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+                subquery: None,
+                projection_items: vec![UntypedTree::Item(UntypedItem::Column {
+                    name: "col_1".to_owned(),
+                    index: 0,
+                    sql_type: SqlType::small_int(),
+                })],
+                column_names: vec!["col_1".to_owned()],
+                filter: None,
+                distinct: None,
+            })),
+            right: Box::new(UntypedQuery::Select(UntypedSelectQuery {
+                full_table_name: Some(FullTableName::from((&OTHER_SCHEMA, &OTHER_TABLE))),
+                subquery: None,
+                projection_items: vec![UntypedTree::UnOp {
+                    op: UnOperator::Cast(SqlType::big_int()),
+                    item: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Literal("1".to_owned())))),
+                }],
+                column_names: vec!["?column?".to_owned()],
+                filter: None,
+                distinct: None,
+            })),
+            column_names: vec!["col_1".to_owned()],
+        }))
+    );
+}
@@ -0,0 +1,319 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use data_definition_execution_plan::CreateViewQuery;
+use data_manipulation_untyped_tree::{UntypedItem, UntypedTree, UntypedValue};
+
+use super::*;
+
+const VIEW: &str = "view_name";
+
+fn create_view_ops(columns: Vec<&str>, query: SelectQuery) -> SchemaChange {
+    SchemaChange::CreateView(CreateViewQuery {
+        full_view_name: FullTableName::from((&SCHEMA, &VIEW)),
+        columns: columns.into_iter().map(ToOwned::to_owned).collect(),
+        definition: serde_json::to_string(&query).unwrap(),
+        source: FullTableName::from((&query.schema_name.clone().unwrap(), &query.table_name.clone().unwrap())),
+        used_functions: vec![],
+        or_replace: false,
+    })
+}
+
+fn view_query() -> SelectQuery {
+    SelectQuery {
+        distinct: None,
+        select_items: vec![
+            SelectItem::UnnamedExpr(Expr::Column("col_2".to_owned())),
+            SelectItem::UnnamedExpr(Expr::Column("col_1".to_owned())),
+        ],
+        schema_name: Some(SCHEMA.to_owned()),
+        table_name: Some(TABLE.to_owned()),
+        where_clause: Some(Expr::BinaryOp {
+            left: Box::new(Expr::Column("col_1".to_owned())),
+            op: BinaryOperator::Gt,
+            right: Box::new(Expr::Value(number(1))),
+        }),
+    }
+}
+
+fn view_filter() -> UntypedTree {
+    UntypedTree::BiOp {
+        left: Box::new(UntypedTree::Item(UntypedItem::Column {
+            name: "col_1".to_owned(),
+            index: 0,
+            sql_type: SqlType::small_int(),
+        })),
+        op: BiOperator::Comparison(Comparison::Gt),
+        right: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(1)))),
+    }
+}
+
+#[test]
+fn select_all_columns_from_view() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::small_int())],
+        ))
+        .unwrap();
+    catalog.apply(create_view_ops(vec!["view_col_1", "view_col_2"], view_query())).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(select(SCHEMA, VIEW)),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![
+                UntypedTree::Item(UntypedItem::Column {
+                    name: "view_col_1".to_owned(),
+                    index: 1,
+                    sql_type: SqlType::small_int()
+                }),
+                UntypedTree::Item(UntypedItem::Column {
+                    name: "view_col_2".to_owned(),
+                    index: 0,
+                    sql_type: SqlType::small_int()
+                })
+            ],
+            column_names: vec!["view_col_1".to_owned(), "view_col_2".to_owned()],
+            filter: Some(view_filter()),
+            distinct: None,
+        }))
+    );
+}
+
+#[test]
+fn select_from_view_with_filter() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::small_int())],
+        ))
+        .unwrap();
+    catalog.apply(create_view_ops(vec!["view_col_1", "view_col_2"], view_query())).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::UnnamedExpr(Expr::Column("view_col_2".to_owned()))],
+            schema_name: Some(SCHEMA.to_owned()),
+            table_name: Some(VIEW.to_owned()),
+            where_clause: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Column("view_col_1".to_owned())),
+                op: BinaryOperator::Lt,
+                right: Box::new(Expr::Value(number(5))),
+            }),
+        })),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![UntypedTree::Item(UntypedItem::Column {
+                name: "view_col_2".to_owned(),
+                index: 0,
+                sql_type: SqlType::small_int()
+            })],
+            column_names: vec!["view_col_2".to_owned()],
+            filter: Some(UntypedTree::BiOp {
+                left: Box::new(view_filter()),
+                op: BiOperator::Logical(BiLogical::And),
+                right: Box::new(UntypedTree::BiOp {
+                    left: Box::new(UntypedTree::Item(UntypedItem::Column {
+                        name: "col_2".to_owned(),
+                        index: 1,
+                        sql_type: SqlType::small_int()
+                    })),
+                    op: BiOperator::Comparison(Comparison::Lt),
+                    right: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(5)))),
+                })
+            }),
+            distinct: None,
+        }))
+    );
+}
+
+#[test]
+fn select_non_existent_column_from_view() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::small_int())],
+        ))
+        .unwrap();
+    catalog.apply(create_view_ops(vec!["view_col_1", "view_col_2"], view_query())).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            VIEW,
+            vec![SelectItem::UnnamedExpr(Expr::Column("col_1".to_owned()))]
+        )),
+        Err(AnalysisError::column_not_found("col_1"))
+    );
+}
+
+#[test]
+fn select_from_distinct_view() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::small_int())],
+        ))
+        .unwrap();
+    let mut view = view_query();
+    view.distinct = Some(Distinct::Rows);
+    view.where_clause = None;
+    catalog.apply(create_view_ops(vec!["view_col_1", "view_col_2"], view)).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            VIEW,
+            vec![SelectItem::UnnamedExpr(Expr::Column("view_col_2".to_owned()))]
+        )),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![UntypedTree::Item(UntypedItem::Column {
+                name: "view_col_2".to_owned(),
+                index: 0,
+                sql_type: SqlType::small_int()
+            })],
+            column_names: vec!["view_col_2".to_owned()],
+            filter: None,
+            distinct: Some(UntypedDistinct::Rows(vec![
+                UntypedTree::Item(UntypedItem::Column {
+                    name: "col_2".to_owned(),
+                    index: 1,
+                    sql_type: SqlType::small_int()
+                }),
+                UntypedTree::Item(UntypedItem::Column {
+                    name: "col_1".to_owned(),
+                    index: 0,
+                    sql_type: SqlType::small_int()
+                })
+            ])),
+        }))
+    );
+}
+
+#[test]
+fn filter_view_defined_with_distinct_on() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::small_int())],
+        ))
+        .unwrap();
+    let mut view = view_query();
+    view.distinct = Some(Distinct::On(vec![Expr::Column("col_1".to_owned())]));
+    catalog.apply(create_view_ops(vec!["view_col_1", "view_col_2"], view)).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::Wildcard],
+            schema_name: Some(SCHEMA.to_owned()),
+            table_name: Some(VIEW.to_owned()),
+            where_clause: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Column("view_col_1".to_owned())),
+                op: BinaryOperator::Lt,
+                right: Box::new(Expr::Value(number(5))),
+            }),
+        })),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: Some(Box::new(UntypedSelectQuery {
+                full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+                subquery: None,
+                projection_items: vec![
+                    UntypedTree::Item(UntypedItem::Column {
+                        name: "col_1".to_owned(),
+                        index: 0,
+                        sql_type: SqlType::small_int()
+                    }),
+                    UntypedTree::Item(UntypedItem::Column {
+                        name: "col_2".to_owned(),
+                        index: 1,
+                        sql_type: SqlType::small_int()
+                    })
+                ],
+                column_names: vec!["col_1".to_owned(), "col_2".to_owned()],
+                filter: Some(view_filter()),
+                distinct: Some(UntypedDistinct::On(vec![UntypedTree::Item(UntypedItem::Column {
+                    name: "col_1".to_owned(),
+                    index: 0,
+                    sql_type: SqlType::small_int()
+                })])),
+            })),
+            projection_items: vec![
+                UntypedTree::Item(UntypedItem::Column {
+                    name: "view_col_1".to_owned(),
+                    index: 1,
+                    sql_type: SqlType::small_int()
+                }),
+                UntypedTree::Item(UntypedItem::Column {
+                    name: "view_col_2".to_owned(),
+                    index: 0,
+                    sql_type: SqlType::small_int()
+                })
+            ],
+            column_names: vec!["view_col_1".to_owned(), "view_col_2".to_owned()],
+            filter: Some(UntypedTree::BiOp {
+                left: Box::new(UntypedTree::Item(UntypedItem::Column {
+                    name: "col_2".to_owned(),
+                    index: 1,
+                    sql_type: SqlType::small_int()
+                })),
+                op: BiOperator::Comparison(Comparison::Lt),
+                right: Box::new(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(5)))),
+            }),
+            distinct: None,
+        }))
+    );
+}
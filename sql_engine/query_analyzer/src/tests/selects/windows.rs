@@ -0,0 +1,271 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_manipulation_operators::{FrameBound, FrameUnits, WindowFrame};
+use query_ast::{OrderByExpr, WindowFrame as AstWindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec};
+
+fn analyzer_with_table(db: &Database) -> QueryAnalyzer<'_> {
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog
+        .apply(create_table_ops(
+            SCHEMA,
+            TABLE,
+            vec![("col_1", SqlType::small_int()), ("col_2", SqlType::small_int())],
+        ))
+        .unwrap();
+    QueryAnalyzer::from(transaction)
+}
+
+fn column(name: &str, index: usize) -> UntypedTree {
+    UntypedTree::Item(UntypedItem::Column {
+        name: name.to_owned(),
+        index,
+        sql_type: SqlType::small_int(),
+    })
+}
+
+fn window(name: &str, args: Vec<Expr>, order_by: Vec<&str>, frame: Option<AstWindowFrame>) -> Expr {
+    Expr::Window {
+        name: name.to_owned(),
+        args,
+        over: Box::new(WindowSpec {
+            partition_by: vec![],
+            order_by: order_by
+                .into_iter()
+                .map(|column| OrderByExpr {
+                    expr: Expr::Column(column.to_owned()),
+                    ascending: true,
+                    nulls_first: false,
+                })
+                .collect(),
+            frame,
+        }),
+    }
+}
+
+#[test]
+fn window_over_partition() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            TABLE,
+            vec![SelectItem::UnnamedExpr(Expr::Window {
+                name: "rank".to_owned(),
+                args: vec![],
+                over: Box::new(WindowSpec {
+                    partition_by: vec![Expr::Column("col_1".to_owned())],
+                    order_by: vec![OrderByExpr {
+                        expr: Expr::Column("col_2".to_owned()),
+                        ascending: false,
+                        nulls_first: true,
+                    }],
+                    frame: None,
+                }),
+            })]
+        )),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![UntypedTree::Window(UntypedWindow {
+                name: "rank".to_owned(),
+                args: vec![],
+                partition_by: vec![column("col_1", 0)],
+                order_by: vec![UntypedSortKey {
+                    expr: column("col_2", 1),
+                    ascending: false,
+                    nulls_first: true,
+                }],
+                frame: WindowFrame::default(),
+            })],
+            column_names: vec!["rank".to_owned()],
+            filter: None,
+            distinct: None,
+        }))
+    );
+}
+
+#[test]
+fn window_with_frame() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            TABLE,
+            vec![SelectItem::ExprWithAlias {
+                expr: window(
+                    "sum",
+                    vec![Expr::Column("col_2".to_owned())],
+                    vec!["col_1"],
+                    Some(AstWindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start: WindowFrameBound::Preceding(1),
+                        end: WindowFrameBound::Following(1),
+                    })
+                ),
+                alias: "moving_sum".to_owned(),
+            }]
+        )),
+        Ok(UntypedQuery::Select(UntypedSelectQuery {
+            full_table_name: Some(FullTableName::from((&SCHEMA, &TABLE))),
+            subquery: None,
+            projection_items: vec![UntypedTree::Window(UntypedWindow {
+                name: "sum".to_owned(),
+                args: vec![column("col_2", 1)],
+                partition_by: vec![],
+                order_by: vec![UntypedSortKey {
+                    expr: column("col_1", 0),
+                    ascending: true,
+                    nulls_first: false,
+                }],
+                frame: WindowFrame {
+                    units: FrameUnits::Rows,
+                    start: FrameBound::Preceding(1),
+                    end: FrameBound::Following(1),
+                },
+            })],
+            column_names: vec!["moving_sum".to_owned()],
+            filter: None,
+            distinct: None,
+        }))
+    );
+}
+
+#[test]
+fn non_existent_window_function() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            TABLE,
+            vec![SelectItem::UnnamedExpr(window("percent_rank", vec![], vec![], None))]
+        )),
+        Err(AnalysisError::function_does_not_exist("percent_rank"))
+    );
+}
+
+#[test]
+fn window_function_with_wrong_number_of_arguments() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            TABLE,
+            vec![SelectItem::UnnamedExpr(window(
+                "row_number",
+                vec![Expr::Column("col_1".to_owned())],
+                vec![],
+                None
+            ))]
+        )),
+        Err(AnalysisError::function_does_not_exist("row_number"))
+    );
+}
+
+#[test]
+fn nested_window_functions() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            TABLE,
+            vec![SelectItem::UnnamedExpr(window(
+                "sum",
+                vec![window("row_number", vec![], vec![], None)],
+                vec![],
+                None
+            ))]
+        )),
+        Err(AnalysisError::windowing_error("window function calls cannot be nested"))
+    );
+}
+
+#[test]
+fn window_function_in_where_clause() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::Wildcard],
+            schema_name: Some(SCHEMA.to_owned()),
+            table_name: Some(TABLE.to_owned()),
+            where_clause: Some(Expr::BinaryOp {
+                left: Box::new(window("row_number", vec![], vec![], None)),
+                op: BinaryOperator::Eq,
+                right: Box::new(Expr::Value(number(1))),
+            }),
+        })),
+        Err(AnalysisError::windowing_error("window functions are not allowed in WHERE"))
+    );
+}
+
+#[test]
+fn window_function_in_update() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(Query::Update(UpdateQuery {
+            schema_name: Some(SCHEMA.to_owned()),
+            table_name: TABLE.to_owned(),
+            assignments: vec![Assignment {
+                column: "col_1".to_owned(),
+                value: window("row_number", vec![], vec![], None),
+            }],
+            where_clause: None,
+        })),
+        Err(AnalysisError::windowing_error("window functions are not allowed in UPDATE"))
+    );
+}
+
+#[test]
+fn range_with_offset_requires_single_order_column() {
+    let db = Database::new("");
+    let analyzer = analyzer_with_table(&db);
+
+    assert_eq!(
+        analyzer.analyze(select_with_columns(
+            SCHEMA,
+            TABLE,
+            vec![SelectItem::UnnamedExpr(window(
+                "count",
+                vec![],
+                vec!["col_1", "col_2"],
+                Some(AstWindowFrame {
+                    units: WindowFrameUnits::Range,
+                    start: WindowFrameBound::Preceding(2),
+                    end: WindowFrameBound::CurrentRow,
+                })
+            ))]
+        )),
+        Err(AnalysisError::windowing_error(
+            "RANGE with offset PRECEDING/FOLLOWING requires exactly one ORDER BY column"
+        ))
+    );
+}
@@ -0,0 +1,205 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_definition_execution_plan::CreateSequenceQuery;
+use data_manipulation_untyped_tree::UntypedValue;
+use definition::ColumnIdentity;
+
+const SEQUENCE: &str = "sequence_name";
+
+fn create_sequence_ops() -> SchemaChange {
+    SchemaChange::CreateSequence(CreateSequenceQuery {
+        full_sequence_name: FullTableName::from((&SCHEMA, &SEQUENCE)),
+        start: 1,
+        increment: 1,
+        min_value: 1,
+        max_value: i64::MAX,
+        if_not_exists: false,
+    })
+}
+
+fn create_table_with_identity_ops(identity: ColumnIdentity) -> SchemaChange {
+    SchemaChange::CreateTable(CreateTableQuery {
+        full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+        column_defs: vec![
+            ColumnInfo {
+                name: "id".to_owned(),
+                sql_type: SqlType::integer(),
+                identity: Some(identity),
+            },
+            ColumnInfo {
+                name: "col".to_owned(),
+                sql_type: SqlType::small_int(),
+                identity: None,
+            },
+        ],
+        if_not_exists: false,
+    })
+}
+
+fn function(name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function { name: name.to_owned(), args }
+}
+
+fn insert(columns: Vec<&str>, values: Vec<Expr>) -> Query {
+    Query::Insert(InsertQuery {
+        schema_name: Some(SCHEMA.to_owned()),
+        table_name: TABLE.to_owned(),
+        columns: columns.into_iter().map(ToOwned::to_owned).collect(),
+        source: InsertSource::Values(Values(vec![values])),
+    })
+}
+
+#[test]
+fn insert_next_value_of_sequence() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::big_int())])).unwrap();
+    catalog.apply(create_sequence_ops()).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(insert(vec![], vec![function("nextval", vec![string("schema_name.sequence_name")])])),
+        Ok(UntypedQuery::Insert(UntypedInsertQuery {
+            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            values: vec![vec![Some(UntypedTree::Sequence {
+                function: SequenceFunction::NextVal,
+                sequence: catalog.sequence(&FullTableName::from((&SCHEMA, &SEQUENCE))).unwrap(),
+                args: vec![],
+            })]],
+        }))
+    );
+}
+
+#[test]
+fn set_value_of_sequence() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::big_int())])).unwrap();
+    catalog.apply(create_sequence_ops()).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(insert(
+            vec![],
+            vec![function("setval", vec![string("schema_name.sequence_name"), Expr::Value(number(10))])]
+        )),
+        Ok(UntypedQuery::Insert(UntypedInsertQuery {
+            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            values: vec![vec![Some(UntypedTree::Sequence {
+                function: SequenceFunction::SetVal,
+                sequence: catalog.sequence(&FullTableName::from((&SCHEMA, &SEQUENCE))).unwrap(),
+                args: vec![UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(10)))],
+            })]],
+        }))
+    );
+}
+
+#[test]
+fn next_value_of_nonexistent_sequence() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::big_int())])).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(insert(vec![], vec![function("nextval", vec![string("non_existent")])])),
+        Err(AnalysisError::sequence_does_not_exist("non_existent"))
+    );
+}
+
+#[test]
+fn unknown_function() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_ops(SCHEMA, TABLE, vec![("col", SqlType::big_int())])).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(insert(vec![], vec![function("unknown", vec![])])),
+        Err(AnalysisError::function_does_not_exist("unknown"))
+    );
+}
+
+#[test]
+fn insert_into_generated_always_column() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_with_identity_ops(ColumnIdentity::Always)).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(insert(vec!["id", "col"], vec![Expr::Value(number(1)), Expr::Value(number(2))])),
+        Err(AnalysisError::cannot_insert_into_generated_column("id"))
+    );
+}
+
+#[test]
+fn insert_into_generated_by_default_column() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_with_identity_ops(ColumnIdentity::ByDefault)).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(insert(vec!["col"], vec![Expr::Value(number(2))])),
+        Ok(UntypedQuery::Insert(UntypedInsertQuery {
+            full_table_name: FullTableName::from((&SCHEMA, &TABLE)),
+            values: vec![vec![None, Some(UntypedTree::Item(UntypedItem::Const(UntypedValue::Int(2))))]],
+        }))
+    );
+}
+
+#[test]
+fn update_generated_always_column() {
+    let db = Database::new("");
+    let transaction = db.transaction();
+    let catalog = CatalogHandler::from(transaction.clone());
+    catalog.apply(create_schema_ops(SCHEMA)).unwrap();
+    catalog.apply(create_table_with_identity_ops(ColumnIdentity::Always)).unwrap();
+
+    let analyzer = QueryAnalyzer::from(transaction);
+
+    assert_eq!(
+        analyzer.analyze(Query::Update(UpdateQuery {
+            schema_name: Some(SCHEMA.to_owned()),
+            table_name: TABLE.to_owned(),
+            assignments: vec![Assignment {
+                column: "id".to_owned(),
+                value: Expr::Value(number(10)),
+            }],
+            where_clause: None,
+        })),
+        Err(AnalysisError::cannot_update_generated_column("id"))
+    );
+}
@@ -0,0 +1,99 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use catalog::PG_CATALOG;
+use query_ast::{InsertSource, SelectItem, SelectQuery, Values};
+
+#[test]
+fn select_from_system_view() {
+    let db = Database::new("");
+    let analyzer = QueryAnalyzer::from(db.transaction());
+
+    assert!(matches!(
+        analyzer.analyze(Query::Select(SelectQuery {
+            distinct: None,
+            select_items: vec![SelectItem::Wildcard],
+            schema_name: Some(PG_CATALOG.to_owned()),
+            table_name: Some("pg_class".to_owned()),
+            where_clause: None,
+        })),
+        Ok(UntypedQuery::Select(_))
+    ));
+}
+
+#[test]
+fn insert_into_system_view() {
+    let db = Database::new("");
+    let analyzer = QueryAnalyzer::from(db.transaction());
+
+    assert_eq!(
+        analyzer.analyze(Query::Insert(InsertQuery {
+            schema_name: Some(PG_CATALOG.to_owned()),
+            table_name: "pg_namespace".to_owned(),
+            columns: vec![],
+            source: InsertSource::Values(Values(vec![vec![Expr::Value(Value::Int(1)), string("new_schema")]])),
+        })),
+        Err(AnalysisError::permission_denied_for_table("pg_namespace"))
+    );
+}
+
+#[test]
+fn update_system_view() {
+    let db = Database::new("");
+    let analyzer = QueryAnalyzer::from(db.transaction());
+
+    assert_eq!(
+        analyzer.analyze(Query::Update(UpdateQuery {
+            schema_name: Some("information_schema".to_owned()),
+            table_name: "tables".to_owned(),
+            assignments: vec![Assignment {
+                column: "table_name".to_owned(),
+                value: string("new_name"),
+            }],
+            where_clause: None,
+        })),
+        Err(AnalysisError::permission_denied_for_table("tables"))
+    );
+}
+
+#[test]
+fn delete_from_system_view() {
+    let db = Database::new("");
+    let analyzer = QueryAnalyzer::from(db.transaction());
+
+    assert_eq!(
+        analyzer.analyze(Query::Delete(DeleteQuery {
+            schema_name: Some(PG_CATALOG.to_owned()),
+            table_name: "pg_attribute".to_owned(),
+            where_clause: None,
+        })),
+        Err(AnalysisError::permission_denied_for_table("pg_attribute"))
+    );
+}
+
+#[test]
+fn non_existent_system_view() {
+    let db = Database::new("");
+    let analyzer = QueryAnalyzer::from(db.transaction());
+
+    assert_eq!(
+        analyzer.analyze(Query::Delete(DeleteQuery {
+            schema_name: Some(PG_CATALOG.to_owned()),
+            table_name: "pg_non_existent".to_owned(),
+            where_clause: None,
+        })),
+        Err(AnalysisError::table_does_not_exist("pg_catalog.pg_non_existent"))
+    );
+}
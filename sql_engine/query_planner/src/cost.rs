@@ -0,0 +1,340 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bigdecimal::ToPrimitive;
+use binary::BinaryValue;
+use catalog::{CatalogHandler, ColumnStatistics, TableStatistics};
+use data_manipulation_operators::{BiLogical, BiOperator, Comparison, IsPredicate, Quantifier, UnOperator};
+use data_manipulation_typed_tree::{TypedItem, TypedTree, TypedValue};
+use definition::FullTableName;
+use std::{
+    cmp::Ordering,
+    sync::{Arc, Mutex},
+};
+use types::SqlTypeFamily;
+
+// the same defaults as PostgreSQL uses when a column has no statistics
+const DEFAULT_EQUALITY_SELECTIVITY: f64 = 0.005;
+const DEFAULT_INEQUALITY_SELECTIVITY: f64 = 1.0 / 3.0;
+const DEFAULT_RANGE_SELECTIVITY: f64 = 0.005;
+
+/// Statistics of tables planned in a session, they are read from the catalog again
+/// once it changes, e.g. when tables are analyzed
+#[derive(Debug, Clone, Default)]
+pub struct StatisticsCache {
+    inner: Arc<Mutex<CachedStatistics>>,
+}
+
+#[derive(Debug, Default)]
+struct CachedStatistics {
+    catalog_version: u64,
+    tables: Vec<(FullTableName, Option<TableStatistics>)>,
+}
+
+impl StatisticsCache {
+    pub fn table_statistics(&self, catalog: &CatalogHandler, full_table_name: &FullTableName) -> Option<TableStatistics> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.catalog_version != catalog.version() {
+            inner.catalog_version = catalog.version();
+            inner.tables.clear();
+        }
+        match inner.tables.iter().find(|(name, _statistics)| name == full_table_name) {
+            Some((_name, statistics)) => statistics.clone(),
+            None => {
+                let statistics = catalog.table_statistics(full_table_name);
+                inner.tables.push((full_table_name.clone(), statistics.clone()));
+                statistics
+            }
+        }
+    }
+}
+
+/// Estimates what part of table records pass a predicate using statistics gathered by `ANALYZE`
+pub struct CostModel {
+    statistics: Option<TableStatistics>,
+}
+
+impl CostModel {
+    pub fn new(statistics: Option<TableStatistics>) -> CostModel {
+        CostModel { statistics }
+    }
+
+    pub fn selectivity(&self, predicate: &TypedTree) -> f64 {
+        let selectivity = match predicate {
+            TypedTree::BiOp {
+                left,
+                op: BiOperator::Logical(BiLogical::And),
+                right,
+                ..
+            } => self.selectivity(left) * self.selectivity(right),
+            TypedTree::BiOp {
+                left,
+                op: BiOperator::Logical(BiLogical::Or),
+                right,
+                ..
+            } => {
+                let left = self.selectivity(left);
+                let right = self.selectivity(right);
+                left + right - left * right
+            }
+            TypedTree::UnOp {
+                op: UnOperator::LogicalNot,
+                item,
+            } => 1.0 - self.selectivity(item),
+            TypedTree::UnOp {
+                op: UnOperator::Is(IsPredicate::Null),
+                item,
+            } => match &**item {
+                TypedTree::Item(TypedItem::Column { name, .. }) => self.null_fraction(name),
+                _ => DEFAULT_EQUALITY_SELECTIVITY,
+            },
+            TypedTree::UnOp {
+                op: UnOperator::Is(IsPredicate::NotNull),
+                item,
+            } => match &**item {
+                TypedTree::Item(TypedItem::Column { name, .. }) => 1.0 - self.null_fraction(name),
+                _ => 1.0 - DEFAULT_EQUALITY_SELECTIVITY,
+            },
+            TypedTree::BiOp {
+                left,
+                op: BiOperator::Comparison(comparison),
+                right,
+                ..
+            } => match (&**left, &**right) {
+                (TypedTree::Item(TypedItem::Column { name, .. }), TypedTree::Item(TypedItem::Const(value))) => {
+                    self.comparison_selectivity(name, *comparison, value)
+                }
+                (TypedTree::Item(TypedItem::Const(value)), TypedTree::Item(TypedItem::Column { name, .. })) => {
+                    self.comparison_selectivity(name, comparison.flip(), value)
+                }
+                _ => default_selectivity(*comparison),
+            },
+            // items of a list are estimated as independent comparisons joined with OR for ANY and with AND for ALL
+            TypedTree::Quantified {
+                left,
+                op: BiOperator::Comparison(comparison),
+                quantifier,
+                list,
+                ..
+            } => {
+                let selectivities = list.iter().map(|item| match (&**left, item) {
+                    (TypedTree::Item(TypedItem::Column { name, .. }), TypedTree::Item(TypedItem::Const(value))) => {
+                        self.comparison_selectivity(name, *comparison, value)
+                    }
+                    _ => default_selectivity(*comparison),
+                });
+                match quantifier {
+                    Quantifier::Any => 1.0 - selectivities.map(|selectivity| 1.0 - selectivity).product::<f64>(),
+                    Quantifier::All => selectivities.product(),
+                }
+            }
+            TypedTree::Between {
+                item, low, high, negated, ..
+            } => {
+                let between = match (&**item, &**low, &**high) {
+                    (
+                        TypedTree::Item(TypedItem::Column { name, .. }),
+                        TypedTree::Item(TypedItem::Const(low)),
+                        TypedTree::Item(TypedItem::Const(high)),
+                    ) => self.range_selectivity(name, low, high),
+                    _ => DEFAULT_RANGE_SELECTIVITY,
+                };
+                if *negated {
+                    1.0 - between
+                } else {
+                    between
+                }
+            }
+            TypedTree::Item(TypedItem::Column {
+                name,
+                sql_type: SqlTypeFamily::Bool,
+                ..
+            }) => self.comparison_selectivity(name, Comparison::Eq, &TypedValue::Bool(true)),
+            _ => DEFAULT_INEQUALITY_SELECTIVITY,
+        };
+        selectivity.clamp(0.0, 1.0)
+    }
+
+    /// splits the predicate into conditions that are all have to be true, conditions that reject
+    /// more rows for less work go first so that the rest of them are not evaluated for these rows
+    pub fn order_conditions(&self, predicate: TypedTree) -> Vec<TypedTree> {
+        let mut conditions = vec![];
+        conjuncts(predicate, &mut conditions);
+        let mut ranked = conditions
+            .into_iter()
+            .map(|condition| ((self.selectivity(&condition) - 1.0) / evaluation_cost(&condition), condition))
+            .collect::<Vec<_>>();
+        ranked.sort_by(|(left, _), (right, _)| left.partial_cmp(right).unwrap_or(Ordering::Equal));
+        ranked.into_iter().map(|(_rank, condition)| condition).collect()
+    }
+
+    fn null_fraction(&self, column_name: &str) -> f64 {
+        match self.statistics.as_ref().and_then(|statistics| statistics.column(column_name)) {
+            Some(column) => column.null_fraction,
+            None => DEFAULT_EQUALITY_SELECTIVITY,
+        }
+    }
+
+    // both bounds exclude NULLs, so they are counted only once
+    fn range_selectivity(&self, column_name: &str, low: &TypedValue, high: &TypedValue) -> f64 {
+        let column = match self.statistics.as_ref().and_then(|statistics| statistics.column(column_name)) {
+            Some(column) => column,
+            None => return DEFAULT_RANGE_SELECTIVITY,
+        };
+        (self.comparison_selectivity(column_name, Comparison::GtEq, low) + self.comparison_selectivity(column_name, Comparison::LtEq, high)
+            - (1.0 - column.null_fraction))
+            .max(0.0)
+    }
+
+    fn comparison_selectivity(&self, column_name: &str, comparison: Comparison, value: &TypedValue) -> f64 {
+        let column = match self.statistics.as_ref().and_then(|statistics| statistics.column(column_name)) {
+            Some(column) => column,
+            None => return default_selectivity(comparison),
+        };
+        let selectivity = match comparison {
+            Comparison::Eq | Comparison::IsNotDistinctFrom => equal(column, value),
+            Comparison::IsDistinctFrom => equal(column, value).map(|equal| 1.0 - equal),
+            Comparison::NotEq => equal(column, value).map(|equal| 1.0 - equal - column.null_fraction),
+            Comparison::Lt => below(column, value, false),
+            Comparison::LtEq => below(column, value, true),
+            Comparison::Gt => below(column, value, true).map(|below| 1.0 - column.null_fraction - below),
+            Comparison::GtEq => below(column, value, false).map(|below| 1.0 - column.null_fraction - below),
+        };
+        selectivity.unwrap_or_else(|| default_selectivity(comparison))
+    }
+}
+
+fn conjuncts(predicate: TypedTree, conditions: &mut Vec<TypedTree>) {
+    match predicate {
+        TypedTree::BiOp {
+            left,
+            op: BiOperator::Logical(BiLogical::And),
+            right,
+            ..
+        } => {
+            conjuncts(*left, conditions);
+            conjuncts(*right, conditions);
+        }
+        condition => conditions.push(condition),
+    }
+}
+
+// every operator and function is a unit of work as `cpu_operator_cost` is in PostgreSQL,
+// reading a column or a constant is counted as a unit too so that the cost is never zero
+fn evaluation_cost(tree: &TypedTree) -> f64 {
+    let all = |trees: &[TypedTree]| trees.iter().map(evaluation_cost).sum::<f64>();
+    match tree {
+        TypedTree::Item(_) => 1.0,
+        TypedTree::UnOp { item, .. } => 1.0 + evaluation_cost(item),
+        TypedTree::BiOp { left, right, .. } => 1.0 + evaluation_cost(left) + evaluation_cost(right),
+        TypedTree::Sequence { args, .. } | TypedTree::Function { args, .. } | TypedTree::Conditional { args, .. } => 1.0 + all(args),
+        TypedTree::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+            ..
+        } => {
+            operand.as_deref().map(evaluation_cost).unwrap_or_default()
+                + all(conditions)
+                + all(results)
+                + else_result.as_deref().map(evaluation_cost).unwrap_or_default()
+        }
+        TypedTree::Quantified { left, list, .. } => evaluation_cost(left) + list.len() as f64 + all(list),
+        TypedTree::Between { item, low, high, .. } => 2.0 + evaluation_cost(item) + evaluation_cost(low) + evaluation_cost(high),
+        TypedTree::Window(_) => 1.0,
+    }
+}
+
+fn default_selectivity(comparison: Comparison) -> f64 {
+    match comparison {
+        Comparison::Eq | Comparison::IsNotDistinctFrom => DEFAULT_EQUALITY_SELECTIVITY,
+        Comparison::NotEq | Comparison::IsDistinctFrom => 1.0 - DEFAULT_EQUALITY_SELECTIVITY,
+        _ => DEFAULT_INEQUALITY_SELECTIVITY,
+    }
+}
+
+// fraction of rows that are neither NULL nor one of the most common values
+fn rest_fraction(column: &ColumnStatistics) -> f64 {
+    let most_common = column.most_common_values.iter().map(|(_, frequency)| frequency).sum::<f64>();
+    (1.0 - column.null_fraction - most_common).max(0.0)
+}
+
+fn equal(column: &ColumnStatistics, value: &TypedValue) -> Option<f64> {
+    for (common, frequency) in column.most_common_values.iter() {
+        if compare(common, value)? == Ordering::Equal {
+            return Some(*frequency);
+        }
+    }
+    let other_distinct = column.distinct.saturating_sub(column.most_common_values.len());
+    if other_distinct == 0 {
+        Some(0.0)
+    } else {
+        Some(rest_fraction(column) / other_distinct as f64)
+    }
+}
+
+fn below(column: &ColumnStatistics, value: &TypedValue, inclusive: bool) -> Option<f64> {
+    let mut selectivity = 0.0;
+    for (common, frequency) in column.most_common_values.iter() {
+        match compare(common, value)? {
+            Ordering::Less => selectivity += frequency,
+            Ordering::Equal if inclusive => selectivity += frequency,
+            _ => {}
+        }
+    }
+    let bounds = &column.histogram_bounds;
+    let histogram = if bounds.len() < 2 {
+        DEFAULT_INEQUALITY_SELECTIVITY
+    } else if compare(&bounds[0], value)? != Ordering::Less {
+        0.0
+    } else if compare(&bounds[bounds.len() - 1], value)? != Ordering::Greater {
+        1.0
+    } else {
+        let buckets = (bounds.len() - 1) as f64;
+        let mut bucket = 0;
+        while compare(&bounds[bucket + 1], value)? != Ordering::Greater {
+            bucket += 1;
+        }
+        let within = match (as_number(&bounds[bucket]), as_number(&bounds[bucket + 1]), value) {
+            (Some(low), Some(high), TypedValue::Num { value, .. }) if high > low => (value.to_f64()? - low) / (high - low),
+            _ => 0.5,
+        };
+        (bucket as f64 + within) / buckets
+    };
+    Some(selectivity + histogram * rest_fraction(column))
+}
+
+fn as_number(value: &BinaryValue) -> Option<f64> {
+    match value {
+        BinaryValue::Int16(value) => Some(*value as f64),
+        BinaryValue::Int32(value) => Some(*value as f64),
+        BinaryValue::Int64(value) => Some(*value as f64),
+        BinaryValue::Float32(value) => Some(**value as f64),
+        BinaryValue::Float64(value) => Some(**value),
+        _ => None,
+    }
+}
+
+fn compare(statistic: &BinaryValue, value: &TypedValue) -> Option<Ordering> {
+    match (statistic, value) {
+        (BinaryValue::Bool(statistic), TypedValue::Bool(value)) => Some(statistic.cmp(value)),
+        (BinaryValue::String(statistic), TypedValue::String(value)) => Some(statistic.cmp(value)),
+        (statistic, TypedValue::Num { value, .. }) => as_number(statistic)?.partial_cmp(&value.to_f64()?),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests;
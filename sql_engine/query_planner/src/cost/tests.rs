@@ -0,0 +1,235 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use bigdecimal::BigDecimal;
+use data_manipulation_operators::BiArithmetic;
+
+fn column(name: &str) -> Box<TypedTree> {
+    Box::new(TypedTree::Item(TypedItem::Column {
+        name: name.to_owned(),
+        sql_type: SqlTypeFamily::Integer,
+        index: 0,
+    }))
+}
+
+fn number(value: i32) -> Box<TypedTree> {
+    Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Num {
+        value: BigDecimal::from(value),
+        type_family: SqlTypeFamily::Integer,
+    })))
+}
+
+fn compare(left: Box<TypedTree>, comparison: Comparison, right: Box<TypedTree>) -> TypedTree {
+    TypedTree::BiOp {
+        type_family: SqlTypeFamily::Bool,
+        left,
+        op: BiOperator::Comparison(comparison),
+        right,
+    }
+}
+
+fn logical(left: TypedTree, op: BiLogical, right: TypedTree) -> TypedTree {
+    TypedTree::BiOp {
+        type_family: SqlTypeFamily::Bool,
+        left: Box::new(left),
+        op: BiOperator::Logical(op),
+        right: Box::new(right),
+    }
+}
+
+// 100 rows: 10% of NULLs, value 5 in 40% of rows, the rest is evenly spread over 0..100
+fn statistics() -> Option<TableStatistics> {
+    Some(TableStatistics {
+        rows: 100,
+        columns: vec![ColumnStatistics {
+            name: "col".to_owned(),
+            null_fraction: 0.1,
+            distinct: 51,
+            most_common_values: vec![(BinaryValue::from(5i32), 0.4)],
+            histogram_bounds: (0..=100).step_by(10).map(BinaryValue::from).collect(),
+        }],
+    })
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "expected {} but was {}", expected, actual);
+}
+
+#[test]
+fn defaults_without_statistics() {
+    let cost_model = CostModel::new(None);
+
+    assert_close(cost_model.selectivity(&compare(column("col"), Comparison::Eq, number(5))), 0.005);
+    assert_close(cost_model.selectivity(&compare(column("col"), Comparison::NotEq, number(5))), 0.995);
+    assert_close(cost_model.selectivity(&compare(column("col"), Comparison::Gt, number(5))), 1.0 / 3.0);
+}
+
+#[test]
+fn equality_with_most_common_value() {
+    let cost_model = CostModel::new(statistics());
+
+    assert_close(cost_model.selectivity(&compare(column("col"), Comparison::Eq, number(5))), 0.4);
+    assert_close(cost_model.selectivity(&compare(number(5), Comparison::Eq, column("col"))), 0.4);
+}
+
+#[test]
+fn equality_with_other_value() {
+    let cost_model = CostModel::new(statistics());
+
+    assert_close(cost_model.selectivity(&compare(column("col"), Comparison::Eq, number(42))), 0.5 / 50.0);
+    assert_close(
+        cost_model.selectivity(&compare(column("col"), Comparison::NotEq, number(42))),
+        1.0 - 0.1 - 0.01,
+    );
+}
+
+#[test]
+fn range_uses_histogram() {
+    let cost_model = CostModel::new(statistics());
+
+    assert_close(
+        cost_model.selectivity(&compare(column("col"), Comparison::Lt, number(25))),
+        0.4 + 0.25 * 0.5,
+    );
+    assert_close(cost_model.selectivity(&compare(column("col"), Comparison::Gt, number(25))), 0.75 * 0.5);
+    assert_close(
+        cost_model.selectivity(&compare(number(25), Comparison::Gt, column("col"))),
+        0.4 + 0.25 * 0.5,
+    );
+}
+
+#[test]
+fn range_outside_of_histogram() {
+    let cost_model = CostModel::new(statistics());
+
+    assert_close(cost_model.selectivity(&compare(column("col"), Comparison::Lt, number(-1))), 0.0);
+    assert_close(cost_model.selectivity(&compare(column("col"), Comparison::LtEq, number(200))), 0.9);
+}
+
+#[test]
+fn logical_operators() {
+    let cost_model = CostModel::new(statistics());
+    let equal = || compare(column("col"), Comparison::Eq, number(5));
+    let less = || compare(column("col"), Comparison::Lt, number(25));
+
+    assert_close(cost_model.selectivity(&logical(equal(), BiLogical::And, less())), 0.4 * 0.525);
+    assert_close(
+        cost_model.selectivity(&logical(equal(), BiLogical::Or, less())),
+        0.4 + 0.525 - 0.4 * 0.525,
+    );
+    assert_close(
+        cost_model.selectivity(&TypedTree::UnOp {
+            op: UnOperator::LogicalNot,
+            item: Box::new(equal()),
+        }),
+        0.6,
+    );
+}
+
+#[test]
+fn unknown_column() {
+    let cost_model = CostModel::new(statistics());
+
+    assert_close(cost_model.selectivity(&compare(column("other"), Comparison::Eq, number(5))), 0.005);
+}
+
+#[test]
+fn null_tests_use_null_fraction() {
+    let cost_model = CostModel::new(statistics());
+    let is_null = |predicate| TypedTree::UnOp {
+        op: UnOperator::Is(predicate),
+        item: column("col"),
+    };
+
+    assert_close(cost_model.selectivity(&is_null(IsPredicate::Null)), 0.1);
+    assert_close(cost_model.selectivity(&is_null(IsPredicate::NotNull)), 0.9);
+    assert_close(
+        cost_model.selectivity(&compare(column("col"), Comparison::IsDistinctFrom, number(5))),
+        0.6,
+    );
+}
+
+#[test]
+fn list_and_range_predicates() {
+    let cost_model = CostModel::new(statistics());
+    let in_list = TypedTree::Quantified {
+        type_family: SqlTypeFamily::Integer,
+        left: column("col"),
+        op: BiOperator::Comparison(Comparison::Eq),
+        quantifier: Quantifier::Any,
+        list: vec![*number(5), *number(42)],
+    };
+    let between = TypedTree::Between {
+        type_family: SqlTypeFamily::Integer,
+        item: column("col"),
+        low: number(0),
+        high: number(100),
+        negated: false,
+    };
+
+    assert_close(cost_model.selectivity(&in_list), 1.0 - 0.6 * 0.99);
+    assert_close(cost_model.selectivity(&between), 0.9);
+    assert_close(CostModel::new(None).selectivity(&between), 0.005);
+}
+
+#[test]
+fn selective_conditions_are_evaluated_first() {
+    let cost_model = CostModel::new(statistics());
+    let not_equal = compare(column("col"), Comparison::NotEq, number(42));
+    let equal = compare(column("col"), Comparison::Eq, number(42));
+    let less = compare(column("col"), Comparison::Lt, number(25));
+
+    assert_eq!(
+        cost_model.order_conditions(logical(
+            logical(not_equal.clone(), BiLogical::And, less.clone()),
+            BiLogical::And,
+            equal.clone()
+        )),
+        vec![equal, less, not_equal]
+    );
+}
+
+#[test]
+fn cheap_conditions_are_evaluated_first() {
+    let cost_model = CostModel::new(None);
+    let expensive = compare(
+        Box::new(TypedTree::BiOp {
+            type_family: SqlTypeFamily::Integer,
+            left: column("col"),
+            op: BiOperator::Arithmetic(BiArithmetic::Mul),
+            right: number(2),
+        }),
+        Comparison::Gt,
+        number(5),
+    );
+    let cheap = compare(column("col"), Comparison::Gt, number(5));
+
+    assert_eq!(
+        cost_model.order_conditions(logical(expensive.clone(), BiLogical::And, cheap.clone())),
+        vec![cheap, expensive]
+    );
+}
+
+#[test]
+fn conditions_of_the_same_rank_keep_their_order() {
+    let cost_model = CostModel::new(None);
+    let first = compare(column("col"), Comparison::Gt, number(5));
+    let second = compare(column("col"), Comparison::Lt, number(10));
+
+    assert_eq!(
+        cost_model.order_conditions(logical(first.clone(), BiLogical::And, second.clone())),
+        vec![first, second]
+    );
+}
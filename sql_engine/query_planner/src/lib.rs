@@ -12,78 +12,231 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod cost;
+
+use crate::cost::CostModel;
+pub use crate::cost::StatisticsCache;
 use catalog::CatalogHandler;
+use data_manipulation_operators::SetOperator;
 use data_manipulation_query_plan::{
-    ConstraintValidator, DeleteQueryPlan, DynamicValues, Filter, FullTableScan, InsertQueryPlan, Projection, QueryPlan, Repeater, SelectQueryPlan,
-    StaticExpressionEval, StaticValues, TableRecordKeys, UpdateQueryPlan,
+    Append, ConstraintValidator, Deadline, DeleteQueryPlan, DynamicValues, Filter, Flow, FullTableScan, HashDistinct, HashSetOp, InsertQueryPlan,
+    Instrumented, Projection, QueryPlan, Repeater, SelectQueryPlan, SingleRow, SortDistinct, StaticExpressionEval, StaticValues, SubqueryScan,
+    TableRecordKeys, UpdateQueryPlan, WindowAgg,
 };
-use data_manipulation_typed_queries::TypedQuery;
+use data_manipulation_typed_queries::{TypedDistinct, TypedQuery};
 use data_manipulation_typed_tree::{TypedItem, TypedTree};
+use definition::FullTableName;
 use storage::Transaction;
+use types::SqlTypeFamily;
 
 pub struct QueryPlanner<'p> {
     transaction: Transaction<'p>,
     catalog: CatalogHandler<'p>,
+    deadline: Deadline,
+    statistics: StatisticsCache,
 }
 
 impl<'p> From<Transaction<'p>> for QueryPlanner<'p> {
     fn from(transaction: Transaction<'p>) -> QueryPlanner<'p> {
+        QueryPlanner::new(transaction, Deadline::default(), StatisticsCache::default())
+    }
+}
+
+impl<'p> QueryPlanner<'p> {
+    /// planned statements are canceled when the `deadline` passes, `statistics` of tables
+    /// are shared between plans of a session
+    pub fn new(transaction: Transaction<'p>, deadline: Deadline, statistics: StatisticsCache) -> QueryPlanner<'p> {
         QueryPlanner {
             transaction: transaction.clone(),
             catalog: CatalogHandler::from(transaction),
+            deadline,
+            statistics,
         }
     }
-}
 
-impl<'p> QueryPlanner<'p> {
     pub fn plan(&self, query: TypedQuery) -> QueryPlan {
+        self.build(query, false)
+    }
+
+    /// every operator of the plan collects execution statistics for `EXPLAIN ANALYZE`
+    pub fn plan_instrumented(&self, query: TypedQuery) -> QueryPlan {
+        self.build(query, true)
+    }
+
+    /// conditions of the filter in the order they are evaluated in and the part of rows that pass them
+    fn filter_conditions(&self, predicate: Option<TypedTree>, full_table_name: Option<&FullTableName>) -> (Vec<TypedTree>, f64) {
+        match predicate {
+            None => (vec![], 1.0),
+            Some(predicate) => {
+                let statistics = full_table_name.and_then(|full_table_name| self.statistics.table_statistics(&self.catalog, full_table_name));
+                let cost_model = CostModel::new(statistics);
+                let selectivity = cost_model.selectivity(&predicate);
+                (cost_model.order_conditions(predicate), selectivity)
+            }
+        }
+    }
+
+    fn build_select(&self, query: TypedQuery, instrumented: bool) -> SelectQueryPlan {
+        match self.build(query, instrumented) {
+            QueryPlan::Select(plan) => plan,
+            _ => unreachable!("only queries that return rows could be sides of a set operation"),
+        }
+    }
+
+    fn build(&self, query: TypedQuery, instrumented: bool) -> QueryPlan {
         match query {
             TypedQuery::Insert(insert) => {
                 let table = self.transaction.lookup_table_ref(&insert.full_table_name);
-                QueryPlan::Insert(InsertQueryPlan::new(
-                    ConstraintValidator::new(
-                        StaticExpressionEval::new(StaticValues::new(insert.values)),
-                        self.catalog.columns(&insert.full_table_name),
-                    ),
-                    table,
-                ))
+                let values = instrument(StaticValues::new(insert.values), instrumented);
+                let evaluated = instrument(StaticExpressionEval::new(values), instrumented);
+                let validated = instrument(
+                    ConstraintValidator::new(evaluated, self.catalog.columns(&insert.full_table_name)),
+                    instrumented,
+                );
+                let defaults = self
+                    .catalog
+                    .column_defaults(&insert.full_table_name)
+                    .into_iter()
+                    .zip(self.catalog.columns(&insert.full_table_name))
+                    .map(|(sequence, (_name, type_family))| sequence.map(|sequence| (sequence, type_family)))
+                    .collect();
+                QueryPlan::Insert(InsertQueryPlan::new(validated, table, defaults))
             }
             TypedQuery::Delete(delete) => {
                 let table = self.transaction.lookup_table_ref(&delete.full_table_name);
-                QueryPlan::Delete(DeleteQueryPlan::new(
-                    TableRecordKeys::new(Filter::new(Projection::new(FullTableScan::new(&table)), delete.filter)),
-                    table,
-                ))
+                let scan = instrument(FullTableScan::new(&table, &self.deadline), instrumented);
+                let projection = instrument(Projection::new(scan), instrumented);
+                let (conditions, selectivity) = self.filter_conditions(delete.filter, Some(&delete.full_table_name));
+                let filter = instrument(Filter::new(projection, conditions, selectivity), instrumented);
+                let keys = instrument(TableRecordKeys::new(filter), instrumented);
+                QueryPlan::Delete(DeleteQueryPlan::new(keys, table))
             }
             TypedQuery::Update(update) => {
                 let table = self.transaction.lookup_table_ref(&update.full_table_name);
-                QueryPlan::Update(UpdateQueryPlan::new(
-                    ConstraintValidator::new(
-                        DynamicValues::new(
-                            Repeater::new(update.assignments),
-                            Filter::new(Projection::new(FullTableScan::new(&table)), update.filter),
-                        ),
-                        self.catalog.columns(&update.full_table_name),
-                    ),
-                    FullTableScan::new(&table),
-                    table,
-                ))
+                let scan = instrument(FullTableScan::new(&table, &self.deadline), instrumented);
+                let projection = instrument(Projection::new(scan), instrumented);
+                let (conditions, selectivity) = self.filter_conditions(update.filter, Some(&update.full_table_name));
+                let filter = instrument(Filter::new(projection, conditions, selectivity), instrumented);
+                let assignments = instrument(Repeater::new(update.assignments), instrumented);
+                let values = instrument(DynamicValues::new(assignments, filter), instrumented);
+                let validated = instrument(
+                    ConstraintValidator::new(values, self.catalog.columns(&update.full_table_name)),
+                    instrumented,
+                );
+                let records = instrument(FullTableScan::new(&table, &self.deadline), instrumented);
+                QueryPlan::Update(UpdateQueryPlan::new(validated, records, table))
             }
             TypedQuery::Select(select) => {
-                let table = self.transaction.lookup_table_ref(&select.full_table_name);
-                QueryPlan::Select(SelectQueryPlan::new(
-                    Filter::new(Projection::new(FullTableScan::new(&table)), select.filter),
-                    select
-                        .projection_items
-                        .into_iter()
-                        .map(|item| match item {
-                            TypedTree::Item(TypedItem::Column { name, .. }) => name,
-                            _ => unimplemented!(),
+                let column_types = match &select.full_table_name {
+                    None => vec![],
+                    Some(full_table_name) => self.catalog.columns_short(full_table_name),
+                };
+                let source = match (select.subquery, &select.full_table_name) {
+                    // subquery returns whole rows of the table
+                    (Some(subquery), _) => instrument(
+                        SubqueryScan::new(self.build_select(TypedQuery::Select(*subquery), instrumented)),
+                        instrumented,
+                    ),
+                    (None, None) => instrument(SingleRow::new(), instrumented),
+                    (None, Some(full_table_name)) => {
+                        self.catalog.refresh_system_view(full_table_name);
+                        let table = self.transaction.lookup_table_ref(full_table_name);
+                        let scan = instrument(FullTableScan::new(&table, &self.deadline), instrumented);
+                        instrument(Projection::new(scan), instrumented)
+                    }
+                };
+                let (conditions, selectivity) = self.filter_conditions(select.filter, select.full_table_name.as_ref());
+                let filter = instrument(Filter::new(source, conditions, selectivity), instrumented);
+                // columns keep their declared type, e.g. `char` or `varchar`, other items are described by their result type
+                let column_defs = select
+                    .column_names
+                    .into_iter()
+                    .zip(select.projection_items.iter())
+                    .map(|(name, item)| match item {
+                        TypedTree::Item(TypedItem::Column { index, .. }) => (name, (&column_types[*index].1).into()),
+                        item => (name, (&item.result_type().unwrap_or(SqlTypeFamily::String)).into()),
+                    })
+                    .collect();
+                // window functions are computed after filtering and before `DISTINCT`,
+                // their results are appended to the table columns of every row
+                let mut windows = vec![];
+                let projection_items = select
+                    .projection_items
+                    .into_iter()
+                    .map(|item| item.extract_windows(&mut windows, column_types.len()))
+                    .collect();
+                let distinct = select.distinct.map(|distinct| match distinct {
+                    TypedDistinct::Rows(keys) => TypedDistinct::Rows(
+                        keys.into_iter()
+                            .map(|key| key.extract_windows(&mut windows, column_types.len()))
+                            .collect(),
+                    ),
+                    TypedDistinct::On(keys) => TypedDistinct::On(
+                        keys.into_iter()
+                            .map(|key| key.extract_windows(&mut windows, column_types.len()))
+                            .collect(),
+                    ),
+                });
+                let windowed = if windows.is_empty() {
+                    filter
+                } else {
+                    instrument(WindowAgg::new(filter, windows), instrumented)
+                };
+                // `DISTINCT ON` is planned over sort as in PostgreSQL, so that rows are ordered by its expressions
+                let distinct = match distinct {
+                    None => windowed,
+                    Some(TypedDistinct::Rows(keys)) => instrument(HashDistinct::new(windowed, keys), instrumented),
+                    Some(TypedDistinct::On(keys)) => instrument(SortDistinct::new(windowed, keys), instrumented),
+                };
+                QueryPlan::Select(SelectQueryPlan::new(distinct, projection_items, column_defs))
+            }
+            TypedQuery::SetOperation(set_operation) => {
+                let left = instrument(SubqueryScan::new(self.build_select(*set_operation.left, instrumented)), instrumented);
+                let right = instrument(SubqueryScan::new(self.build_select(*set_operation.right, instrumented)), instrumented);
+                // rows of sides are the values of result columns
+                let columns = set_operation
+                    .column_names
+                    .iter()
+                    .zip(set_operation.column_types.iter())
+                    .enumerate()
+                    .map(|(index, (name, sql_type))| {
+                        TypedTree::Item(TypedItem::Column {
+                            name: name.clone(),
+                            sql_type: *sql_type,
+                            index,
                         })
-                        .collect(),
-                    self.catalog.columns_short(&select.full_table_name),
-                ))
+                    })
+                    .collect::<Vec<_>>();
+                let source = match set_operation.op {
+                    SetOperator::Union => {
+                        let append = instrument(Append::new(vec![left, right]), instrumented);
+                        if set_operation.all {
+                            append
+                        } else {
+                            instrument(HashDistinct::new(append, columns.clone()), instrumented)
+                        }
+                    }
+                    op => instrument(HashSetOp::new(left, right, op, set_operation.all), instrumented),
+                };
+                let column_defs = set_operation
+                    .column_names
+                    .into_iter()
+                    .zip(set_operation.column_types.iter())
+                    .map(|(name, sql_type)| (name, sql_type.into()))
+                    .collect();
+                QueryPlan::Select(SelectQueryPlan::new(source, columns, column_defs))
             }
         }
     }
 }
+
+fn instrument<F: Flow + 'static>(flow: Box<F>, instrumented: bool) -> Box<dyn Flow<Output = F::Output>>
+where
+    F::Output: 'static,
+{
+    if instrumented {
+        Instrumented::new(flow)
+    } else {
+        flow
+    }
+}
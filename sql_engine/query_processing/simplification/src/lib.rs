@@ -0,0 +1,24 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use data_manipulation_query_result::QueryExecutionError;
+use data_manipulation_typed_tree::TypedTree;
+
+pub struct Simplifier;
+
+impl Simplifier {
+    pub fn simplify(&self, tree: TypedTree) -> Result<TypedTree, QueryExecutionError> {
+        tree.simplify()
+    }
+}
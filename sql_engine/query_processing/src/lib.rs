@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use query_processing_simplification::*;
 pub use query_processing_type_check::*;
 pub use query_processing_type_coercion::*;
 pub use query_processing_type_inference::*;
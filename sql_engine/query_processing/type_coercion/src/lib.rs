@@ -12,12 +12,144 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use data_manipulation_typed_tree::TypedTree;
+use data_manipulation_operators::{UnOperator, WindowFunction};
+use data_manipulation_typed_tree::{TypedSortKey, TypedTree, TypedWindow};
+use types::{SqlType, SqlTypeFamily};
 
 pub struct TypeCoercion;
 
 impl TypeCoercion {
     pub fn coerce(&self, tree: TypedTree) -> TypedTree {
-        tree
+        match tree {
+            TypedTree::BiOp {
+                type_family,
+                left,
+                op,
+                right,
+            } => TypedTree::BiOp {
+                type_family,
+                left: Box::new(self.coerce(*left)),
+                op,
+                right: Box::new(self.coerce(*right)),
+            },
+            TypedTree::UnOp { op, item } => TypedTree::UnOp {
+                op,
+                item: Box::new(self.coerce(*item)),
+            },
+            TypedTree::Sequence { function, sequence, args } => TypedTree::Sequence {
+                function,
+                sequence,
+                args: args.into_iter().map(|arg| self.coerce(arg)).collect(),
+            },
+            TypedTree::Function { type_family, function, args } => TypedTree::Function {
+                type_family,
+                function,
+                args: args.into_iter().map(|arg| self.coerce(arg)).collect(),
+            },
+            TypedTree::Case {
+                type_family,
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => TypedTree::Case {
+                type_family,
+                operand: operand.map(|operand| Box::new(self.coerce(*operand))),
+                conditions: conditions.into_iter().map(|condition| self.coerce(condition)).collect(),
+                results: results.into_iter().map(|result| self.coerce_to(result, type_family)).collect(),
+                else_result: else_result.map(|else_result| Box::new(self.coerce_to(*else_result, type_family))),
+            },
+            TypedTree::Conditional { type_family, function, args } => TypedTree::Conditional {
+                type_family,
+                function,
+                args: args.into_iter().map(|arg| self.coerce_to(arg, type_family)).collect(),
+            },
+            TypedTree::Quantified {
+                type_family,
+                left,
+                op,
+                quantifier,
+                list,
+            } => TypedTree::Quantified {
+                type_family,
+                left: Box::new(self.coerce(*left)),
+                op,
+                quantifier,
+                list: list.into_iter().map(|item| self.coerce(item)).collect(),
+            },
+            TypedTree::Between {
+                type_family,
+                item,
+                low,
+                high,
+                negated,
+            } => TypedTree::Between {
+                type_family,
+                item: Box::new(self.coerce(*item)),
+                low: Box::new(self.coerce(*low)),
+                high: Box::new(self.coerce(*high)),
+                negated,
+            },
+            TypedTree::Window(window) => {
+                let function = window.function;
+                let value_type = window.args.first().and_then(TypedTree::result_type);
+                let args = window
+                    .args
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, arg)| match (function, index) {
+                        // default of lag and lead is returned in place of the value
+                        (WindowFunction::Lag, 2) | (WindowFunction::Lead, 2) => self.coerce_to(arg, value_type),
+                        _ => self.coerce(arg),
+                    })
+                    .collect();
+                TypedTree::Window(TypedWindow {
+                    args,
+                    partition_by: window.partition_by.into_iter().map(|key| self.coerce(key)).collect(),
+                    order_by: window
+                        .order_by
+                        .into_iter()
+                        .map(|key| TypedSortKey {
+                            expr: self.coerce(key.expr),
+                            ..key
+                        })
+                        .collect(),
+                    ..window
+                })
+            }
+            item @ TypedTree::Item(_) => item,
+        }
+    }
+
+    /// branches of conditional expressions and columns of set operations are cast to their common type
+    pub fn coerce_to(&self, tree: TypedTree, type_family: Option<SqlTypeFamily>) -> TypedTree {
+        let tree = self.coerce(tree);
+        match (tree.result_type(), type_family) {
+            (Some(actual), Some(expected)) if actual != expected => match sql_type(expected) {
+                Some(sql_type) => TypedTree::UnOp {
+                    op: UnOperator::Cast(sql_type),
+                    item: Box::new(tree),
+                },
+                None => tree,
+            },
+            _ => tree,
+        }
+    }
+}
+
+// values could not be cast to `timestamp with time zone` as there is no column type for it yet
+fn sql_type(type_family: SqlTypeFamily) -> Option<SqlType> {
+    match type_family {
+        SqlTypeFamily::Bool => Some(SqlType::bool()),
+        SqlTypeFamily::String => Some(SqlType::var_char(255)),
+        SqlTypeFamily::SmallInt => Some(SqlType::small_int()),
+        SqlTypeFamily::Integer => Some(SqlType::integer()),
+        SqlTypeFamily::BigInt => Some(SqlType::big_int()),
+        SqlTypeFamily::Real => Some(SqlType::real()),
+        SqlTypeFamily::Double => Some(SqlType::double_precision()),
+        SqlTypeFamily::TimestampTz => None,
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_manipulation_operators::ConditionalFunction;
+use data_manipulation_typed_tree::TypedItem;
+
+fn column(sql_type: SqlTypeFamily, index: usize) -> TypedTree {
+    TypedTree::Item(TypedItem::Column {
+        name: "col".to_owned(),
+        sql_type,
+        index,
+    })
+}
+
+#[test]
+fn branches_are_cast_to_common_type() {
+    assert_eq!(
+        TypeCoercion.coerce(TypedTree::Conditional {
+            type_family: Some(SqlTypeFamily::BigInt),
+            function: ConditionalFunction::Coalesce,
+            args: vec![
+                column(SqlTypeFamily::Integer, 0),
+                column(SqlTypeFamily::BigInt, 1),
+                TypedTree::Item(TypedItem::Null(None))
+            ],
+        }),
+        TypedTree::Conditional {
+            type_family: Some(SqlTypeFamily::BigInt),
+            function: ConditionalFunction::Coalesce,
+            args: vec![
+                TypedTree::UnOp {
+                    op: UnOperator::Cast(SqlType::big_int()),
+                    item: Box::new(column(SqlTypeFamily::Integer, 0))
+                },
+                column(SqlTypeFamily::BigInt, 1),
+                TypedTree::Item(TypedItem::Null(None))
+            ],
+        }
+    );
+}
+
+#[test]
+fn case_conditions_are_not_cast() {
+    let tree = TypedTree::Case {
+        type_family: Some(SqlTypeFamily::String),
+        operand: Some(Box::new(column(SqlTypeFamily::Integer, 0))),
+        conditions: vec![column(SqlTypeFamily::SmallInt, 1)],
+        results: vec![column(SqlTypeFamily::String, 2)],
+        else_result: None,
+    };
+
+    assert_eq!(TypeCoercion.coerce(tree.clone()), tree);
+}
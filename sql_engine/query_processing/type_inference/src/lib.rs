@@ -13,8 +13,10 @@
 // limitations under the License.
 
 use bigdecimal::{BigDecimal, FromPrimitive};
-use data_manipulation_typed_tree::{TypedItem, TypedTree, TypedValue};
-use data_manipulation_untyped_tree::{UntypedItem, UntypedTree, UntypedValue};
+use data_manipulation_functions::FunctionRegistry;
+use data_manipulation_operators::{ConditionalFunction, WindowFunction};
+use data_manipulation_typed_tree::{TypedItem, TypedSortKey, TypedTree, TypedValue, TypedWindow};
+use data_manipulation_untyped_tree::{UntypedItem, UntypedTree, UntypedValue, UntypedWindow};
 use std::ops::RangeInclusive;
 use types::{Bool, SqlTypeFamily};
 
@@ -25,6 +27,7 @@ pub struct TypeInference {
     big_int_range: RangeInclusive<BigDecimal>,
     real_range: RangeInclusive<BigDecimal>,
     double_precision_range: RangeInclusive<BigDecimal>,
+    functions: FunctionRegistry,
 }
 
 impl Default for TypeInference {
@@ -35,6 +38,7 @@ impl Default for TypeInference {
             big_int_range: BigDecimal::from(i64::MIN)..=BigDecimal::from(i64::MAX),
             real_range: BigDecimal::from_f32(f32::MIN).unwrap()..=BigDecimal::from_f32(f32::MAX).unwrap(),
             double_precision_range: BigDecimal::from_f64(f64::MIN).unwrap()..=BigDecimal::from_f64(f64::MAX).unwrap(),
+            functions: FunctionRegistry::default(),
         }
     }
 }
@@ -97,7 +101,8 @@ impl TypeInference {
                     },
                     (Some(left_type_family), None) => left_type_family,
                     (None, Some(right_type_family)) => right_type_family,
-                    (None, None) => unimplemented!(),
+                    // both operands are untyped NULLs, PostgreSQL resolves such unknowns as text
+                    (None, None) => SqlTypeFamily::String,
                 };
                 TypedTree::BiOp {
                     type_family,
@@ -119,9 +124,125 @@ impl TypeInference {
                 type_family: SqlTypeFamily::BigInt,
             })),
             UntypedTree::Item(UntypedItem::Const(UntypedValue::Null)) => TypedTree::Item(TypedItem::Null(None)),
+            UntypedTree::Sequence { function, sequence, args } => TypedTree::Sequence {
+                function,
+                sequence,
+                args: args.into_iter().map(|arg| self.infer_type(arg, param_types)).collect(),
+            },
+            UntypedTree::Function { name, args } => {
+                let args = args.into_iter().map(|arg| self.infer_type(arg, param_types)).collect::<Vec<_>>();
+                let arg_types = args.iter().map(TypedTree::result_type).collect::<Vec<_>>();
+                match self.functions.resolve(&name, &arg_types) {
+                    Some((function, type_family)) => TypedTree::Function { type_family, function, args },
+                    None => unreachable!("function {} has to be checked by query analyzer", name),
+                }
+            }
+            UntypedTree::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                let operand = operand.map(|operand| Box::new(self.infer_type(*operand, param_types)));
+                let conditions = conditions.into_iter().map(|condition| self.infer_type(condition, param_types)).collect();
+                let results = results.into_iter().map(|result| self.infer_type(result, param_types)).collect::<Vec<_>>();
+                let else_result = else_result.map(|else_result| Box::new(self.infer_type(*else_result, param_types)));
+                TypedTree::Case {
+                    type_family: common_type(results.iter().chain(else_result.as_deref())),
+                    operand,
+                    conditions,
+                    results,
+                    else_result,
+                }
+            }
+            UntypedTree::Conditional { function, args } => {
+                let args = args.into_iter().map(|arg| self.infer_type(arg, param_types)).collect::<Vec<_>>();
+                let type_family = match function {
+                    ConditionalFunction::NullIf => args.first().and_then(TypedTree::result_type),
+                    _ => common_type(args.iter()),
+                };
+                TypedTree::Conditional { type_family, function, args }
+            }
+            UntypedTree::Quantified { left, op, quantifier, list } => {
+                let left = self.infer_type(*left, param_types);
+                let list = list.into_iter().map(|item| self.infer_type(item, param_types)).collect::<Vec<_>>();
+                TypedTree::Quantified {
+                    type_family: common_type(Some(&left).into_iter().chain(list.iter())).unwrap_or(SqlTypeFamily::String),
+                    left: Box::new(left),
+                    op,
+                    quantifier,
+                    list,
+                }
+            }
+            UntypedTree::Between { item, low, high, negated } => {
+                let item = self.infer_type(*item, param_types);
+                let low = self.infer_type(*low, param_types);
+                let high = self.infer_type(*high, param_types);
+                TypedTree::Between {
+                    type_family: common_type(vec![&item, &low, &high].into_iter()).unwrap_or(SqlTypeFamily::String),
+                    item: Box::new(item),
+                    low: Box::new(low),
+                    high: Box::new(high),
+                    negated,
+                }
+            }
+            UntypedTree::Window(UntypedWindow {
+                name,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            }) => {
+                let function = match WindowFunction::resolve(&name, args.len()) {
+                    Some(function) => function,
+                    None => unreachable!("window function {} has to be checked by query analyzer", name),
+                };
+                let args = args.into_iter().map(|arg| self.infer_type(arg, param_types)).collect::<Vec<_>>();
+                let type_family = match function {
+                    WindowFunction::RowNumber | WindowFunction::Rank | WindowFunction::DenseRank | WindowFunction::Count => {
+                        Some(SqlTypeFamily::BigInt)
+                    }
+                    WindowFunction::Avg => Some(SqlTypeFamily::Double),
+                    WindowFunction::Sum => match args.first().and_then(TypedTree::result_type) {
+                        Some(SqlTypeFamily::SmallInt) | Some(SqlTypeFamily::Integer) => Some(SqlTypeFamily::BigInt),
+                        other => other,
+                    },
+                    WindowFunction::Lag
+                    | WindowFunction::Lead
+                    | WindowFunction::FirstValue
+                    | WindowFunction::LastValue
+                    | WindowFunction::Min
+                    | WindowFunction::Max => args.first().and_then(TypedTree::result_type),
+                };
+                TypedTree::Window(TypedWindow {
+                    type_family,
+                    function,
+                    args,
+                    partition_by: partition_by.into_iter().map(|key| self.infer_type(key, param_types)).collect(),
+                    order_by: order_by
+                        .into_iter()
+                        .map(|key| TypedSortKey {
+                            expr: self.infer_type(key.expr, param_types),
+                            ascending: key.ascending,
+                            nulls_first: key.nulls_first,
+                        })
+                        .collect(),
+                    frame,
+                })
+            }
         }
     }
 }
 
+// branches of conditional expressions and operands of predicates with many of them
+// are unified to the widest of their types, an operand of incomparable type is left
+// to be cast to the common type
+fn common_type<'t, T: Iterator<Item = &'t TypedTree>>(trees: T) -> Option<SqlTypeFamily> {
+    trees.filter_map(TypedTree::result_type).fold(None, |common, type_family| match common {
+        None => Some(type_family),
+        Some(common) => Some(common.compare(&type_family).unwrap_or(common)),
+    })
+}
+
 #[cfg(test)]
 mod tests;
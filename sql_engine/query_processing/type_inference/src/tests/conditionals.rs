@@ -0,0 +1,88 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use std::str::FromStr;
+
+fn small_int(value: i32) -> TypedTree {
+    TypedTree::Item(TypedItem::Const(TypedValue::Num {
+        value: BigDecimal::from(value),
+        type_family: SqlTypeFamily::SmallInt,
+    }))
+}
+
+#[test]
+fn case_branches_are_unified_to_the_widest_type() {
+    let type_inference = TypeInference::default();
+    let untyped_tree = UntypedTree::Case {
+        operand: None,
+        conditions: vec![UntypedTree::Item(UntypedItem::Const(UntypedValue::Bool(Bool(true))))],
+        results: vec![untyped_number(BigDecimal::from(1))],
+        else_result: Some(Box::new(untyped_number(BigDecimal::from_str("1.5").unwrap()))),
+    };
+
+    assert_eq!(
+        type_inference.infer_type(untyped_tree, &[]),
+        TypedTree::Case {
+            type_family: Some(SqlTypeFamily::Real),
+            operand: None,
+            conditions: vec![TypedTree::Item(TypedItem::Const(TypedValue::Bool(true)))],
+            results: vec![small_int(1)],
+            else_result: Some(Box::new(TypedTree::Item(TypedItem::Const(TypedValue::Num {
+                value: BigDecimal::from_str("1.5").unwrap(),
+                type_family: SqlTypeFamily::Real
+            }))))
+        }
+    );
+}
+
+#[test]
+fn coalesce_ignores_untyped_nulls() {
+    let type_inference = TypeInference::default();
+    let untyped_tree = UntypedTree::Conditional {
+        function: ConditionalFunction::Coalesce,
+        args: vec![
+            UntypedTree::Item(UntypedItem::Const(UntypedValue::Null)),
+            untyped_number(BigDecimal::from(1)),
+            untyped_number(BigDecimal::from(100_000)),
+        ],
+    };
+
+    assert_eq!(
+        type_inference.infer_type(untyped_tree, &[]),
+        TypedTree::Conditional {
+            type_family: Some(SqlTypeFamily::Integer),
+            function: ConditionalFunction::Coalesce,
+            args: vec![
+                TypedTree::Item(TypedItem::Null(None)),
+                small_int(1),
+                TypedTree::Item(TypedItem::Const(TypedValue::Num {
+                    value: BigDecimal::from(100_000),
+                    type_family: SqlTypeFamily::Integer
+                }))
+            ]
+        }
+    );
+}
+
+#[test]
+fn null_if_has_type_of_the_first_argument() {
+    let type_inference = TypeInference::default();
+    let untyped_tree = UntypedTree::Conditional {
+        function: ConditionalFunction::NullIf,
+        args: vec![untyped_number(BigDecimal::from(1)), untyped_number(BigDecimal::from(100_000))],
+    };
+
+    assert_eq!(type_inference.infer_type(untyped_tree, &[]).type_family(), Some(SqlTypeFamily::SmallInt));
+}
@@ -0,0 +1,82 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use data_manipulation_functions::ScalarFunction;
+
+#[test]
+fn function_with_string_argument() {
+    let type_inference = TypeInference::default();
+    let untyped_tree = UntypedTree::Function {
+        name: "upper".to_owned(),
+        args: vec![untyped_string("abc".to_owned())],
+    };
+
+    assert_eq!(
+        type_inference.infer_type(untyped_tree, &[]),
+        TypedTree::Function {
+            type_family: SqlTypeFamily::String,
+            function: ScalarFunction::Upper,
+            args: vec![TypedTree::Item(TypedItem::Const(TypedValue::String("abc".to_owned())))]
+        }
+    );
+}
+
+#[test]
+fn function_overload_by_argument_types() {
+    let type_inference = TypeInference::default();
+    let untyped_tree = UntypedTree::Function {
+        name: "mod".to_owned(),
+        args: vec![untyped_number(BigDecimal::from(7)), untyped_number(BigDecimal::from(100_000))],
+    };
+
+    assert_eq!(
+        type_inference.infer_type(untyped_tree, &[]),
+        TypedTree::Function {
+            type_family: SqlTypeFamily::Integer,
+            function: ScalarFunction::Mod,
+            args: vec![
+                TypedTree::Item(TypedItem::Const(TypedValue::Num {
+                    value: BigDecimal::from(7),
+                    type_family: SqlTypeFamily::SmallInt
+                })),
+                TypedTree::Item(TypedItem::Const(TypedValue::Num {
+                    value: BigDecimal::from(100_000),
+                    type_family: SqlTypeFamily::Integer
+                }))
+            ]
+        }
+    );
+}
+
+#[test]
+fn function_with_parameter() {
+    let type_inference = TypeInference::default();
+    let untyped_tree = UntypedTree::Function {
+        name: "round".to_owned(),
+        args: vec![UntypedTree::Item(UntypedItem::Param(0))],
+    };
+
+    assert_eq!(
+        type_inference.infer_type(untyped_tree, &[SqlTypeFamily::Integer]),
+        TypedTree::Function {
+            type_family: SqlTypeFamily::Double,
+            function: ScalarFunction::Round,
+            args: vec![TypedTree::Item(TypedItem::Param {
+                index: 0,
+                type_family: Some(SqlTypeFamily::Integer)
+            })]
+        }
+    );
+}
@@ -14,9 +14,13 @@
 
 use super::*;
 
+#[cfg(test)]
+mod conditionals;
 #[cfg(test)]
 mod constants;
 #[cfg(test)]
+mod functions;
+#[cfg(test)]
 mod operations;
 
 fn untyped_number(num: BigDecimal) -> UntypedTree {
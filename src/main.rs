@@ -12,12 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use node_engine::NodeEngine;
+use node_engine::{settings::ServerConfig, NodeEngine};
 use storage::Database;
 
 fn main() {
     simple_logger::SimpleLogger::from_env().init().expect("to initialize logger");
 
-    let root_path = std::env::var("ROOT_PATH").unwrap_or_else(|_| "./root_directory".to_owned());
-    NodeEngine::default().start(Database::new(root_path.as_str()));
+    let config = match std::env::var("CONFIG_FILE") {
+        Ok(path) => ServerConfig::from_file(path),
+        Err(_) => Ok(ServerConfig::default()),
+    };
+    let config = match config.and_then(ServerConfig::with_env) {
+        Ok(config) => config,
+        Err(error) => panic!("{}", error),
+    };
+    let database = Database::new(config.root_path.as_str());
+    NodeEngine::new(config).start(database);
 }
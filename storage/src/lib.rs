@@ -15,21 +15,35 @@
 use binary::BinaryValue;
 use dashmap::DashMap;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     fmt::{self, Debug, Formatter},
-    iter::FromIterator,
+    ops::Bound,
     rc::Rc,
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc, Mutex, MutexGuard, RwLock,
     },
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 pub type Key = Vec<BinaryValue>;
 pub type Value = Vec<BinaryValue>;
 
+/// Returns table records one by one in the order of their keys without copying the table.
+///
+/// The cursor does not read a snapshot of the table. It remembers the key of the last
+/// returned record and, on every step, looks up the next key under a short read lock,
+/// so that a plan could change the table it scans, e.g. `DELETE` and `UPDATE`:
+///  * records deleted or updated ahead of the cursor are skipped or returned updated,
+///    changes of already returned records are not seen
+///  * records inserted during the scan are returned, because keys of new records are
+///    greater than keys of existing ones
+///
+/// A transaction holds the database lock, so only the statements of the transaction
+/// itself could change the table while it is scanned.
 pub struct Cursor {
-    source: Box<dyn Iterator<Item = (Vec<BinaryValue>, Vec<BinaryValue>)>>,
+    table: Arc<InMemoryTableHandleInner>,
+    last_key: Option<Key>,
 }
 
 impl Debug for Cursor {
@@ -38,19 +52,21 @@ impl Debug for Cursor {
     }
 }
 
-impl FromIterator<(Vec<BinaryValue>, Vec<BinaryValue>)> for Cursor {
-    fn from_iter<T: IntoIterator<Item = (Vec<BinaryValue>, Vec<BinaryValue>)>>(iter: T) -> Cursor {
-        Cursor {
-            source: Box::new(iter.into_iter().collect::<Vec<(Vec<BinaryValue>, Vec<BinaryValue>)>>().into_iter()),
-        }
-    }
-}
-
 impl Iterator for Cursor {
     type Item = (Key, Value);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.source.next()
+        // the lock is taken per record so that a plan could write into the table it is scanning
+        let records = self.table.records.read().unwrap();
+        let record = match self.last_key.as_ref() {
+            None => records.iter().next(),
+            Some(last_key) => records.range::<Key, _>((Bound::Excluded(last_key), Bound::Unbounded)).next(),
+        }
+        .map(|(key, value)| (key.clone(), value.clone()));
+        if let Some((key, _value)) = record.as_ref() {
+            self.last_key = Some(key.clone());
+        }
+        record
     }
 }
 
@@ -67,8 +83,17 @@ impl Database {
     }
 
     pub fn transaction(&self) -> Transaction {
+        self.session_transaction(SessionSequences::default())
+    }
+
+    /// transaction of a session where sequences remember the values they returned to it
+    pub fn session_transaction(&self, session_sequences: SessionSequences) -> Transaction<'_> {
+        let guard = Rc::new(self.inner.lock().unwrap());
+        let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
         Transaction {
-            guard: Rc::new(self.inner.lock().unwrap()),
+            guard,
+            session_sequences,
+            started_at: since_epoch.as_micros() as i64,
         }
     }
 }
@@ -76,9 +101,20 @@ impl Database {
 #[derive(Clone)]
 pub struct Transaction<'t> {
     guard: Rc<MutexGuard<'t, DatabaseInner>>,
+    session_sequences: SessionSequences,
+    started_at: i64,
 }
 
 impl<'t> Transaction<'t> {
+    pub fn session_sequences(&self) -> SessionSequences {
+        self.session_sequences.clone()
+    }
+
+    /// microseconds since the Unix epoch at which the transaction acquired the database
+    pub fn started_at(&self) -> i64 {
+        self.started_at
+    }
+
     pub fn lookup_table_ref<T: Into<String>>(&self, full_table_name: T) -> TableRef {
         TableRef::from(self.guard.lookup_tree(full_table_name))
     }
@@ -90,6 +126,25 @@ impl<'t> Transaction<'t> {
     pub fn create_tree<T: Into<String>>(&self, full_table_name: T) {
         self.guard.create_tree(full_table_name)
     }
+
+    /// replaces the table tree with an empty one, record ids of the table start over
+    pub fn truncate_tree<T: Into<String>>(&self, full_table_name: T) {
+        self.guard.create_tree(full_table_name)
+    }
+
+    /// changes every time database objects are created, dropped or altered
+    pub fn catalog_version(&self) -> u64 {
+        self.guard.catalog_version.load(Ordering::SeqCst)
+    }
+
+    pub fn advance_catalog_version(&self) {
+        self.guard.catalog_version.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// unique identifier of a newly created database object, stays the same for the object lifetime
+    pub fn next_object_id(&self) -> u64 {
+        self.guard.object_ids.fetch_add(1, Ordering::SeqCst)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -123,6 +178,131 @@ impl TableRef {
     pub fn scan(&self) -> Cursor {
         self.inner.select()
     }
+
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.inner.records.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// positions of values in `DEFINITION_SCHEMA.SEQUENCES` records
+pub const SEQUENCE_SCHEMA: usize = 1;
+pub const SEQUENCE_NAME: usize = 2;
+const SEQUENCE_START: usize = 3;
+const SEQUENCE_INCREMENT: usize = 4;
+const SEQUENCE_MIN_VALUE: usize = 5;
+const SEQUENCE_MAX_VALUE: usize = 6;
+const SEQUENCE_LAST_VALUE: usize = 7;
+const SEQUENCE_IS_CALLED: usize = 8;
+/// table which identity column owns the sequence
+pub const SEQUENCE_OWNER: usize = 9;
+
+/// Values that sequences returned to a session, `currval` sees only the ones of its own session
+#[derive(Debug, Clone, Default)]
+pub struct SessionSequences {
+    last_values: Arc<Mutex<HashMap<Key, i64>>>,
+}
+
+impl SessionSequences {
+    fn last_value(&self, key: &Key) -> Option<i64> {
+        self.last_values.lock().unwrap().get(key).copied()
+    }
+
+    fn remember(&self, key: Key, value: i64) {
+        self.last_values.lock().unwrap().insert(key, value);
+    }
+}
+
+/// Sequence generator which state is kept in its `DEFINITION_SCHEMA.SEQUENCES` record
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    name: String,
+    table: TableRef,
+    key: Key,
+    session: SessionSequences,
+}
+
+impl Sequence {
+    pub fn new(name: String, table: TableRef, key: Key, session: SessionSequences) -> Sequence {
+        Sequence { name, table, key, session }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn increment(&self) -> i64 {
+        self.record()[SEQUENCE_INCREMENT].as_i64()
+    }
+
+    pub fn bounds(&self) -> (i64, i64) {
+        let record = self.record();
+        (record[SEQUENCE_MIN_VALUE].as_i64(), record[SEQUENCE_MAX_VALUE].as_i64())
+    }
+
+    /// advances the sequence and returns its new value, `None` if the sequence reached its bound
+    pub fn next_value(&self) -> Option<i64> {
+        let mut record = self.record();
+        let last_value = record[SEQUENCE_LAST_VALUE].as_i64();
+        let next_value = if record[SEQUENCE_IS_CALLED] == BinaryValue::from_bool(true) {
+            let (min_value, max_value) = (record[SEQUENCE_MIN_VALUE].as_i64(), record[SEQUENCE_MAX_VALUE].as_i64());
+            last_value
+                .checked_add(record[SEQUENCE_INCREMENT].as_i64())
+                .filter(|value| (min_value..=max_value).contains(value))?
+        } else {
+            last_value
+        };
+        record[SEQUENCE_LAST_VALUE] = BinaryValue::from(next_value);
+        record[SEQUENCE_IS_CALLED] = BinaryValue::from_bool(true);
+        self.table.write_key(self.key.clone(), Some(record));
+        self.session.remember(self.key.clone(), next_value);
+        Some(next_value)
+    }
+
+    /// value most recently returned by `next_value` or set by `set_value` in the session,
+    /// `None` if the session has not advanced the sequence yet
+    pub fn current_value(&self) -> Option<i64> {
+        self.session.last_value(&self.key)
+    }
+
+    /// makes `value` the current value of the sequence, `false` if it is out of the sequence bounds
+    pub fn set_value(&self, value: i64) -> bool {
+        let mut record = self.record();
+        if !(record[SEQUENCE_MIN_VALUE].as_i64()..=record[SEQUENCE_MAX_VALUE].as_i64()).contains(&value) {
+            return false;
+        }
+        record[SEQUENCE_LAST_VALUE] = BinaryValue::from(value);
+        record[SEQUENCE_IS_CALLED] = BinaryValue::from_bool(true);
+        self.table.write_key(self.key.clone(), Some(record));
+        self.session.remember(self.key.clone(), value);
+        true
+    }
+
+    /// makes the next `next_value` return the start value of the sequence,
+    /// values already returned to sessions are kept for `current_value`
+    pub fn restart(&self) {
+        let mut record = self.record();
+        record[SEQUENCE_LAST_VALUE] = record[SEQUENCE_START].clone();
+        record[SEQUENCE_IS_CALLED] = BinaryValue::from_bool(false);
+        self.table.write_key(self.key.clone(), Some(record));
+    }
+
+    fn record(&self) -> Value {
+        self.table.inner.inner.records.read().unwrap().get(&self.key).cloned().unwrap()
+    }
+}
+
+impl PartialEq for Sequence {
+    fn eq(&self, other: &Sequence) -> bool {
+        self.name == other.name
+    }
 }
 
 const DEFINITION_SCHEMA: &str = "DEFINITION_SCHEMA";
@@ -130,22 +310,65 @@ const SCHEMATA_TABLE: &str = "SCHEMATA";
 const TABLES_TABLE: &str = "TABLES";
 const INDEXES_TABLE: &str = "TABLES";
 const COLUMNS_TABLE: &str = "COLUMNS";
+const VIEWS_TABLE: &str = "VIEWS";
+const SEQUENCES_TABLE: &str = "SEQUENCES";
+const FUNCTIONS_TABLE: &str = "FUNCTIONS";
+const PARAMETERS_TABLE: &str = "PARAMETERS";
+const FUNCTION_USAGE_TABLE: &str = "FUNCTION_USAGE";
+const STATISTICS_TABLE: &str = "STATISTICS";
+const COLUMN_STATISTICS_TABLE: &str = "COLUMN_STATISTICS";
+const COLUMN_STATISTIC_VALUES_TABLE: &str = "COLUMN_STATISTIC_VALUES";
+
+// the same object ids as PostgreSQL has for its predefined schemas
+const PG_CATALOG_OID: u64 = 11;
+const PUBLIC_OID: u64 = 2200;
+const INFORMATION_SCHEMA_OID: u64 = 13000;
+const FIRST_NORMAL_OBJECT_ID: u64 = 16384;
 
 pub struct DatabaseInner {
     trees: DashMap<String, TableInner>,
+    catalog_version: AtomicU64,
+    object_ids: AtomicU64,
 }
 
 impl DatabaseInner {
     pub fn create() -> DatabaseInner {
-        let this = DatabaseInner { trees: DashMap::default() };
+        let this = DatabaseInner {
+            trees: DashMap::default(),
+            catalog_version: AtomicU64::default(),
+            object_ids: AtomicU64::new(FIRST_NORMAL_OBJECT_ID),
+        };
 
         // database bootstrap
         this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, SCHEMATA_TABLE));
-        this.lookup_tree(format!("{}.{}", DEFINITION_SCHEMA, SCHEMATA_TABLE))
-            .insert(vec![vec![BinaryValue::from("IN_MEMORY"), BinaryValue::from("public")]]);
+        this.lookup_tree(format!("{}.{}", DEFINITION_SCHEMA, SCHEMATA_TABLE)).insert(vec![
+            vec![
+                BinaryValue::from("IN_MEMORY"),
+                BinaryValue::from("public"),
+                BinaryValue::from_u64(PUBLIC_OID),
+            ],
+            vec![
+                BinaryValue::from("IN_MEMORY"),
+                BinaryValue::from("pg_catalog"),
+                BinaryValue::from_u64(PG_CATALOG_OID),
+            ],
+            vec![
+                BinaryValue::from("IN_MEMORY"),
+                BinaryValue::from("information_schema"),
+                BinaryValue::from_u64(INFORMATION_SCHEMA_OID),
+            ],
+        ]);
         this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, TABLES_TABLE));
         this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, COLUMNS_TABLE));
         this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, INDEXES_TABLE));
+        this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, VIEWS_TABLE));
+        this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, SEQUENCES_TABLE));
+        this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, FUNCTIONS_TABLE));
+        this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, PARAMETERS_TABLE));
+        this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, FUNCTION_USAGE_TABLE));
+        this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, STATISTICS_TABLE));
+        this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, COLUMN_STATISTICS_TABLE));
+        this.create_tree(format!("{}.{}", DEFINITION_SCHEMA, COLUMN_STATISTIC_VALUES_TABLE));
 
         this
     }
@@ -188,13 +411,10 @@ impl TableInner {
     }
 
     pub fn select(&self) -> Cursor {
-        self.inner
-            .records
-            .read()
-            .unwrap()
-            .iter()
-            .map(|(key, value)| (key.clone(), value.clone()))
-            .collect::<Cursor>()
+        Cursor {
+            table: self.inner.clone(),
+            last_key: None,
+        }
     }
 
     pub fn insert(&self, data: Vec<Value>) -> Vec<Key> {
@@ -269,3 +489,6 @@ impl PartialEq for TableInner {
         self.name == other.name
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,115 @@
+// Copyright 2020 - 2021 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn table_with(transaction: &Transaction<'_>, values: &[i32]) -> (TableRef, Vec<Key>) {
+    transaction.create_tree("schema_name.table_name");
+    let table = transaction.lookup_table_ref("schema_name.table_name");
+    let keys = values.iter().map(|value| table.write(vec![BinaryValue::from(*value)])).collect();
+    (table, keys)
+}
+
+fn values(cursor: Cursor) -> Vec<BinaryValue> {
+    cursor.flat_map(|(_key, value)| value).collect()
+}
+
+#[test]
+fn cursor_returns_records_in_key_order() {
+    let database = Database::new("");
+    let transaction = database.transaction();
+    let (table, _keys) = table_with(&transaction, &[3, 1, 2]);
+
+    assert_eq!(
+        values(table.scan()),
+        vec![BinaryValue::from(3), BinaryValue::from(1), BinaryValue::from(2)]
+    );
+}
+
+#[test]
+fn cursor_continues_after_deletes() {
+    let database = Database::new("");
+    let transaction = database.transaction();
+    let (table, keys) = table_with(&transaction, &[1, 2, 3, 4]);
+
+    let mut cursor = table.scan();
+    assert_eq!(cursor.next(), Some((keys[0].clone(), vec![BinaryValue::from(1)])));
+
+    table.write_key(keys[0].clone(), None);
+    table.write_key(keys[1].clone(), None);
+
+    assert_eq!(values(cursor), vec![BinaryValue::from(3), BinaryValue::from(4)]);
+}
+
+#[test]
+fn cursor_returns_records_inserted_during_scan() {
+    let database = Database::new("");
+    let transaction = database.transaction();
+    let (table, _keys) = table_with(&transaction, &[1, 2]);
+
+    let mut cursor = table.scan();
+    assert_eq!(cursor.next().map(|(_key, value)| value), Some(vec![BinaryValue::from(1)]));
+
+    table.write(vec![BinaryValue::from(3)]);
+
+    assert_eq!(values(cursor), vec![BinaryValue::from(2), BinaryValue::from(3)]);
+}
+
+#[test]
+fn cursor_sees_updates_only_ahead_of_it() {
+    let database = Database::new("");
+    let transaction = database.transaction();
+    let (table, keys) = table_with(&transaction, &[1, 2, 3]);
+
+    let mut cursor = table.scan();
+    assert_eq!(cursor.next().map(|(_key, value)| value), Some(vec![BinaryValue::from(1)]));
+
+    table.write_key(keys[0].clone(), Some(vec![BinaryValue::from(10)]));
+    table.write_key(keys[2].clone(), Some(vec![BinaryValue::from(30)]));
+
+    assert_eq!(values(cursor), vec![BinaryValue::from(2), BinaryValue::from(30)]);
+    assert_eq!(
+        values(table.scan()),
+        vec![BinaryValue::from(10), BinaryValue::from(2), BinaryValue::from(30)]
+    );
+}
+
+#[test]
+fn restarted_sequence_starts_over() {
+    let database = Database::new("");
+    let transaction = database.transaction();
+    transaction.create_tree("schema_name.sequences");
+    let table = transaction.lookup_table_ref("schema_name.sequences");
+    let key = table.write(vec![
+        BinaryValue::from("IN_MEMORY"),
+        BinaryValue::from("schema_name"),
+        BinaryValue::from("sequence_name"),
+        BinaryValue::from(10i64),
+        BinaryValue::from(5i64),
+        BinaryValue::from(1i64),
+        BinaryValue::from(100i64),
+        BinaryValue::from(10i64),
+        BinaryValue::from_bool(false),
+        BinaryValue::from("table_name"),
+    ]);
+    let sequence = Sequence::new("sequence_name".to_owned(), table, key, transaction.session_sequences());
+
+    assert_eq!(sequence.next_value(), Some(10));
+    assert_eq!(sequence.next_value(), Some(15));
+
+    sequence.restart();
+
+    assert_eq!(sequence.current_value(), Some(15));
+    assert_eq!(sequence.next_value(), Some(10));
+}
@@ -34,6 +34,7 @@ pub enum SqlTypeFamily {
     BigInt,
     Real,
     Double,
+    TimestampTz,
 }
 
 impl Display for SqlTypeFamily {
@@ -46,6 +47,7 @@ impl Display for SqlTypeFamily {
             SqlTypeFamily::BigInt => write!(f, "bigint"),
             SqlTypeFamily::Real => write!(f, "real"),
             SqlTypeFamily::Double => write!(f, "double precision"),
+            SqlTypeFamily::TimestampTz => write!(f, "timestamp with time zone"),
         }
     }
 }
@@ -227,6 +229,9 @@ impl From<&u32> for SqlTypeFamily {
             &BIGINT => SqlTypeFamily::BigInt,
             &CHAR | &VARCHAR => SqlTypeFamily::String,
             &BOOL => SqlTypeFamily::Bool,
+            &REAL => SqlTypeFamily::Real,
+            &DOUBLE_PRECISION => SqlTypeFamily::Double,
+            &TIMESTAMPTZ => SqlTypeFamily::TimestampTz,
             _ => unimplemented!(),
         }
     }
@@ -241,7 +246,23 @@ impl From<&SqlType> for u32 {
             SqlType::Num(Num::SmallInt) => SMALLINT,
             SqlType::Num(Num::Integer) => INT,
             SqlType::Num(Num::BigInt) => BIGINT,
-            SqlType::Num(Num::Real) | SqlType::Num(Num::Double) => unreachable!(),
+            SqlType::Num(Num::Real) => REAL,
+            SqlType::Num(Num::Double) => DOUBLE_PRECISION,
+        }
+    }
+}
+
+impl From<&SqlTypeFamily> for u32 {
+    fn from(type_family: &SqlTypeFamily) -> u32 {
+        match type_family {
+            SqlTypeFamily::Bool => BOOL,
+            SqlTypeFamily::String => VARCHAR,
+            SqlTypeFamily::SmallInt => SMALLINT,
+            SqlTypeFamily::Integer => INT,
+            SqlTypeFamily::BigInt => BIGINT,
+            SqlTypeFamily::Real => REAL,
+            SqlTypeFamily::Double => DOUBLE_PRECISION,
+            SqlTypeFamily::TimestampTz => TIMESTAMPTZ,
         }
     }
 }